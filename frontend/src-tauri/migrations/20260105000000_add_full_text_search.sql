@@ -0,0 +1,89 @@
+-- Migration: Add FTS5 full-text search indexes
+-- Each searchable source gets its own FTS5 table whose rowid mirrors the rowid
+-- of the source row, so triggers can keep the index in sync cheaply:
+--   1. transcripts_fts   - transcript segments (transcripts.transcript)
+--   2. summaries_fts     - generated summaries (summary_processes.result -> $.markdown)
+--   3. meeting_notes_fts - user notes (meeting_notes.notes_markdown)
+-- The id columns are UNINDEXED: they are stored for joins but never matched.
+
+-- 1. Transcript segments
+CREATE VIRTUAL TABLE IF NOT EXISTS transcripts_fts USING fts5(
+    transcript,
+    meeting_id UNINDEXED,
+    transcript_id UNINDEXED,
+    tokenize = 'unicode61'
+);
+
+INSERT INTO transcripts_fts (rowid, transcript, meeting_id, transcript_id)
+SELECT rowid, transcript, meeting_id, id FROM transcripts;
+
+CREATE TRIGGER IF NOT EXISTS transcripts_fts_after_insert AFTER INSERT ON transcripts BEGIN
+    INSERT INTO transcripts_fts (rowid, transcript, meeting_id, transcript_id)
+    VALUES (new.rowid, new.transcript, new.meeting_id, new.id);
+END;
+
+CREATE TRIGGER IF NOT EXISTS transcripts_fts_after_delete AFTER DELETE ON transcripts BEGIN
+    DELETE FROM transcripts_fts WHERE rowid = old.rowid;
+END;
+
+CREATE TRIGGER IF NOT EXISTS transcripts_fts_after_update AFTER UPDATE OF transcript, meeting_id ON transcripts BEGIN
+    DELETE FROM transcripts_fts WHERE rowid = old.rowid;
+    INSERT INTO transcripts_fts (rowid, transcript, meeting_id, transcript_id)
+    VALUES (new.rowid, new.transcript, new.meeting_id, new.id);
+END;
+
+-- 2. Summaries (result is JSON; only index the markdown body and skip invalid JSON)
+CREATE VIRTUAL TABLE IF NOT EXISTS summaries_fts USING fts5(
+    markdown,
+    meeting_id UNINDEXED,
+    tokenize = 'unicode61'
+);
+
+INSERT INTO summaries_fts (rowid, markdown, meeting_id)
+SELECT rowid, json_extract(result, '$.markdown'), meeting_id
+FROM summary_processes
+WHERE json_valid(result) AND json_extract(result, '$.markdown') IS NOT NULL;
+
+CREATE TRIGGER IF NOT EXISTS summaries_fts_after_insert AFTER INSERT ON summary_processes
+WHEN json_valid(new.result) AND json_extract(new.result, '$.markdown') IS NOT NULL BEGIN
+    INSERT INTO summaries_fts (rowid, markdown, meeting_id)
+    VALUES (new.rowid, json_extract(new.result, '$.markdown'), new.meeting_id);
+END;
+
+CREATE TRIGGER IF NOT EXISTS summaries_fts_after_delete AFTER DELETE ON summary_processes BEGIN
+    DELETE FROM summaries_fts WHERE rowid = old.rowid;
+END;
+
+CREATE TRIGGER IF NOT EXISTS summaries_fts_after_update AFTER UPDATE OF result ON summary_processes BEGIN
+    DELETE FROM summaries_fts WHERE rowid = old.rowid;
+    INSERT INTO summaries_fts (rowid, markdown, meeting_id)
+    SELECT new.rowid, json_extract(new.result, '$.markdown'), new.meeting_id
+    WHERE json_valid(new.result) AND json_extract(new.result, '$.markdown') IS NOT NULL;
+END;
+
+-- 3. Meeting notes
+CREATE VIRTUAL TABLE IF NOT EXISTS meeting_notes_fts USING fts5(
+    notes_markdown,
+    meeting_id UNINDEXED,
+    tokenize = 'unicode61'
+);
+
+INSERT INTO meeting_notes_fts (rowid, notes_markdown, meeting_id)
+SELECT rowid, notes_markdown, meeting_id FROM meeting_notes WHERE notes_markdown IS NOT NULL;
+
+CREATE TRIGGER IF NOT EXISTS meeting_notes_fts_after_insert AFTER INSERT ON meeting_notes
+WHEN new.notes_markdown IS NOT NULL BEGIN
+    INSERT INTO meeting_notes_fts (rowid, notes_markdown, meeting_id)
+    VALUES (new.rowid, new.notes_markdown, new.meeting_id);
+END;
+
+CREATE TRIGGER IF NOT EXISTS meeting_notes_fts_after_delete AFTER DELETE ON meeting_notes BEGIN
+    DELETE FROM meeting_notes_fts WHERE rowid = old.rowid;
+END;
+
+CREATE TRIGGER IF NOT EXISTS meeting_notes_fts_after_update AFTER UPDATE OF notes_markdown ON meeting_notes BEGIN
+    DELETE FROM meeting_notes_fts WHERE rowid = old.rowid;
+    INSERT INTO meeting_notes_fts (rowid, notes_markdown, meeting_id)
+    SELECT new.rowid, new.notes_markdown, new.meeting_id
+    WHERE new.notes_markdown IS NOT NULL;
+END;
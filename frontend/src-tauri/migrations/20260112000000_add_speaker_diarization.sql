@@ -0,0 +1,19 @@
+-- Migration: Add speaker diarization results
+-- speaker_label holds the diarized voice ('Speaker 1', 'Speaker 2', ...) and is
+-- independent of the existing speaker column, which only records 'mic'/'system'.
+ALTER TABLE transcripts ADD COLUMN speaker_label TEXT;
+
+-- One row per diarized speaker in a meeting. The embedding is the L2-normalized
+-- centroid of the speaker's voice embeddings, stored as little-endian f32s.
+CREATE TABLE IF NOT EXISTS meeting_speakers (
+    meeting_id TEXT NOT NULL,
+    label TEXT NOT NULL,
+    embedding BLOB NOT NULL,
+    segment_count INTEGER NOT NULL DEFAULT 0,
+    total_duration REAL NOT NULL DEFAULT 0.0,
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (meeting_id, label),
+    FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_transcripts_speaker_label ON transcripts(meeting_id, speaker_label);
@@ -0,0 +1,17 @@
+-- Migration: Add named voice profiles
+-- A profile is a named speaker whose voice embedding is learned from meetings where
+-- the user named that speaker. New diarization runs match speakers against these
+-- profiles so the same person is labelled automatically in later meetings.
+CREATE TABLE IF NOT EXISTS speaker_profiles (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    embedding BLOB NOT NULL,
+    sample_count INTEGER NOT NULL DEFAULT 1,
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_speaker_profiles_name ON speaker_profiles(name COLLATE NOCASE);
+
+-- Which profile (if any) a diarized meeting speaker was matched to or saved as
+ALTER TABLE meeting_speakers ADD COLUMN profile_id TEXT REFERENCES speaker_profiles(id) ON DELETE SET NULL;
@@ -0,0 +1,18 @@
+-- Migration: Add word-level timestamps
+-- One row per transcribed word, with times in seconds from recording start (same
+-- clock as transcripts.audio_start_time). Used for playback highlighting and
+-- word-accurate trimming. Rows exist only for engines that report token timings.
+CREATE TABLE IF NOT EXISTS transcript_words (
+    transcript_id TEXT NOT NULL,
+    meeting_id TEXT NOT NULL,
+    word_index INTEGER NOT NULL,
+    word TEXT NOT NULL,
+    start_time REAL NOT NULL,
+    end_time REAL NOT NULL,
+    confidence REAL,
+    PRIMARY KEY (transcript_id, word_index),
+    FOREIGN KEY (transcript_id) REFERENCES transcripts(id) ON DELETE CASCADE,
+    FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_transcript_words_meeting ON transcript_words(meeting_id, start_time);
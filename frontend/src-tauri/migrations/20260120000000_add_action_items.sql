@@ -0,0 +1,22 @@
+-- Migration: Add structured action items and decisions
+-- Extracted from the transcript by the LLM after a summary is generated (or on
+-- demand). kind is 'action_item' or 'decision'; owner and due_date only apply to
+-- action items. source_segment_id points at the transcripts row the item was
+-- taken from so the UI can jump to it.
+CREATE TABLE IF NOT EXISTS action_items (
+    id TEXT PRIMARY KEY,
+    meeting_id TEXT NOT NULL,
+    kind TEXT NOT NULL DEFAULT 'action_item' CHECK (kind IN ('action_item', 'decision')),
+    text TEXT NOT NULL,
+    owner TEXT,
+    due_date TEXT,
+    source_segment_id TEXT,
+    status TEXT NOT NULL DEFAULT 'open' CHECK (status IN ('open', 'completed', 'cancelled')),
+    created_at TEXT NOT NULL,
+    updated_at TEXT NOT NULL,
+    completed_at TEXT,
+    FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_action_items_meeting ON action_items(meeting_id);
+CREATE INDEX IF NOT EXISTS idx_action_items_status ON action_items(status, due_date);
@@ -0,0 +1,30 @@
+-- Migration: Add the embeddings index for Q&A over the meeting archive
+-- Transcript windows and summary sections are embedded per embedding model
+-- ('builtin-ai:gemma3:1b', 'ollama:nomic-embed-text', ...) so switching models
+-- keeps the old index until the new one is built. qa_index_state records the
+-- content signature each meeting was indexed at; a meeting is re-indexed when
+-- its transcripts or summary change.
+CREATE TABLE IF NOT EXISTS qa_index_state (
+    meeting_id TEXT NOT NULL,
+    embedding_model TEXT NOT NULL,
+    signature TEXT NOT NULL,
+    passage_count INTEGER NOT NULL DEFAULT 0,
+    indexed_at TEXT NOT NULL,
+    PRIMARY KEY (meeting_id, embedding_model),
+    FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+);
+
+-- embedding is a little-endian f32 vector, L2-normalized
+CREATE TABLE IF NOT EXISTS qa_passages (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    meeting_id TEXT NOT NULL,
+    embedding_model TEXT NOT NULL,
+    source TEXT NOT NULL CHECK (source IN ('transcript', 'summary')),
+    transcript_id TEXT,
+    audio_start_time REAL,
+    text TEXT NOT NULL,
+    embedding BLOB NOT NULL,
+    FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_qa_passages_model ON qa_passages(embedding_model, meeting_id);
@@ -0,0 +1,5 @@
+-- Migration: Add LLM fallback chain to settings table
+-- JSON array of {"provider", "model"} entries tried in order when the summary
+-- provider fails, e.g. [{"provider":"ollama","model":"llama3.2:latest"}]
+
+ALTER TABLE settings ADD COLUMN llmFallbackChain TEXT;
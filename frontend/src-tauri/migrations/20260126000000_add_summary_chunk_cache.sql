@@ -0,0 +1,17 @@
+-- Migration: Add the cache of intermediate summaries
+-- Chunk summaries (level 0) and combined summaries (level 1 and up) of the
+-- hierarchical summary pipeline, keyed by a hash of their input, the
+-- 'provider:model' that wrote them and a hash of the prompt. Regenerating a
+-- summary with another template or after a partial failure reuses every entry
+-- whose input is unchanged.
+CREATE TABLE IF NOT EXISTS summary_chunk_cache (
+    meeting_id TEXT NOT NULL,
+    level INTEGER NOT NULL,
+    content_hash TEXT NOT NULL,
+    model TEXT NOT NULL,
+    prompt_hash TEXT NOT NULL,
+    summary TEXT NOT NULL,
+    created_at TEXT NOT NULL,
+    PRIMARY KEY (meeting_id, level, content_hash, model, prompt_hash),
+    FOREIGN KEY (meeting_id) REFERENCES meetings(id) ON DELETE CASCADE
+);
@@ -5,15 +5,18 @@ use tauri::{AppHandle, Runtime};
 use tauri_plugin_store::StoreExt;
 
 use crate::{
+    audio::transcription::WordTiming,
     database::{
-        models::MeetingModel,
+        models::{MeetingModel, MeetingSpeaker, SpeakerProfile, TranscriptWord},
         repositories::{
-            meeting::MeetingsRepository, setting::SettingsRepository,
+            meeting::MeetingsRepository, search::SearchRepository, setting::SettingsRepository,
+            speaker::SpeakersRepository, speaker_profile::SpeakerProfilesRepository,
             transcript::TranscriptsRepository,
         },
     },
+    diarization::is_generic_speaker_label,
     state::AppState,
-    summary::CustomOpenAIConfig,
+    summary::{CustomOpenAIConfig, FallbackModel, LLMProvider},
 };
 
 // Hardcoded server URL
@@ -46,6 +49,47 @@ pub struct TranscriptSearchResult {
     pub timestamp: String,
 }
 
+/// Where a full-text search hit came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SearchSource {
+    Transcript,
+    Summary,
+    Notes,
+}
+
+/// A single match inside `SearchHit::text`, as character (not byte) offsets
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchMatch {
+    pub start: usize,
+    pub end: usize,
+}
+
+/// Ranked full-text search hit across transcripts, summaries and notes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SearchHit {
+    pub meeting_id: String,
+    pub meeting_title: String,
+    pub source: SearchSource,
+    /// Transcript segment id for transcript hits, meeting id otherwise
+    pub source_id: String,
+    pub text: String,
+    /// FTS5 snippet with matches wrapped in `<mark>` tags
+    pub snippet: String,
+    /// Negated BM25 rank (higher is more relevant)
+    pub score: f64,
+    pub matches: Vec<SearchMatch>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<String>,
+    // Recording-relative timestamps so the UI can jump to the audio
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audio_start_time: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audio_end_time: Option<f64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speaker: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ProfileRequest {
     pub email: String,
@@ -137,6 +181,8 @@ pub struct MeetingTranscript {
     pub audio_end_time: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub duration: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speaker_label: Option<String>,
 }
 
 /// Meeting metadata without transcripts (for pagination)
@@ -188,6 +234,11 @@ pub struct TranscriptSegment {
     pub audio_end_time: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub duration: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub speaker_label: Option<String>,
+    // Word timings in seconds from recording start (from the transcript-update event)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub words: Vec<WordTiming>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -382,6 +433,232 @@ pub async fn api_search_transcripts<R: Runtime>(
     }
 }
 
+/// Ranked full-text search across transcripts, summaries and notes.
+///
+/// Supports phrases, prefix (`term*`) and boolean (`AND`/`OR`/`NOT`) queries.
+/// `sources` defaults to all three when omitted.
+#[tauri::command]
+pub async fn api_search_meetings<R: Runtime>(
+    _app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    query: String,
+    sources: Option<Vec<SearchSource>>,
+    limit: Option<i64>,
+    offset: Option<i64>,
+) -> Result<Vec<SearchHit>, String> {
+    log_info!(
+        "api_search_meetings called with query: '{}', sources: {:?}",
+        query,
+        sources
+    );
+
+    let pool = state.db_manager.pool();
+    let sources = sources.unwrap_or_else(|| {
+        vec![
+            SearchSource::Transcript,
+            SearchSource::Summary,
+            SearchSource::Notes,
+        ]
+    });
+
+    match SearchRepository::search(
+        pool,
+        &query,
+        &sources,
+        limit.unwrap_or(50),
+        offset.unwrap_or(0),
+    )
+    .await
+    {
+        Ok(hits) => {
+            log_info!("Search completed successfully with {} hits.", hits.len());
+            Ok(hits)
+        }
+        Err(e) => {
+            log_error!("Error searching meetings for query '{}': {}", query, e);
+            Err(format!("Failed to search meetings: {}", e))
+        }
+    }
+}
+
+/// Word-level timings of a meeting in playback order, for highlighting the
+/// current word and trimming at word boundaries. `transcript_ids` limits the
+/// result to those transcript rows (e.g. the currently loaded page).
+#[tauri::command]
+pub async fn api_get_transcript_words<R: Runtime>(
+    _app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    transcript_ids: Option<Vec<String>>,
+) -> Result<Vec<TranscriptWord>, String> {
+    log_info!("api_get_transcript_words called for meeting_id: {}", meeting_id);
+    let pool = state.db_manager.pool();
+
+    TranscriptsRepository::get_transcript_words(pool, &meeting_id, transcript_ids.as_deref())
+        .await
+        .map_err(|e| {
+            log_error!("Error loading word timings for meeting {}: {}", meeting_id, e);
+            format!("Failed to load word timings: {}", e)
+        })
+}
+
+#[tauri::command]
+pub async fn api_get_meeting_speakers<R: Runtime>(
+    _app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+) -> Result<Vec<MeetingSpeaker>, String> {
+    log_info!("api_get_meeting_speakers called for meeting_id: {}", meeting_id);
+    let pool = state.db_manager.pool();
+
+    SpeakersRepository::get_meeting_speakers(pool, &meeting_id)
+        .await
+        .map_err(|e| {
+            log_error!("Error loading speakers for meeting {}: {}", meeting_id, e);
+            format!("Failed to load speakers: {}", e)
+        })
+}
+
+/// Renames a speaker within a meeting. With `save_profile` (default true) the
+/// name is also stored as a voice profile so the speaker is recognized in
+/// later meetings.
+#[tauri::command]
+pub async fn api_rename_meeting_speaker<R: Runtime>(
+    _app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    label: String,
+    new_name: String,
+    save_profile: Option<bool>,
+) -> Result<(), String> {
+    log_info!(
+        "api_rename_meeting_speaker called for meeting_id: {}, '{}' -> '{}'",
+        meeting_id,
+        label,
+        new_name
+    );
+
+    let new_name = new_name.trim();
+    if new_name.is_empty() {
+        return Err("Speaker name cannot be empty".to_string());
+    }
+    let save_profile = save_profile.unwrap_or(true);
+    if save_profile && is_generic_speaker_label(new_name) {
+        return Err(format!(
+            "'{}' is reserved for unnamed speakers; choose a different name",
+            new_name
+        ));
+    }
+
+    let pool = state.db_manager.pool();
+    match SpeakersRepository::rename_speaker(pool, &meeting_id, &label, new_name, save_profile)
+        .await
+    {
+        Ok(true) => Ok(()),
+        Ok(false) => Err(format!("Speaker '{}' not found in meeting", label)),
+        Err(e) => {
+            log_error!("Error renaming speaker '{}' in meeting {}: {}", label, meeting_id, e);
+            Err(format!("Failed to rename speaker: {}", e))
+        }
+    }
+}
+
+/// Merges speakers that diarization split apart into `target_label`
+#[tauri::command]
+pub async fn api_merge_meeting_speakers<R: Runtime>(
+    _app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    source_labels: Vec<String>,
+    target_label: String,
+) -> Result<(), String> {
+    log_info!(
+        "api_merge_meeting_speakers called for meeting_id: {}, {:?} -> '{}'",
+        meeting_id,
+        source_labels,
+        target_label
+    );
+    let pool = state.db_manager.pool();
+
+    SpeakersRepository::merge_speakers(pool, &meeting_id, &source_labels, &target_label)
+        .await
+        .map_err(|e| {
+            log_error!("Error merging speakers in meeting {}: {}", meeting_id, e);
+            format!("Failed to merge speakers: {}", e)
+        })
+}
+
+/// Moves selected transcript segments of a speaker to a new speaker.
+/// Returns the new speaker's label.
+#[tauri::command]
+pub async fn api_split_meeting_speaker<R: Runtime>(
+    _app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    label: String,
+    transcript_ids: Vec<String>,
+    new_label: Option<String>,
+) -> Result<String, String> {
+    log_info!(
+        "api_split_meeting_speaker called for meeting_id: {}, label: '{}', {} segments",
+        meeting_id,
+        label,
+        transcript_ids.len()
+    );
+    let pool = state.db_manager.pool();
+
+    let new_label = new_label
+        .map(|l| l.trim().to_string())
+        .filter(|l| !l.is_empty());
+    SpeakersRepository::split_speaker(
+        pool,
+        &meeting_id,
+        &label,
+        &transcript_ids,
+        new_label.as_deref(),
+    )
+    .await
+    .map_err(|e| {
+        log_error!("Error splitting speaker '{}' in meeting {}: {}", label, meeting_id, e);
+        format!("Failed to split speaker: {}", e)
+    })
+}
+
+#[tauri::command]
+pub async fn api_get_speaker_profiles<R: Runtime>(
+    _app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<SpeakerProfile>, String> {
+    log_info!("api_get_speaker_profiles called");
+    let pool = state.db_manager.pool();
+
+    SpeakerProfilesRepository::get_profiles(pool)
+        .await
+        .map_err(|e| {
+            log_error!("Error loading speaker profiles: {}", e);
+            format!("Failed to load speaker profiles: {}", e)
+        })
+}
+
+#[tauri::command]
+pub async fn api_delete_speaker_profile<R: Runtime>(
+    _app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    profile_id: String,
+) -> Result<(), String> {
+    log_info!("api_delete_speaker_profile called for profile_id: {}", profile_id);
+    let pool = state.db_manager.pool();
+
+    match SpeakerProfilesRepository::delete_profile(pool, &profile_id).await {
+        Ok(true) => Ok(()),
+        Ok(false) => Err(format!("Speaker profile not found: {}", profile_id)),
+        Err(e) => {
+            log_error!("Error deleting speaker profile {}: {}", profile_id, e);
+            Err(format!("Failed to delete speaker profile: {}", e))
+        }
+    }
+}
+
 #[tauri::command]
 pub async fn api_get_profile<R: Runtime>(
     app: AppHandle<R>,
@@ -878,6 +1155,7 @@ pub async fn api_get_meeting_transcripts<R: Runtime>(
                     audio_start_time: t.audio_start_time,
                     audio_end_time: t.audio_end_time,
                     duration: t.duration,
+                    speaker_label: t.speaker_label,
                 })
                 .collect::<Vec<_>>();
 
@@ -928,7 +1206,7 @@ pub async fn api_save_meeting_title<R: Runtime>(
 
 #[tauri::command]
 pub async fn api_save_transcript<R: Runtime>(
-    _app: AppHandle<R>,
+    app: AppHandle<R>,
     state: tauri::State<'_, AppState>,
     meeting_title: String,
     transcripts: Vec<serde_json::Value>,
@@ -972,6 +1250,8 @@ pub async fn api_save_transcript<R: Runtime>(
 
     let pool = state.db_manager.pool();
 
+    let has_recording = folder_path.is_some();
+
     // Now, call the repository with the correctly typed data.
     match TranscriptsRepository::save_transcript(
         pool,
@@ -986,6 +1266,22 @@ pub async fn api_save_transcript<R: Runtime>(
                 "Successfully saved transcript and created meeting with id: {}",
                 meeting_id
             );
+
+            // Optionally identify speakers from the saved recording in the background
+            if has_recording && crate::diarization::downloaded_model_path().is_some() {
+                let diarize = crate::audio::recording_preferences::load_recording_preferences(&app)
+                    .await
+                    .map(|prefs| prefs.diarize_after_recording)
+                    .unwrap_or(false);
+                if diarize {
+                    log_info!("Starting speaker diarization for meeting {}", meeting_id);
+                    crate::diarization::spawn_meeting_diarization(
+                        app.clone(),
+                        meeting_id.clone(),
+                        None,
+                    );
+                }
+            }
             Ok(serde_json::json!({
                 "status": "success",
                 "message": "Transcript saved successfully",
@@ -1268,6 +1564,55 @@ pub async fn api_get_custom_openai_config<R: Runtime>(
     }
 }
 
+// ===== LLM FALLBACK CHAIN COMMANDS =====
+
+/// Saves the providers to fall back to, in order, when the summary provider fails
+#[tauri::command]
+pub async fn api_save_llm_fallback_chain<R: Runtime>(
+    _app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    chain: Vec<FallbackModel>,
+) -> Result<serde_json::Value, String> {
+    log_info!(
+        "api_save_llm_fallback_chain called with {} entries",
+        chain.len()
+    );
+
+    for entry in &chain {
+        LLMProvider::from_str(&entry.provider)?;
+        if entry.model.trim().is_empty() {
+            return Err(format!("Model name is required for {}", entry.provider));
+        }
+    }
+
+    let pool = state.db_manager.pool();
+    match SettingsRepository::save_llm_fallback_chain(pool, &chain).await {
+        Ok(()) => {
+            log_info!("✅ Successfully saved LLM fallback chain");
+            Ok(serde_json::json!({
+                "status": "success",
+                "message": "Fallback providers saved successfully"
+            }))
+        }
+        Err(e) => {
+            log_error!("❌ Failed to save LLM fallback chain: {}", e);
+            Err(format!("Failed to save fallback providers: {}", e))
+        }
+    }
+}
+
+/// Gets the LLM fallback chain
+#[tauri::command]
+pub async fn api_get_llm_fallback_chain<R: Runtime>(
+    _app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+) -> Result<Vec<FallbackModel>, String> {
+    let pool = state.db_manager.pool();
+    SettingsRepository::get_llm_fallback_chain(pool)
+        .await
+        .map_err(|e| format!("Failed to get fallback providers: {}", e))
+}
+
 /// Tests the connection to a custom OpenAI-compatible endpoint
 /// Makes a minimal request to verify the endpoint is reachable and responds correctly
 #[tauri::command]
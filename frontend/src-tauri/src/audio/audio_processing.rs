@@ -8,8 +8,10 @@ use rubato::{
 };
 use std::path::PathBuf;
 use nnnoiseless::DenoiseState;
+use std::collections::VecDeque;
 
 use super::encode::encode_single_audio; // Correct path to encode module
+use super::denoise::Denoiser;
 
 /// Sanitize a filename to be safe for filesystem use
 pub fn sanitize_filename(name: &str) -> String {
@@ -232,20 +234,37 @@ impl LoudnessNormalizer {
 /// Benefits:
 /// - 10-15 dB noise reduction in typical office/home environments
 /// - Preserves speech quality and intelligibility
-/// - Low latency (~10ms per frame)
+/// - Low latency (one 10ms frame)
 /// - Cross-platform (works on macOS, Windows, Linux)
 pub struct NoiseSuppressionProcessor {
     denoiser: DenoiseState<'static>,
     frame_buffer: Vec<f32>,
     frame_size: usize,  // 480 samples at 48kHz = 10ms
+    /// Share of the denoised signal in the output (the rest is the original)
+    strength: f32,
+    /// Original audio, delayed by one frame to line up with RNNoise's output
+    dry_delay: VecDeque<f32>,
 }
 
+/// RNNoise works on samples in the 16-bit integer range
+const RNNOISE_SCALE: f32 = 32768.0;
+
 impl NoiseSuppressionProcessor {
     /// Create a new noise suppression processor
     ///
     /// # Arguments
     /// * `sample_rate` - Must be 48000 Hz (RNNoise requirement)
     pub fn new(sample_rate: u32) -> Result<Self> {
+        Self::with_strength(sample_rate, 1.0)
+    }
+
+    /// Create a noise suppression processor that blends the denoised signal
+    /// with the original
+    ///
+    /// # Arguments
+    /// * `sample_rate` - Must be 48000 Hz (RNNoise requirement)
+    /// * `strength` - 0.0 (original audio) to 1.0 (fully denoised)
+    pub fn with_strength(sample_rate: u32, strength: f32) -> Result<Self> {
         if sample_rate != 48000 {
             return Err(anyhow::anyhow!(
                 "Noise suppression requires 48kHz sample rate, got {}Hz",
@@ -254,56 +273,67 @@ impl NoiseSuppressionProcessor {
         }
 
         const FRAME_SIZE: usize = DenoiseState::FRAME_SIZE;
+        let strength = strength.clamp(0.0, 1.0);
 
-        info!("Initializing RNNoise noise suppression (frame size: {} samples, 10ms @ 48kHz)", FRAME_SIZE);
+        info!("Initializing RNNoise noise suppression (frame size: {} samples, 10ms @ 48kHz, strength {:.2})",
+              FRAME_SIZE, strength);
 
         Ok(Self {
             denoiser: *DenoiseState::new(),
             frame_buffer: Vec::with_capacity(FRAME_SIZE * 2),
             frame_size: FRAME_SIZE,
+            strength,
+            dry_delay: VecDeque::from(vec![0.0; FRAME_SIZE]),
         })
     }
+}
 
+impl Denoiser for NoiseSuppressionProcessor {
     /// Apply noise suppression to audio samples
     ///
     /// Processes audio in 480-sample frames (10ms at 48kHz).
     /// Buffers partial frames for next call.
     ///
-    /// CRITICAL FIX: Always returns same length as input to prevent latency accumulation
-    ///
     /// # Arguments
     /// * `samples` - Input audio samples at 48kHz
     ///
     /// # Returns
-    /// Noise-suppressed audio samples (SAME LENGTH as input)
-    pub fn process(&mut self, samples: &[f32]) -> Vec<f32> {
+    /// Noise-suppressed audio samples, one frame behind the input
+    fn process(&mut self, samples: &[f32]) -> Vec<f32> {
         if samples.is_empty() {
             return Vec::new();
         }
 
-        // CRITICAL: Remember original input length
-        let input_len = samples.len();
-
         // Add new samples to buffer
         self.frame_buffer.extend_from_slice(samples);
 
-        let mut output = Vec::with_capacity(input_len);
+        let mut output = Vec::with_capacity(self.frame_buffer.len());
+        let mut input_frame = vec![0.0f32; self.frame_size];
+        let mut denoised_frame = vec![0.0f32; self.frame_size];
 
         // Process complete frames
-        while self.frame_buffer.len() >= self.frame_size {
-            // Extract one frame
-            let frame: Vec<f32> = self.frame_buffer.drain(0..self.frame_size).collect();
+        let mut consumed = 0;
+        while self.frame_buffer.len() - consumed >= self.frame_size {
+            let frame = &self.frame_buffer[consumed..consumed + self.frame_size];
+            consumed += self.frame_size;
 
-            // RNNoise processes audio: separate input and output buffers
-            let mut denoised_frame = vec![0.0f32; self.frame_size];
+            for (scaled, &sample) in input_frame.iter_mut().zip(frame) {
+                *scaled = sample * RNNOISE_SCALE;
+            }
 
-            // Apply noise suppression
             // process_frame(output: &mut [f32], input: &[f32]) -> f32
             // Returns VAD probability (0.0-1.0), higher means more likely to be speech
-            let _vad_prob = self.denoiser.process_frame(&mut denoised_frame, &frame);
-
-            output.extend_from_slice(&denoised_frame);
+            let _vad_prob = self.denoiser.process_frame(&mut denoised_frame, &input_frame);
+
+            // RNNoise's output lags its input by one frame; blend it with the
+            // original from the same time
+            self.dry_delay.extend(frame);
+            for &wet in &denoised_frame {
+                let dry = self.dry_delay.pop_front().unwrap_or(0.0);
+                output.push(self.strength * wet / RNNOISE_SCALE + (1.0 - self.strength) * dry);
+            }
         }
+        self.frame_buffer.drain(..consumed);
 
         // Return processed output without forcing length matching
         // Frame-based processing naturally creates variable-length output
@@ -312,31 +342,12 @@ impl NoiseSuppressionProcessor {
     }
 
     /// Get the number of buffered samples waiting for processing
-    pub fn buffered_samples(&self) -> usize {
+    fn buffered_samples(&self) -> usize {
         self.frame_buffer.len()
     }
 
-    /// Flush any remaining buffered samples
-    /// Call this at the end of recording to process partial frames
-    pub fn flush(&mut self) -> Vec<f32> {
-        if self.frame_buffer.is_empty() {
-            return Vec::new();
-        }
-
-        // Pad the remaining samples to a full frame with zeros
-        let remaining = self.frame_buffer.len();
-        let mut input_frame = self.frame_buffer.clone();
-        if input_frame.len() < self.frame_size {
-            input_frame.resize(self.frame_size, 0.0);
-        }
-
-        let mut output = vec![0.0f32; self.frame_size];
-        self.denoiser.process_frame(&mut output, &input_frame);
-        self.frame_buffer.clear();
-
-        // Return only the original samples (without padding)
-        output.truncate(remaining);
-        output
+    fn latency_samples(&self) -> usize {
+        self.frame_size
     }
 }
 
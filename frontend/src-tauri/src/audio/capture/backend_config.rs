@@ -16,28 +16,49 @@ pub enum AudioCaptureBackend {
     /// Uses direct Core Audio API with aggregate device + tap
     #[cfg(target_os = "macos")]
     CoreAudio,
+
+    /// PipeWire / PulseAudio backend (Linux only)
+    /// Records the monitor of the default sink through the PulseAudio API,
+    /// which PipeWire also serves via pipewire-pulse
+    #[cfg(target_os = "linux")]
+    PulseAudio,
 }
 
 impl AudioCaptureBackend {
     /// Get human-readable name
     pub fn name(&self) -> &'static str {
         match self {
+            #[cfg(not(target_os = "linux"))]
             AudioCaptureBackend::ScreenCaptureKit => "ScreenCaptureKit",
+            // Kept under the same id so saved preferences stay valid
+            #[cfg(target_os = "linux")]
+            AudioCaptureBackend::ScreenCaptureKit => "ALSA (CPAL)",
             #[cfg(target_os = "macos")]
             AudioCaptureBackend::CoreAudio => "Core Audio",
+            #[cfg(target_os = "linux")]
+            AudioCaptureBackend::PulseAudio => "PipeWire / PulseAudio",
         }
     }
 
     /// Get description
     pub fn description(&self) -> &'static str {
         match self {
+            #[cfg(not(target_os = "linux"))]
             AudioCaptureBackend::ScreenCaptureKit => {
                 "Apple's ScreenCaptureKit framework - Higher level API with good compatibility"
             }
+            #[cfg(target_os = "linux")]
+            AudioCaptureBackend::ScreenCaptureKit => {
+                "ALSA monitor devices through CPAL - For systems without a sound server"
+            }
             #[cfg(target_os = "macos")]
             AudioCaptureBackend::CoreAudio => {
                 "Direct Core Audio API - Lower latency, more control over audio pipeline"
             }
+            #[cfg(target_os = "linux")]
+            AudioCaptureBackend::PulseAudio => {
+                "Sound server API - Follows the default output when you switch speakers or headphones"
+            }
         }
     }
 
@@ -47,6 +68,8 @@ impl AudioCaptureBackend {
             "screencapturekit" => Some(AudioCaptureBackend::ScreenCaptureKit),
             #[cfg(target_os = "macos")]
             "coreaudio" | "core_audio" => Some(AudioCaptureBackend::CoreAudio),
+            #[cfg(target_os = "linux")]
+            "pulseaudio" | "pipewire" | "pulse" => Some(AudioCaptureBackend::PulseAudio),
             _ => None,
         }
     }
@@ -57,6 +80,8 @@ impl AudioCaptureBackend {
             AudioCaptureBackend::ScreenCaptureKit => "screencapturekit".to_string(),
             #[cfg(target_os = "macos")]
             AudioCaptureBackend::CoreAudio => "coreaudio".to_string(),
+            #[cfg(target_os = "linux")]
+            AudioCaptureBackend::PulseAudio => "pulseaudio".to_string(),
         }
     }
 
@@ -67,7 +92,15 @@ impl AudioCaptureBackend {
             vec![AudioCaptureBackend::ScreenCaptureKit, AudioCaptureBackend::CoreAudio]
         }
 
-        #[cfg(not(target_os = "macos"))]
+        #[cfg(target_os = "linux")]
+        {
+            vec![
+                AudioCaptureBackend::ScreenCaptureKit,
+                AudioCaptureBackend::PulseAudio,
+            ]
+        }
+
+        #[cfg(not(any(target_os = "macos", target_os = "linux")))]
         {
             vec![AudioCaptureBackend::ScreenCaptureKit]
         }
@@ -78,7 +111,10 @@ impl AudioCaptureBackend {
         #[cfg(target_os = "macos")]
         return AudioCaptureBackend::CoreAudio;
 
-        #[cfg(not(target_os = "macos"))]
+        #[cfg(target_os = "linux")]
+        return AudioCaptureBackend::PulseAudio;
+
+        #[cfg(not(any(target_os = "macos", target_os = "linux")))]
         return AudioCaptureBackend::ScreenCaptureKit;
     }
 }
@@ -158,6 +194,8 @@ mod tests {
         assert_eq!(AudioCaptureBackend::ScreenCaptureKit.to_string(), "screencapturekit");
         #[cfg(target_os = "macos")]
         assert_eq!(AudioCaptureBackend::CoreAudio.to_string(), "coreaudio");
+        #[cfg(target_os = "linux")]
+        assert_eq!(AudioCaptureBackend::PulseAudio.to_string(), "pulseaudio");
     }
 
     #[test]
@@ -177,6 +215,15 @@ mod tests {
                 Some(AudioCaptureBackend::CoreAudio)
             );
         }
+        #[cfg(target_os = "linux")]
+        {
+            for name in ["pulseaudio", "PipeWire", "pulse"] {
+                assert_eq!(
+                    AudioCaptureBackend::from_string(name),
+                    Some(AudioCaptureBackend::PulseAudio)
+                );
+            }
+        }
     }
 
     #[test]
@@ -186,6 +233,9 @@ mod tests {
 
         #[cfg(target_os = "macos")]
         assert!(backends.contains(&AudioCaptureBackend::CoreAudio));
+
+        #[cfg(target_os = "linux")]
+        assert!(backends.contains(&AudioCaptureBackend::PulseAudio));
     }
 
     #[test]
@@ -193,7 +243,13 @@ mod tests {
         #[cfg(target_os = "macos")]
         assert_eq!(AudioCaptureBackend::default(), AudioCaptureBackend::CoreAudio);
 
-        #[cfg(not(target_os = "macos"))]
+        #[cfg(target_os = "linux")]
+        assert_eq!(
+            AudioCaptureBackend::default(),
+            AudioCaptureBackend::PulseAudio
+        );
+
+        #[cfg(not(any(target_os = "macos", target_os = "linux")))]
         assert_eq!(AudioCaptureBackend::default(), AudioCaptureBackend::ScreenCaptureKit);
     }
 
@@ -205,7 +261,10 @@ mod tests {
         #[cfg(target_os = "macos")]
         assert_eq!(config.get(), AudioCaptureBackend::CoreAudio);
 
-        #[cfg(not(target_os = "macos"))]
+        #[cfg(target_os = "linux")]
+        assert_eq!(config.get(), AudioCaptureBackend::PulseAudio);
+
+        #[cfg(not(any(target_os = "macos", target_os = "linux")))]
         assert_eq!(config.get(), AudioCaptureBackend::ScreenCaptureKit);
 
         #[cfg(target_os = "macos")]
@@ -215,12 +274,22 @@ mod tests {
             assert_eq!(config.get(), AudioCaptureBackend::CoreAudio);
         }
 
+        #[cfg(target_os = "linux")]
+        {
+            // Test falling back to CPAL
+            config.set(AudioCaptureBackend::ScreenCaptureKit);
+            assert_eq!(config.get(), AudioCaptureBackend::ScreenCaptureKit);
+        }
+
         // Test reset
         config.reset();
         #[cfg(target_os = "macos")]
         assert_eq!(config.get(), AudioCaptureBackend::CoreAudio);
 
-        #[cfg(not(target_os = "macos"))]
+        #[cfg(target_os = "linux")]
+        assert_eq!(config.get(), AudioCaptureBackend::PulseAudio);
+
+        #[cfg(not(any(target_os = "macos", target_os = "linux")))]
         assert_eq!(config.get(), AudioCaptureBackend::ScreenCaptureKit);
     }
 }
\ No newline at end of file
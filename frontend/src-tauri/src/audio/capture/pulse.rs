@@ -0,0 +1,850 @@
+// PipeWire / PulseAudio system audio capture for Linux
+//
+// Records the monitor source of an output sink through the PulseAudio client
+// API. PipeWire desktops serve the same API through pipewire-pulse, so this
+// works on both. When recording the default output, the stream is moved to
+// the new default sink's monitor whenever the user switches outputs.
+// Alternatively only chosen apps are recorded, through monitor streams of
+// their sink inputs.
+
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, VecDeque};
+use std::rc::Rc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, RwLock};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use libpulse_binding as pulse;
+use log::{error, info, warn};
+use once_cell::sync::Lazy;
+use pulse::callbacks::ListResult;
+use pulse::context::subscribe::{Facility, InterestMaskSet};
+use pulse::context::{Context, FlagSet as ContextFlagSet, State as ContextState};
+use pulse::def::BufferAttr;
+use pulse::mainloop::standard::{IterateResult, Mainloop};
+use pulse::operation::{Operation, State as OperationState};
+use pulse::proplist::Proplist;
+use pulse::sample::{Format, Spec};
+use pulse::stream::{FlagSet as StreamFlagSet, PeekResult, State as StreamState, Stream};
+
+/// Device name of the entry that always records the current default output
+pub const DEFAULT_SINK_DEVICE: &str = "System Audio (Default Output)";
+
+/// Suffix of the per-sink device names listed for system audio
+const SINK_DEVICE_SUFFIX: &str = " (System Audio)";
+
+/// The capture format; the sound server converts from the sink's format
+pub const SAMPLE_RATE: u32 = 48000;
+pub const CHANNELS: u16 = 1;
+
+/// Server-side fragment size: ~20ms of mono f32
+const FRAGMENT_BYTES: u32 = SAMPLE_RATE / 50 * 4;
+
+const APP_NAME: &str = "Meetily";
+
+/// An output sink whose monitor can be recorded
+#[derive(Debug, Clone, PartialEq)]
+pub struct PulseSinkInfo {
+    pub index: u32,
+    /// Sink name, e.g. "alsa_output.pci-0000_00_1f.3.analog-stereo"
+    pub name: String,
+    /// Human-readable description, e.g. "Built-in Audio Analog Stereo"
+    pub description: String,
+    /// Name of the sink's monitor source
+    pub monitor_source: String,
+    pub sample_rate: u32,
+    pub channels: u8,
+    pub is_default: bool,
+    pub is_bluetooth: bool,
+}
+
+impl PulseSinkInfo {
+    /// Name this sink is listed under in the device list
+    pub fn device_name(&self) -> String {
+        format!("{}{}", self.description, SINK_DEVICE_SUFFIX)
+    }
+}
+
+/// Which sink's monitor to record
+#[derive(Debug, Clone, PartialEq)]
+pub enum PulseTarget {
+    /// The default sink, followed when it changes
+    DefaultSink,
+    /// A specific sink, by name
+    Sink(String),
+    /// Only the audio of these apps, on whichever sink they play
+    Apps(Vec<String>),
+}
+
+/// An app stream playing on the sound server
+#[derive(Debug, Clone, PartialEq)]
+pub struct PulseSinkInput {
+    pub index: u32,
+    /// Index of the sink it plays on
+    pub sink: u32,
+    /// `application.name`, e.g. "Firefox"
+    pub app_name: String,
+    /// `application.process.binary`, e.g. "firefox-bin"
+    pub binary: Option<String>,
+    pub pid: Option<u32>,
+    /// What is playing, e.g. the tab title
+    pub media_name: String,
+}
+
+impl PulseSinkInput {
+    /// Whether the input belongs to one of `apps`, by app or binary name
+    pub fn matches(&self, apps: &[String]) -> bool {
+        apps.iter().any(|app| {
+            self.app_name.eq_ignore_ascii_case(app)
+                || self
+                    .binary
+                    .as_deref()
+                    .is_some_and(|binary| binary.eq_ignore_ascii_case(app))
+        })
+    }
+}
+
+/// An app stream recording from a source, e.g. a call app using the mic
+#[derive(Debug, Clone, PartialEq)]
+pub struct PulseSourceOutput {
+    pub index: u32,
+    /// Index of the source it records
+    pub source: u32,
+    /// `application.name`
+    pub app_name: String,
+    /// `application.process.binary`
+    pub binary: Option<String>,
+    pub pid: Option<u32>,
+}
+
+/// Apps system audio is restricted to; empty records everything. Mirrors the
+/// `system_audio_apps` recording preference.
+static APP_FILTER: Lazy<RwLock<Vec<String>>> = Lazy::new(|| RwLock::new(Vec::new()));
+
+/// Get the apps system audio is restricted to
+pub fn app_filter() -> Vec<String> {
+    APP_FILTER.read().unwrap().clone()
+}
+
+/// Restrict system audio to the given apps (empty records everything)
+pub fn set_app_filter(apps: Vec<String>) {
+    *APP_FILTER.write().unwrap() = apps;
+}
+
+/// Connects to the sound server and waits until the context is ready
+fn connect() -> Result<(Mainloop, Context)> {
+    let mut mainloop =
+        Mainloop::new().ok_or_else(|| anyhow!("Failed to create PulseAudio mainloop"))?;
+    let mut context = Context::new(&mainloop, APP_NAME)
+        .ok_or_else(|| anyhow!("Failed to create PulseAudio context"))?;
+    context
+        .connect(None, ContextFlagSet::NOAUTOSPAWN, None)
+        .map_err(|e| anyhow!("Failed to connect to PipeWire/PulseAudio: {}", e))?;
+
+    loop {
+        if let IterateResult::Quit(_) | IterateResult::Err(_) = mainloop.iterate(true) {
+            return Err(anyhow!("PulseAudio mainloop stopped while connecting"));
+        }
+        match context.get_state() {
+            ContextState::Ready => return Ok((mainloop, context)),
+            ContextState::Failed | ContextState::Terminated => {
+                return Err(anyhow!("No PipeWire or PulseAudio server is running"));
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Runs the mainloop until an operation completes
+fn wait_for<F: ?Sized>(mainloop: &mut Mainloop, operation: &Operation<F>) -> Result<()> {
+    while operation.get_state() == OperationState::Running {
+        if let IterateResult::Quit(_) | IterateResult::Err(_) = mainloop.iterate(true) {
+            return Err(anyhow!("PulseAudio mainloop stopped during an operation"));
+        }
+    }
+    Ok(())
+}
+
+/// Name of the current default sink
+fn default_sink_name(mainloop: &mut Mainloop, context: &Context) -> Result<Option<String>> {
+    let default_sink = Rc::new(RefCell::new(None));
+    let operation = context.introspect().get_server_info({
+        let default_sink = default_sink.clone();
+        move |info| {
+            *default_sink.borrow_mut() = info.default_sink_name.as_ref().map(|n| n.to_string());
+        }
+    });
+    wait_for(mainloop, &operation)?;
+    let name = default_sink.borrow_mut().take();
+    Ok(name)
+}
+
+/// Whether a PipeWire or PulseAudio server is reachable
+pub fn is_available() -> bool {
+    connect().is_ok()
+}
+
+/// Lists the output sinks of the sound server
+pub fn list_sinks() -> Result<Vec<PulseSinkInfo>> {
+    let (mut mainloop, mut context) = connect()?;
+    let sinks = query_sinks(&mut mainloop, &context);
+    context.disconnect();
+    sinks
+}
+
+/// Lists the app streams currently playing
+pub fn list_sink_inputs() -> Result<Vec<PulseSinkInput>> {
+    let (mut mainloop, mut context) = connect()?;
+    let inputs = query_sink_inputs(&mut mainloop, &context);
+    context.disconnect();
+    inputs
+}
+
+fn query_sinks(mainloop: &mut Mainloop, context: &Context) -> Result<Vec<PulseSinkInfo>> {
+    let default_sink = default_sink_name(mainloop, context)?;
+
+    let sinks = Rc::new(RefCell::new(Vec::new()));
+    let operation = context.introspect().get_sink_info_list({
+        let sinks = sinks.clone();
+        move |result| {
+            let ListResult::Item(sink) = result else {
+                return;
+            };
+            let (Some(name), Some(monitor_source)) = (&sink.name, &sink.monitor_source_name) else {
+                return;
+            };
+            let name = name.to_string();
+            sinks.borrow_mut().push(PulseSinkInfo {
+                index: sink.index,
+                description: sink
+                    .description
+                    .as_ref()
+                    .map(|d| d.to_string())
+                    .unwrap_or_else(|| name.clone()),
+                monitor_source: monitor_source.to_string(),
+                sample_rate: sink.sample_spec.rate,
+                channels: sink.sample_spec.channels,
+                is_default: false,
+                is_bluetooth: sink.proplist.get_str("device.bus").as_deref() == Some("bluetooth"),
+                name,
+            });
+        }
+    });
+    wait_for(mainloop, &operation)?;
+
+    let mut sinks = sinks.take();
+    for sink in &mut sinks {
+        sink.is_default = default_sink.as_deref() == Some(sink.name.as_str());
+    }
+    Ok(sinks)
+}
+
+/// App name, binary and pid of a client stream; streams without an app
+/// name or binary aren't reported
+fn app_identity(proplist: &Proplist) -> Option<(String, Option<String>, Option<u32>)> {
+    let binary = proplist.get_str("application.process.binary");
+    let app_name = proplist
+        .get_str("application.name")
+        .or_else(|| binary.clone())?;
+    let pid = proplist
+        .get_str("application.process.id")
+        .and_then(|pid| pid.parse().ok());
+    Some((app_name, binary, pid))
+}
+
+fn query_source_outputs(
+    mainloop: &mut Mainloop,
+    context: &Context,
+) -> Result<Vec<PulseSourceOutput>> {
+    let outputs = Rc::new(RefCell::new(Vec::new()));
+    let operation = context.introspect().get_source_output_info_list({
+        let outputs = outputs.clone();
+        move |result| {
+            let ListResult::Item(output) = result else {
+                return;
+            };
+            let Some((app_name, binary, pid)) = app_identity(&output.proplist) else {
+                return;
+            };
+            outputs.borrow_mut().push(PulseSourceOutput {
+                index: output.index,
+                source: output.source,
+                app_name,
+                binary,
+                pid,
+            });
+        }
+    });
+    wait_for(mainloop, &operation)?;
+    let outputs = outputs.take();
+    Ok(outputs)
+}
+
+fn query_sink_inputs(mainloop: &mut Mainloop, context: &Context) -> Result<Vec<PulseSinkInput>> {
+    let inputs = Rc::new(RefCell::new(Vec::new()));
+    let operation = context.introspect().get_sink_input_info_list({
+        let inputs = inputs.clone();
+        move |result| {
+            let ListResult::Item(input) = result else {
+                return;
+            };
+            let Some((app_name, binary, pid)) = app_identity(&input.proplist) else {
+                return;
+            };
+            inputs.borrow_mut().push(PulseSinkInput {
+                index: input.index,
+                sink: input.sink,
+                app_name,
+                binary,
+                pid,
+                media_name: input
+                    .proplist
+                    .get_str("media.name")
+                    .or_else(|| input.name.as_ref().map(|n| n.to_string()))
+                    .unwrap_or_default(),
+            });
+        }
+    });
+    wait_for(mainloop, &operation)?;
+    let inputs = inputs.take();
+    Ok(inputs)
+}
+
+/// Resolves a device name from the device list to a capture target
+pub fn resolve_target(device_name: &str) -> Option<PulseTarget> {
+    if device_name == DEFAULT_SINK_DEVICE {
+        return Some(PulseTarget::DefaultSink);
+    }
+    let description = device_name.strip_suffix(SINK_DEVICE_SUFFIX)?;
+    let sinks = list_sinks().ok()?;
+    sinks
+        .into_iter()
+        .find(|sink| sink.description == description || sink.name == description)
+        .map(|sink| PulseTarget::Sink(sink.name))
+}
+
+/// Watches the app streams of the sound server: what plays and what records
+pub struct PulseStreamWatcher {
+    mainloop: Mainloop,
+    context: Context,
+    changed: Rc<Cell<bool>>,
+}
+
+impl PulseStreamWatcher {
+    pub fn new() -> Result<Self> {
+        let (mainloop, mut context) = connect()?;
+        let changed = Rc::new(Cell::new(true));
+        context.set_subscribe_callback(Some(Box::new({
+            let changed = changed.clone();
+            move |facility, _, _| {
+                if matches!(facility, Some(Facility::SinkInput | Facility::SourceOutput)) {
+                    changed.set(true);
+                }
+            }
+        })));
+        context.subscribe(
+            InterestMaskSet::SINK_INPUT | InterestMaskSet::SOURCE_OUTPUT,
+            |_| {},
+        );
+        Ok(Self {
+            mainloop,
+            context,
+            changed,
+        })
+    }
+
+    /// Returns the app streams if they changed since the last call (always on
+    /// the first call). Doesn't block.
+    pub fn poll(&mut self) -> Result<Option<(Vec<PulseSinkInput>, Vec<PulseSourceOutput>)>> {
+        if let IterateResult::Quit(_) | IterateResult::Err(_) = self.mainloop.iterate(false) {
+            return Err(anyhow!("PulseAudio mainloop stopped"));
+        }
+        if !matches!(self.context.get_state(), ContextState::Ready) {
+            return Err(anyhow!("Lost the connection to PipeWire/PulseAudio"));
+        }
+        if !self.changed.replace(false) {
+            return Ok(None);
+        }
+        let inputs = query_sink_inputs(&mut self.mainloop, &self.context)?;
+        let outputs = query_source_outputs(&mut self.mainloop, &self.context)?;
+        Ok(Some((inputs, outputs)))
+    }
+}
+
+impl Drop for PulseStreamWatcher {
+    fn drop(&mut self) {
+        self.context.disconnect();
+    }
+}
+
+/// A running monitor recording. Samples are mono f32 at `SAMPLE_RATE`.
+pub struct PulseCapture {
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl PulseCapture {
+    /// Starts recording `target`, calling `on_samples` from the capture thread
+    pub fn start<F>(target: PulseTarget, on_samples: F) -> Result<Self>
+    where
+        F: FnMut(&[f32]) + Send + 'static,
+    {
+        let stop = Arc::new(AtomicBool::new(false));
+        let (ready_tx, ready_rx) = mpsc::channel();
+
+        // The mainloop and its objects aren't Send, so everything lives on
+        // the capture thread
+        let thread = std::thread::Builder::new()
+            .name("pulse-capture".to_string())
+            .spawn({
+                let stop = stop.clone();
+                move || {
+                    if let Err(e) = run_capture(target, on_samples, &stop, &ready_tx) {
+                        error!("❌ PulseAudio: Capture failed: {}", e);
+                        // Only delivered if startup failed
+                        let _ = ready_tx.send(Err(e.to_string()));
+                    }
+                }
+            })?;
+
+        match ready_rx.recv() {
+            Ok(Ok(())) => Ok(Self {
+                stop,
+                thread: Some(thread),
+            }),
+            Ok(Err(e)) => {
+                let _ = thread.join();
+                Err(anyhow!(e))
+            }
+            Err(_) => {
+                let _ = thread.join();
+                Err(anyhow!("PulseAudio capture thread exited during startup"))
+            }
+        }
+    }
+
+    /// Stops the recording and waits for the capture thread
+    pub fn stop(mut self) {
+        self.shutdown();
+    }
+
+    fn shutdown(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        if let Some(thread) = self.thread.take() {
+            if thread.join().is_err() {
+                warn!("PulseAudio capture thread panicked");
+            }
+        }
+    }
+}
+
+impl Drop for PulseCapture {
+    fn drop(&mut self) {
+        self.shutdown();
+    }
+}
+
+fn run_capture<F>(
+    target: PulseTarget,
+    on_samples: F,
+    stop: &AtomicBool,
+    ready_tx: &mpsc::Sender<std::result::Result<(), String>>,
+) -> Result<()>
+where
+    F: FnMut(&[f32]),
+{
+    let (mut mainloop, mut context) = connect()?;
+    let result = match target {
+        PulseTarget::Apps(apps) => record_apps(
+            &mut mainloop,
+            &mut context,
+            &apps,
+            on_samples,
+            stop,
+            ready_tx,
+        ),
+        target => record_sink(
+            &mut mainloop,
+            &mut context,
+            target,
+            on_samples,
+            stop,
+            ready_tx,
+        ),
+    };
+    context.disconnect();
+    result
+}
+
+/// Opens a record stream on `source`, restricted to one sink input's audio
+/// when `sink_input` is set
+fn open_record_stream(
+    mainloop: &mut Mainloop,
+    context: &mut Context,
+    source: &str,
+    sink_input: Option<u32>,
+) -> Result<Stream> {
+    let spec = Spec {
+        format: Format::F32le,
+        rate: SAMPLE_RATE,
+        channels: CHANNELS as u8,
+    };
+    let mut stream = Stream::new(context, "System audio capture", &spec, None)
+        .ok_or_else(|| anyhow!("Failed to create PulseAudio record stream"))?;
+    if let Some(index) = sink_input {
+        stream
+            .set_monitor_stream(index)
+            .map_err(|e| anyhow!("Failed to monitor sink input {}: {}", index, e))?;
+    }
+    let buffer_attr = BufferAttr {
+        maxlength: u32::MAX,
+        tlength: u32::MAX,
+        prebuf: u32::MAX,
+        minreq: u32::MAX,
+        fragsize: FRAGMENT_BYTES,
+    };
+    stream
+        .connect_record(
+            Some(source),
+            Some(&buffer_attr),
+            StreamFlagSet::ADJUST_LATENCY,
+        )
+        .map_err(|e| anyhow!("Failed to record {}: {}", source, e))?;
+
+    loop {
+        if let IterateResult::Quit(_) | IterateResult::Err(_) = mainloop.iterate(true) {
+            return Err(anyhow!(
+                "PulseAudio mainloop stopped while starting the stream"
+            ));
+        }
+        match stream.get_state() {
+            StreamState::Ready => return Ok(stream),
+            StreamState::Failed | StreamState::Terminated => {
+                return Err(anyhow!("Failed to record {}", source));
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Reads everything the server has sent on `stream`, passing each fragment
+/// to `on_samples`. Returns whether anything was read.
+fn drain_stream(
+    stream: &mut Stream,
+    samples: &mut Vec<f32>,
+    mut on_samples: impl FnMut(&[f32]),
+) -> Result<bool> {
+    if matches!(
+        stream.get_state(),
+        StreamState::Failed | StreamState::Terminated
+    ) {
+        return Err(anyhow!("PulseAudio record stream ended"));
+    }
+
+    let mut received = false;
+    loop {
+        match stream.peek() {
+            Ok(PeekResult::Data(bytes)) => {
+                samples.clear();
+                samples.extend(
+                    bytes
+                        .chunks_exact(4)
+                        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]])),
+                );
+            }
+            Ok(PeekResult::Hole(_)) => samples.clear(),
+            Ok(PeekResult::Empty) => return Ok(received),
+            Err(e) => return Err(anyhow!("Failed to read from PulseAudio: {}", e)),
+        }
+        if let Err(e) = stream.discard() {
+            return Err(anyhow!("Failed to read from PulseAudio: {}", e));
+        }
+        if !samples.is_empty() {
+            on_samples(samples.as_slice());
+        }
+        received = true;
+    }
+}
+
+/// Records the monitor of a sink, following the default sink if asked to
+fn record_sink<F>(
+    mainloop: &mut Mainloop,
+    context: &mut Context,
+    target: PulseTarget,
+    mut on_samples: F,
+    stop: &AtomicBool,
+    ready_tx: &mpsc::Sender<std::result::Result<(), String>>,
+) -> Result<()>
+where
+    F: FnMut(&[f32]),
+{
+    let mut sink = match &target {
+        PulseTarget::Sink(name) => name.clone(),
+        _ => default_sink_name(mainloop, context)?
+            .ok_or_else(|| anyhow!("The sound server has no default output"))?,
+    };
+    let mut stream = open_record_stream(mainloop, context, &format!("{}.monitor", sink), None)?;
+
+    // Server events tell us when the default sink changes
+    let server_changed = Rc::new(Cell::new(false));
+    if target == PulseTarget::DefaultSink {
+        context.set_subscribe_callback(Some(Box::new({
+            let server_changed = server_changed.clone();
+            move |facility, _, _| {
+                if facility == Some(Facility::Server) {
+                    server_changed.set(true);
+                }
+            }
+        })));
+        context.subscribe(InterestMaskSet::SERVER, |_| {});
+    }
+
+    info!(
+        "✅ PulseAudio: Recording monitor of {} ({:?})",
+        sink, target
+    );
+    let _ = ready_tx.send(Ok(()));
+
+    let mut samples = Vec::new();
+    while !stop.load(Ordering::SeqCst) {
+        if let IterateResult::Quit(_) | IterateResult::Err(_) = mainloop.iterate(false) {
+            return Err(anyhow!("PulseAudio mainloop stopped"));
+        }
+        let received = drain_stream(&mut stream, &mut samples, &mut on_samples)?;
+
+        if server_changed.replace(false) {
+            if let Ok(Some(default_sink)) = default_sink_name(mainloop, context) {
+                if default_sink != sink {
+                    info!(
+                        "🔄 PulseAudio: Default output changed to {}, following it",
+                        default_sink
+                    );
+                    if let Some(index) = stream.get_index() {
+                        context.introspect().move_source_output_by_name(
+                            index,
+                            &format!("{}.monitor", default_sink),
+                            Some(Box::new(|success| {
+                                if !success {
+                                    warn!("PulseAudio: Failed to move capture to the new default output");
+                                }
+                            })),
+                        );
+                    }
+                    sink = default_sink;
+                }
+            }
+        }
+
+        if !received {
+            std::thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    let _ = stream.disconnect();
+    info!("PulseAudio: Stopped recording monitor of {}", sink);
+    Ok(())
+}
+
+/// A monitor stream of one app's sink input
+struct AppStream {
+    stream: Stream,
+    /// Index of the sink the input plays on; the stream is reopened if the
+    /// input moves
+    sink: u32,
+}
+
+/// Records the sink inputs of the given apps, mixed into one signal. Apps
+/// that start or stop playing during the recording are picked up as the
+/// server reports them.
+fn record_apps<F>(
+    mainloop: &mut Mainloop,
+    context: &mut Context,
+    apps: &[String],
+    mut on_samples: F,
+    stop: &AtomicBool,
+    ready_tx: &mpsc::Sender<std::result::Result<(), String>>,
+) -> Result<()>
+where
+    F: FnMut(&[f32]),
+{
+    let inputs_changed = Rc::new(Cell::new(true));
+    context.set_subscribe_callback(Some(Box::new({
+        let inputs_changed = inputs_changed.clone();
+        move |facility, _, _| {
+            if facility == Some(Facility::SinkInput) {
+                inputs_changed.set(true);
+            }
+        }
+    })));
+    context.subscribe(InterestMaskSet::SINK_INPUT, |_| {});
+
+    info!("✅ PulseAudio: Recording the audio of {:?}", apps);
+    let _ = ready_tx.send(Ok(()));
+
+    let mut streams: HashMap<u32, AppStream> = HashMap::new();
+    let mut mixer = AppMixer::default();
+    let mut samples = Vec::new();
+    let chunk = (FRAGMENT_BYTES / 4) as usize;
+    while !stop.load(Ordering::SeqCst) {
+        if let IterateResult::Quit(_) | IterateResult::Err(_) = mainloop.iterate(false) {
+            return Err(anyhow!("PulseAudio mainloop stopped"));
+        }
+
+        if inputs_changed.replace(false) {
+            let inputs = query_sink_inputs(mainloop, context)?;
+            let sinks = query_sinks(mainloop, context)?;
+            let wanted: Vec<&PulseSinkInput> =
+                inputs.iter().filter(|input| input.matches(apps)).collect();
+
+            streams.retain(|index, app_stream| {
+                let keep = wanted
+                    .iter()
+                    .any(|input| input.index == *index && input.sink == app_stream.sink);
+                if !keep {
+                    let _ = app_stream.stream.disconnect();
+                    mixer.remove(*index);
+                }
+                keep
+            });
+            for input in wanted {
+                if streams.contains_key(&input.index) {
+                    continue;
+                }
+                let Some(sink) = sinks.iter().find(|sink| sink.index == input.sink) else {
+                    continue;
+                };
+                match open_record_stream(mainloop, context, &sink.monitor_source, Some(input.index))
+                {
+                    Ok(stream) => {
+                        info!(
+                            "🎧 PulseAudio: Recording {} ({}) on {}",
+                            input.app_name, input.media_name, sink.name
+                        );
+                        streams.insert(
+                            input.index,
+                            AppStream {
+                                stream,
+                                sink: input.sink,
+                            },
+                        );
+                    }
+                    Err(e) => warn!("PulseAudio: Can't record {}: {}", input.app_name, e),
+                }
+            }
+        }
+
+        let mut received = false;
+        let mut ended = Vec::new();
+        for (index, app_stream) in streams.iter_mut() {
+            match drain_stream(&mut app_stream.stream, &mut samples, |data| {
+                mixer.push(*index, data)
+            }) {
+                Ok(read) => received |= read,
+                // The app went away; the next sink input event cleans up
+                Err(_) => ended.push(*index),
+            }
+        }
+        for index in ended {
+            streams.remove(&index);
+            mixer.remove(index);
+        }
+        while let Some(mixed) = mixer.pop_chunk(chunk) {
+            on_samples(&mixed);
+        }
+
+        if !received {
+            std::thread::sleep(Duration::from_millis(5));
+        }
+    }
+
+    for (_, mut app_stream) in streams {
+        let _ = app_stream.stream.disconnect();
+    }
+    info!("PulseAudio: Stopped recording the audio of {:?}", apps);
+    Ok(())
+}
+
+/// Mixes the monitor streams of several apps into one signal. Apps only send
+/// audio while playing, so a stream that falls a whole chunk behind the
+/// others is padded with silence instead of holding the mix back.
+#[derive(Default)]
+struct AppMixer {
+    buffers: HashMap<u32, VecDeque<f32>>,
+}
+
+impl AppMixer {
+    fn push(&mut self, id: u32, samples: &[f32]) {
+        self.buffers.entry(id).or_default().extend(samples);
+    }
+
+    fn remove(&mut self, id: u32) {
+        self.buffers.remove(&id);
+    }
+
+    /// Mixes the next `chunk` samples, if enough audio has arrived
+    fn pop_chunk(&mut self, chunk: usize) -> Option<Vec<f32>> {
+        let longest = self.buffers.values().map(VecDeque::len).max()?;
+        let all_ready = self.buffers.values().all(|buffer| buffer.len() >= chunk);
+        if longest < chunk || (!all_ready && longest < 2 * chunk) {
+            return None;
+        }
+
+        let mut mixed = vec![0.0f32; chunk];
+        for buffer in self.buffers.values_mut() {
+            let take = buffer.len().min(chunk);
+            for (out, sample) in mixed.iter_mut().zip(buffer.drain(..take)) {
+                *out += sample;
+            }
+        }
+        for sample in &mut mixed {
+            *sample = sample.clamp(-1.0, 1.0);
+        }
+        Some(mixed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sink_input_matches_app_filter() {
+        let input = PulseSinkInput {
+            index: 7,
+            sink: 0,
+            app_name: "Firefox".to_string(),
+            binary: Some("firefox-bin".to_string()),
+            pid: Some(4242),
+            media_name: "Meet - Weekly sync".to_string(),
+        };
+        assert!(input.matches(&["firefox".to_string()]));
+        assert!(input.matches(&["zoom".to_string(), "FIREFOX-BIN".to_string()]));
+        assert!(!input.matches(&["Zoom".to_string()]));
+        assert!(!input.matches(&[]));
+    }
+
+    #[test]
+    fn test_app_mixer() {
+        let mut mixer = AppMixer::default();
+        mixer.push(1, &[0.25; 4]);
+        // Waits for the second app
+        mixer.push(2, &[0.5; 2]);
+        assert_eq!(mixer.pop_chunk(4), None);
+
+        mixer.push(2, &[0.5, 0.75]);
+        assert_eq!(mixer.pop_chunk(4), Some(vec![0.75, 0.75, 0.75, 1.0]));
+
+        // An app that stopped playing is padded with silence once the other
+        // is a chunk ahead
+        mixer.push(1, &[0.25; 8]);
+        mixer.push(2, &[0.5]);
+        assert_eq!(mixer.pop_chunk(4), Some(vec![0.75, 0.25, 0.25, 0.25]));
+        assert_eq!(mixer.pop_chunk(4), None);
+
+        mixer.remove(2);
+        assert_eq!(mixer.pop_chunk(4), Some(vec![0.25; 4]));
+        assert_eq!(mixer.pop_chunk(4), None);
+    }
+}
@@ -1,4 +1,5 @@
 use crate::api::TranscriptSegment;
+use crate::audio::transcription::WordTiming;
 use anyhow::Result;
 use log::{debug, info};
 use std::path::Path;
@@ -35,11 +36,14 @@ pub(crate) async fn unload_engine_after_batch(use_parakeet: bool) {
 }
 
 /// Create transcript segments from transcription results.
-/// Each tuple is (text, start_ms, end_ms) from VAD timestamps.
-pub(crate) fn create_transcript_segments(transcripts: &[(String, f64, f64)]) -> Vec<TranscriptSegment> {
+/// Each tuple is (text, start_ms, end_ms, words) with VAD timestamps and word
+/// timings already in seconds from recording start.
+pub(crate) fn create_transcript_segments(
+    transcripts: &[(String, f64, f64, Vec<WordTiming>)],
+) -> Vec<TranscriptSegment> {
     transcripts
         .iter()
-        .map(|(text, start_ms, end_ms)| {
+        .map(|(text, start_ms, end_ms, words)| {
             let start_seconds = start_ms / 1000.0;
             let end_seconds = end_ms / 1000.0;
             let duration = end_seconds - start_seconds;
@@ -51,6 +55,8 @@ pub(crate) fn create_transcript_segments(transcripts: &[(String, f64, f64)]) ->
                 audio_start_time: Some(start_seconds),
                 audio_end_time: Some(end_seconds),
                 duration: Some(duration),
+                speaker_label: None,
+                words: words.clone(),
             }
         })
         .collect()
@@ -73,6 +79,8 @@ pub(crate) fn write_transcripts_json(folder: &Path, segments: &[TranscriptSegmen
                 "audio_start_time": s.audio_start_time,
                 "audio_end_time": s.audio_end_time,
                 "duration": s.duration,
+                "speaker_label": s.speaker_label,
+                "words": s.words,
                 "sequence_id": i
             })
         }).collect::<Vec<_>>()
@@ -17,6 +17,7 @@ use symphonia::core::meta::MetadataOptions;
 use symphonia::core::probe::Hint;
 
 use super::audio_processing::{audio_to_mono, resample, resample_audio};
+use super::denoise::{denoise_recording, DenoiseSettings, DENOISE_SAMPLE_RATE};
 use super::ffmpeg::find_ffmpeg_path;
 
 /// Extensions requiring ffmpeg pre-conversion (Symphonia lacks these demuxers/codecs)
@@ -106,6 +107,37 @@ impl DecodedAudio {
             mono_samples
         }
     }
+
+    /// Reduce background noise before the audio is converted for Whisper.
+    ///
+    /// The denoiser runs on 48kHz mono, so the result is mono at 48kHz and
+    /// keeps the original duration. Returns the audio unchanged if `settings`
+    /// disable noise suppression.
+    pub fn denoised(self, settings: DenoiseSettings) -> Result<DecodedAudio> {
+        if !settings.is_active() {
+            return Ok(self);
+        }
+
+        let mono_samples = if self.channels > 1 {
+            audio_to_mono(&self.samples, self.channels)
+        } else {
+            self.samples
+        };
+        let mono_samples = normalize_audio_samples(mono_samples);
+        let samples_48k = chunked_resample_with_progress(
+            &mono_samples,
+            self.sample_rate,
+            DENOISE_SAMPLE_RATE,
+            None,
+        );
+
+        Ok(DecodedAudio {
+            samples: denoise_recording(&samples_48k, settings)?,
+            sample_rate: DENOISE_SAMPLE_RATE,
+            channels: 1,
+            duration_seconds: self.duration_seconds,
+        })
+    }
 }
 
 /// Resample large audio files in fixed-size chunks through the sinc resampler.
@@ -0,0 +1,221 @@
+// Noise suppression for live and imported audio
+//
+// Keyboard clicks and fan noise open VAD segments that Whisper then
+// hallucinates on. A Denoiser runs on each capture stream in
+// AudioCapture::process_audio_data, ahead of mixing and VAD, and on imported
+// files once they are decoded. Which sources are denoised, and how strongly,
+// comes from the recording preferences.
+
+use anyhow::Result;
+use log::info;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::sync::RwLock;
+
+use super::audio_processing::NoiseSuppressionProcessor;
+use super::recording_state::DeviceType;
+
+/// Sample rate denoisers run at
+pub const DENOISE_SAMPLE_RATE: u32 = 48000;
+
+/// A noise suppressor for 48kHz mono audio
+///
+/// Implementations may buffer partial frames, so the output of one call can
+/// be shorter or longer than its input; over a whole stream it lags the input
+/// by `latency_samples()`.
+pub trait Denoiser: Send {
+    /// Denoise the next samples of the stream
+    fn process(&mut self, samples: &[f32]) -> Vec<f32>;
+
+    /// Number of input samples waiting for a complete frame
+    fn buffered_samples(&self) -> usize;
+
+    /// How far the output lags the input, in samples
+    fn latency_samples(&self) -> usize;
+}
+
+/// Noise suppression settings of one audio source
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct DenoiseSettings {
+    pub enabled: bool,
+    /// 0.0 leaves the audio untouched, 1.0 removes as much noise as possible
+    pub strength: f32,
+}
+
+impl DenoiseSettings {
+    pub const fn new(enabled: bool, strength: f32) -> Self {
+        Self { enabled, strength }
+    }
+
+    /// Whether this source needs a denoiser at all
+    pub fn is_active(&self) -> bool {
+        self.enabled && self.strength > 0.0
+    }
+}
+
+/// Noise suppression settings per audio source
+///
+/// Every source is off until the user opts in: Whisper copes with most
+/// background noise on its own, and denoising changes the saved recording.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NoiseSuppressionSettings {
+    pub microphone: DenoiseSettings,
+    /// Call audio has usually been denoised by the sender already
+    pub system: DenoiseSettings,
+    pub imports: DenoiseSettings,
+}
+
+impl Default for NoiseSuppressionSettings {
+    fn default() -> Self {
+        Self {
+            microphone: DenoiseSettings::new(false, 1.0),
+            system: DenoiseSettings::new(false, 1.0),
+            imports: DenoiseSettings::new(false, 1.0),
+        }
+    }
+}
+
+impl NoiseSuppressionSettings {
+    /// Settings of a capture stream
+    pub fn for_device(&self, device_type: &DeviceType) -> DenoiseSettings {
+        match device_type {
+            DeviceType::Microphone => self.microphone,
+            DeviceType::System => self.system,
+        }
+    }
+}
+
+static NOISE_SUPPRESSION: Lazy<RwLock<NoiseSuppressionSettings>> =
+    Lazy::new(|| RwLock::new(NoiseSuppressionSettings::default()));
+
+/// Get the noise suppression settings new recordings and imports use
+pub fn settings() -> NoiseSuppressionSettings {
+    *NOISE_SUPPRESSION.read().unwrap()
+}
+
+/// Set the noise suppression settings new recordings and imports use
+pub fn set_settings(settings: NoiseSuppressionSettings) {
+    *NOISE_SUPPRESSION.write().unwrap() = settings;
+}
+
+/// Create a denoiser with the given settings, or None if they disable it
+pub fn create_denoiser(settings: DenoiseSettings) -> Result<Option<Box<dyn Denoiser>>> {
+    if !settings.is_active() {
+        return Ok(None);
+    }
+    let processor =
+        NoiseSuppressionProcessor::with_strength(DENOISE_SAMPLE_RATE, settings.strength)?;
+    Ok(Some(Box::new(processor)))
+}
+
+/// Denoise a complete recording at 48kHz
+///
+/// Unlike a live stream, the output is aligned with the input and has the
+/// same length.
+pub fn denoise_recording(samples: &[f32], settings: DenoiseSettings) -> Result<Vec<f32>> {
+    let Some(mut denoiser) = create_denoiser(settings)? else {
+        return Ok(samples.to_vec());
+    };
+    info!(
+        "Denoising {:.1}s of audio (strength {:.2})",
+        samples.len() as f64 / DENOISE_SAMPLE_RATE as f64,
+        settings.strength
+    );
+
+    Ok(process_aligned(denoiser.as_mut(), samples))
+}
+
+/// Run `samples` through `denoiser` and undo its latency
+fn process_aligned(denoiser: &mut dyn Denoiser, samples: &[f32]) -> Vec<f32> {
+    let latency = denoiser.latency_samples();
+    let mut output = denoiser.process(samples);
+
+    // Push the audio still buffered or delayed out with silence
+    let silence = vec![0.0; latency.max(1)];
+    while output.len() < samples.len() + latency {
+        output.extend(denoiser.process(&silence));
+    }
+
+    output.drain(..latency);
+    output.truncate(samples.len());
+    output
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic white noise at `level` (peak)
+    fn noise(len: usize, level: f32) -> Vec<f32> {
+        let mut state = 1u32;
+        (0..len)
+            .map(|_| {
+                state = state.wrapping_mul(1664525).wrapping_add(1013904223);
+                level * ((state >> 8) as f32 / (1u32 << 23) as f32 - 1.0)
+            })
+            .collect()
+    }
+
+    fn energy(samples: &[f32]) -> f32 {
+        samples.iter().map(|s| s * s).sum()
+    }
+
+    #[test]
+    fn test_settings_per_source() {
+        // Opt-in: nothing is denoised for existing users
+        let settings = NoiseSuppressionSettings::default();
+        assert!(!settings.for_device(&DeviceType::Microphone).is_active());
+        assert!(!settings.for_device(&DeviceType::System).is_active());
+        assert!(!settings.imports.is_active());
+        assert!(!DenoiseSettings::new(true, 0.0).is_active());
+
+        // Sources missing from stored preferences keep their defaults
+        let stored: NoiseSuppressionSettings =
+            serde_json::from_str(r#"{"microphone": {"enabled": true, "strength": 0.5}}"#).unwrap();
+        assert_eq!(stored.microphone, DenoiseSettings::new(true, 0.5));
+        assert!(stored.for_device(&DeviceType::Microphone).is_active());
+        assert_eq!(stored.imports, NoiseSuppressionSettings::default().imports);
+    }
+
+    #[test]
+    fn test_denoise_recording_is_aligned() {
+        // At zero strength only the delayed dry signal comes out, so any
+        // misalignment would show
+        let input = noise(DENOISE_SAMPLE_RATE as usize / 2 + 123, 0.1);
+        let mut denoiser =
+            NoiseSuppressionProcessor::with_strength(DENOISE_SAMPLE_RATE, 0.0).unwrap();
+        assert_eq!(process_aligned(&mut denoiser, &input), input);
+
+        let settings = DenoiseSettings::new(true, 1.0);
+        assert_eq!(
+            denoise_recording(&input, settings).unwrap().len(),
+            input.len()
+        );
+        let disabled = DenoiseSettings::new(false, 1.0);
+        assert_eq!(denoise_recording(&input, disabled).unwrap(), input);
+    }
+
+    #[test]
+    fn test_steady_noise_is_suppressed() {
+        // Fan-like hiss with nobody talking
+        let input = noise(DENOISE_SAMPLE_RATE as usize * 2, 0.05);
+        let full = denoise_recording(&input, DenoiseSettings::new(true, 1.0)).unwrap();
+        let half = denoise_recording(&input, DenoiseSettings::new(true, 0.5)).unwrap();
+
+        let settled = DENOISE_SAMPLE_RATE as usize / 2..input.len();
+        let reduction = |output: &[f32]| {
+            10.0 * (energy(&input[settled.clone()]) / energy(&output[settled.clone()])).log10()
+        };
+        assert!(
+            reduction(&full) > 6.0,
+            "noise reduced by only {:.1}dB",
+            reduction(&full)
+        );
+        assert!(reduction(&half) > 1.0 && reduction(&half) < reduction(&full));
+    }
+}
@@ -1,6 +1,8 @@
 use anyhow::Result;
 use cpal::traits::{DeviceTrait, HostTrait};
+use log::{debug, warn};
 
+use crate::audio::capture::{get_current_backend, pulse, AudioCaptureBackend};
 use crate::audio::devices::configuration::{AudioDevice, DeviceType};
 
 /// Configure Linux audio devices using ALSA/PulseAudio
@@ -14,6 +16,32 @@ pub fn configure_linux_audio(host: &cpal::Host) -> Result<Vec<AudioDevice>> {
         }
     }
 
+    // With the PipeWire/PulseAudio backend, list the sound server's sinks: they
+    // have proper names and the default sink's monitor follows output switches
+    let sinks = if get_current_backend() == AudioCaptureBackend::PulseAudio {
+        pulse::list_sinks()
+    } else {
+        Ok(Vec::new())
+    };
+    match sinks {
+        Ok(sinks) if !sinks.is_empty() => {
+            devices.push(AudioDevice::new(
+                pulse::DEFAULT_SINK_DEVICE.to_string(),
+                DeviceType::Output,
+            ));
+            for sink in sinks {
+                debug!(
+                    "PipeWire/PulseAudio sink: {} ({} Hz, {} ch, default: {}, bluetooth: {})",
+                    sink.name, sink.sample_rate, sink.channels, sink.is_default, sink.is_bluetooth
+                );
+                devices.push(AudioDevice::new(sink.device_name(), DeviceType::Output));
+            }
+            return Ok(devices);
+        }
+        Ok(_) => {}
+        Err(e) => warn!("PipeWire/PulseAudio unavailable ({}), scanning ALSA", e),
+    }
+
     // Add PulseAudio monitor sources for system audio
     if let Ok(pulse_host) = cpal::host_from_id(cpal::HostId::Alsa) {
         for device in pulse_host.input_devices()? {
@@ -35,6 +35,19 @@ pub fn default_output_device() -> Result<AudioDevice> {
         return Ok(AudioDevice::new(device.name()?, DeviceType::Output));
     }
 
+    #[cfg(target_os = "linux")]
+    {
+        use crate::audio::capture::{get_current_backend, pulse, AudioCaptureBackend};
+
+        // Record whatever output is default at the time, following changes
+        if get_current_backend() == AudioCaptureBackend::PulseAudio && pulse::is_available() {
+            return Ok(AudioDevice::new(
+                pulse::DEFAULT_SINK_DEVICE.to_string(),
+                DeviceType::Output,
+            ));
+        }
+    }
+
     #[cfg(not(any(target_os = "macos", target_os = "windows")))]
     {
         let host = cpal::default_host();
@@ -0,0 +1,450 @@
+// Acoustic echo cancellation
+//
+// When the far end of a call plays through laptop speakers, the microphone
+// picks it up again. Mixed with the system stream, every remote utterance
+// would then be transcribed twice, slightly out of sync.
+//
+// EchoCanceller uses the system stream as the reference signal and subtracts
+// its estimated echo from the microphone before mixing and VAD. The echo path
+// is modelled by a partitioned-block frequency-domain adaptive filter
+// (overlap-save, 10ms blocks, 250ms tail). It runs as two filters: the
+// background filter adapts on every block, and is copied into the foreground
+// filter, which produces the output, only while it cancels more echo. Near-end
+// speech during double talk slows adaptation down (it lowers the correlation
+// between the microphone and the echo estimate), and whatever it still does
+// to the background filter doesn't reach the output.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use log::info;
+use realfft::num_complex::Complex32;
+use realfft::{ComplexToReal, RealFftPlanner, RealToComplex};
+
+/// Length of the echo path the filter can model (speaker-to-mic delay plus
+/// room reverberation, plus any skew between the two capture streams)
+const TAIL_MS: u32 = 250;
+
+/// Normalized adaptation step of the background filter
+const STEP_SIZE: f32 = 0.5;
+
+/// Reference blocks quieter than this (mean square) don't adapt the filter
+const MIN_REFERENCE_POWER: f32 = 1e-8;
+
+/// The step is scaled down as the correlation between the microphone and the
+/// echo estimate drops from FULL_STEP_CORRELATION to DOUBLE_TALK_CORRELATION
+/// (near-end speech the reference can't explain), but never below MIN_STEP_SCALE
+/// so the filter can still follow a changed echo path
+const FULL_STEP_CORRELATION: f32 = 0.95;
+const DOUBLE_TALK_CORRELATION: f32 = 0.5;
+const MIN_STEP_SCALE: f32 = 0.1;
+
+/// Smoothing of the per-block energies the two filters are compared on
+const ERROR_SMOOTHING: f32 = 0.5;
+
+/// The background filter replaces the foreground one once its error is this
+/// much lower, and at least 6dB below the microphone...
+const COPY_RATIO: f32 = 0.9;
+const MIN_CANCELLATION: f32 = 0.25;
+
+/// ...and is reset to the foreground filter once its error is this much higher
+const RESET_RATIO: f32 = 4.0;
+
+static ECHO_CANCELLATION_ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Whether new recordings cancel speaker echo from the microphone
+pub fn is_enabled() -> bool {
+    ECHO_CANCELLATION_ENABLED.load(Ordering::Relaxed)
+}
+
+/// Enable or disable echo cancellation for new recordings
+pub fn set_enabled(enabled: bool) {
+    ECHO_CANCELLATION_ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Removes the echo of a reference signal (system audio) from the microphone
+pub struct EchoCanceller {
+    block_size: usize,
+    fft: Arc<dyn RealToComplex<f32>>,
+    ifft: Arc<dyn ComplexToReal<f32>>,
+    /// Spectra of the last reference blocks (each with the block before it),
+    /// newest first
+    reference_spectra: VecDeque<Vec<Complex32>>,
+    /// Per-bin energy of `reference_spectra`, for step normalization
+    reference_energy: Vec<f32>,
+    previous_reference: Vec<f32>,
+    background: Vec<Vec<Complex32>>,
+    foreground: Vec<Vec<Complex32>>,
+    background_error: f32,
+    foreground_error: f32,
+    mic_energy: f32,
+}
+
+impl EchoCanceller {
+    /// Create an echo canceller for mono audio at `sample_rate`
+    pub fn new(sample_rate: u32) -> Self {
+        let block_size = (sample_rate / 100).max(1) as usize;
+        let fft_size = block_size * 2;
+        let bins = block_size + 1;
+        let partitions = (TAIL_MS / 10) as usize;
+
+        let mut planner = RealFftPlanner::<f32>::new();
+
+        info!(
+            "Initializing echo cancellation: {} partitions of {} samples ({}ms tail) @ {}Hz",
+            partitions, block_size, TAIL_MS, sample_rate
+        );
+
+        Self {
+            block_size,
+            fft: planner.plan_fft_forward(fft_size),
+            ifft: planner.plan_fft_inverse(fft_size),
+            reference_spectra: (0..partitions)
+                .map(|_| vec![Complex32::default(); bins])
+                .collect(),
+            reference_energy: vec![0.0; bins],
+            previous_reference: vec![0.0; block_size],
+            background: vec![vec![Complex32::default(); bins]; partitions],
+            foreground: vec![vec![Complex32::default(); bins]; partitions],
+            background_error: 0.0,
+            foreground_error: 0.0,
+            mic_energy: 0.0,
+        }
+    }
+
+    /// Cancel the echo of `reference` from `mic`
+    ///
+    /// Both must be the same stretch of time; the output has the length of `mic`.
+    /// Audio is processed in 10ms blocks, so windows should be a multiple of
+    /// 10ms (the pipeline's are); the last block of any other is zero-padded.
+    pub fn process(&mut self, mic: &[f32], reference: &[f32]) -> Vec<f32> {
+        let mut output = Vec::with_capacity(mic.len());
+        let mut mic_block = vec![0.0; self.block_size];
+        let mut reference_block = vec![0.0; self.block_size];
+
+        for start in (0..mic.len()).step_by(self.block_size) {
+            for i in 0..self.block_size {
+                mic_block[i] = mic.get(start + i).copied().unwrap_or(0.0);
+                reference_block[i] = reference.get(start + i).copied().unwrap_or(0.0);
+            }
+            let cleaned = self.process_block(&mic_block, &reference_block);
+            let len = self.block_size.min(mic.len() - start);
+            output.extend_from_slice(&cleaned[..len]);
+        }
+
+        output
+    }
+
+    fn process_block(&mut self, mic: &[f32], reference: &[f32]) -> Vec<f32> {
+        // Overlap-save: each spectrum covers the previous block and this one
+        let mut frame = Vec::with_capacity(self.block_size * 2);
+        frame.extend_from_slice(&self.previous_reference);
+        frame.extend_from_slice(reference);
+        self.previous_reference.copy_from_slice(reference);
+        let spectrum = self.forward(frame);
+
+        let oldest = self.reference_spectra.pop_back().unwrap_or_default();
+        for (k, energy) in self.reference_energy.iter_mut().enumerate() {
+            let removed = oldest.get(k).map_or(0.0, |x| x.norm_sqr());
+            *energy = (*energy + spectrum[k].norm_sqr() - removed).max(0.0);
+        }
+        self.reference_spectra.push_front(spectrum);
+
+        let foreground_out = self.filter_error(&self.foreground, mic);
+        let background_out = self.filter_error(&self.background, mic);
+
+        let reference_power = energy(reference) / reference.len() as f32;
+        if reference_power > MIN_REFERENCE_POWER {
+            let mic_energy = energy(mic);
+            let foreground_echo: f32 = mic
+                .iter()
+                .zip(&foreground_out)
+                .map(|(d, e)| (d - e) * (d - e))
+                .sum();
+            let step_scale = if foreground_echo < 1e-3 * mic_energy {
+                // Nothing learnt yet to tell double talk from a new echo path
+                1.0
+            } else {
+                let correlation = echo_correlation(mic, &foreground_out)
+                    .max(echo_correlation(mic, &background_out));
+                ((correlation - DOUBLE_TALK_CORRELATION)
+                    / (FULL_STEP_CORRELATION - DOUBLE_TALK_CORRELATION))
+                    .clamp(MIN_STEP_SCALE, 1.0)
+            };
+            self.adapt_background(&background_out, step_scale);
+
+            let smooth = |previous: f32, current: f32| {
+                ERROR_SMOOTHING * previous + (1.0 - ERROR_SMOOTHING) * current
+            };
+            self.foreground_error = smooth(self.foreground_error, energy(&foreground_out));
+            self.background_error = smooth(self.background_error, energy(&background_out));
+            self.mic_energy = smooth(self.mic_energy, mic_energy);
+
+            if self.background_error < COPY_RATIO * self.foreground_error
+                && self.background_error < MIN_CANCELLATION * self.mic_energy
+            {
+                self.foreground.clone_from(&self.background);
+                self.foreground_error = self.background_error;
+            } else if self.background_error > RESET_RATIO * self.foreground_error {
+                self.background.clone_from(&self.foreground);
+                self.background_error = self.foreground_error;
+            }
+        }
+
+        // A stale foreground filter (the echo path changed) must not add echo
+        if energy(&foreground_out) > energy(mic) {
+            mic.to_vec()
+        } else {
+            foreground_out
+        }
+    }
+
+    /// Microphone minus the echo `filter` predicts for the current block
+    fn filter_error(&self, filter: &[Vec<Complex32>], mic: &[f32]) -> Vec<f32> {
+        let mut echo = vec![Complex32::default(); self.block_size + 1];
+        for (weights, spectrum) in filter.iter().zip(&self.reference_spectra) {
+            for ((e, w), x) in echo.iter_mut().zip(weights).zip(spectrum) {
+                *e += w * x;
+            }
+        }
+        let echo = self.inverse(echo);
+
+        // The first half is circular-convolution garbage
+        mic.iter()
+            .zip(&echo[self.block_size..])
+            .map(|(d, y)| d - y)
+            .collect()
+    }
+
+    /// One normalized, gradient-constrained step of the background filter
+    fn adapt_background(&mut self, error: &[f32], step_scale: f32) {
+        let mut frame = vec![0.0; self.block_size];
+        frame.extend_from_slice(error);
+        let error_spectrum = self.forward(frame);
+
+        // Regularizes bins the reference barely excites
+        let regularization = MIN_REFERENCE_POWER * (self.block_size * 2) as f32;
+        let step = step_scale * STEP_SIZE;
+
+        for p in 0..self.background.len() {
+            let gradient: Vec<Complex32> = self.reference_spectra[p]
+                .iter()
+                .zip(&error_spectrum)
+                .zip(&self.reference_energy)
+                .map(|((x, e), energy)| x.conj() * e * (step / (energy + regularization)))
+                .collect();
+
+            // Keep the filter causal and within one block per partition
+            let mut gradient = self.inverse(gradient);
+            gradient[self.block_size..].fill(0.0);
+            let gradient = self.forward(gradient);
+
+            for (w, g) in self.background[p].iter_mut().zip(&gradient) {
+                *w += g;
+            }
+        }
+    }
+
+    fn forward(&self, mut frame: Vec<f32>) -> Vec<Complex32> {
+        let mut spectrum = self.fft.make_output_vec();
+        self.fft
+            .process(&mut frame, &mut spectrum)
+            .expect("FFT buffer sizes match the plan");
+        spectrum
+    }
+
+    /// Inverse FFT, scaled so that `inverse(forward(x)) == x`
+    fn inverse(&self, mut spectrum: Vec<Complex32>) -> Vec<f32> {
+        // A real signal's DC and Nyquist bins have no imaginary part
+        spectrum[0].im = 0.0;
+        if let Some(last) = spectrum.last_mut() {
+            last.im = 0.0;
+        }
+        let mut frame = self.ifft.make_output_vec();
+        self.ifft
+            .process(&mut spectrum, &mut frame)
+            .expect("FFT buffer sizes match the plan");
+        let scale = 1.0 / frame.len() as f32;
+        frame.iter_mut().for_each(|s| *s *= scale);
+        frame
+    }
+}
+
+fn energy(samples: &[f32]) -> f32 {
+    samples.iter().map(|s| s * s).sum()
+}
+
+/// Normalized cross-correlation of the microphone and the echo a filter
+/// estimated (the microphone minus the filter's output): close to 1 while
+/// the microphone only hears the reference
+fn echo_correlation(mic: &[f32], error: &[f32]) -> f32 {
+    let (mut cross, mut echo) = (0.0f32, 0.0f32);
+    for (d, e) in mic.iter().zip(error) {
+        let y = d - e;
+        cross += d * y;
+        echo += y * y;
+    }
+    let norm = (energy(mic) * echo).sqrt();
+    if norm > 0.0 {
+        cross / norm
+    } else {
+        0.0
+    }
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE_RATE: u32 = 48000;
+    /// The pipeline's mixing window
+    const WINDOW: usize = 28800;
+
+    /// Deterministic noise in [-1, 1]
+    struct Noise(u32);
+
+    impl Noise {
+        fn next(&mut self) -> f32 {
+            self.0 = self.0.wrapping_mul(1664525).wrapping_add(1013904223);
+            (self.0 >> 8) as f32 / (1u32 << 23) as f32 - 1.0
+        }
+    }
+
+    /// Speech-like fixture: syllables of random length, each a vowel (a
+    /// gliding pitch with formant-shaped harmonics) with some breath noise,
+    /// separated by pauses
+    fn talker(seconds: f32, pitch_hz: f32, seed: u32) -> Vec<f32> {
+        let len = (seconds * SAMPLE_RATE as f32) as usize;
+        let ms = SAMPLE_RATE as f32 / 1000.0;
+        let mut noise = Noise(seed);
+        let mut uniform = |low: f32, high: f32| low + (high - low) * (noise.next() + 1.0) / 2.0;
+        let mut breath = Noise(seed.wrapping_add(1));
+        let mut samples = Vec::with_capacity(len);
+        let mut phase = 0.0f32;
+
+        while samples.len() < len {
+            let syllable = (uniform(120.0, 320.0) * ms) as usize;
+            let pause = (uniform(40.0, 200.0) * ms) as usize;
+            let (start_pitch, end_pitch) =
+                (pitch_hz * uniform(0.8, 1.2), pitch_hz * uniform(0.8, 1.2));
+            let (f1, f2) = (uniform(300.0, 800.0), uniform(900.0, 2300.0));
+
+            for n in 0..syllable {
+                let progress = n as f32 / syllable as f32;
+                let envelope = (std::f32::consts::PI * progress).sin();
+                let pitch = start_pitch + (end_pitch - start_pitch) * progress;
+                phase += 2.0 * std::f32::consts::PI * pitch / SAMPLE_RATE as f32;
+                let voiced: f32 = (1..=30)
+                    .map(|h| (h as f32, h as f32 * pitch))
+                    .filter(|(_, f)| *f < 4000.0)
+                    .map(|(h, f)| {
+                        let formants = 1.0
+                            + 3.0 * (-((f - f1) / 150.0).powi(2)).exp()
+                            + 2.0 * (-((f - f2) / 250.0).powi(2)).exp();
+                        formants * (h * phase).sin() / h
+                    })
+                    .sum();
+                samples.push(0.1 * envelope * (voiced + 0.2 * breath.next()));
+            }
+            samples.resize(samples.len() + pause, 0.0);
+        }
+
+        samples.truncate(len);
+        samples
+    }
+
+    /// Fixture room: the direct sound 20ms after playback, a few early
+    /// reflections and a decaying tail of late ones
+    fn echo_path(signal: &[f32]) -> Vec<f32> {
+        let ms = SAMPLE_RATE as usize / 1000;
+        let mut noise = Noise(7);
+        let mut response: Vec<(usize, f32)> = vec![
+            (20 * ms, 0.5),
+            (25 * ms, 0.25),
+            (31 * ms, -0.15),
+            (42 * ms, 0.1),
+        ];
+        for i in 1..=40 {
+            let delay = 20 * ms + i * ms / 2 + 7;
+            response.push((delay, 0.1 * (-(i as f32) / 10.0).exp() * noise.next()));
+        }
+
+        let mut echo = vec![0.0; signal.len()];
+        for (delay, h) in response {
+            for (out, s) in echo[delay.min(signal.len())..].iter_mut().zip(signal) {
+                *out += h * s;
+            }
+        }
+        echo
+    }
+
+    /// Run the canceller over `mic` in pipeline-sized windows
+    fn cancel(mic: &[f32], reference: &[f32]) -> Vec<f32> {
+        let mut canceller = EchoCanceller::new(SAMPLE_RATE);
+        mic.chunks(WINDOW)
+            .zip(reference.chunks(WINDOW))
+            .flat_map(|(mic, reference)| canceller.process(mic, reference))
+            .collect()
+    }
+
+    fn db(ratio: f32) -> f32 {
+        10.0 * ratio.log10()
+    }
+
+    #[test]
+    fn test_far_end_echo_is_suppressed() {
+        let far = talker(8.0, 140.0, 1);
+        let mic = echo_path(&far);
+
+        let cleaned = cancel(&mic, &far);
+        assert_eq!(cleaned.len(), mic.len());
+
+        // Once converged, little of the far end is left in the microphone
+        let converged = SAMPLE_RATE as usize * 4..mic.len();
+        let suppression = db(energy(&mic[converged.clone()]) / energy(&cleaned[converged]));
+        assert!(
+            suppression > 18.0,
+            "echo suppressed by only {:.1}dB",
+            suppression
+        );
+    }
+
+    #[test]
+    fn test_near_end_speech_survives_double_talk() {
+        let far = talker(8.0, 140.0, 1);
+        let echo = echo_path(&far);
+        // The local speaker starts talking over the far end after 4 seconds
+        let double_talk = SAMPLE_RATE as usize * 4..far.len();
+        let mut near = talker(8.0, 220.0, 2);
+        near[..double_talk.start].fill(0.0);
+        let mic: Vec<f32> = echo.iter().zip(&near).map(|(e, s)| e + s).collect();
+
+        let cleaned = cancel(&mic, &far);
+
+        // Everything in the output besides the local speaker is echo the
+        // canceller left or speech it distorted
+        let residual: Vec<f32> = cleaned.iter().zip(&near).map(|(c, s)| c - s).collect();
+        let near = energy(&near[double_talk.clone()]);
+        let before = db(near / energy(&echo[double_talk.clone()]));
+        let after = db(near / energy(&residual[double_talk]));
+        assert!(
+            after > before + 6.0 && after > 12.0,
+            "local speech {:.1}dB above the echo before, {:.1}dB above the residual after",
+            before,
+            after
+        );
+    }
+
+    #[test]
+    fn test_silent_reference_passes_mic_through() {
+        // Headphones: nothing plays through the speakers
+        let mic = talker(1.0, 220.0, 2);
+        let cleaned = cancel(&mic, &vec![0.0; mic.len()]);
+        assert_eq!(cleaned, mic);
+    }
+}
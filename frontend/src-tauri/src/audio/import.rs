@@ -2,14 +2,22 @@
 
 use crate::api::TranscriptSegment;
 use crate::audio::decoder::{decode_audio_file, decode_audio_file_with_progress};
+use crate::audio::denoise::{DenoiseSettings, NoiseSuppressionSettings};
+use crate::audio::transcription::{
+    is_cloud_provider, load_cloud_provider, offset_words, TranscriptionProvider, WordTiming,
+};
 use crate::audio::vad::get_speech_chunks_with_progress;
 use crate::config::{DEFAULT_WHISPER_MODEL, DEFAULT_PARAKEET_MODEL};
+use crate::database::repositories::speaker::SpeakersRepository;
+use crate::database::repositories::transcript::TranscriptsRepository;
+use crate::diarization::{apply_speaker_labels, DiarizedSpeaker};
 use crate::parakeet_engine::ParakeetEngine;
 use crate::state::AppState;
 use crate::whisper_engine::WhisperEngine;
 use anyhow::{anyhow, Result};
 use log::{debug, error, info, warn};
 use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
@@ -20,7 +28,7 @@ use uuid::Uuid;
 use super::audio_processing::create_meeting_folder;
 use super::common::{create_transcript_segments, split_segment_at_silence, write_transcripts_json};
 use super::constants::AUDIO_EXTENSIONS;
-use super::recording_preferences::get_default_recordings_folder;
+use super::recording_preferences::{get_default_recordings_folder, load_recording_preferences};
 
 /// Global flag to track if import is in progress
 static IMPORT_IN_PROGRESS: AtomicBool = AtomicBool::new(false);
@@ -250,6 +258,87 @@ fn extract_duration_from_metadata(path: &Path) -> Result<f64> {
     Ok(duration_seconds)
 }
 
+/// Receives progress from the import pipeline. The app forwards it to the
+/// frontend as events; the CLI prints it.
+pub trait ImportObserver: Send + Sync {
+    fn progress(&self, stage: &str, progress: u32, message: &str);
+
+    /// Non-fatal problem (e.g. no speech detected)
+    fn warning(&self, warning: ImportWarning) {
+        warn!("{}", warning.warning);
+    }
+
+    /// Checked between stages and inside long-running loops
+    fn is_cancelled(&self) -> bool {
+        IMPORT_CANCELLED.load(Ordering::SeqCst)
+    }
+}
+
+/// Emits import events to the frontend
+struct AppImportObserver<R: Runtime>(AppHandle<R>);
+
+impl<R: Runtime> ImportObserver for AppImportObserver<R> {
+    fn progress(&self, stage: &str, progress: u32, message: &str) {
+        emit_progress(&self.0, stage, progress, message);
+    }
+
+    fn warning(&self, warning: ImportWarning) {
+        warn!("{}", warning.warning);
+        let _ = self.0.emit("import-warning", warning);
+    }
+}
+
+/// A loaded engine for batch transcription
+#[derive(Clone)]
+pub enum ImportEngine {
+    Whisper(Arc<WhisperEngine>),
+    Parakeet(Arc<ParakeetEngine>),
+    /// Deepgram, ElevenLabs, Groq or OpenAI
+    Cloud(Arc<dyn TranscriptionProvider>),
+}
+
+impl ImportEngine {
+    /// Transcribes one 16kHz mono segment, returning text, confidence and word timings
+    /// relative to the segment start
+    pub async fn transcribe(
+        &self,
+        samples: Vec<f32>,
+        language: Option<String>,
+    ) -> Result<(String, f32, Vec<WordTiming>)> {
+        match self {
+            Self::Parakeet(engine) => {
+                let (text, words) = engine.transcribe_audio_with_words(samples).await?;
+                Ok((text, 0.9f32, words))
+            }
+            Self::Whisper(engine) => {
+                let (text, conf, _, words) = engine
+                    .transcribe_audio_with_confidence(samples, language)
+                    .await?;
+                Ok((text, conf, words))
+            }
+            Self::Cloud(provider) => {
+                let result = provider.transcribe(samples, language).await?;
+                Ok((result.text, result.confidence.unwrap_or(0.9f32), result.words))
+            }
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Parakeet(_) => "Parakeet",
+            Self::Whisper(_) => "Whisper",
+            Self::Cloud(provider) => provider.provider_name(),
+        }
+    }
+}
+
+/// Transcript of an audio file, ready to be saved as a meeting or written out
+pub struct TranscribedAudio {
+    pub segments: Vec<TranscriptSegment>,
+    pub speakers: Vec<DiarizedSpeaker>,
+    pub duration_seconds: f64,
+}
+
 /// Start import of an audio file
 pub async fn start_import<R: Runtime>(
     app: AppHandle<R>,
@@ -258,6 +347,7 @@ pub async fn start_import<R: Runtime>(
     language: Option<String>,
     model: Option<String>,
     provider: Option<String>,
+    enable_diarization: Option<bool>,
 ) -> Result<ImportResult> {
     // Acquire guard - ensures flag is cleared even on panic/early return
     let _guard = ImportGuard::acquire().map_err(|e| anyhow!(e))?;
@@ -266,6 +356,7 @@ pub async fn start_import<R: Runtime>(
     IMPORT_CANCELLED.store(false, Ordering::SeqCst);
 
     let use_parakeet = provider.as_deref() == Some("parakeet");
+    let use_cloud = provider.as_deref().is_some_and(is_cloud_provider);
     let result = run_import(
         app.clone(),
         source_path,
@@ -273,11 +364,15 @@ pub async fn start_import<R: Runtime>(
         language,
         model,
         provider,
+        enable_diarization,
     )
     .await;
 
-    // Unload the engine after the batch job (success, failure, or cancellation)
-    super::common::unload_engine_after_batch(use_parakeet).await;
+    // Unload the engine after the batch job (success, failure, or cancellation);
+    // cloud providers hold no local model
+    if !use_cloud {
+        super::common::unload_engine_after_batch(use_parakeet).await;
+    }
 
     // Guard will automatically clear flag on drop
     // No need for manual: IMPORT_IN_PROGRESS.store(false, Ordering::SeqCst);
@@ -315,72 +410,88 @@ async fn run_import<R: Runtime>(
     language: Option<String>,
     model: Option<String>,
     provider: Option<String>,
+    enable_diarization: Option<bool>,
 ) -> Result<ImportResult> {
     let source = PathBuf::from(&source_path);
 
-    // Validate source file
-    if !source.exists() {
-        return Err(anyhow!("Source file not found: {}", source.display()));
-    }
-
     info!(
-        "Starting import for '{}' from {} with language {:?}, model {:?}, provider {:?}",
-        title, source_path, language, model, provider
+        "Starting import for '{}' from {} with language {:?}, model {:?}, provider {:?}, diarization {:?}",
+        title, source_path, language, model, provider, enable_diarization
     );
 
-    // Determine which provider to use (default to whisper)
-    let use_parakeet = provider.as_deref() == Some("parakeet");
-
-    emit_progress(&app, "copying", 5, "Creating meeting folder...");
-
-    // Check for cancellation
-    if IMPORT_CANCELLED.load(Ordering::SeqCst) {
-        return Err(anyhow!("Import cancelled"));
-    }
-
-    // Create meeting folder
-    let base_folder = get_default_recordings_folder();
-    let meeting_folder = create_meeting_folder(&base_folder, &title, false)?;
+    let app_state = app
+        .try_state::<AppState>()
+        .ok_or_else(|| anyhow!("App state not available"))?;
+    let pool = app_state.db_manager.pool();
+    let observer: Arc<dyn ImportObserver> = Arc::new(AppImportObserver(app.clone()));
 
-    // Copy audio file to meeting folder
-    emit_progress(&app, "copying", 10, "Copying audio file...");
+    let denoise = match load_recording_preferences(&app).await {
+        Ok(preferences) => preferences.noise_suppression.imports,
+        Err(e) => {
+            warn!(
+                "Failed to load recording preferences, using default noise suppression: {}",
+                e
+            );
+            NoiseSuppressionSettings::default().imports
+        }
+    };
 
-    let dest_filename = format!(
-        "audio.{}",
-        source
-            .extension()
-            .and_then(|e| e.to_str())
-            .unwrap_or("mp4")
-    );
-    let dest_path = meeting_folder.join(&dest_filename);
+    observer.progress("transcribing", 5, "Loading transcription engine...");
+    let engine = if let Some(cloud) = provider.as_deref().filter(|p| is_cloud_provider(p)) {
+        ImportEngine::Cloud(
+            load_cloud_provider(pool, cloud, model.as_deref())
+                .await
+                .map_err(|e| anyhow!(e))?,
+        )
+    } else if provider.as_deref() == Some("parakeet") {
+        ImportEngine::Parakeet(get_or_init_parakeet(pool, model.as_deref()).await?)
+    } else {
+        ImportEngine::Whisper(get_or_init_whisper(pool, model.as_deref()).await?)
+    };
 
-    let src = source.clone();
-    let dst = dest_path.clone();
-    tokio::task::spawn_blocking(move || std::fs::copy(&src, &dst))
-        .await
-        .map_err(|e| anyhow!("Copy task join error: {}", e))?
-        .map_err(|e| anyhow!("Failed to copy audio file: {}", e))?;
+    let transcribed = transcribe_file(
+        &source,
+        &engine,
+        language,
+        enable_diarization.unwrap_or(true),
+        denoise,
+        Some(pool),
+        observer.clone(),
+    )
+    .await?;
 
-    info!("Copied audio to: {}", dest_path.display());
+    save_import(pool, &source, &title, &transcribed, observer.as_ref()).await
+}
 
-    // Check for cancellation
-    if IMPORT_CANCELLED.load(Ordering::SeqCst) {
-        // Cleanup: remove the meeting folder
-        let _ = std::fs::remove_dir_all(&meeting_folder);
-        return Err(anyhow!("Import cancelled"));
+/// Decodes, segments and transcribes an audio file, then labels speakers when
+/// requested and the diarization model is available. Needs no AppHandle, so it
+/// backs both the import command and the CLI. `denoise` is applied to the
+/// decoded audio; `pool` enables voice-profile matching for diarized speakers.
+pub async fn transcribe_file(
+    source: &Path,
+    engine: &ImportEngine,
+    language: Option<String>,
+    enable_diarization: bool,
+    denoise: DenoiseSettings,
+    pool: Option<&SqlitePool>,
+    observer: Arc<dyn ImportObserver>,
+) -> Result<TranscribedAudio> {
+    // Validate source file
+    if !source.exists() {
+        return Err(anyhow!("Source file not found: {}", source.display()));
     }
 
-    emit_progress(&app, "decoding", 15, "Decoding audio file...");
+    observer.progress("decoding", 15, "Decoding audio file...");
 
     // Decode the audio file with progress updates
-    let app_for_decode = app.clone();
+    let observer_for_decode = observer.clone();
     let decode_progress = Box::new(move |progress: u32, msg: &str| {
         // Map decode progress: 15% + (progress * 0.05) to go from 15% to 20%
         let overall_progress = 15 + ((progress as f32 * 0.05) as u32);
-        emit_progress(&app_for_decode, "decoding", overall_progress, msg);
+        observer_for_decode.progress("decoding", overall_progress, msg);
     });
 
-    let path_for_decode = dest_path.clone();
+    let path_for_decode = source.to_path_buf();
     let decoded = tokio::task::spawn_blocking(move || {
         decode_audio_file_with_progress(&path_for_decode, Some(decode_progress))
     })
@@ -393,20 +504,34 @@ async fn run_import<R: Runtime>(
         duration_seconds, decoded.sample_rate, decoded.channels
     );
 
-    emit_progress(&app, "resampling", 20, "Converting audio format...");
+    // Check for cancellation
+    if observer.is_cancelled() {
+        return Err(anyhow!("Import cancelled"));
+    }
+
+    // Reduce keyboard and fan noise so VAD doesn't open segments on it
+    let decoded = if denoise.is_active() {
+        observer.progress("denoising", 20, "Reducing background noise...");
+        tokio::task::spawn_blocking(move || decoded.denoised(denoise))
+            .await
+            .map_err(|e| anyhow!("Denoise task join error: {}", e))??
+    } else {
+        decoded
+    };
+
+    observer.progress("resampling", 20, "Converting audio format...");
 
     // Check for cancellation
-    if IMPORT_CANCELLED.load(Ordering::SeqCst) {
-        let _ = std::fs::remove_dir_all(&meeting_folder);
+    if observer.is_cancelled() {
         return Err(anyhow!("Import cancelled"));
     }
 
     // Convert to 16kHz mono format with progress updates
-    let app_for_resample = app.clone();
+    let observer_for_resample = observer.clone();
     let resample_progress = Box::new(move |progress: u32, msg: &str| {
         // Map resample progress: 20% + (progress * 0.05) to go from 20% to 25%
         let overall_progress = 20 + ((progress as f32 * 0.05) as u32);
-        emit_progress(&app_for_resample, "resampling", overall_progress, msg);
+        observer_for_resample.progress("resampling", overall_progress, msg);
     });
 
     let audio_samples = tokio::task::spawn_blocking(move || {
@@ -419,16 +544,15 @@ async fn run_import<R: Runtime>(
         audio_samples.len()
     );
 
-    emit_progress(&app, "vad", 25, "Detecting speech segments...");
+    observer.progress("vad", 25, "Detecting speech segments...");
 
     // Check for cancellation
-    if IMPORT_CANCELLED.load(Ordering::SeqCst) {
-        let _ = std::fs::remove_dir_all(&meeting_folder);
+    if observer.is_cancelled() {
         return Err(anyhow!("Import cancelled"));
     }
 
     // Use VAD to find speech segments
-    let app_for_vad = app.clone();
+    let observer_for_vad = observer.clone();
 
     let speech_segments = tokio::task::spawn_blocking(move || {
         get_speech_chunks_with_progress(
@@ -436,8 +560,7 @@ async fn run_import<R: Runtime>(
             VAD_REDEMPTION_TIME_MS,
             |vad_progress, segments_found| {
                 let overall_progress = 25 + (vad_progress as f32 * 0.05) as u32;
-                emit_progress(
-                    &app_for_vad,
+                observer_for_vad.progress(
                     "vad",
                     overall_progress,
                     &format!(
@@ -445,7 +568,7 @@ async fn run_import<R: Runtime>(
                         vad_progress, segments_found
                     ),
                 );
-                !IMPORT_CANCELLED.load(Ordering::SeqCst)
+                !observer_for_vad.is_cancelled()
             },
         )
     })
@@ -483,42 +606,21 @@ async fn run_import<R: Runtime>(
     }
 
     if total_segments == 0 {
-        warn!("No speech detected in audio");
-
-        // Emit warning to frontend
-        let _ = app.emit(
-            "import-warning",
-            ImportWarning {
-                warning: "No speech detected in audio file".to_string(),
-                details: Some(
-                    "The file was imported successfully, but VAD did not detect any speech. \
-                     The meeting was created but contains no transcripts.".to_string()
-                ),
-            },
-        );
+        observer.warning(ImportWarning {
+            warning: "No speech detected in audio file".to_string(),
+            details: Some(
+                "The file was imported successfully, but VAD did not detect any speech. \
+                 The meeting was created but contains no transcripts.".to_string()
+            ),
+        });
         // Still create the meeting, just with no transcripts
     }
 
     // Check for cancellation
-    if IMPORT_CANCELLED.load(Ordering::SeqCst) {
-        let _ = std::fs::remove_dir_all(&meeting_folder);
+    if observer.is_cancelled() {
         return Err(anyhow!("Import cancelled"));
     }
 
-    emit_progress(&app, "transcribing", 30, "Loading transcription engine...");
-
-    // Initialize the appropriate engine
-    let whisper_engine = if !use_parakeet && total_segments > 0 {
-        Some(get_or_init_whisper(&app, model.as_deref()).await?)
-    } else {
-        None
-    };
-    let parakeet_engine = if use_parakeet && total_segments > 0 {
-        Some(get_or_init_parakeet(&app, model.as_deref()).await?)
-    } else {
-        None
-    };
-
     // Split very long segments at silence boundaries for better transcription quality.
     // Hard cuts at arbitrary sample positions lose words at boundaries. Instead, scan
     // for the lowest-energy window near the target split point and cut there.
@@ -545,19 +647,17 @@ async fn run_import<R: Runtime>(
     info!("Processing {} segments (after splitting)", processable_count);
 
     // Process each speech segment
-    let mut all_transcripts: Vec<(String, f64, f64)> = Vec::new();
+    let mut all_transcripts: Vec<(String, f64, f64, Vec<WordTiming>)> = Vec::new();
     let mut total_confidence = 0.0f32;
 
     for (i, segment) in processable_segments.iter().enumerate() {
-        if IMPORT_CANCELLED.load(Ordering::SeqCst) {
-            let _ = std::fs::remove_dir_all(&meeting_folder);
+        if observer.is_cancelled() {
             return Err(anyhow!("Import cancelled"));
         }
 
         let progress = 30 + ((i as f32 / processable_count.max(1) as f32) * 50.0) as u32;
         let segment_duration_sec = (segment.end_timestamp_ms - segment.start_timestamp_ms) / 1000.0;
-        emit_progress(
-            &app,
+        observer.progress(
             "transcribing",
             progress,
             &format!(
@@ -579,21 +679,10 @@ async fn run_import<R: Runtime>(
         }
 
         // Transcribe
-        let (text, conf) = if use_parakeet {
-            let engine = parakeet_engine.as_ref().unwrap();
-            let text = engine
-                .transcribe_audio(segment.samples.clone())
-                .await
-                .map_err(|e| anyhow!("Parakeet transcription failed on segment {}: {}", i, e))?;
-            (text, 0.9f32)
-        } else {
-            let engine = whisper_engine.as_ref().unwrap();
-            let (text, conf, _) = engine
-                .transcribe_audio_with_confidence(segment.samples.clone(), language.clone())
-                .await
-                .map_err(|e| anyhow!("Whisper transcription failed on segment {}: {}", i, e))?;
-            (text, conf)
-        };
+        let (text, conf, mut words) = engine
+            .transcribe(segment.samples.clone(), language.clone())
+            .await
+            .map_err(|e| anyhow!("{} transcription failed on segment {}: {}", engine.name(), i, e))?;
 
         let trimmed = text.trim();
         if !trimmed.is_empty() {
@@ -602,7 +691,8 @@ async fn run_import<R: Runtime>(
                 i + 1, processable_count, segment_duration_sec, conf,
                 if trimmed.len() > 80 { let mut end = 80; while !trimmed.is_char_boundary(end) { end -= 1; } &trimmed[..end] } else { trimmed }
             );
-            all_transcripts.push((text, segment.start_timestamp_ms, segment.end_timestamp_ms));
+            offset_words(&mut words, segment.start_timestamp_ms / 1000.0);
+            all_transcripts.push((text, segment.start_timestamp_ms, segment.end_timestamp_ms, words));
             total_confidence += conf;
         } else {
             debug!("Segment {}/{}: {:.1}s — empty transcription", i + 1, processable_count, segment_duration_sec);
@@ -622,54 +712,144 @@ async fn run_import<R: Runtime>(
     );
 
     // Check for cancellation
-    if IMPORT_CANCELLED.load(Ordering::SeqCst) {
-        let _ = std::fs::remove_dir_all(&meeting_folder);
+    if observer.is_cancelled() {
         return Err(anyhow!("Import cancelled"));
     }
 
-    emit_progress(&app, "saving", 85, "Creating meeting...");
+    // Identify speakers (skipped when disabled or the embedding model is not downloaded)
+    let mut diarization = if enable_diarization && transcribed_count > 0 {
+        observer.progress("diarizing", 80, "Identifying speakers...");
+        let observer_for_diarization = observer.clone();
+        match crate::diarization::diarize_speech_segments(
+            Arc::new(speech_segments),
+            None,
+            move |progress| {
+                let overall_progress = 80 + progress * 5 / 100;
+                observer_for_diarization.progress("diarizing", overall_progress, "Identifying speakers...");
+                !observer_for_diarization.is_cancelled()
+            },
+        )
+        .await
+        {
+            Ok(result) => result,
+            Err(e) => {
+                warn!("Speaker diarization failed, continuing without speaker labels: {}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    // Check for cancellation
+    if observer.is_cancelled() {
+        return Err(anyhow!("Import cancelled"));
+    }
 
     // Create transcript segments
-    let segments = create_transcript_segments(&all_transcripts);
+    let mut segments = create_transcript_segments(&all_transcripts);
+    if let Some(result) = diarization.as_mut() {
+        if let Some(pool) = pool {
+            crate::diarization::recognize_known_speakers(pool, result).await;
+        }
+        apply_speaker_labels(&mut segments, &result.turns);
+    }
 
-    // Save to database
-    let app_state = app
-        .try_state::<AppState>()
-        .ok_or_else(|| anyhow!("App state not available"))?;
+    Ok(TranscribedAudio {
+        segments,
+        speakers: diarization.map(|d| d.speakers).unwrap_or_default(),
+        duration_seconds,
+    })
+}
 
-    let meeting_id = create_meeting_with_transcripts(
-        app_state.db_manager.pool(),
-        &title,
-        &segments,
+/// Saves a transcribed file as a new meeting: creates the meeting folder, copies
+/// the audio into it, inserts the meeting and transcripts, and writes
+/// transcripts.json and metadata.json next to the recording
+pub async fn save_import(
+    pool: &SqlitePool,
+    source: &Path,
+    title: &str,
+    transcribed: &TranscribedAudio,
+    observer: &dyn ImportObserver,
+) -> Result<ImportResult> {
+    if observer.is_cancelled() {
+        return Err(anyhow!("Import cancelled"));
+    }
+
+    observer.progress("copying", 85, "Creating meeting folder...");
+
+    // Create meeting folder
+    let base_folder = get_default_recordings_folder();
+    let meeting_folder = create_meeting_folder(&base_folder, title, false)?;
+
+    // Copy audio file to meeting folder
+    observer.progress("copying", 87, "Copying audio file...");
+
+    let dest_filename = format!(
+        "audio.{}",
+        source
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or("mp4")
+    );
+    let dest_path = meeting_folder.join(&dest_filename);
+
+    let src = source.to_path_buf();
+    let dst = dest_path.clone();
+    let copied = tokio::task::spawn_blocking(move || std::fs::copy(&src, &dst))
+        .await
+        .map_err(|e| anyhow!("Copy task join error: {}", e))?;
+    if let Err(e) = copied {
+        let _ = std::fs::remove_dir_all(&meeting_folder);
+        return Err(anyhow!("Failed to copy audio file: {}", e));
+    }
+
+    info!("Copied audio to: {}", dest_path.display());
+
+    observer.progress("saving", 90, "Creating meeting...");
+
+    // Save to database
+    let meeting_id = match create_meeting_with_transcripts(
+        pool,
+        title,
+        &transcribed.segments,
+        &transcribed.speakers,
         meeting_folder.to_string_lossy().to_string(),
     )
-    .await?;
+    .await
+    {
+        Ok(id) => id,
+        Err(e) => {
+            let _ = std::fs::remove_dir_all(&meeting_folder);
+            return Err(e);
+        }
+    };
 
     // Write transcripts.json and metadata.json to the meeting folder
-    emit_progress(&app, "saving", 90, "Writing transcript files...");
+    observer.progress("saving", 95, "Writing transcript files...");
 
-    if let Err(e) = write_transcripts_json(&meeting_folder, &segments) {
+    if let Err(e) = write_transcripts_json(&meeting_folder, &transcribed.segments) {
         warn!("Failed to write transcripts.json: {}", e);
     }
 
     if let Err(e) = write_import_metadata(
         &meeting_folder,
         &meeting_id,
-        &title,
-        duration_seconds,
+        title,
+        transcribed.duration_seconds,
         &dest_filename,
         "import",
     ) {
         warn!("Failed to write metadata.json: {}", e);
     }
 
-    emit_progress(&app, "complete", 100, "Import complete");
+    observer.progress("complete", 100, "Import complete");
 
     Ok(ImportResult {
         meeting_id,
-        title,
-        segments_count: segments.len(),
-        duration_seconds,
+        title: title.to_string(),
+        segments_count: transcribed.segments.len(),
+        duration_seconds: transcribed.duration_seconds,
     })
 }
 
@@ -687,10 +867,11 @@ fn emit_progress<R: Runtime>(app: &AppHandle<R>, stage: &str, progress: u32, mes
 
 
 /// Create a new meeting with transcripts in the database
-async fn create_meeting_with_transcripts(
+pub async fn create_meeting_with_transcripts(
     pool: &sqlx::SqlitePool,
     title: &str,
     segments: &[TranscriptSegment],
+    speakers: &[DiarizedSpeaker],
     folder_path: String,
 ) -> Result<String> {
     let meeting_id = format!("meeting-{}", Uuid::new_v4());
@@ -719,8 +900,8 @@ async fn create_meeting_with_transcripts(
     // Insert transcripts
     for segment in segments {
         sqlx::query(
-            "INSERT INTO transcripts (id, meeting_id, transcript, timestamp, audio_start_time, audio_end_time, duration)
-             VALUES (?, ?, ?, ?, ?, ?, ?)",
+            "INSERT INTO transcripts (id, meeting_id, transcript, timestamp, audio_start_time, audio_end_time, duration, speaker_label)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
         )
         .bind(&segment.id)
         .bind(&meeting_id)
@@ -729,9 +910,20 @@ async fn create_meeting_with_transcripts(
         .bind(segment.audio_start_time)
         .bind(segment.audio_end_time)
         .bind(segment.duration)
+        .bind(&segment.speaker_label)
         .execute(&mut *tx)
         .await
         .map_err(|e| anyhow!("Failed to insert transcript: {}", e))?;
+
+        TranscriptsRepository::insert_transcript_words(&mut *tx, &meeting_id, &segment.id, &segment.words)
+            .await
+            .map_err(|e| anyhow!("Failed to insert word timings: {}", e))?;
+    }
+
+    if !speakers.is_empty() {
+        SpeakersRepository::replace_meeting_speakers(&mut *tx, &meeting_id, speakers)
+            .await
+            .map_err(|e| anyhow!("Failed to save speakers: {}", e))?;
     }
 
     tx.commit()
@@ -748,8 +940,8 @@ async fn create_meeting_with_transcripts(
 }
 
 /// Get or initialize the Whisper engine
-async fn get_or_init_whisper<R: Runtime>(
-    app: &AppHandle<R>,
+async fn get_or_init_whisper(
+    pool: &SqlitePool,
     requested_model: Option<&str>,
 ) -> Result<Arc<WhisperEngine>> {
     use crate::whisper_engine::commands::WHISPER_ENGINE;
@@ -763,7 +955,7 @@ async fn get_or_init_whisper<R: Runtime>(
         Some(e) => {
             let target_model = match requested_model {
                 Some(model) => model.to_string(),
-                None => get_configured_model(app, "whisper").await?,
+                None => get_configured_model(pool, "whisper").await?,
             };
 
             let current_model = e.get_current_model().await;
@@ -794,8 +986,8 @@ async fn get_or_init_whisper<R: Runtime>(
 }
 
 /// Get or initialize the Parakeet engine
-async fn get_or_init_parakeet<R: Runtime>(
-    app: &AppHandle<R>,
+async fn get_or_init_parakeet(
+    pool: &SqlitePool,
     requested_model: Option<&str>,
 ) -> Result<Arc<ParakeetEngine>> {
     use crate::parakeet_engine::commands::PARAKEET_ENGINE;
@@ -809,7 +1001,7 @@ async fn get_or_init_parakeet<R: Runtime>(
         Some(e) => {
             let target_model = match requested_model {
                 Some(model) => model.to_string(),
-                None => get_configured_model(app, "parakeet").await?,
+                None => get_configured_model(pool, "parakeet").await?,
             };
 
             let current_model = e.get_current_model().await;
@@ -839,16 +1031,13 @@ async fn get_or_init_parakeet<R: Runtime>(
     }
 }
 
-/// Get the configured model from database
-async fn get_configured_model<R: Runtime>(app: &AppHandle<R>, provider_type: &str) -> Result<String> {
-    let app_state = app
-        .try_state::<AppState>()
-        .ok_or_else(|| anyhow!("App state not available"))?;
-
+/// Get the configured model from database, falling back to the default model
+/// for `provider_type` ("whisper" or "parakeet")
+pub async fn get_configured_model(pool: &SqlitePool, provider_type: &str) -> Result<String> {
     let result: Option<(String, String)> = sqlx::query_as(
         "SELECT provider, model FROM transcript_settings WHERE id = '1'",
     )
-    .fetch_optional(app_state.db_manager.pool())
+    .fetch_optional(pool)
     .await
     .map_err(|e| anyhow!("Failed to query config: {}", e))?;
 
@@ -968,6 +1157,7 @@ pub async fn start_import_audio_command<R: Runtime>(
     language: Option<String>,
     model: Option<String>,
     provider: Option<String>,
+    enable_diarization: Option<bool>,
 ) -> Result<ImportStarted, String> {
     // Check if import is already in progress (guard will be acquired in start_import)
     if IMPORT_IN_PROGRESS.load(Ordering::SeqCst) {
@@ -976,7 +1166,7 @@ pub async fn start_import_audio_command<R: Runtime>(
 
     // Spawn import in background
     tauri::async_runtime::spawn(async move {
-        let result = start_import(app, source_path, title, language, model, provider).await;
+        let result = start_import(app, source_path, title, language, model, provider, enable_diarization).await;
 
         if let Err(e) = result {
             error!("Import failed: {}", e);
@@ -1181,6 +1371,21 @@ mod tests {
                 audio_start_time: Some(0.0),
                 audio_end_time: Some(1.5),
                 duration: Some(1.5),
+                speaker_label: Some("Speaker 1".to_string()),
+                words: vec![
+                    WordTiming {
+                        text: "Hello".to_string(),
+                        start: 0.0,
+                        end: 0.6,
+                        confidence: Some(0.9),
+                    },
+                    WordTiming {
+                        text: "world".to_string(),
+                        start: 0.7,
+                        end: 1.4,
+                        confidence: None,
+                    },
+                ],
             },
             TranscriptSegment {
                 id: "t-2".to_string(),
@@ -1189,6 +1394,8 @@ mod tests {
                 audio_start_time: Some(2.0),
                 audio_end_time: Some(3.5),
                 duration: Some(1.5),
+                speaker_label: None,
+                words: Vec::new(),
             },
         ];
 
@@ -1207,6 +1414,12 @@ mod tests {
         assert_eq!(parsed["segments"][1]["text"], "Second segment");
         assert_eq!(parsed["segments"][0]["sequence_id"], 0);
         assert_eq!(parsed["segments"][1]["sequence_id"], 1);
+        assert_eq!(parsed["segments"][0]["speaker_label"], "Speaker 1");
+        assert!(parsed["segments"][1]["speaker_label"].is_null());
+        assert_eq!(parsed["segments"][0]["words"][1]["text"], "world");
+        assert_eq!(parsed["segments"][0]["words"][1]["start"], 0.7);
+        assert!(parsed["segments"][0]["words"][1].get("confidence").is_none());
+        assert_eq!(parsed["segments"][1]["words"].as_array().unwrap().len(), 0);
 
         // Verify temp file was cleaned up
         assert!(!dir.path().join(".transcripts.json.tmp").exists());
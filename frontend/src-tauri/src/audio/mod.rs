@@ -14,6 +14,8 @@ pub mod permissions;
 pub mod device_detection;
 pub mod diagnostics;
 pub mod ffmpeg_mixer;  // NEW: FFmpeg-style adaptive audio mixer
+pub mod echo_cancellation;  // Removes speaker echo of system audio from the mic
+pub mod denoise;  // Per-source noise suppression for live capture and imports
 
 // New simplified audio system
 pub mod recording_state;
@@ -68,7 +70,7 @@ pub use capture::{
 // Export system audio detection functionality
 pub use system_detector::{
     SystemAudioDetector, SystemAudioEvent, SystemAudioCallback,
-    new_system_audio_callback
+    new_system_audio_callback, list_audio_producing_apps, AudioApp, MeetingAppFilter
 };
 
 // Export system audio commands
@@ -76,7 +78,7 @@ pub use system_audio_commands::{
     start_system_audio_capture_command, list_system_audio_devices_command,
     check_system_audio_permissions_command, start_system_audio_monitoring,
     stop_system_audio_monitoring, get_system_audio_monitoring_status,
-    init_system_audio_state
+    list_audio_producing_apps_command, init_system_audio_state
 };
 
 // Export new simplified components
@@ -109,7 +111,10 @@ pub use diagnostics::{
 };
 
 // Export FFmpeg mixer
-pub use ffmpeg_mixer::{FFmpegAudioMixer, BufferStats, RNNOISE_APPLY_ENABLED};
+pub use ffmpeg_mixer::{FFmpegAudioMixer, BufferStats};
+
+// Export noise suppression
+pub use denoise::{Denoiser, DenoiseSettings, NoiseSuppressionSettings};
 
 pub use vad::{extract_speech_16k};
 
@@ -10,8 +10,10 @@ use rubato::{Resampler, SincFixedIn, SincInterpolationParameters, SincInterpolat
 
 use super::devices::AudioDevice;
 use super::recording_state::{AudioChunk, AudioError, RecordingState, DeviceType};
-use super::audio_processing::{audio_to_mono, LoudnessNormalizer, NoiseSuppressionProcessor, HighPassFilter};
+use super::audio_processing::{audio_to_mono, LoudnessNormalizer, HighPassFilter};
+use super::denoise::{self, Denoiser};
 use super::vad::{ContinuousVadProcessor};
+use super::echo_cancellation::{self, EchoCanceller};
 
 /// Ring buffer for synchronized audio mixing
 /// Accumulates samples from mic and system streams until we have aligned windows
@@ -205,8 +207,9 @@ pub struct AudioCapture {
     // Buffering for variable-size chunks → fixed-size resampler input
     resampler_input_buffer: Arc<std::sync::Mutex<Vec<f32>>>,
     resampler_chunk_size: usize,  // Fixed chunk size for resampler (512 samples)
+    // Noise suppression (per source, from recording preferences)
+    noise_suppressor: Arc<std::sync::Mutex<Option<Box<dyn Denoiser>>>>,
     // Audio enhancement processors (microphone only)
-    noise_suppressor: Arc<std::sync::Mutex<Option<NoiseSuppressionProcessor>>>,
     high_pass_filter: Arc<std::sync::Mutex<Option<HighPassFilter>>>,
     // EBU R128 normalizer for microphone audio (per-device, stateful)
     normalizer: Arc<std::sync::Mutex<Option<LoudnessNormalizer>>>,
@@ -265,27 +268,27 @@ impl AudioCapture {
             );
         }
 
-        // Initialize audio enhancement processors for MICROPHONE ONLY
-        // System audio doesn't need enhancement (already clean)
-        let (noise_suppressor, high_pass_filter, normalizer) = if matches!(device_type, DeviceType::Microphone) {
-            // Initialize noise suppression (RNNoise) at 48kHz - CONDITIONAL based on flag
-            let ns = if super::ffmpeg_mixer::RNNOISE_APPLY_ENABLED {
-                match NoiseSuppressionProcessor::new(TARGET_SAMPLE_RATE) {
-                    Ok(processor) => {
-                        info!("✅ RNNoise noise suppression ENABLED for microphone '{}' (10-15 dB reduction)", device.name);
-                        Some(processor)
-                    }
-                    Err(e) => {
-                        warn!("⚠️ Failed to create noise suppressor: {}, continuing without noise suppression", e);
-                        None
-                    }
-                }
-            } else {
-                info!("ℹ️ RNNoise noise suppression DISABLED for microphone '{}' (flag: RNNOISE_APPLY_ENABLED=false)", device.name);
-                info!("   Whisper handles noise well internally - RNNoise is optional");
+        // Initialize noise suppression (RNNoise) at 48kHz - enabled and strength per source
+        let denoise_settings = denoise::settings().for_device(&device_type);
+        let noise_suppressor = match denoise::create_denoiser(denoise_settings) {
+            Ok(Some(denoiser)) => {
+                info!("✅ Noise suppression ENABLED for {:?} '{}' (strength {:.2})",
+                      device_type, device.name, denoise_settings.strength);
+                Some(denoiser)
+            }
+            Ok(None) => {
+                info!("ℹ️ Noise suppression DISABLED for {:?} '{}' (recording preferences)", device_type, device.name);
                 None
-            };
+            }
+            Err(e) => {
+                warn!("⚠️ Failed to create noise suppressor: {}, continuing without noise suppression", e);
+                None
+            }
+        };
 
+        // Initialize audio enhancement processors for MICROPHONE ONLY
+        // System audio doesn't need enhancement (already clean)
+        let (high_pass_filter, normalizer) = if matches!(device_type, DeviceType::Microphone) {
             // Initialize high-pass filter (removes rumble below 80 Hz)
             let hpf = {
                 let filter = HighPassFilter::new(TARGET_SAMPLE_RATE, 80.0);
@@ -305,11 +308,11 @@ impl AudioCapture {
                 }
             };
 
-            (ns, hpf, norm)
+            (hpf, norm)
         } else {
             // System audio: no enhancement needed
             info!("ℹ️ System audio '{}' captured raw (no enhancement)", device.name);
-            (None, None, None)
+            (None, None)
         };
 
         // CRITICAL FIX: Initialize persistent resampler to preserve energy across chunks
@@ -519,41 +522,8 @@ impl AudioCapture {
                 }
             }
 
-            // STEP 2: Apply RNNoise noise suppression (10-15 dB reduction) - CONDITIONAL
-            if super::ffmpeg_mixer::RNNOISE_APPLY_ENABLED {
-                if let Ok(mut ns_lock) = self.noise_suppressor.lock() {
-                    if let Some(ref mut suppressor) = *ns_lock {
-                        let before_len = mono_data.len();
-                        mono_data = suppressor.process(&mono_data);
-                        let after_len = mono_data.len();
-
-                        // CRITICAL MONITORING: Track buffer health
-                        let chunk_id = self.chunk_counter.load(std::sync::atomic::Ordering::SeqCst);
-                        if chunk_id % 100 == 0 {
-                            let buffered = suppressor.buffered_samples();
-                            let length_delta = (before_len as i32 - after_len as i32).abs();
-
-                            debug!("🔇 Noise suppression health: in={}, out={}, delta={}, buffered={}, RMS={:.4}",
-                                   before_len, after_len, length_delta, buffered,
-                                   if !mono_data.is_empty() {
-                                       (mono_data.iter().map(|&x| x * x).sum::<f32>() / mono_data.len() as f32).sqrt()
-                                   } else { 0.0 });
-
-                            // WARN if accumulating samples (potential latency buildup)
-                            if buffered > 1000 {
-                                warn!("⚠️ RNNoise accumulating samples: {} buffered (potential latency issue!)",
-                                      buffered);
-                            }
-
-                            // WARN if significant length mismatch
-                            if length_delta > 50 {
-                                warn!("⚠️ RNNoise length mismatch: input={} output={} (delta={})",
-                                      before_len, after_len, length_delta);
-                            }
-                        }
-                    }
-                }
-            }
+            // STEP 2: Apply RNNoise noise suppression (10-15 dB reduction) - if enabled for the mic
+            mono_data = self.suppress_noise(mono_data);
 
             // STEP 3: Apply EBU R128 normalization (professional loudness standard)
             if let Ok(mut normalizer_lock) = self.normalizer.lock() {
@@ -569,6 +539,14 @@ impl AudioCapture {
                     }
                 }
             }
+        } else {
+            // System audio: noise suppression only, if enabled for it
+            mono_data = self.suppress_noise(mono_data);
+        }
+
+        // Noise suppressor is still buffering a partial frame - nothing to send yet
+        if mono_data.is_empty() {
+            return;
         }
 
         // Create audio chunk with stream-specific timestamp (get ID first for logging)
@@ -644,6 +622,44 @@ impl AudioCapture {
         }
     }
 
+    /// Apply this source's noise suppressor (if any) to 48kHz mono audio
+    fn suppress_noise(&self, mut mono_data: Vec<f32>) -> Vec<f32> {
+        if let Ok(mut ns_lock) = self.noise_suppressor.lock() {
+            if let Some(ref mut suppressor) = *ns_lock {
+                let before_len = mono_data.len();
+                mono_data = suppressor.process(&mono_data);
+                let after_len = mono_data.len();
+
+                // CRITICAL MONITORING: Track buffer health
+                let chunk_id = self.chunk_counter.load(std::sync::atomic::Ordering::SeqCst);
+                if chunk_id % 100 == 0 {
+                    let buffered = suppressor.buffered_samples();
+                    let length_delta = (before_len as i32 - after_len as i32).abs();
+
+                    debug!("🔇 Noise suppression health: in={}, out={}, delta={}, buffered={}, RMS={:.4}",
+                           before_len, after_len, length_delta, buffered,
+                           if !mono_data.is_empty() {
+                               (mono_data.iter().map(|&x| x * x).sum::<f32>() / mono_data.len() as f32).sqrt()
+                           } else { 0.0 });
+
+                    // WARN if accumulating samples (potential latency buildup)
+                    if buffered > 1000 {
+                        warn!("⚠️ RNNoise accumulating samples: {} buffered (potential latency issue!)",
+                              buffered);
+                    }
+
+                    // WARN if significant length mismatch (partial frames account for up to one frame)
+                    if length_delta as usize > suppressor.latency_samples() {
+                        warn!("⚠️ RNNoise length mismatch: input={} output={} (delta={})",
+                              before_len, after_len, length_delta);
+                    }
+                }
+            }
+        }
+
+        mono_data
+    }
+
     /// Handle stream errors with enhanced disconnect detection
     pub fn handle_stream_error(&self, error: cpal::StreamError) {
         error!("Audio stream error for {}: {}", self.device.name, error);
@@ -692,6 +708,8 @@ pub struct AudioPipeline {
     // PROFESSIONAL AUDIO MIXING: Ring buffer + RMS-based mixer
     ring_buffer: AudioMixerRingBuffer,
     mixer: ProfessionalAudioMixer,
+    // ECHO CANCELLATION: removes system audio played through speakers from the mic
+    echo_canceller: Option<EchoCanceller>,
     // Recording sender for pre-mixed audio
     recording_sender_for_mixed: Option<mpsc::UnboundedSender<AudioChunk>>,
 }
@@ -741,6 +759,14 @@ impl AudioPipeline {
         let ring_buffer = AudioMixerRingBuffer::new(sample_rate);
         let mixer = ProfessionalAudioMixer::new(sample_rate);
 
+        // System audio is the echo canceller's reference signal
+        let echo_canceller = if echo_cancellation::is_enabled() {
+            Some(EchoCanceller::new(sample_rate))
+        } else {
+            info!("ℹ️ Echo cancellation disabled in recording preferences");
+            None
+        };
+
         // Note: target_chunk_duration_ms is ignored - VAD controls segmentation now
         let _ = target_chunk_duration_ms;
 
@@ -759,6 +785,7 @@ impl AudioPipeline {
             // Initialize professional audio mixing
             ring_buffer,
             mixer,
+            echo_canceller,
             recording_sender_for_mixed: None,  // Will be set by manager
         }
     }
@@ -822,6 +849,13 @@ impl AudioPipeline {
                     // STEP 2: Mix audio in fixed windows when both streams have sufficient data
                     while self.ring_buffer.can_mix() {
                         if let Some((mic_window, sys_window)) = self.ring_buffer.extract_window() {
+                            // Remove the far end's echo (system audio picked up from the speakers)
+                            // from the mic, so it isn't mixed in and transcribed twice
+                            let mic_window = match self.echo_canceller {
+                                Some(ref mut canceller) => canceller.process(&mic_window, &sys_window),
+                                None => mic_window,
+                            };
+
                             // Simple mixing without aggressive ducking
                             let mixed_clean = self.mixer.mix_window(&mic_window, &sys_window);
 
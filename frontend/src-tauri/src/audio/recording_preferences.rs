@@ -1,6 +1,6 @@
 use log::{info, warn};
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tauri::{AppHandle, Runtime};
 use tauri_plugin_store::StoreExt;
 
@@ -8,9 +8,11 @@ use anyhow::Result;
 #[cfg(target_os = "macos")]
 use log::error;
 
-#[cfg(target_os = "macos")]
+#[cfg(any(target_os = "macos", target_os = "linux"))]
 use crate::audio::capture::AudioCaptureBackend;
 
+use crate::audio::denoise::NoiseSuppressionSettings;
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct RecordingPreferences {
     pub save_folder: PathBuf,
@@ -20,9 +22,33 @@ pub struct RecordingPreferences {
     pub preferred_mic_device: Option<String>,
     #[serde(default)]
     pub preferred_system_device: Option<String>,
-    #[cfg(target_os = "macos")]
+    /// Run speaker diarization on the recording once it has been saved
+    #[serde(default)]
+    pub diarize_after_recording: bool,
+    /// Cancel the echo of system audio (the far end of a call played through
+    /// speakers) from the microphone before mixing. Opt-in: it changes the
+    /// recording, and headset users have no echo to remove.
+    #[serde(default)]
+    pub echo_cancellation: bool,
+    /// Which sources (microphone, system audio, imported files) are denoised
+    /// before transcription, and how strongly
+    #[serde(default)]
+    pub noise_suppression: NoiseSuppressionSettings,
+    /// Apps that always trigger the "Start recording?" prompt when they start
+    /// a call; if set, no other app does
+    #[serde(default)]
+    pub meeting_detection_allowed_apps: Vec<String>,
+    /// Apps that never trigger it
+    #[serde(default)]
+    pub meeting_detection_denied_apps: Vec<String>,
+    #[cfg(any(target_os = "macos", target_os = "linux"))]
     #[serde(default)]
     pub system_audio_backend: Option<String>,
+    /// Only record system audio from these apps (app or binary names, as
+    /// listed by `list_audio_producing_apps_command`); empty records everything
+    #[cfg(target_os = "linux")]
+    #[serde(default)]
+    pub system_audio_apps: Vec<String>,
 }
 
 impl Default for RecordingPreferences {
@@ -33,8 +59,17 @@ impl Default for RecordingPreferences {
             file_format: "mp4".to_string(),
             preferred_mic_device: None,
             preferred_system_device: None,
+            diarize_after_recording: false,
+            echo_cancellation: false,
+            noise_suppression: NoiseSuppressionSettings::default(),
+            meeting_detection_allowed_apps: Vec::new(),
+            meeting_detection_denied_apps: Vec::new(),
             #[cfg(target_os = "macos")]
             system_audio_backend: Some("coreaudio".to_string()),
+            #[cfg(target_os = "linux")]
+            system_audio_backend: Some("pulseaudio".to_string()),
+            #[cfg(target_os = "linux")]
+            system_audio_apps: Vec::new(),
         }
     }
 }
@@ -110,8 +145,8 @@ pub async fn load_recording_preferences<R: Runtime>(
         match serde_json::from_value::<RecordingPreferences>(value.clone()) {
             Ok(mut p) => {
                 info!("Loaded recording preferences from store");
-                // Update macOS backend to current value if needed
-                #[cfg(target_os = "macos")]
+                // Update macOS/Linux backend to current value if needed
+                #[cfg(any(target_os = "macos", target_os = "linux"))]
                 {
                     let backend = crate::audio::capture::get_current_backend();
                     p.system_audio_backend = Some(backend.to_string());
@@ -128,6 +163,12 @@ pub async fn load_recording_preferences<R: Runtime>(
         RecordingPreferences::default()
     };
 
+    // Capture and the pipeline read these when a recording starts
+    #[cfg(target_os = "linux")]
+    crate::audio::capture::pulse::set_app_filter(prefs.system_audio_apps.clone());
+    crate::audio::echo_cancellation::set_enabled(prefs.echo_cancellation);
+    crate::audio::denoise::set_settings(prefs.noise_suppression);
+
     info!("Loaded recording preferences: save_folder={:?}, auto_save={}, format={}, mic={:?}, system={:?}",
           prefs.save_folder, prefs.auto_save, prefs.file_format,
           prefs.preferred_mic_device, prefs.preferred_system_device);
@@ -163,7 +204,7 @@ pub async fn save_recording_preferences<R: Runtime>(
     info!("Successfully persisted recording preferences to disk");
 
     // Save backend preference to global config
-    #[cfg(target_os = "macos")]
+    #[cfg(any(target_os = "macos", target_os = "linux"))]
     if let Some(backend_str) = &preferences.system_audio_backend {
         if let Some(backend) = AudioCaptureBackend::from_string(backend_str) {
             info!("Setting audio capture backend to: {:?}", backend);
@@ -171,12 +212,40 @@ pub async fn save_recording_preferences<R: Runtime>(
         }
     }
 
+    #[cfg(target_os = "linux")]
+    crate::audio::capture::pulse::set_app_filter(preferences.system_audio_apps.clone());
+    crate::audio::echo_cancellation::set_enabled(preferences.echo_cancellation);
+    crate::audio::denoise::set_settings(preferences.noise_suppression);
+
     // Ensure the directory exists
     ensure_recordings_directory(&preferences.save_folder)?;
 
     Ok(())
 }
 
+/// Read the saved noise suppression settings from the preferences store in
+/// `data_dir` without a running app (used by the CLI)
+pub fn load_noise_suppression_settings(data_dir: &Path) -> NoiseSuppressionSettings {
+    let path = data_dir.join("recording_preferences.json");
+    let stored = std::fs::read_to_string(&path)
+        .ok()
+        .and_then(|json| serde_json::from_str::<serde_json::Value>(&json).ok())
+        .and_then(|store| store.get("preferences")?.get("noise_suppression").cloned());
+
+    match stored.map(serde_json::from_value::<NoiseSuppressionSettings>) {
+        Some(Ok(settings)) => settings,
+        Some(Err(e)) => {
+            warn!(
+                "Invalid noise suppression settings in {}: {}, using defaults",
+                path.display(),
+                e
+            );
+            NoiseSuppressionSettings::default()
+        }
+        None => NoiseSuppressionSettings::default(),
+    }
+}
+
 /// Tauri commands for recording preferences
 #[tauri::command]
 pub async fn get_recording_preferences<R: Runtime>(
@@ -259,15 +328,15 @@ pub async fn select_recording_folder<R: Runtime>(
 /// Get available audio capture backends for the current platform
 #[tauri::command]
 pub async fn get_available_audio_backends() -> Result<Vec<String>, String> {
-    #[cfg(target_os = "macos")]
+    #[cfg(any(target_os = "macos", target_os = "linux"))]
     {
         let backends = crate::audio::capture::get_available_backends();
         Ok(backends.iter().map(|b| b.to_string()).collect())
     }
 
-    #[cfg(not(target_os = "macos"))]
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
     {
-        // Only ScreenCaptureKit available on non-macOS
+        // Only ScreenCaptureKit available on other platforms
         Ok(vec!["screencapturekit".to_string()])
     }
 }
@@ -275,13 +344,13 @@ pub async fn get_available_audio_backends() -> Result<Vec<String>, String> {
 /// Get current audio capture backend
 #[tauri::command]
 pub async fn get_current_audio_backend() -> Result<String, String> {
-    #[cfg(target_os = "macos")]
+    #[cfg(any(target_os = "macos", target_os = "linux"))]
     {
         let backend = crate::audio::capture::get_current_backend();
         Ok(backend.to_string())
     }
 
-    #[cfg(not(target_os = "macos"))]
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
     {
         Ok("screencapturekit".to_string())
     }
@@ -332,7 +401,27 @@ pub async fn set_audio_backend(backend: String) -> Result<(), String> {
         Ok(())
     }
 
-    #[cfg(not(target_os = "macos"))]
+    #[cfg(target_os = "linux")]
+    {
+        let backend_enum = AudioCaptureBackend::from_string(&backend)
+            .ok_or_else(|| format!("Invalid backend: {}", backend))?;
+
+        // The sound server must be reachable, otherwise every recording would
+        // silently fall back to CPAL
+        if backend_enum == AudioCaptureBackend::PulseAudio
+            && !crate::audio::capture::pulse::is_available()
+        {
+            return Err("No PipeWire or PulseAudio server is running. \
+                Start pipewire-pulse or pulseaudio, or use the ALSA backend."
+                .to_string());
+        }
+
+        info!("Setting audio backend to: {:?}", backend_enum);
+        crate::audio::capture::set_current_backend(backend_enum);
+        Ok(())
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
     {
         if backend != "screencapturekit" {
             return Err(format!(
@@ -375,7 +464,20 @@ pub async fn get_audio_backend_info() -> Result<Vec<BackendInfo>, String> {
         Ok(backends)
     }
 
-    #[cfg(not(target_os = "macos"))]
+    #[cfg(target_os = "linux")]
+    {
+        let backends = crate::audio::capture::get_available_backends()
+            .into_iter()
+            .map(|backend| BackendInfo {
+                id: backend.to_string(),
+                name: backend.name().to_string(),
+                description: backend.description().to_string(),
+            })
+            .collect();
+        Ok(backends)
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
     {
         Ok(vec![BackendInfo {
             id: "screencapturekit".to_string(),
@@ -1,10 +1,14 @@
 // Retranscription module - allows re-processing stored audio with different settings
 
 use crate::audio::decoder::decode_audio_file;
+use crate::audio::transcription::{is_cloud_provider, load_cloud_provider, offset_words, WordTiming};
 use crate::audio::vad::get_speech_chunks_with_progress;
 use super::common::{create_transcript_segments, split_segment_at_silence, write_transcripts_json};
 use super::constants::AUDIO_EXTENSIONS;
 use crate::config::{DEFAULT_WHISPER_MODEL, DEFAULT_PARAKEET_MODEL};
+use crate::database::repositories::speaker::SpeakersRepository;
+use crate::database::repositories::transcript::TranscriptsRepository;
+use crate::diarization::apply_speaker_labels;
 use crate::parakeet_engine::ParakeetEngine;
 use crate::state::AppState;
 use crate::whisper_engine::WhisperEngine;
@@ -94,6 +98,7 @@ pub async fn start_retranscription<R: Runtime>(
     language: Option<String>,
     model: Option<String>,
     provider: Option<String>,
+    enable_diarization: Option<bool>,
 ) -> Result<RetranscriptionResult> {
     // Acquire guard - ensures flag is cleared even on panic/early return
     let _guard = RetranscriptionGuard::acquire().map_err(|e| anyhow!(e))?;
@@ -102,10 +107,14 @@ pub async fn start_retranscription<R: Runtime>(
     RETRANSCRIPTION_CANCELLED.store(false, Ordering::SeqCst);
 
     let use_parakeet = provider.as_deref() == Some("parakeet");
-    let result = run_retranscription(app.clone(), meeting_id.clone(), meeting_folder_path, language, model, provider).await;
+    let use_cloud = provider.as_deref().is_some_and(is_cloud_provider);
+    let result = run_retranscription(app.clone(), meeting_id.clone(), meeting_folder_path, language, model, provider, enable_diarization).await;
 
-    // Unload the engine after the batch job (success, failure, or cancellation)
-    super::common::unload_engine_after_batch(use_parakeet).await;
+    // Unload the engine after the batch job (success, failure, or cancellation);
+    // cloud providers hold no local model
+    if !use_cloud {
+        super::common::unload_engine_after_batch(use_parakeet).await;
+    }
 
     // Guard will automatically clear flag on drop
     // No need for manual: RETRANSCRIPTION_IN_PROGRESS.store(false, Ordering::SeqCst);
@@ -138,7 +147,7 @@ pub async fn start_retranscription<R: Runtime>(
 
 /// Find audio file in meeting folder
 /// Tries common names first, then scans for any file with an audio extension
-fn find_audio_file(folder: &Path) -> Result<PathBuf> {
+pub(crate) fn find_audio_file(folder: &Path) -> Result<PathBuf> {
     let candidates = [
         "audio.mp4", "audio.m4a", "audio.wav", "audio.mp3",
         "audio.flac", "audio.ogg", "recording.mp4",
@@ -176,11 +185,13 @@ async fn run_retranscription<R: Runtime>(
     language: Option<String>,
     model: Option<String>,
     provider: Option<String>,
+    enable_diarization: Option<bool>,
 ) -> Result<RetranscriptionResult> {
     let folder_path = PathBuf::from(&meeting_folder_path);
     let audio_path = find_audio_file(&folder_path)?;
 
     // Determine which provider to use (default to whisper)
+    let cloud_provider_name = provider.as_deref().filter(|p| is_cloud_provider(p));
     let use_parakeet = provider.as_deref() == Some("parakeet");
 
     info!(
@@ -299,7 +310,19 @@ async fn run_retranscription<R: Runtime>(
     emit_progress(&app, &meeting_id, "transcribing", 25, "Loading transcription engine...");
 
     // Initialize the appropriate engine once (not per-segment)
-    let whisper_engine = if !use_parakeet {
+    let cloud_provider = match cloud_provider_name {
+        Some(name) => {
+            let app_state = app
+                .try_state::<AppState>()
+                .ok_or_else(|| anyhow!("App state not available"))?;
+            let provider = load_cloud_provider(app_state.db_manager.pool(), name, model.as_deref())
+                .await
+                .map_err(|e| anyhow!(e))?;
+            Some(provider)
+        }
+        None => None,
+    };
+    let whisper_engine = if !use_parakeet && cloud_provider.is_none() {
         Some(get_or_init_whisper(&app, model.as_deref()).await?)
     } else {
         None
@@ -336,7 +359,7 @@ async fn run_retranscription<R: Runtime>(
     info!("Processing {} segments (after splitting)", processable_count);
 
     // Process each speech segment with progress updates
-    let mut all_transcripts: Vec<(String, f64, f64)> = Vec::new(); // (text, start_ms, end_ms)
+    let mut all_transcripts: Vec<(String, f64, f64, Vec<WordTiming>)> = Vec::new(); // (text, start_ms, end_ms, words)
     let mut total_confidence = 0.0f32;
 
     for (i, segment) in processable_segments.iter().enumerate() {
@@ -346,7 +369,7 @@ async fn run_retranscription<R: Runtime>(
         }
 
         // Calculate progress (25% to 80% range for transcription)
-        let progress = 25 + ((i as f32 / processable_count as f32) * 55.0) as u32;
+        let progress = 25 + ((i as f32 / processable_count as f32) * 50.0) as u32;
         let segment_duration_sec = (segment.end_timestamp_ms - segment.start_timestamp_ms) / 1000.0;
         emit_progress(
             &app,
@@ -368,20 +391,26 @@ async fn run_retranscription<R: Runtime>(
         }
 
         // Transcribe this segment
-        let (text, conf) = if use_parakeet {
+        let (text, conf, mut words) = if let Some(provider) = cloud_provider.as_ref() {
+            let result = provider
+                .transcribe(segment.samples.clone(), language.clone())
+                .await
+                .map_err(|e| anyhow!("{} transcription failed on segment {}: {}", provider.provider_name(), i, e))?;
+            (result.text, result.confidence.unwrap_or(0.9f32), result.words)
+        } else if use_parakeet {
             let engine = parakeet_engine.as_ref().unwrap();
-            let text = engine
-                .transcribe_audio(segment.samples.clone())
+            let (text, words) = engine
+                .transcribe_audio_with_words(segment.samples.clone())
                 .await
                 .map_err(|e| anyhow!("Parakeet transcription failed on segment {}: {}", i, e))?;
-            (text, 0.9f32)
+            (text, 0.9f32, words)
         } else {
             let engine = whisper_engine.as_ref().unwrap();
-            let (text, conf, _) = engine
+            let (text, conf, _, words) = engine
                 .transcribe_audio_with_confidence(segment.samples.clone(), language.clone())
                 .await
                 .map_err(|e| anyhow!("Whisper transcription failed on segment {}: {}", i, e))?;
-            (text, conf)
+            (text, conf, words)
         };
 
         // Skip empty transcripts
@@ -392,7 +421,8 @@ async fn run_retranscription<R: Runtime>(
                 i + 1, processable_count, segment_duration_sec, conf,
                 if trimmed.len() > 80 { let mut end = 80; while !trimmed.is_char_boundary(end) { end -= 1; } &trimmed[..end] } else { trimmed }
             );
-            all_transcripts.push((text, segment.start_timestamp_ms, segment.end_timestamp_ms));
+            offset_words(&mut words, segment.start_timestamp_ms / 1000.0);
+            all_transcripts.push((text, segment.start_timestamp_ms, segment.end_timestamp_ms, words));
             total_confidence += conf;
         } else {
             debug!("Segment {}/{}: {:.1}s — empty transcription", i + 1, processable_count, segment_duration_sec);
@@ -416,23 +446,70 @@ async fn run_retranscription<R: Runtime>(
         return Err(anyhow!("Retranscription cancelled"));
     }
 
+    // Identify speakers (skipped when disabled or the embedding model is not downloaded)
+    let mut diarization = if enable_diarization.unwrap_or(true) && transcribed_count > 0 {
+        emit_progress(&app, &meeting_id, "diarizing", 75, "Identifying speakers...");
+        let app_for_diarization = app.clone();
+        let meeting_id_for_diarization = meeting_id.clone();
+        match crate::diarization::diarize_speech_segments(
+            Arc::new(speech_segments),
+            None,
+            move |progress| {
+                emit_progress(
+                    &app_for_diarization,
+                    &meeting_id_for_diarization,
+                    "diarizing",
+                    75 + progress * 5 / 100,
+                    "Identifying speakers...",
+                );
+                !RETRANSCRIPTION_CANCELLED.load(Ordering::SeqCst)
+            },
+        )
+        .await
+        {
+            Ok(result) => result,
+            Err(e) => {
+                warn!("Speaker diarization failed, continuing without speaker labels: {}", e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    // Check for cancellation
+    if RETRANSCRIPTION_CANCELLED.load(Ordering::SeqCst) {
+        return Err(anyhow!("Retranscription cancelled"));
+    }
+
     emit_progress(&app, &meeting_id, "saving", 80, "Saving transcripts...");
 
     // Create transcript segments with proper timestamps from VAD
-    let segments = create_transcript_segments(&all_transcripts);
+    let mut segments = create_transcript_segments(&all_transcripts);
 
     // Save to database
     let app_state = app
         .try_state::<AppState>()
         .ok_or_else(|| anyhow!("App state not available"))?;
+    let pool = app_state.db_manager.pool();
+
+    if let Some(result) = diarization.as_mut() {
+        crate::diarization::recognize_known_speakers(pool, result).await;
+        apply_speaker_labels(&mut segments, &result.turns);
+    }
 
     // Wrap delete+insert+update in a transaction to prevent data loss
-    let pool = app_state.db_manager.pool();
     let mut conn = pool.acquire().await.map_err(|e| anyhow!("DB error: {}", e))?;
     let mut tx = sqlx::Connection::begin(&mut *conn)
         .await
         .map_err(|e| anyhow!("Failed to start transaction: {}", e))?;
 
+    sqlx::query("DELETE FROM transcript_words WHERE meeting_id = ?")
+        .bind(&meeting_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| anyhow!("Failed to delete existing word timings: {}", e))?;
+
     sqlx::query("DELETE FROM transcripts WHERE meeting_id = ?")
         .bind(&meeting_id)
         .execute(&mut *tx)
@@ -441,8 +518,8 @@ async fn run_retranscription<R: Runtime>(
 
     for segment in &segments {
         sqlx::query(
-            "INSERT INTO transcripts (id, meeting_id, transcript, timestamp, audio_start_time, audio_end_time, duration)
-             VALUES (?, ?, ?, ?, ?, ?, ?)"
+            "INSERT INTO transcripts (id, meeting_id, transcript, timestamp, audio_start_time, audio_end_time, duration, speaker_label)
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?)"
         )
         .bind(&segment.id)
         .bind(&meeting_id)
@@ -451,11 +528,22 @@ async fn run_retranscription<R: Runtime>(
         .bind(segment.audio_start_time)
         .bind(segment.audio_end_time)
         .bind(segment.duration)
+        .bind(&segment.speaker_label)
         .execute(&mut *tx)
         .await
         .map_err(|e| anyhow!("Failed to insert transcript: {}", e))?;
+
+        TranscriptsRepository::insert_transcript_words(&mut *tx, &meeting_id, &segment.id, &segment.words)
+            .await
+            .map_err(|e| anyhow!("Failed to insert word timings: {}", e))?;
     }
 
+    // Old speaker clusters no longer match the new transcript rows
+    let speakers = diarization.as_ref().map(|d| d.speakers.as_slice()).unwrap_or(&[]);
+    SpeakersRepository::replace_meeting_speakers(&mut *tx, &meeting_id, speakers)
+        .await
+        .map_err(|e| anyhow!("Failed to save speakers: {}", e))?;
+
     tx.commit().await
         .map_err(|e| anyhow!("Failed to commit transaction: {}", e))?;
 
@@ -782,6 +870,7 @@ pub async fn start_retranscription_command<R: Runtime>(
     language: Option<String>,
     model: Option<String>,
     provider: Option<String>,
+    enable_diarization: Option<bool>,
 ) -> Result<RetranscriptionStarted, String> {
 
     // Check if retranscription is already in progress (guard will be acquired in start_retranscription)
@@ -801,6 +890,7 @@ pub async fn start_retranscription_command<R: Runtime>(
             language,
             model,
             provider,
+            enable_diarization,
         )
         .await;
 
@@ -13,6 +13,9 @@ use super::capture::{AudioCaptureBackend, get_current_backend};
 #[cfg(target_os = "macos")]
 use super::capture::CoreAudioCapture;
 
+#[cfg(target_os = "linux")]
+use super::capture::{pulse, PulseCapture};
+
 /// Stream backend implementation
 pub enum StreamBackend {
     /// CPAL-based stream (ScreenCaptureKit or default)
@@ -22,6 +25,11 @@ pub enum StreamBackend {
     CoreAudio {
         task: Option<tokio::task::JoinHandle<()>>,
     },
+    /// PipeWire / PulseAudio monitor recording (Linux only)
+    #[cfg(target_os = "linux")]
+    Pulse {
+        capture: Option<PulseCapture>,
+    },
 }
 
 // SAFETY: While Stream doesn't implement Send, we ensure it's only accessed
@@ -87,6 +95,35 @@ impl AudioStream {
             return Self::create_core_audio_stream(device, state, device_type, recording_sender).await;
         }
 
+        // On Linux, system audio devices listed by the sound server are recorded
+        // through it; anything else (e.g. an ALSA device) still goes through CPAL
+        #[cfg(target_os = "linux")]
+        if device_type == DeviceType::System && backend_type == AudioCaptureBackend::PulseAudio {
+            // An app filter records only those apps, whichever output they use
+            let apps = pulse::app_filter();
+            let target = if apps.is_empty() {
+                pulse::resolve_target(&device.name)
+            } else {
+                Some(pulse::PulseTarget::Apps(apps))
+            };
+            match target {
+                Some(target) => {
+                    info!("🎵 Stream: Using PipeWire/PulseAudio backend for system audio ({:?})", target);
+                    match Self::create_pulse_stream(
+                        device.clone(),
+                        state.clone(),
+                        device_type,
+                        recording_sender.clone(),
+                        target,
+                    ) {
+                        Ok(stream) => return Ok(stream),
+                        Err(e) => warn!("⚠️ Stream: PipeWire/PulseAudio capture failed, falling back to CPAL: {}", e),
+                    }
+                }
+                None => info!("🎵 Stream: {} is not a PipeWire/PulseAudio sink, using CPAL", device.name),
+            }
+        }
+
         // Default path: use CPAL
         #[cfg(target_os = "macos")]
         let backend_name = if backend_type == AudioCaptureBackend::ScreenCaptureKit {
@@ -233,6 +270,39 @@ impl AudioStream {
         })
     }
 
+    /// Create a PipeWire / PulseAudio monitor stream (Linux only)
+    #[cfg(target_os = "linux")]
+    fn create_pulse_stream(
+        device: Arc<AudioDevice>,
+        state: Arc<RecordingState>,
+        device_type: DeviceType,
+        recording_sender: Option<mpsc::UnboundedSender<super::recording_state::AudioChunk>>,
+        target: pulse::PulseTarget,
+    ) -> Result<Self> {
+        // The sound server converts the sink's format to mono 48kHz for us
+        let capture = AudioCapture::new(
+            device.clone(),
+            state,
+            pulse::SAMPLE_RATE,
+            pulse::CHANNELS,
+            device_type,
+            recording_sender,
+        );
+
+        let pulse_capture = PulseCapture::start(target, move |data| {
+            capture.process_audio_data(data);
+        })?;
+
+        info!("✅ Stream: PipeWire/PulseAudio stream started for device: {}", device.name);
+
+        Ok(Self {
+            device,
+            backend: StreamBackend::Pulse {
+                capture: Some(pulse_capture),
+            },
+        })
+    }
+
     /// Build stream based on sample format
     fn build_stream(
         device: &Device,
@@ -343,6 +413,15 @@ impl AudioStream {
                     info!("Core Audio task aborted");
                 }
             }
+            #[cfg(target_os = "linux")]
+            StreamBackend::Pulse { capture } => {
+                // Joins the capture thread, which drops the callback and its
+                // AudioCapture clone
+                if let Some(capture) = capture {
+                    capture.stop();
+                    info!("PipeWire/PulseAudio capture stopped");
+                }
+            }
         }
 
         // Explicitly drop self.device Arc reference
@@ -1,8 +1,10 @@
-use tauri::{command, AppHandle, Emitter, State};
+use tauri::{command, AppHandle, Emitter, Manager, State};
 use crate::audio::{
     start_system_audio_capture, list_system_audio_devices, check_system_audio_permissions,
-    SystemAudioDetector, SystemAudioEvent, new_system_audio_callback
+    SystemAudioDetector, SystemAudioEvent, new_system_audio_callback,
+    list_audio_producing_apps, AudioApp, MeetingAppFilter
 };
+use crate::notifications::commands::{show_meeting_detected_notification, NotificationManagerState};
 use std::sync::{Arc, Mutex};
 use anyhow::Result;
 
@@ -28,6 +30,13 @@ pub async fn list_system_audio_devices_command() -> Result<Vec<String>, String>
         .map_err(|e| format!("Failed to list system audio devices: {}", e))
 }
 
+/// List the apps currently producing audio, for choosing which apps to record
+#[command]
+pub async fn list_audio_producing_apps_command() -> Result<Vec<AudioApp>, String> {
+    list_audio_producing_apps()
+        .map_err(|e| format!("Failed to list apps producing audio: {}", e))
+}
+
 /// Check if the app has permission to access system audio
 #[command]
 pub async fn check_system_audio_permissions_command() -> bool {
@@ -40,6 +49,18 @@ pub async fn start_system_audio_monitoring(
     app_handle: AppHandle,
     detector_state: State<'_, SystemAudioDetectorState>
 ) -> Result<(), String> {
+    // Allow/deny lists of apps that may trigger the record prompt
+    let filter = match crate::audio::recording_preferences::load_recording_preferences(&app_handle).await {
+        Ok(prefs) => MeetingAppFilter::new(
+            prefs.meeting_detection_allowed_apps,
+            prefs.meeting_detection_denied_apps,
+        ),
+        Err(e) => {
+            tracing::warn!("Failed to load meeting detection preferences: {}", e);
+            MeetingAppFilter::default()
+        }
+    };
+
     let mut detector_guard = detector_state.lock()
         .map_err(|e| format!("Failed to acquire detector lock: {}", e))?;
 
@@ -47,14 +68,26 @@ pub async fn start_system_audio_monitoring(
         return Err("System audio monitoring is already active".to_string());
     }
 
-    let mut detector = SystemAudioDetector::new();
+    let mut detector = SystemAudioDetector::with_filter(filter);
 
     // Create callback that emits events to the frontend
     let callback = new_system_audio_callback(move |event| {
         match event {
             SystemAudioEvent::SystemAudioStarted(apps) => {
                 tracing::info!("System audio started by apps: {:?}", apps);
-                let _ = app_handle.emit("system-audio-started", apps);
+                let _ = app_handle.emit("system-audio-started", apps.clone());
+
+                // Offer to record the call unless we already are
+                let app_handle = app_handle.clone();
+                tauri::async_runtime::spawn(async move {
+                    if crate::audio::recording_commands::is_recording().await {
+                        return;
+                    }
+                    let manager_state = app_handle.state::<NotificationManagerState<tauri::Wry>>();
+                    if let Err(e) = show_meeting_detected_notification(&manager_state, apps).await {
+                        tracing::error!("Failed to show meeting detected notification: {}", e);
+                    }
+                });
             }
             SystemAudioEvent::SystemAudioStopped => {
                 let _ = app_handle.emit("system-audio-stopped", ());
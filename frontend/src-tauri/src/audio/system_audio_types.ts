@@ -18,11 +18,20 @@ export interface SystemAudioCommands {
 
   // Get the current status of system audio monitoring
   getSystemAudioMonitoringStatus(): Promise<boolean>;
+
+  // List the apps currently producing audio
+  listAudioProducingAppsCommand(): Promise<AudioApp[]>;
+}
+
+export interface AudioApp {
+  name: string;
+  binary: string | null;
+  pid: number | null;
 }
 
 // Event types emitted by the system audio detector
 export interface SystemAudioEvents {
-  'system-audio-started': string[]; // Array of app names using system audio
+  'system-audio-started': string[]; // Array of app names using system audio (on Linux: apps in a call)
   'system-audio-stopped': void;
 }
 
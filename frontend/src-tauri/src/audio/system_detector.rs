@@ -354,6 +354,57 @@ impl MacOSSystemAudioDetector {
     }
 }
 
+/// An app currently producing audio
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct AudioApp {
+    /// Display name, e.g. "Firefox" or "Zoom"
+    pub name: String,
+    /// Executable name, where the platform reports it
+    pub binary: Option<String>,
+    pub pid: Option<u32>,
+}
+
+/// Lists the apps currently producing audio
+pub fn list_audio_producing_apps() -> anyhow::Result<Vec<AudioApp>> {
+    #[cfg(target_os = "macos")]
+    {
+        Ok(list_system_audio_using_apps()
+            .into_iter()
+            .map(|name| AudioApp {
+                name,
+                binary: None,
+                pid: None,
+            })
+            .collect())
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        // An app can have several sink inputs (e.g. one per browser tab)
+        let mut apps: Vec<AudioApp> = Vec::new();
+        for input in crate::audio::capture::pulse::list_sink_inputs()? {
+            if !apps
+                .iter()
+                .any(|app| app.name == input.app_name && app.binary == input.binary)
+            {
+                apps.push(AudioApp {
+                    name: input.app_name,
+                    binary: input.binary,
+                    pid: input.pid,
+                });
+            }
+        }
+        Ok(apps)
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+    {
+        Err(anyhow::anyhow!(
+            "Listing apps that produce audio is not supported on this platform"
+        ))
+    }
+}
+
 #[cfg(target_os = "macos")]
 fn list_system_audio_using_apps() -> Vec<String> {
     match ca::System::processes() {
@@ -378,21 +429,300 @@ fn list_system_audio_using_apps() -> Vec<String> {
     }
 }
 
-// Stub implementation for non-macOS platforms
-#[cfg(not(target_os = "macos"))]
+/// Which apps may trigger meeting detection. Names are matched
+/// case-insensitively against the app name (or, on Linux, its binary).
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MeetingAppFilter {
+    /// If set, only these apps are detected
+    pub allowed: Vec<String>,
+    /// Never detected, e.g. a music player
+    pub denied: Vec<String>,
+}
+
+impl MeetingAppFilter {
+    pub fn new(allowed: Vec<String>, denied: Vec<String>) -> Self {
+        Self { allowed, denied }
+    }
+
+    fn listed(list: &[String], names: &[&str]) -> bool {
+        list.iter()
+            .any(|entry| names.iter().any(|name| name.eq_ignore_ascii_case(entry)))
+    }
+
+    /// Whether the app is explicitly allowed
+    pub fn is_allowed(&self, names: &[&str]) -> bool {
+        Self::listed(&self.allowed, names)
+    }
+
+    pub fn is_denied(&self, names: &[&str]) -> bool {
+        Self::listed(&self.denied, names)
+    }
+
+    /// Whether an app may trigger detection, going by the lists alone
+    pub fn permits(&self, names: &[&str]) -> bool {
+        !self.is_denied(names) && (self.allowed.is_empty() || self.is_allowed(names))
+    }
+
+    /// Wraps a callback so it only hears about permitted apps. A start event
+    /// left with no apps is dropped, and so is the stop event that follows it.
+    #[cfg(not(target_os = "linux"))]
+    fn wrap(self, callback: SystemAudioCallback) -> SystemAudioCallback {
+        let announced = std::sync::atomic::AtomicBool::new(false);
+        new_system_audio_callback(move |event| match event {
+            SystemAudioEvent::SystemAudioStarted(apps) => {
+                let apps: Vec<String> = apps
+                    .into_iter()
+                    .filter(|app| self.permits(&[app.as_str()]))
+                    .collect();
+                if !apps.is_empty() {
+                    announced.store(true, std::sync::atomic::Ordering::SeqCst);
+                    callback(SystemAudioEvent::SystemAudioStarted(apps));
+                }
+            }
+            SystemAudioEvent::SystemAudioStopped => {
+                if announced.swap(false, std::sync::atomic::Ordering::SeqCst) {
+                    callback(SystemAudioEvent::SystemAudioStopped);
+                }
+            }
+        })
+    }
+}
+
+/// Debounces call detection: a call has to be seen for `start_delay` before
+/// it starts and gone for `stop_delay` before it ends, so a notification
+/// sound or a device switch mid-call doesn't produce events
+#[cfg(target_os = "linux")]
+struct CallDebouncer {
+    active: bool,
+    changed_since: Option<std::time::Instant>,
+    start_delay: std::time::Duration,
+    stop_delay: std::time::Duration,
+}
+
+#[cfg(target_os = "linux")]
+impl CallDebouncer {
+    fn new(start_delay: std::time::Duration, stop_delay: std::time::Duration) -> Self {
+        Self {
+            active: false,
+            changed_since: None,
+            start_delay,
+            stop_delay,
+        }
+    }
+
+    /// Returns the new state when the call starts (true) or ends (false)
+    fn update(&mut self, now: std::time::Instant, detected: bool) -> Option<bool> {
+        if detected == self.active {
+            self.changed_since = None;
+            return None;
+        }
+        let since = *self.changed_since.get_or_insert(now);
+        let delay = if detected {
+            self.start_delay
+        } else {
+            self.stop_delay
+        };
+        if now.duration_since(since) < delay {
+            return None;
+        }
+        self.active = detected;
+        self.changed_since = None;
+        Some(detected)
+    }
+}
+
+/// Apps that only use audio for calls (matched within the app or binary name)
+#[cfg(target_os = "linux")]
+const KNOWN_MEETING_APPS: &[&str] = &["zoom", "webex", "jitsi", "whereby"];
+
+/// Chat apps also play message sounds, so like browsers they are only in a
+/// call while they record the microphone
+#[cfg(target_os = "linux")]
+const KNOWN_CHAT_APPS: &[&str] = &["teams", "skype", "slack", "discord"];
+
+/// Browsers are only in a call while they record the microphone (WebRTC)
+#[cfg(target_os = "linux")]
+const KNOWN_BROWSERS: &[&str] = &[
+    "firefox",
+    "chrome",
+    "chromium",
+    "brave",
+    "msedge",
+    "microsoft edge",
+    "vivaldi",
+    "opera",
+];
+
+#[cfg(target_os = "linux")]
+fn is_known(list: &[&str], names: &[&str]) -> bool {
+    names.iter().any(|name| {
+        let name = name.to_lowercase();
+        list.iter().any(|known| name.contains(known))
+    })
+}
+
+/// Apps that are in a call: known meeting apps playing or recording audio,
+/// and chat apps and browsers recording the microphone. With an allow list, any allowed app
+/// recording the microphone counts instead.
+#[cfg(target_os = "linux")]
+fn meeting_apps(
+    inputs: &[crate::audio::capture::PulseSinkInput],
+    outputs: &[crate::audio::capture::PulseSourceOutput],
+    filter: &MeetingAppFilter,
+    own_pid: u32,
+) -> Vec<String> {
+    let playing = inputs
+        .iter()
+        .map(|input| (&input.app_name, &input.binary, input.pid, false));
+    let recording = outputs
+        .iter()
+        .map(|output| (&output.app_name, &output.binary, output.pid, true));
+
+    let mut apps: Vec<String> = Vec::new();
+    for (app_name, binary, pid, uses_mic) in recording.chain(playing) {
+        // Our own capture streams
+        if pid == Some(own_pid) {
+            continue;
+        }
+        let names: Vec<&str> = std::iter::once(app_name.as_str())
+            .chain(binary.as_deref())
+            .collect();
+        if !filter.permits(&names) {
+            continue;
+        }
+        let in_call = if filter.allowed.is_empty() {
+            is_known(KNOWN_MEETING_APPS, &names)
+                || (uses_mic
+                    && (is_known(KNOWN_CHAT_APPS, &names) || is_known(KNOWN_BROWSERS, &names)))
+        } else {
+            uses_mic
+        };
+        if in_call && !apps.contains(app_name) {
+            apps.push(app_name.clone());
+        }
+    }
+    apps
+}
+
+/// Detects calls on Linux from the PipeWire/PulseAudio app streams
+#[cfg(target_os = "linux")]
+#[derive(Default)]
+pub struct LinuxSystemAudioDetector {
+    stop: std::sync::Arc<std::sync::atomic::AtomicBool>,
+    thread: Option<std::thread::JoinHandle<()>>,
+}
+
+#[cfg(target_os = "linux")]
+impl LinuxSystemAudioDetector {
+    const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(250);
+    const RECONNECT_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+    const CALL_START_DELAY: std::time::Duration = std::time::Duration::from_secs(2);
+    const CALL_STOP_DELAY: std::time::Duration = std::time::Duration::from_secs(5);
+
+    pub fn start(&mut self, callback: SystemAudioCallback, filter: MeetingAppFilter) {
+        use crate::audio::capture::PulseStreamWatcher;
+        use std::sync::atomic::Ordering;
+        use std::time::Instant;
+
+        if self.thread.is_some() {
+            return; // Already running
+        }
+        self.stop.store(false, Ordering::SeqCst);
+        let stop = self.stop.clone();
+
+        // The PulseAudio mainloop isn't Send, so the watcher lives on its own thread
+        let thread = std::thread::Builder::new()
+            .name("meeting-detector".to_string())
+            .spawn(move || {
+                let own_pid = std::process::id();
+                let mut watcher: Option<PulseStreamWatcher> = None;
+                let mut retry_at = Instant::now();
+                let mut debouncer =
+                    CallDebouncer::new(Self::CALL_START_DELAY, Self::CALL_STOP_DELAY);
+                let mut apps = Vec::new();
+
+                while !stop.load(Ordering::SeqCst) {
+                    if watcher.is_none() && Instant::now() >= retry_at {
+                        match PulseStreamWatcher::new() {
+                            Ok(new_watcher) => {
+                                tracing::info!("Watching PipeWire/PulseAudio streams for calls");
+                                watcher = Some(new_watcher);
+                            }
+                            Err(e) => {
+                                tracing::warn!("Meeting detection unavailable: {}", e);
+                                retry_at = Instant::now() + Self::RECONNECT_INTERVAL;
+                            }
+                        }
+                    }
+                    if let Some(current) = watcher.as_mut() {
+                        match current.poll() {
+                            Ok(Some((inputs, outputs))) => {
+                                apps = meeting_apps(&inputs, &outputs, &filter, own_pid);
+                            }
+                            Ok(None) => {}
+                            Err(e) => {
+                                tracing::warn!("Lost PipeWire/PulseAudio connection: {}", e);
+                                watcher = None;
+                                apps.clear();
+                                retry_at = Instant::now() + Self::RECONNECT_INTERVAL;
+                            }
+                        }
+                    }
+
+                    match debouncer.update(Instant::now(), !apps.is_empty()) {
+                        Some(true) => {
+                            let event = SystemAudioEvent::SystemAudioStarted(apps.clone());
+                            tracing::info!(event = ?event, "detected");
+                            callback(event);
+                        }
+                        Some(false) => {
+                            let event = SystemAudioEvent::SystemAudioStopped;
+                            tracing::info!(event = ?event, "detected");
+                            callback(event);
+                        }
+                        None => {}
+                    }
+
+                    std::thread::sleep(Self::POLL_INTERVAL);
+                }
+            });
+
+        match thread {
+            Ok(thread) => self.thread = Some(thread),
+            Err(e) => tracing::error!("Failed to start meeting detection: {}", e),
+        }
+    }
+
+    pub fn stop(&mut self) {
+        self.stop.store(true, std::sync::atomic::Ordering::SeqCst);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl Drop for LinuxSystemAudioDetector {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+// Stub implementation for other platforms
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
 pub struct MacOSSystemAudioDetector;
 
-#[cfg(not(target_os = "macos"))]
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
 impl Default for MacOSSystemAudioDetector {
     fn default() -> Self {
         Self
     }
 }
 
-#[cfg(not(target_os = "macos"))]
+#[cfg(not(any(target_os = "macos", target_os = "linux")))]
 impl MacOSSystemAudioDetector {
     pub fn start(&mut self, _callback: SystemAudioCallback) {
-        tracing::warn!("System audio detection is only supported on macOS");
+        tracing::warn!("System audio detection is only supported on macOS and Linux");
     }
 
     pub fn stop(&mut self) {}
@@ -401,7 +731,11 @@ impl MacOSSystemAudioDetector {
 /// Public interface for system audio detection
 #[derive(Default)]
 pub struct SystemAudioDetector {
+    #[cfg(not(target_os = "linux"))]
     inner: MacOSSystemAudioDetector,
+    #[cfg(target_os = "linux")]
+    inner: LinuxSystemAudioDetector,
+    filter: MeetingAppFilter,
 }
 
 impl SystemAudioDetector {
@@ -409,8 +743,20 @@ impl SystemAudioDetector {
         Self::default()
     }
 
+    /// Detector that only reports the apps `filter` permits
+    pub fn with_filter(filter: MeetingAppFilter) -> Self {
+        Self {
+            filter,
+            ..Self::default()
+        }
+    }
+
     pub fn start(&mut self, callback: SystemAudioCallback) {
-        self.inner.start(callback);
+        #[cfg(target_os = "linux")]
+        self.inner.start(callback, self.filter.clone());
+
+        #[cfg(not(target_os = "linux"))]
+        self.inner.start(self.filter.clone().wrap(callback));
     }
 
     pub fn stop(&mut self) {
@@ -433,4 +779,101 @@ mod tests {
         tokio::time::sleep(tokio::time::Duration::from_secs(30)).await;
         detector.stop();
     }
+
+    #[test]
+    fn test_meeting_app_filter() {
+        let filter = MeetingAppFilter::new(vec![], vec!["Spotify".to_string()]);
+        assert!(filter.permits(&["Zoom"]));
+        assert!(!filter.permits(&["spotify"]));
+
+        let filter = MeetingAppFilter::new(vec!["zoom".to_string()], vec![]);
+        assert!(filter.permits(&["ZOOM VoiceEngine", "zoom"]));
+        assert!(!filter.permits(&["Firefox"]));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_call_debouncer() {
+        use std::time::{Duration, Instant};
+
+        let mut debouncer = CallDebouncer::new(Duration::from_secs(2), Duration::from_secs(5));
+        let start = Instant::now();
+        let at = |secs: u64| start + Duration::from_secs(secs);
+
+        assert_eq!(debouncer.update(at(0), true), None);
+        // A blip doesn't start a call
+        assert_eq!(debouncer.update(at(1), false), None);
+        assert_eq!(debouncer.update(at(2), true), None);
+        assert_eq!(debouncer.update(at(4), true), Some(true));
+        assert_eq!(debouncer.update(at(5), true), None);
+
+        assert_eq!(debouncer.update(at(6), false), None);
+        assert_eq!(debouncer.update(at(10), false), None);
+        assert_eq!(debouncer.update(at(11), false), Some(false));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_meeting_apps() {
+        use crate::audio::capture::{PulseSinkInput, PulseSourceOutput};
+
+        let input = |app: &str, binary: &str, pid: u32| PulseSinkInput {
+            index: pid,
+            sink: 0,
+            app_name: app.to_string(),
+            binary: Some(binary.to_string()),
+            pid: Some(pid),
+            media_name: String::new(),
+        };
+        let output = |app: &str, binary: &str, pid: u32| PulseSourceOutput {
+            index: pid,
+            source: 0,
+            app_name: app.to_string(),
+            binary: Some(binary.to_string()),
+            pid: Some(pid),
+        };
+        let inputs = vec![
+            input("ZOOM VoiceEngine", "zoom", 10),
+            input("Firefox", "firefox", 11),
+            input("Spotify", "spotify", 12),
+        ];
+        let filter = MeetingAppFilter::default();
+
+        // A browser playing audio isn't a call
+        assert_eq!(
+            meeting_apps(&inputs[1..], &[], &filter, 1),
+            Vec::<String>::new()
+        );
+        // A browser using the mic is, and so is a meeting app playing audio
+        let outputs = vec![
+            output("Firefox", "firefox", 11),
+            output("Meetily", "meetily", 1),
+        ];
+        assert_eq!(
+            meeting_apps(&inputs, &outputs, &filter, 1),
+            vec!["Firefox".to_string(), "ZOOM VoiceEngine".to_string()]
+        );
+
+        // A Slack or Discord message sound isn't a call, joining a huddle is
+        let chat = vec![input("Slack", "slack", 14), input("Discord", "Discord", 15)];
+        assert_eq!(meeting_apps(&chat, &[], &filter, 1), Vec::<String>::new());
+        assert_eq!(
+            meeting_apps(&chat, &[output("Slack", "slack", 14)], &filter, 1),
+            vec!["Slack".to_string()]
+        );
+
+        let filter = MeetingAppFilter::new(vec![], vec!["firefox".to_string()]);
+        assert_eq!(
+            meeting_apps(&inputs, &outputs, &filter, 1),
+            vec!["ZOOM VoiceEngine".to_string()]
+        );
+
+        // An allowed app counts once it uses the mic, known or not
+        let filter = MeetingAppFilter::new(vec!["mumble".to_string()], vec![]);
+        let outputs = vec![output("Mumble", "mumble", 13)];
+        assert_eq!(
+            meeting_apps(&inputs, &outputs, &filter, 1),
+            vec!["Mumble".to_string()]
+        );
+    }
 }
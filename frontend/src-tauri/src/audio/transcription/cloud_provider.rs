@@ -0,0 +1,412 @@
+// audio/transcription/cloud_provider.rs
+//
+// Shared plumbing for the cloud speech-to-text providers (Deepgram, ElevenLabs,
+// Groq, OpenAI): provider selection, WAV encoding, batching and retries.
+
+use super::deepgram_provider::DeepgramProvider;
+use super::elevenlabs_provider::ElevenLabsProvider;
+use super::openai_provider::OpenAiProvider;
+use super::provider::{offset_words, TranscriptResult, TranscriptionError, TranscriptionProvider};
+use crate::database::repositories::setting::SettingsRepository;
+use log::warn;
+use reqwest::{header, RequestBuilder, Response, StatusCode};
+use sqlx::SqlitePool;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Sample rate of the audio handed to providers (16kHz mono)
+const SAMPLE_RATE: u32 = 16_000;
+
+/// Longest audio sent in one request. Keeps 16-bit WAV uploads (32 KB/s) well
+/// under the 25 MB limit of the OpenAI-compatible APIs.
+pub const MAX_BATCH_SECONDS: usize = 600;
+
+/// Attempts per request before giving up on rate limits, 5xx and network errors
+const MAX_ATTEMPTS: u32 = 3;
+const INITIAL_BACKOFF_MS: u64 = 500;
+/// Upper bound for a server-requested Retry-After delay
+const MAX_RETRY_AFTER_SECS: u64 = 30;
+
+/// Per-request timeout; a 10 minute batch can take a while to process
+pub const REQUEST_TIMEOUT: Duration = Duration::from_secs(180);
+
+/// Provider names as stored in `transcript_settings.provider`
+pub const CLOUD_PROVIDERS: [&str; 4] = ["deepgram", "elevenLabs", "groq", "openai"];
+
+/// Whether `provider` names a cloud speech-to-text provider
+pub fn is_cloud_provider(provider: &str) -> bool {
+    CLOUD_PROVIDERS.contains(&provider)
+}
+
+/// Model used when none is configured
+pub fn default_model(provider: &str) -> Option<&'static str> {
+    match provider {
+        "deepgram" => Some("nova-3"),
+        "elevenLabs" => Some("scribe_v1"),
+        "groq" => Some("whisper-large-v3-turbo"),
+        "openai" => Some("whisper-1"),
+        _ => None,
+    }
+}
+
+/// Builds the cloud provider for a `transcript_settings` provider name
+pub fn create_cloud_provider(
+    provider: &str,
+    model: &str,
+    api_key: Option<String>,
+) -> Result<Arc<dyn TranscriptionProvider>, String> {
+    let default = default_model(provider)
+        .ok_or_else(|| format!("'{}' is not a cloud transcription provider", provider))?;
+    let api_key = api_key
+        .map(|key| key.trim().to_string())
+        .filter(|key| !key.is_empty())
+        .ok_or_else(|| {
+            format!(
+                "No API key saved for '{}'. Add one in the transcript settings.",
+                provider
+            )
+        })?;
+    let model = if model.trim().is_empty() {
+        default.to_string()
+    } else {
+        model.trim().to_string()
+    };
+
+    Ok(match provider {
+        "deepgram" => Arc::new(DeepgramProvider::new(api_key, model)),
+        "elevenLabs" => Arc::new(ElevenLabsProvider::new(api_key, model)),
+        "groq" => Arc::new(OpenAiProvider::groq(api_key, model)),
+        _ => Arc::new(OpenAiProvider::openai(api_key, model)),
+    })
+}
+
+/// Builds a cloud provider from saved settings. Uses the configured model when
+/// `model` is None and the configured provider matches, else the default model.
+pub async fn load_cloud_provider(
+    pool: &SqlitePool,
+    provider: &str,
+    model: Option<&str>,
+) -> Result<Arc<dyn TranscriptionProvider>, String> {
+    let api_key = SettingsRepository::get_transcript_api_key(pool, provider)
+        .await
+        .map_err(|e| format!("Failed to load API key for '{}': {}", provider, e))?;
+
+    let model = match model {
+        Some(model) => model.to_string(),
+        None => match SettingsRepository::get_transcript_config(pool).await {
+            Ok(Some(config)) if config.provider == provider => config.model,
+            _ => String::new(),
+        },
+    };
+
+    create_cloud_provider(provider, &model, api_key)
+}
+
+/// Language code to send, or None to let the provider detect it
+pub fn language_hint(language: Option<&str>) -> Option<&str> {
+    match language {
+        Some("auto") | Some("auto-translate") | Some("") | None => None,
+        Some(lang) => Some(lang),
+    }
+}
+
+/// Encodes 16kHz mono f32 samples as a 16-bit PCM WAV file
+pub fn encode_wav(samples: &[f32]) -> Vec<u8> {
+    let data_len = (samples.len() * 2) as u32;
+    let mut wav = Vec::with_capacity(44 + data_len as usize);
+
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_len).to_le_bytes());
+    wav.extend_from_slice(b"WAVE");
+    wav.extend_from_slice(b"fmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes()); // fmt chunk size
+    wav.extend_from_slice(&1u16.to_le_bytes()); // PCM
+    wav.extend_from_slice(&1u16.to_le_bytes()); // mono
+    wav.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+    wav.extend_from_slice(&(SAMPLE_RATE * 2).to_le_bytes()); // byte rate
+    wav.extend_from_slice(&2u16.to_le_bytes()); // block align
+    wav.extend_from_slice(&16u16.to_le_bytes()); // bits per sample
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_len.to_le_bytes());
+
+    for sample in samples {
+        let value = (sample.clamp(-1.0, 1.0) * i16::MAX as f32) as i16;
+        wav.extend_from_slice(&value.to_le_bytes());
+    }
+    wav
+}
+
+/// Transcribes audio in batches of at most `max_batch_seconds`, sending each
+/// batch as a WAV file. Texts are joined, word timings shifted to be relative
+/// to the start of `audio`, and confidences averaged by batch length.
+pub async fn transcribe_in_batches<F, Fut>(
+    audio: &[f32],
+    max_batch_seconds: usize,
+    mut transcribe_batch: F,
+) -> Result<TranscriptResult, TranscriptionError>
+where
+    F: FnMut(Vec<u8>) -> Fut,
+    Fut: Future<Output = Result<TranscriptResult, TranscriptionError>>,
+{
+    let batch_len = max_batch_seconds * SAMPLE_RATE as usize;
+    let mut result = TranscriptResult {
+        text: String::new(),
+        confidence: None,
+        is_partial: false,
+        words: Vec::new(),
+    };
+    let mut confidence_sum = 0.0f32;
+    let mut confidence_samples = 0usize;
+
+    for (index, batch) in audio.chunks(batch_len.max(1)).enumerate() {
+        let mut batch_result = transcribe_batch(encode_wav(batch)).await?;
+
+        let text = batch_result.text.trim();
+        if !text.is_empty() {
+            if !result.text.is_empty() {
+                result.text.push(' ');
+            }
+            result.text.push_str(text);
+        }
+        if let Some(confidence) = batch_result.confidence {
+            confidence_sum += confidence * batch.len() as f32;
+            confidence_samples += batch.len();
+        }
+        offset_words(
+            &mut batch_result.words,
+            (index * batch_len) as f64 / SAMPLE_RATE as f64,
+        );
+        result.words.extend(batch_result.words);
+    }
+
+    if confidence_samples > 0 {
+        result.confidence = Some(confidence_sum / confidence_samples as f32);
+    }
+    Ok(result)
+}
+
+/// Sends a request built by `build`, retrying rate limits, server errors and
+/// network failures with exponential backoff (or the server's Retry-After).
+/// Returns the body of the first successful response.
+pub async fn send_with_retry<F>(
+    provider_name: &str,
+    language: Option<&str>,
+    build: F,
+) -> Result<String, TranscriptionError>
+where
+    F: Fn() -> RequestBuilder,
+{
+    let mut attempt = 0;
+    loop {
+        attempt += 1;
+        let last_attempt = attempt >= MAX_ATTEMPTS;
+
+        let response = match build().send().await {
+            Ok(response) => response,
+            Err(e) => {
+                if last_attempt || !(e.is_timeout() || e.is_connect() || e.is_request()) {
+                    return Err(TranscriptionError::Network(format!(
+                        "{} request failed: {}",
+                        provider_name, e
+                    )));
+                }
+                let delay = backoff_delay(attempt);
+                warn!(
+                    "{} request failed (attempt {}/{}): {}, retrying in {:?}",
+                    provider_name, attempt, MAX_ATTEMPTS, e, delay
+                );
+                tokio::time::sleep(delay).await;
+                continue;
+            }
+        };
+
+        let status = response.status();
+        if status.is_success() {
+            return response.text().await.map_err(|e| {
+                TranscriptionError::Network(format!(
+                    "Failed to read {} response: {}",
+                    provider_name, e
+                ))
+            });
+        }
+
+        let retry_after = retry_after(&response);
+        let body = response.text().await.unwrap_or_default();
+        if last_attempt || !is_retryable(status) {
+            return Err(map_http_error(
+                provider_name,
+                status,
+                &body,
+                retry_after,
+                language,
+            ));
+        }
+
+        let delay = retry_after
+            .map(|secs| Duration::from_secs(secs.min(MAX_RETRY_AFTER_SECS)))
+            .unwrap_or_else(|| backoff_delay(attempt));
+        warn!(
+            "{} returned {} (attempt {}/{}), retrying in {:?}",
+            provider_name, status, attempt, MAX_ATTEMPTS, delay
+        );
+        tokio::time::sleep(delay).await;
+    }
+}
+
+fn is_retryable(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS
+        || status == StatusCode::REQUEST_TIMEOUT
+        || status.is_server_error()
+}
+
+fn backoff_delay(attempt: u32) -> Duration {
+    Duration::from_millis(INITIAL_BACKOFF_MS * 2u64.pow(attempt.saturating_sub(1)))
+}
+
+/// Retry-After in seconds; HTTP-date values are ignored in favour of backoff
+fn retry_after(response: &Response) -> Option<u64> {
+    response
+        .headers()
+        .get(header::RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<f64>().ok())
+        .map(|secs| secs.max(0.0).ceil() as u64)
+}
+
+/// Maps a failed response onto a `TranscriptionError`
+pub fn map_http_error(
+    provider_name: &str,
+    status: StatusCode,
+    body: &str,
+    retry_after_secs: Option<u64>,
+    language: Option<&str>,
+) -> TranscriptionError {
+    let message = error_message(body).unwrap_or_else(|| body.trim().to_string());
+    match status {
+        StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => {
+            TranscriptionError::AuthenticationFailed(format!("{}: {}", provider_name, message))
+        }
+        StatusCode::TOO_MANY_REQUESTS => TranscriptionError::RateLimited { retry_after_secs },
+        StatusCode::BAD_REQUEST | StatusCode::UNPROCESSABLE_ENTITY
+            if language.is_some() && message.to_lowercase().contains("language") =>
+        {
+            TranscriptionError::UnsupportedLanguage(language.unwrap_or_default().to_string())
+        }
+        _ => TranscriptionError::EngineFailed(format!(
+            "{} returned {}: {}",
+            provider_name, status, message
+        )),
+    }
+}
+
+/// Error message from the JSON error bodies the providers return:
+/// `{"error": {"message"}}` (OpenAI, Groq), `{"detail": {"message"}}` or
+/// `{"detail": "..."}` (ElevenLabs), `{"err_msg"}` (Deepgram)
+fn error_message(body: &str) -> Option<String> {
+    let json: serde_json::Value = serde_json::from_str(body).ok()?;
+    [
+        "/error/message",
+        "/detail/message",
+        "/err_msg",
+        "/message",
+        "/error",
+        "/detail",
+    ]
+    .iter()
+    .find_map(|pointer| json.pointer(pointer).and_then(|v| v.as_str()))
+    .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_wav_header_and_samples() {
+        let wav = encode_wav(&[0.0, 1.0, -1.0, 2.0]);
+        assert_eq!(wav.len(), 44 + 8);
+        assert_eq!(&wav[0..4], b"RIFF");
+        assert_eq!(u32::from_le_bytes(wav[4..8].try_into().unwrap()), 36 + 8);
+        assert_eq!(u32::from_le_bytes(wav[24..28].try_into().unwrap()), 16_000);
+        assert_eq!(u32::from_le_bytes(wav[40..44].try_into().unwrap()), 8);
+        let samples: Vec<i16> = wav[44..]
+            .chunks(2)
+            .map(|b| i16::from_le_bytes([b[0], b[1]]))
+            .collect();
+        assert_eq!(samples, vec![0, i16::MAX, -i16::MAX, i16::MAX]);
+    }
+
+    #[test]
+    fn test_map_http_error() {
+        let auth = map_http_error(
+            "OpenAI",
+            StatusCode::UNAUTHORIZED,
+            r#"{"error":{"message":"Incorrect API key provided"}}"#,
+            None,
+            None,
+        );
+        assert!(
+            matches!(auth, TranscriptionError::AuthenticationFailed(ref m) if m.contains("Incorrect API key"))
+        );
+
+        let limited = map_http_error("Groq", StatusCode::TOO_MANY_REQUESTS, "", Some(7), None);
+        assert!(matches!(
+            limited,
+            TranscriptionError::RateLimited {
+                retry_after_secs: Some(7)
+            }
+        ));
+
+        let language = map_http_error(
+            "Deepgram",
+            StatusCode::BAD_REQUEST,
+            r#"{"err_code":"Bad Request","err_msg":"No such language: xx"}"#,
+            None,
+            Some("xx"),
+        );
+        assert!(matches!(language, TranscriptionError::UnsupportedLanguage(ref l) if l == "xx"));
+
+        let other = map_http_error(
+            "ElevenLabs",
+            StatusCode::BAD_REQUEST,
+            r#"{"detail":{"status":"invalid_file","message":"File is empty"}}"#,
+            None,
+            None,
+        );
+        assert!(
+            matches!(other, TranscriptionError::EngineFailed(ref m) if m.contains("File is empty"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_transcribe_in_batches_offsets_words() {
+        let audio = vec![0.0f32; SAMPLE_RATE as usize * 5];
+        let mut calls = 0;
+        let result = transcribe_in_batches(&audio, 2, |wav| {
+            calls += 1;
+            let seconds = (wav.len() - 44) as f64 / 2.0 / SAMPLE_RATE as f64;
+            async move {
+                Ok(TranscriptResult {
+                    text: format!(" part {} ", seconds),
+                    confidence: Some(if seconds < 2.0 { 0.5 } else { 1.0 }),
+                    is_partial: false,
+                    words: vec![super::super::provider::WordTiming {
+                        text: "part".to_string(),
+                        start: 0.5,
+                        end: 1.0,
+                        confidence: None,
+                    }],
+                })
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(calls, 3);
+        assert_eq!(result.text, "part 2 part 2 part 1");
+        let starts: Vec<f64> = result.words.iter().map(|w| w.start).collect();
+        assert_eq!(starts, vec![0.5, 2.5, 4.5]);
+        assert!((result.confidence.unwrap() - 0.9).abs() < 1e-6);
+    }
+}
@@ -0,0 +1,187 @@
+// audio/transcription/deepgram_provider.rs
+//
+// Deepgram pre-recorded transcription provider (POST /v1/listen).
+
+use super::cloud_provider::{
+    language_hint, send_with_retry, transcribe_in_batches, MAX_BATCH_SECONDS, REQUEST_TIMEOUT,
+};
+use super::provider::{TranscriptResult, TranscriptionError, TranscriptionProvider, WordTiming};
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Deserialize;
+
+const LISTEN_URL: &str = "https://api.deepgram.com/v1/listen";
+
+#[derive(Debug, Deserialize)]
+struct ListenResponse {
+    results: ListenResults,
+}
+
+#[derive(Debug, Deserialize)]
+struct ListenResults {
+    channels: Vec<ListenChannel>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ListenChannel {
+    alternatives: Vec<ListenAlternative>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ListenAlternative {
+    transcript: String,
+    confidence: Option<f32>,
+    #[serde(default)]
+    words: Vec<ListenWord>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ListenWord {
+    word: String,
+    punctuated_word: Option<String>,
+    start: f64,
+    end: f64,
+    confidence: Option<f32>,
+}
+
+/// Deepgram transcription provider (nova-3, nova-2, ...)
+pub struct DeepgramProvider {
+    client: Client,
+    api_key: String,
+    model: String,
+}
+
+impl DeepgramProvider {
+    pub fn new(api_key: String, model: String) -> Self {
+        Self {
+            client: Client::new(),
+            api_key,
+            model,
+        }
+    }
+
+    async fn transcribe_wav(
+        &self,
+        wav: Vec<u8>,
+        language: Option<&str>,
+    ) -> Result<TranscriptResult, TranscriptionError> {
+        let mut query = vec![
+            ("model", self.model.as_str()),
+            ("smart_format", "true"),
+            ("punctuate", "true"),
+        ];
+        match language {
+            Some(lang) => query.push(("language", lang)),
+            None => query.push(("detect_language", "true")),
+        }
+
+        let body = send_with_retry(self.provider_name(), language, || {
+            self.client
+                .post(LISTEN_URL)
+                .query(&query)
+                .header("Authorization", format!("Token {}", self.api_key))
+                .header("Content-Type", "audio/wav")
+                .timeout(REQUEST_TIMEOUT)
+                .body(wav.clone())
+        })
+        .await?;
+
+        parse_listen_response(&body)
+    }
+}
+
+/// Parses the first alternative of the first channel
+fn parse_listen_response(body: &str) -> Result<TranscriptResult, TranscriptionError> {
+    let response: ListenResponse = serde_json::from_str(body).map_err(|e| {
+        TranscriptionError::EngineFailed(format!("Invalid Deepgram response: {}", e))
+    })?;
+
+    let Some(alternative) = response
+        .results
+        .channels
+        .into_iter()
+        .next()
+        .and_then(|channel| channel.alternatives.into_iter().next())
+    else {
+        return Ok(TranscriptResult {
+            text: String::new(),
+            confidence: None,
+            is_partial: false,
+            words: Vec::new(),
+        });
+    };
+
+    Ok(TranscriptResult {
+        text: alternative.transcript.trim().to_string(),
+        confidence: alternative.confidence,
+        is_partial: false,
+        words: alternative
+            .words
+            .into_iter()
+            .map(|word| WordTiming {
+                text: word.punctuated_word.unwrap_or(word.word),
+                start: word.start,
+                end: word.end,
+                confidence: word.confidence,
+            })
+            .collect(),
+    })
+}
+
+#[async_trait]
+impl TranscriptionProvider for DeepgramProvider {
+    async fn transcribe(
+        &self,
+        audio: Vec<f32>,
+        language: Option<String>,
+    ) -> std::result::Result<TranscriptResult, TranscriptionError> {
+        let language = language_hint(language.as_deref());
+        transcribe_in_batches(&audio, MAX_BATCH_SECONDS, |wav| {
+            self.transcribe_wav(wav, language)
+        })
+        .await
+    }
+
+    async fn is_model_loaded(&self) -> bool {
+        // Nothing to load; the API key is checked on the first request
+        true
+    }
+
+    async fn get_current_model(&self) -> Option<String> {
+        Some(self.model.clone())
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "Deepgram"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_listen_response() {
+        let body = r#"{
+            "metadata": {"request_id": "r-1"},
+            "results": {"channels": [{"alternatives": [{
+                "transcript": "hello world",
+                "confidence": 0.98,
+                "words": [
+                    {"word": "hello", "start": 0.08, "end": 0.4, "confidence": 0.99, "punctuated_word": "Hello"},
+                    {"word": "world", "start": 0.4, "end": 0.9, "confidence": 0.97, "punctuated_word": "world."}
+                ]
+            }]}]}
+        }"#;
+        let result = parse_listen_response(body).unwrap();
+        assert_eq!(result.text, "hello world");
+        assert_eq!(result.confidence, Some(0.98));
+        assert_eq!(result.words.len(), 2);
+        assert_eq!(result.words[0].text, "Hello");
+        assert_eq!(result.words[1].text, "world.");
+        assert_eq!((result.words[1].start, result.words[1].end), (0.4, 0.9));
+
+        let empty = parse_listen_response(r#"{"results": {"channels": []}}"#).unwrap();
+        assert!(empty.text.is_empty());
+    }
+}
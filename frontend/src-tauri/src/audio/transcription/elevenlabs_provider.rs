@@ -0,0 +1,158 @@
+// audio/transcription/elevenlabs_provider.rs
+//
+// ElevenLabs Scribe transcription provider (POST /v1/speech-to-text).
+
+use super::cloud_provider::{
+    language_hint, send_with_retry, transcribe_in_batches, MAX_BATCH_SECONDS, REQUEST_TIMEOUT,
+};
+use super::provider::{TranscriptResult, TranscriptionError, TranscriptionProvider, WordTiming};
+use async_trait::async_trait;
+use reqwest::multipart::{Form, Part};
+use reqwest::Client;
+use serde::Deserialize;
+
+const SPEECH_TO_TEXT_URL: &str = "https://api.elevenlabs.io/v1/speech-to-text";
+
+#[derive(Debug, Deserialize)]
+struct SpeechToTextResponse {
+    text: String,
+    #[serde(default)]
+    words: Vec<SpeechToTextWord>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SpeechToTextWord {
+    text: String,
+    start: Option<f64>,
+    end: Option<f64>,
+    /// "word", "spacing" or "audio_event"
+    #[serde(rename = "type")]
+    kind: String,
+    logprob: Option<f32>,
+}
+
+/// ElevenLabs transcription provider (scribe_v1, ...)
+pub struct ElevenLabsProvider {
+    client: Client,
+    api_key: String,
+    model: String,
+}
+
+impl ElevenLabsProvider {
+    pub fn new(api_key: String, model: String) -> Self {
+        Self {
+            client: Client::new(),
+            api_key,
+            model,
+        }
+    }
+
+    async fn transcribe_wav(
+        &self,
+        wav: Vec<u8>,
+        language: Option<&str>,
+    ) -> Result<TranscriptResult, TranscriptionError> {
+        let body = send_with_retry(self.provider_name(), language, || {
+            // Multipart forms can't be cloned, so each attempt builds its own
+            let mut form = Form::new()
+                .text("model_id", self.model.clone())
+                .text("timestamps_granularity", "word")
+                .text("tag_audio_events", "false")
+                .part("file", Part::bytes(wav.clone()).file_name("audio.wav"));
+            if let Some(lang) = language {
+                form = form.text("language_code", lang.to_string());
+            }
+            self.client
+                .post(SPEECH_TO_TEXT_URL)
+                .header("xi-api-key", &self.api_key)
+                .timeout(REQUEST_TIMEOUT)
+                .multipart(form)
+        })
+        .await?;
+
+        parse_speech_to_text_response(&body)
+    }
+}
+
+/// Keeps timed words, dropping spacing and audio events. Word confidence is
+/// derived from the log probability when present.
+fn parse_speech_to_text_response(body: &str) -> Result<TranscriptResult, TranscriptionError> {
+    let response: SpeechToTextResponse = serde_json::from_str(body).map_err(|e| {
+        TranscriptionError::EngineFailed(format!("Invalid ElevenLabs response: {}", e))
+    })?;
+
+    let words = response
+        .words
+        .into_iter()
+        .filter(|word| word.kind == "word")
+        .filter_map(|word| {
+            Some(WordTiming {
+                text: word.text.trim().to_string(),
+                start: word.start?,
+                end: word.end?,
+                confidence: word.logprob.map(f32::exp),
+            })
+        })
+        .collect();
+
+    Ok(TranscriptResult {
+        text: response.text.trim().to_string(),
+        confidence: None,
+        is_partial: false,
+        words,
+    })
+}
+
+#[async_trait]
+impl TranscriptionProvider for ElevenLabsProvider {
+    async fn transcribe(
+        &self,
+        audio: Vec<f32>,
+        language: Option<String>,
+    ) -> std::result::Result<TranscriptResult, TranscriptionError> {
+        let language = language_hint(language.as_deref());
+        transcribe_in_batches(&audio, MAX_BATCH_SECONDS, |wav| {
+            self.transcribe_wav(wav, language)
+        })
+        .await
+    }
+
+    async fn is_model_loaded(&self) -> bool {
+        // Nothing to load; the API key is checked on the first request
+        true
+    }
+
+    async fn get_current_model(&self) -> Option<String> {
+        Some(self.model.clone())
+    }
+
+    fn provider_name(&self) -> &'static str {
+        "ElevenLabs"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_speech_to_text_response() {
+        let body = r#"{
+            "language_code": "en",
+            "language_probability": 0.99,
+            "text": "Hi there (laughs)",
+            "words": [
+                {"text": "Hi", "start": 0.1, "end": 0.3, "type": "word", "logprob": 0.0},
+                {"text": " ", "start": 0.3, "end": 0.35, "type": "spacing"},
+                {"text": "there", "start": 0.35, "end": 0.7, "type": "word"},
+                {"text": "(laughs)", "start": 0.8, "end": 1.2, "type": "audio_event"}
+            ]
+        }"#;
+        let result = parse_speech_to_text_response(body).unwrap();
+        assert_eq!(result.text, "Hi there (laughs)");
+        let words: Vec<&str> = result.words.iter().map(|w| w.text.as_str()).collect();
+        assert_eq!(words, vec!["Hi", "there"]);
+        assert_eq!(result.words[0].confidence, Some(1.0));
+        assert_eq!(result.words[1].confidence, None);
+    }
+}
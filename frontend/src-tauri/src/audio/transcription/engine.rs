@@ -2,6 +2,7 @@
 //
 // TranscriptionEngine enum and model initialization/validation logic.
 
+use super::cloud_provider::{create_cloud_provider, is_cloud_provider};
 use super::provider::TranscriptionProvider;
 use log::{info, warn};
 use std::sync::Arc;
@@ -51,7 +52,7 @@ impl TranscriptionEngine {
 // MODEL VALIDATION AND INITIALIZATION
 // ============================================================================
 
-/// Validate that transcription models (Whisper or Parakeet) are ready before starting recording
+/// Validate that transcription models (Whisper or Parakeet) or cloud API keys are ready before starting recording
 pub async fn validate_transcription_model_ready<R: Runtime>(app: &AppHandle<R>) -> Result<(), String> {
     // Check transcript configuration to determine which engine to validate
     let config = match crate::api::api::api_get_transcript_config(
@@ -135,10 +136,28 @@ pub async fn validate_transcription_model_ready<R: Runtime>(app: &AppHandle<R>)
                 }
             }
         }
+        provider if is_cloud_provider(provider) => {
+            info!("🔍 Validating {} cloud transcription settings...", provider);
+            // Cloud providers need no model download, only an API key
+            match create_cloud_provider(provider, &config.model, config.api_key) {
+                Ok(engine) => {
+                    info!(
+                        "✅ {} transcription ready with model {}",
+                        engine.provider_name(),
+                        engine.get_current_model().await.unwrap_or_default()
+                    );
+                    Ok(())
+                }
+                Err(e) => {
+                    warn!("❌ Cloud transcription validation failed: {}", e);
+                    Err(e)
+                }
+            }
+        }
         other => {
             warn!("❌ Unsupported transcription provider for local recording: {}", other);
             Err(format!(
-                "Provider '{}' is not supported for local transcription. Please select 'localWhisper' or 'parakeet'.",
+                "Provider '{}' is not supported for transcription. Please select 'localWhisper', 'parakeet' or a cloud provider.",
                 other
             ))
         }
@@ -212,6 +231,11 @@ pub async fn get_or_init_transcription_engine<R: Runtime>(
                 }
             }
         }
+        provider if is_cloud_provider(provider) => {
+            info!("☁️ Initializing {} cloud transcription engine", provider);
+            let engine = create_cloud_provider(provider, &config.model, config.api_key)?;
+            Ok(TranscriptionEngine::Provider(engine))
+        }
         "localWhisper" | _ => {
             info!("🎤 Initializing Whisper transcription engine");
             let whisper_engine = get_or_init_whisper(app).await?;
@@ -5,13 +5,24 @@
 pub mod provider;
 pub mod whisper_provider;
 pub mod parakeet_provider;
+pub mod cloud_provider;
+pub mod deepgram_provider;
+pub mod elevenlabs_provider;
+pub mod openai_provider;
 pub mod engine;
 pub mod worker;
 
 // Re-export commonly used types
-pub use provider::{TranscriptionError, TranscriptionProvider, TranscriptResult};
+pub use provider::{
+    offset_words, words_from_tokens, TokenTiming, TranscriptionError, TranscriptionProvider,
+    TranscriptResult, WordTiming,
+};
 pub use whisper_provider::WhisperProvider;
 pub use parakeet_provider::ParakeetProvider;
+pub use cloud_provider::{create_cloud_provider, is_cloud_provider, load_cloud_provider};
+pub use deepgram_provider::DeepgramProvider;
+pub use elevenlabs_provider::ElevenLabsProvider;
+pub use openai_provider::OpenAiProvider;
 pub use engine::{
     TranscriptionEngine,
     validate_transcription_model_ready,
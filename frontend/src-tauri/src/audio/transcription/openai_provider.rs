@@ -0,0 +1,207 @@
+// audio/transcription/openai_provider.rs
+//
+// OpenAI-compatible transcription provider (POST /audio/transcriptions), used
+// for OpenAI and Groq Whisper.
+
+use super::cloud_provider::{
+    language_hint, send_with_retry, transcribe_in_batches, MAX_BATCH_SECONDS, REQUEST_TIMEOUT,
+};
+use super::provider::{TranscriptResult, TranscriptionError, TranscriptionProvider, WordTiming};
+use async_trait::async_trait;
+use reqwest::multipart::{Form, Part};
+use reqwest::Client;
+use serde::Deserialize;
+
+const OPENAI_BASE_URL: &str = "https://api.openai.com/v1";
+const GROQ_BASE_URL: &str = "https://api.groq.com/openai/v1";
+
+#[derive(Debug, Deserialize)]
+struct TranscriptionResponse {
+    text: String,
+    #[serde(default)]
+    words: Vec<TranscriptionWord>,
+    #[serde(default)]
+    segments: Vec<TranscriptionSegment>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TranscriptionWord {
+    word: String,
+    start: f64,
+    end: f64,
+}
+
+#[derive(Debug, Deserialize)]
+struct TranscriptionSegment {
+    avg_logprob: Option<f32>,
+}
+
+/// OpenAI (whisper-1, gpt-4o-transcribe, ...) or Groq (whisper-large-v3, ...)
+pub struct OpenAiProvider {
+    client: Client,
+    api_key: String,
+    model: String,
+    base_url: &'static str,
+    name: &'static str,
+}
+
+impl OpenAiProvider {
+    pub fn openai(api_key: String, model: String) -> Self {
+        Self::new(api_key, model, OPENAI_BASE_URL, "OpenAI")
+    }
+
+    pub fn groq(api_key: String, model: String) -> Self {
+        Self::new(api_key, model, GROQ_BASE_URL, "Groq")
+    }
+
+    fn new(api_key: String, model: String, base_url: &'static str, name: &'static str) -> Self {
+        Self {
+            client: Client::new(),
+            api_key,
+            model,
+            base_url,
+            name,
+        }
+    }
+
+    /// Whisper models return `verbose_json` with word timings; the GPT-4o
+    /// transcription models only support plain `json`
+    fn supports_verbose_json(&self) -> bool {
+        self.model.contains("whisper")
+    }
+
+    async fn transcribe_wav(
+        &self,
+        wav: Vec<u8>,
+        language: Option<&str>,
+        translate: bool,
+    ) -> Result<TranscriptResult, TranscriptionError> {
+        let endpoint = if translate {
+            "translations"
+        } else {
+            "transcriptions"
+        };
+        let url = format!("{}/audio/{}", self.base_url, endpoint);
+        let verbose = self.supports_verbose_json();
+
+        let body = send_with_retry(self.name, language, || {
+            // Multipart forms can't be cloned, so each attempt builds its own
+            let mut form = Form::new()
+                .text("model", self.model.clone())
+                .part("file", Part::bytes(wav.clone()).file_name("audio.wav"));
+            if verbose {
+                form = form
+                    .text("response_format", "verbose_json")
+                    .text("timestamp_granularities[]", "word")
+                    .text("timestamp_granularities[]", "segment");
+            } else {
+                form = form.text("response_format", "json");
+            }
+            if let (Some(lang), false) = (language, translate) {
+                form = form.text("language", lang.to_string());
+            }
+            self.client
+                .post(&url)
+                .bearer_auth(&self.api_key)
+                .timeout(REQUEST_TIMEOUT)
+                .multipart(form)
+        })
+        .await?;
+
+        parse_transcription_response(&body)
+    }
+}
+
+/// Confidence is the mean segment probability, as for local Whisper
+fn parse_transcription_response(body: &str) -> Result<TranscriptResult, TranscriptionError> {
+    let response: TranscriptionResponse = serde_json::from_str(body).map_err(|e| {
+        TranscriptionError::EngineFailed(format!("Invalid transcription response: {}", e))
+    })?;
+
+    let probabilities: Vec<f32> = response
+        .segments
+        .iter()
+        .filter_map(|segment| segment.avg_logprob.map(f32::exp))
+        .collect();
+    let confidence = (!probabilities.is_empty())
+        .then(|| probabilities.iter().sum::<f32>() / probabilities.len() as f32);
+
+    Ok(TranscriptResult {
+        text: response.text.trim().to_string(),
+        confidence,
+        is_partial: false,
+        words: response
+            .words
+            .into_iter()
+            .map(|word| WordTiming {
+                text: word.word.trim().to_string(),
+                start: word.start,
+                end: word.end,
+                confidence: None,
+            })
+            .collect(),
+    })
+}
+
+#[async_trait]
+impl TranscriptionProvider for OpenAiProvider {
+    async fn transcribe(
+        &self,
+        audio: Vec<f32>,
+        language: Option<String>,
+    ) -> std::result::Result<TranscriptResult, TranscriptionError> {
+        // "auto-translate" maps onto the translations endpoint (Whisper models only)
+        let translate =
+            language.as_deref() == Some("auto-translate") && self.supports_verbose_json();
+        let language = language_hint(language.as_deref());
+        transcribe_in_batches(&audio, MAX_BATCH_SECONDS, |wav| {
+            self.transcribe_wav(wav, language, translate)
+        })
+        .await
+    }
+
+    async fn is_model_loaded(&self) -> bool {
+        // Nothing to load; the API key is checked on the first request
+        true
+    }
+
+    async fn get_current_model(&self) -> Option<String> {
+        Some(self.model.clone())
+    }
+
+    fn provider_name(&self) -> &'static str {
+        self.name
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_verbose_json_response() {
+        let body = r#"{
+            "task": "transcribe",
+            "language": "english",
+            "duration": 1.2,
+            "text": " Ship it on Monday.",
+            "words": [
+                {"word": "Ship", "start": 0.0, "end": 0.3},
+                {"word": "it", "start": 0.3, "end": 0.4},
+                {"word": "on", "start": 0.4, "end": 0.6},
+                {"word": "Monday", "start": 0.6, "end": 1.1}
+            ],
+            "segments": [{"id": 0, "avg_logprob": 0.0, "no_speech_prob": 0.01}]
+        }"#;
+        let result = parse_transcription_response(body).unwrap();
+        assert_eq!(result.text, "Ship it on Monday.");
+        assert_eq!(result.words.len(), 4);
+        assert_eq!(result.words[3].end, 1.1);
+        assert_eq!(result.confidence, Some(1.0));
+
+        let plain = parse_transcription_response(r#"{"text":"Hello."}"#).unwrap();
+        assert_eq!(plain.text, "Hello.");
+        assert!(plain.words.is_empty());
+        assert_eq!(plain.confidence, None);
+    }
+}
@@ -1,9 +1,10 @@
 // audio/transcription/provider.rs
 //
 // Defines the unified TranscriptionProvider trait and common types for all
-// transcription engines (Whisper, Parakeet, future providers).
+// transcription engines (Whisper, Parakeet, cloud providers).
 
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
 
 // ============================================================================
 // TRANSCRIPTION PROVIDER TRAIT & ERROR TYPES
@@ -16,6 +17,12 @@ pub enum TranscriptionError {
     AudioTooShort { samples: usize, minimum: usize },
     EngineFailed(String),
     UnsupportedLanguage(String),
+    // Cloud provider rejected the API key (missing, invalid or without access)
+    AuthenticationFailed(String),
+    // Cloud provider kept rate limiting after retries
+    RateLimited { retry_after_secs: Option<u64> },
+    // Cloud provider could not be reached after retries
+    Network(String),
 }
 
 impl std::fmt::Display for TranscriptionError {
@@ -31,21 +38,96 @@ impl std::fmt::Display for TranscriptionError {
             Self::UnsupportedLanguage(lang) => {
                 write!(f, "Language '{}' is not supported by this provider", lang)
             }
+            Self::AuthenticationFailed(msg) => write!(f, "Authentication failed: {}", msg),
+            Self::RateLimited {
+                retry_after_secs: Some(secs),
+            } => write!(f, "Rate limited by provider, retry after {}s", secs),
+            Self::RateLimited {
+                retry_after_secs: None,
+            } => write!(f, "Rate limited by provider"),
+            Self::Network(msg) => write!(f, "Network error: {}", msg),
         }
     }
 }
 
 impl std::error::Error for TranscriptionError {}
 
+/// A transcribed word with its timing in seconds.
+/// Providers report times relative to the start of the transcribed audio;
+/// callers shift them to recording-relative time before storing or emitting.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WordTiming {
+    pub text: String,
+    pub start: f64,
+    pub end: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f32>,
+}
+
+/// A decoded sub-word token with its timing, as produced by the engines
+#[derive(Debug, Clone)]
+pub struct TokenTiming {
+    pub text: String,
+    pub start: f64,
+    pub end: f64,
+    pub probability: Option<f32>,
+}
+
+/// Groups sub-word tokens into words. A token starting with whitespace begins a
+/// new word (Whisper BPE and Parakeet SentencePiece both mark word starts this
+/// way); other tokens, including punctuation, extend the current word.
+pub fn words_from_tokens(tokens: &[TokenTiming]) -> Vec<WordTiming> {
+    let mut words: Vec<WordTiming> = Vec::new();
+    let mut probabilities: Vec<Vec<f32>> = Vec::new();
+
+    for token in tokens {
+        if token.text.trim().is_empty() {
+            continue;
+        }
+        let starts_word = token.text.starts_with(char::is_whitespace) || words.is_empty();
+        if starts_word {
+            words.push(WordTiming {
+                text: token.text.trim().to_string(),
+                start: token.start,
+                end: token.end.max(token.start),
+                confidence: None,
+            });
+            probabilities.push(Vec::new());
+        } else if let Some(word) = words.last_mut() {
+            word.text.push_str(token.text.trim_end());
+            word.end = word.end.max(token.end);
+        }
+        if let (Some(p), Some(word_probs)) = (token.probability, probabilities.last_mut()) {
+            word_probs.push(p);
+        }
+    }
+
+    for (word, probs) in words.iter_mut().zip(probabilities) {
+        if !probs.is_empty() {
+            word.confidence = Some(probs.iter().sum::<f32>() / probs.len() as f32);
+        }
+    }
+    words
+}
+
+/// Shifts word timings by `offset_seconds` (e.g. from chunk- to recording-relative time)
+pub fn offset_words(words: &mut [WordTiming], offset_seconds: f64) {
+    for word in words.iter_mut() {
+        word.start += offset_seconds;
+        word.end += offset_seconds;
+    }
+}
+
 /// Unified transcription result across all providers
 #[derive(Debug, Clone)]
 pub struct TranscriptResult {
     pub text: String,
     pub confidence: Option<f32>, // None if provider doesn't support confidence scores
     pub is_partial: bool,
+    pub words: Vec<WordTiming>, // Empty if provider doesn't report word timings
 }
 
-/// Trait for transcription providers (Whisper, Parakeet, future providers)
+/// Trait for transcription providers (Whisper, Parakeet, cloud providers)
 #[async_trait]
 pub trait TranscriptionProvider: Send + Sync {
     /// Transcribe audio samples to text
@@ -55,7 +137,7 @@ pub trait TranscriptionProvider: Send + Sync {
     /// * `language` - Optional language hint (e.g., "en", "es", "fr")
     ///
     /// # Returns
-    /// * `TranscriptResult` with text, optional confidence, and partial flag
+    /// * `TranscriptResult` with text, optional confidence, partial flag and word timings
     async fn transcribe(
         &self,
         audio: Vec<f32>,
@@ -71,3 +153,43 @@ pub trait TranscriptionProvider: Send + Sync {
     /// Get the provider name (for logging/debugging)
     fn provider_name(&self) -> &'static str;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(text: &str, start: f64, end: f64, p: Option<f32>) -> TokenTiming {
+        TokenTiming {
+            text: text.to_string(),
+            start,
+            end,
+            probability: p,
+        }
+    }
+
+    #[test]
+    fn test_words_from_tokens_joins_subwords_and_punctuation() {
+        let tokens = vec![
+            token(" Hel", 0.0, 0.2, Some(0.8)),
+            token("lo", 0.2, 0.4, Some(0.6)),
+            token(",", 0.4, 0.45, None),
+            token(" ", 0.45, 0.5, None),
+            token(" world", 0.5, 0.9, Some(0.9)),
+        ];
+        let words = words_from_tokens(&tokens);
+        assert_eq!(words.len(), 2);
+        assert_eq!(words[0].text, "Hello,");
+        assert_eq!((words[0].start, words[0].end), (0.0, 0.45));
+        assert!((words[0].confidence.unwrap() - 0.7).abs() < 1e-6);
+        assert_eq!(words[1].text, "world");
+        assert_eq!((words[1].start, words[1].end), (0.5, 0.9));
+    }
+
+    #[test]
+    fn test_offset_words() {
+        let mut words = words_from_tokens(&[token("Hi", 1.0, 1.5, None)]);
+        offset_words(&mut words, 10.0);
+        assert_eq!((words[0].start, words[0].end), (11.0, 11.5));
+        assert_eq!(words[0].confidence, None);
+    }
+}
@@ -0,0 +1,434 @@
+//! Headless command-line interface for batch transcription and summarization.
+//!
+//! Runs the same import and summary pipelines as the desktop app against the same
+//! database and model directories, without a window or Tauri runtime:
+//!
+//! ```text
+//! meetily-cli transcribe call.mp3 --engine parakeet --format srt > call.srt
+//! meetily-cli transcribe call.mp3 --engine deepgram --language en
+//! meetily-cli import call.mp3 --title "Weekly sync"
+//! meetily-cli summarize <meeting-id> --template standard_meeting --provider ollama
+//! ```
+//!
+//! Logs go to stderr; results go to stdout (or `--output`), so the commands can be
+//! chained in scripts and cron jobs.
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::{anyhow, Context, Result};
+use app_lib::audio::import::{
+    cancel_import, get_configured_model, save_import, transcribe_file, ImportEngine,
+    ImportObserver, TranscribedAudio,
+};
+use app_lib::audio::recording_preferences::load_noise_suppression_settings;
+use app_lib::audio::transcription::load_cloud_provider;
+use app_lib::database::manager::DatabaseManager;
+use app_lib::database::repositories::setting::SettingsRepository;
+use app_lib::database::repositories::summary::SummaryProcessesRepository;
+use app_lib::export::document::{ExportDocument, ExportLine, ExportOptions, LineParts};
+use app_lib::export::subtitles::render_srt;
+use app_lib::parakeet_engine::ParakeetEngine;
+use app_lib::summary::SummaryService;
+use app_lib::whisper_engine::WhisperEngine;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use sqlx::SqlitePool;
+
+/// Same directory Tauri resolves as app_data_dir for the desktop app
+const APP_IDENTIFIER: &str = "com.meetily.ai";
+
+#[derive(Parser)]
+#[command(
+    name = "meetily-cli",
+    version,
+    about = "Transcribe and summarize meetings without the desktop app"
+)]
+struct Cli {
+    /// App data directory (database and models). Defaults to the desktop app's.
+    #[arg(long, global = true, env = "MEETILY_DATA_DIR")]
+    data_dir: Option<PathBuf>,
+
+    /// Log progress and pipeline details to stderr
+    #[arg(short, long, global = true)]
+    verbose: bool,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Transcribe an audio file and print the transcript
+    Transcribe {
+        file: PathBuf,
+
+        #[command(flatten)]
+        engine: EngineArgs,
+
+        #[arg(long, value_enum, default_value_t = OutputFormat::Txt)]
+        format: OutputFormat,
+
+        /// Write to a file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Transcribe an audio file and save it as a new meeting; prints the meeting ID
+    Import {
+        file: PathBuf,
+
+        /// Meeting title (defaults to the file name)
+        #[arg(long)]
+        title: Option<String>,
+
+        #[command(flatten)]
+        engine: EngineArgs,
+    },
+    /// Generate and store the summary of a saved meeting; prints the markdown
+    Summarize {
+        meeting_id: String,
+
+        #[arg(long, default_value = "standard_meeting")]
+        template: String,
+
+        /// LLM provider (ollama, openai, claude, groq, openrouter, builtin-ai,
+        /// custom-openai). Defaults to the app's configured provider.
+        #[arg(long)]
+        provider: Option<String>,
+
+        /// Model name. Defaults to the app's configured model.
+        #[arg(long)]
+        model: Option<String>,
+
+        /// Extra context for the summary prompt
+        #[arg(long, default_value = "")]
+        prompt: String,
+
+        /// Generate the template's sections as JSON and render the markdown from it
+        #[arg(long)]
+        structured: bool,
+
+        /// Write to a file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+#[derive(Args)]
+struct EngineArgs {
+    #[arg(long, value_enum, default_value_t = Engine::Whisper)]
+    engine: Engine,
+
+    /// Model name (e.g. large-v3-turbo). Defaults to the app's configured model.
+    #[arg(long)]
+    model: Option<String>,
+
+    /// Language code for Whisper and cloud engines (e.g. en, de). Auto-detected when omitted.
+    #[arg(long)]
+    language: Option<String>,
+
+    /// Skip speaker diarization
+    #[arg(long)]
+    no_diarization: bool,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum Engine {
+    Whisper,
+    Parakeet,
+    /// Cloud providers use the API key saved in the app's transcript settings
+    Deepgram,
+    #[value(name = "elevenlabs")]
+    ElevenLabs,
+    Groq,
+    #[value(name = "openai")]
+    OpenAi,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum OutputFormat {
+    Json,
+    Srt,
+    Txt,
+}
+
+/// Prints import progress to stderr when verbose
+struct CliObserver {
+    verbose: bool,
+}
+
+impl ImportObserver for CliObserver {
+    fn progress(&self, stage: &str, progress: u32, message: &str) {
+        if self.verbose {
+            eprintln!("[{:>3}%] {}: {}", progress, stage, message);
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let cli = Cli::parse();
+
+    let default_level = if cli.verbose { "info" } else { "warn" };
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or(default_level))
+        .target(env_logger::Target::Stderr)
+        .init();
+
+    // Ctrl-C stops the pipeline at the next checkpoint instead of killing mid-write
+    tokio::spawn(async {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            eprintln!("Cancelling...");
+            cancel_import();
+        }
+    });
+
+    if let Err(e) = run(cli).await {
+        eprintln!("Error: {:#}", e);
+        std::process::exit(1);
+    }
+}
+
+async fn run(cli: Cli) -> Result<()> {
+    let data_dir = match cli.data_dir {
+        Some(dir) => dir,
+        None => dirs::data_dir()
+            .ok_or_else(|| anyhow!("Could not find the system data directory; pass --data-dir"))?
+            .join(APP_IDENTIFIER),
+    };
+    let db = open_database(&data_dir).await?;
+    let pool = db.pool();
+    let observer = Arc::new(CliObserver {
+        verbose: cli.verbose,
+    });
+
+    match cli.command {
+        Command::Transcribe {
+            file,
+            engine,
+            format,
+            output,
+        } => {
+            let transcribed = transcribe(&data_dir, pool, &file, &engine, observer).await?;
+            let title = file_title(&file);
+            let rendered = match format {
+                OutputFormat::Json => serde_json::to_string_pretty(&serde_json::json!({
+                    "source": file.to_string_lossy(),
+                    "duration_seconds": transcribed.duration_seconds,
+                    "segments": transcribed.segments,
+                }))?,
+                OutputFormat::Srt => render_srt(
+                    &export_document(title, &transcribed),
+                    &ExportOptions::default(),
+                ),
+                OutputFormat::Txt => render_text(&export_document(title, &transcribed)),
+            };
+            write_output(output.as_deref(), &rendered)
+        }
+        Command::Import {
+            file,
+            title,
+            engine,
+        } => {
+            let transcribed = transcribe(&data_dir, pool, &file, &engine, observer.clone()).await?;
+            let title = title.unwrap_or_else(|| file_title(&file));
+            let result = save_import(pool, &file, &title, &transcribed, observer.as_ref()).await?;
+            eprintln!(
+                "Imported '{}' ({} segments, {:.0}s)",
+                result.title, result.segments_count, result.duration_seconds
+            );
+            println!("{}", result.meeting_id);
+            Ok(())
+        }
+        Command::Summarize {
+            meeting_id,
+            template,
+            provider,
+            model,
+            prompt,
+            structured,
+            output,
+        } => {
+            let markdown = summarize(
+                &data_dir,
+                pool,
+                &meeting_id,
+                &template,
+                provider,
+                model,
+                prompt,
+                structured,
+            )
+            .await?;
+            write_output(output.as_deref(), &markdown)
+        }
+    }
+}
+
+/// Opens (and migrates) the app database inside `data_dir`
+async fn open_database(data_dir: &Path) -> Result<DatabaseManager> {
+    let db_path = data_dir.join("meeting_minutes.sqlite");
+    let legacy_path = data_dir.join("meeting_minutes.db");
+    DatabaseManager::new(&db_path.to_string_lossy(), &legacy_path.to_string_lossy())
+        .await
+        .with_context(|| format!("Failed to open database at {}", db_path.display()))
+}
+
+async fn transcribe(
+    data_dir: &Path,
+    pool: &SqlitePool,
+    file: &Path,
+    args: &EngineArgs,
+    observer: Arc<CliObserver>,
+) -> Result<TranscribedAudio> {
+    let models_dir = data_dir.join("models");
+    app_lib::diarization::set_models_directory_from_data_dir(data_dir);
+
+    let engine = match args.engine {
+        Engine::Whisper => {
+            let model = match &args.model {
+                Some(model) => model.clone(),
+                None => get_configured_model(pool, "whisper").await?,
+            };
+            let engine = WhisperEngine::new_with_models_dir(Some(models_dir))?;
+            engine.discover_models().await?;
+            engine
+                .load_model(&model)
+                .await
+                .with_context(|| format!("Failed to load Whisper model '{}'", model))?;
+            ImportEngine::Whisper(Arc::new(engine))
+        }
+        Engine::Parakeet => {
+            let model = match &args.model {
+                Some(model) => model.clone(),
+                None => get_configured_model(pool, "parakeet").await?,
+            };
+            let engine = ParakeetEngine::new_with_models_dir(Some(models_dir))?;
+            engine.discover_models().await?;
+            engine
+                .load_model(&model)
+                .await
+                .with_context(|| format!("Failed to load Parakeet model '{}'", model))?;
+            ImportEngine::Parakeet(Arc::new(engine))
+        }
+        Engine::Deepgram | Engine::ElevenLabs | Engine::Groq | Engine::OpenAi => {
+            let provider = match args.engine {
+                Engine::Deepgram => "deepgram",
+                Engine::ElevenLabs => "elevenLabs",
+                Engine::Groq => "groq",
+                _ => "openai",
+            };
+            let engine = load_cloud_provider(pool, provider, args.model.as_deref())
+                .await
+                .map_err(|e| anyhow!(e))?;
+            ImportEngine::Cloud(engine)
+        }
+    };
+
+    // Same noise suppression the app applies to imports
+    let denoise = load_noise_suppression_settings(data_dir).imports;
+
+    transcribe_file(
+        file,
+        &engine,
+        args.language.clone(),
+        !args.no_diarization,
+        denoise,
+        Some(pool),
+        observer,
+    )
+    .await
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn summarize(
+    data_dir: &Path,
+    pool: &SqlitePool,
+    meeting_id: &str,
+    template: &str,
+    provider: Option<String>,
+    model: Option<String>,
+    prompt: String,
+    structured: bool,
+) -> Result<String> {
+    let (provider, model) = match (provider, model) {
+        (Some(provider), Some(model)) => (provider, model),
+        (provider, model) => {
+            let config = SettingsRepository::get_model_config(pool)
+                .await?
+                .ok_or_else(|| {
+                    anyhow!("No model configured in the app; pass --provider and --model")
+                })?;
+            (
+                provider.unwrap_or(config.provider),
+                model.unwrap_or(config.model),
+            )
+        }
+    };
+
+    SummaryProcessesRepository::create_or_reset_process(pool, meeting_id).await?;
+    log::info!(
+        "Summarizing {} with {} / {} using template '{}'",
+        meeting_id,
+        provider,
+        model,
+        template
+    );
+
+    SummaryService::process_transcript_background(
+        Some(data_dir.to_path_buf()),
+        pool.clone(),
+        meeting_id.to_string(),
+        // The service reads the stored segments itself
+        String::new(),
+        provider,
+        model,
+        prompt,
+        template.to_string(),
+        structured,
+        None,
+    )
+    .await
+    .map_err(|e| anyhow!(e))
+}
+
+fn file_title(file: &Path) -> String {
+    file.file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "Imported meeting".to_string())
+}
+
+fn export_document(title: String, transcribed: &TranscribedAudio) -> ExportDocument {
+    ExportDocument {
+        title,
+        created_at: chrono::Utc::now(),
+        summary_markdown: None,
+        notes_markdown: None,
+        lines: transcribed.segments.iter().map(ExportLine::from).collect(),
+        audio_path: None,
+        folder: None,
+    }
+}
+
+/// One "[MM:SS] Speaker: text" line per segment
+fn render_text(doc: &ExportDocument) -> String {
+    let options = ExportOptions::default();
+    doc.lines
+        .iter()
+        .map(|line| LineParts::new(line, &options).to_plain())
+        .collect::<Vec<_>>()
+        .join("\n")
+        + "\n"
+}
+
+fn write_output(path: Option<&Path>, content: &str) -> Result<()> {
+    match path {
+        Some(path) => std::fs::write(path, content)
+            .with_context(|| format!("Failed to write {}", path.display())),
+        None => {
+            print!("{}", content);
+            if !content.ends_with('\n') {
+                println!();
+            }
+            Ok(())
+        }
+    }
+}
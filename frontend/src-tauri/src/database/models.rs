@@ -35,6 +35,77 @@ pub struct Transcript {
     pub audio_start_time: Option<f64>,
     pub audio_end_time: Option<f64>,
     pub duration: Option<f64>,
+    // Diarized speaker ('Speaker 1', ...); NULL until diarization has run
+    pub speaker_label: Option<String>,
+}
+
+/// A transcribed word with recording-relative timing
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct TranscriptWord {
+    pub transcript_id: String,
+    pub word_index: i64,
+    pub word: String,
+    pub start_time: f64,
+    pub end_time: f64,
+    pub confidence: Option<f64>,
+}
+
+/// A transcript segment as the summary, action item and Q&A pipelines read it
+#[derive(Debug, Clone, FromRow)]
+pub struct TranscriptLineRow {
+    pub id: String,
+    pub speaker_label: Option<String>,
+    pub transcript: String,
+    pub audio_start_time: Option<f64>,
+    pub audio_end_time: Option<f64>,
+}
+
+/// A diarized speaker within one meeting
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct MeetingSpeaker {
+    pub meeting_id: String,
+    pub label: String,
+    // Little-endian f32 centroid of the speaker's voice embeddings
+    #[serde(skip)]
+    pub embedding: Vec<u8>,
+    pub segment_count: i64,
+    pub total_duration: f64,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub profile_id: Option<String>,
+}
+
+/// A named voice profile shared across meetings
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct SpeakerProfile {
+    pub id: String,
+    pub name: String,
+    // Little-endian f32 running mean of the embeddings this profile was trained on
+    #[serde(skip)]
+    pub embedding: Vec<u8>,
+    pub sample_count: i64,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// An action item or decision extracted from a meeting, joined with the meeting title
+#[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
+pub struct ActionItem {
+    pub id: String,
+    pub meeting_id: String,
+    pub meeting_title: String,
+    /// 'action_item' or 'decision'
+    pub kind: String,
+    pub text: String,
+    pub owner: Option<String>,
+    /// YYYY-MM-DD
+    pub due_date: Option<String>,
+    /// transcripts.id the item was taken from
+    pub source_segment_id: Option<String>,
+    /// 'open', 'completed' or 'cancelled'
+    pub status: String,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+    pub updated_at: chrono::DateTime<chrono::Utc>,
+    pub completed_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
 #[derive(Debug, Clone, FromRow, Serialize, Deserialize)]
@@ -89,6 +160,9 @@ pub struct Setting {
     #[sqlx(rename = "openRouterApiKey")]
     #[serde(rename = "openRouterApiKey")]
     pub open_router_api_key: Option<String>,
+    #[sqlx(rename = "geminiApiKey")]
+    #[serde(rename = "geminiApiKey")]
+    pub gemini_api_key: Option<String>,
     #[sqlx(rename = "ollamaEndpoint")]
     #[serde(rename = "ollamaEndpoint")]
     pub ollama_endpoint: Option<String>,
@@ -96,6 +170,10 @@ pub struct Setting {
     #[sqlx(rename = "customOpenAIConfig")]
     #[serde(rename = "customOpenAIConfig")]
     pub custom_openai_config: Option<String>,
+    /// LLM fallback chain stored as JSON
+    #[sqlx(rename = "llmFallbackChain")]
+    #[serde(rename = "llmFallbackChain")]
+    pub llm_fallback_chain: Option<String>,
 }
 
 impl Setting {
@@ -0,0 +1,172 @@
+use crate::database::models::ActionItem;
+use crate::summary::extraction::{dedup_key, ActionItemKind, ActionItemStatus, ExtractedItem};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use sqlx::{Error as SqlxError, QueryBuilder, Sqlite, SqlitePool};
+use std::collections::HashSet;
+use tracing::info;
+use uuid::Uuid;
+
+const MAX_LIST_LIMIT: i64 = 1000;
+
+const SELECT_ACTION_ITEMS: &str =
+    "SELECT a.id, a.meeting_id, m.title AS meeting_title, a.kind, a.text, a.owner,
+        a.due_date, a.source_segment_id, a.status, a.created_at, a.updated_at, a.completed_at
+ FROM action_items a
+ JOIN meetings m ON m.id = a.meeting_id";
+
+/// Filters for listing action items across meetings; every field is optional
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ActionItemFilter {
+    pub meeting_id: Option<String>,
+    pub status: Option<ActionItemStatus>,
+    pub kind: Option<ActionItemKind>,
+    /// Case-insensitive substring of the owner's name
+    pub owner: Option<String>,
+    /// Inclusive YYYY-MM-DD bounds; items without a due date are excluded when set
+    pub due_after: Option<String>,
+    pub due_before: Option<String>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+pub struct ActionItemsRepository;
+
+impl ActionItemsRepository {
+    /// Lists items with open ones first, then by due date (undated last) and
+    /// newest meeting
+    pub async fn list(
+        pool: &SqlitePool,
+        filter: &ActionItemFilter,
+    ) -> Result<Vec<ActionItem>, SqlxError> {
+        let mut query: QueryBuilder<Sqlite> = QueryBuilder::new(SELECT_ACTION_ITEMS);
+        query.push(" WHERE 1 = 1");
+        if let Some(meeting_id) = &filter.meeting_id {
+            query.push(" AND a.meeting_id = ").push_bind(meeting_id);
+        }
+        if let Some(status) = filter.status {
+            query.push(" AND a.status = ").push_bind(status.as_str());
+        }
+        if let Some(kind) = filter.kind {
+            query.push(" AND a.kind = ").push_bind(kind.as_str());
+        }
+        if let Some(owner) = filter
+            .owner
+            .as_deref()
+            .map(str::trim)
+            .filter(|o| !o.is_empty())
+        {
+            query
+                .push(" AND a.owner LIKE ")
+                .push_bind(format!("%{}%", owner));
+        }
+        if let Some(due_after) = &filter.due_after {
+            query.push(" AND a.due_date >= ").push_bind(due_after);
+        }
+        if let Some(due_before) = &filter.due_before {
+            query.push(" AND a.due_date <= ").push_bind(due_before);
+        }
+        query.push(
+            " ORDER BY a.status != 'open', a.due_date IS NULL, a.due_date, m.created_at DESC, a.rowid",
+        );
+        query
+            .push(" LIMIT ")
+            .push_bind(filter.limit.unwrap_or(200).clamp(1, MAX_LIST_LIMIT))
+            .push(" OFFSET ")
+            .push_bind(filter.offset.unwrap_or(0).max(0));
+
+        query.build_query_as::<ActionItem>().fetch_all(pool).await
+    }
+
+    pub async fn get(pool: &SqlitePool, id: &str) -> Result<Option<ActionItem>, SqlxError> {
+        sqlx::query_as::<_, ActionItem>(&format!("{} WHERE a.id = ?", SELECT_ACTION_ITEMS))
+            .bind(id)
+            .fetch_optional(pool)
+            .await
+    }
+
+    /// Replaces a meeting's open items with a fresh extraction. Items the user has
+    /// completed or cancelled are kept, and new items duplicating them are skipped.
+    /// Returns the number of items inserted.
+    pub async fn replace_extracted(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        items: &[ExtractedItem],
+    ) -> Result<usize, SqlxError> {
+        let mut transaction = pool.begin().await?;
+
+        sqlx::query("DELETE FROM action_items WHERE meeting_id = ? AND status = 'open'")
+            .bind(meeting_id)
+            .execute(&mut *transaction)
+            .await?;
+
+        let kept: Vec<(String, String)> =
+            sqlx::query_as("SELECT kind, text FROM action_items WHERE meeting_id = ?")
+                .bind(meeting_id)
+                .fetch_all(&mut *transaction)
+                .await?;
+        let mut seen: HashSet<String> = kept
+            .iter()
+            .map(|(kind, text)| dedup_key(kind, text))
+            .collect();
+
+        let now = Utc::now();
+        let mut inserted = 0;
+        for item in items {
+            if !seen.insert(dedup_key(item.kind.as_str(), &item.text)) {
+                continue;
+            }
+            sqlx::query(
+                "INSERT INTO action_items (id, meeting_id, kind, text, owner, due_date, source_segment_id, status, created_at, updated_at)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, 'open', ?, ?)",
+            )
+            .bind(format!("action-{}", Uuid::new_v4()))
+            .bind(meeting_id)
+            .bind(item.kind.as_str())
+            .bind(&item.text)
+            .bind(&item.owner)
+            .bind(&item.due_date)
+            .bind(&item.source_segment_id)
+            .bind(now)
+            .bind(now)
+            .execute(&mut *transaction)
+            .await?;
+            inserted += 1;
+        }
+
+        transaction.commit().await?;
+        info!(
+            "Stored {} extracted action items for meeting {} ({} kept)",
+            inserted,
+            meeting_id,
+            kept.len()
+        );
+        Ok(inserted)
+    }
+
+    /// Sets an item's status; completed_at tracks when it was last completed.
+    /// Returns None if the item doesn't exist.
+    pub async fn set_status(
+        pool: &SqlitePool,
+        id: &str,
+        status: ActionItemStatus,
+    ) -> Result<Option<ActionItem>, SqlxError> {
+        let now = Utc::now();
+        let completed_at = (status == ActionItemStatus::Completed).then_some(now);
+        let result = sqlx::query(
+            "UPDATE action_items SET status = ?, completed_at = ?, updated_at = ? WHERE id = ?",
+        )
+        .bind(status.as_str())
+        .bind(completed_at)
+        .bind(now)
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            return Ok(None);
+        }
+        Self::get(pool, id).await
+    }
+}
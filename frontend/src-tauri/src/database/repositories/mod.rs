@@ -1,5 +1,11 @@
+pub mod action_item;
 pub mod meeting;
+pub mod qa_index;
+pub mod search;
 pub mod setting;
+pub mod speaker;
+pub mod speaker_profile;
 pub mod summary;
+pub mod summary_cache;
 pub mod transcript;
 pub mod transcript_chunk;
@@ -0,0 +1,234 @@
+use crate::database::repositories::speaker::{blob_to_embedding, embedding_to_blob};
+use crate::diarization::clustering::cosine_similarity;
+use crate::qa::passages::{push_top_k, Passage, PassageSource};
+use chrono::Utc;
+use futures_util::TryStreamExt;
+use sha2::{Digest, Sha256};
+use sqlx::{Error as SqlxError, FromRow, QueryBuilder, Sqlite, SqlitePool};
+use std::collections::HashMap;
+use tracing::info;
+
+/// A stored passage returned by a similarity search
+#[derive(Debug, Clone)]
+pub struct RetrievedPassage {
+    pub meeting_id: String,
+    pub meeting_title: String,
+    pub meeting_created_at: chrono::DateTime<Utc>,
+    pub passage: Passage,
+    pub score: f32,
+}
+
+#[derive(FromRow)]
+struct PassageRow {
+    meeting_id: String,
+    meeting_title: String,
+    meeting_created_at: chrono::DateTime<Utc>,
+    source: String,
+    transcript_id: Option<String>,
+    audio_start_time: Option<f64>,
+    text: String,
+    embedding: Vec<u8>,
+}
+
+pub struct QaIndexRepository;
+
+impl QaIndexRepository {
+    /// Current content signature of every meeting (or the given ones): a SHA-256
+    /// of its transcript segments and when the summary was last saved. Rows are
+    /// streamed, so the whole archive can be checked on every question.
+    pub async fn meeting_signatures(
+        pool: &SqlitePool,
+        meeting_ids: Option<&[String]>,
+    ) -> Result<Vec<(String, String)>, SqlxError> {
+        let mut query: QueryBuilder<Sqlite> = QueryBuilder::new(
+            "SELECT m.id,
+                COALESCE((SELECT s.updated_at FROM summary_processes s
+                          WHERE s.meeting_id = m.id AND s.status = 'completed'), ''),
+                t.id, t.speaker_label, t.transcript, t.audio_start_time
+             FROM meetings m
+             LEFT JOIN transcripts t ON t.meeting_id = m.id",
+        );
+        if let Some(ids) = meeting_ids {
+            if ids.is_empty() {
+                return Ok(Vec::new());
+            }
+            query.push(" WHERE m.id IN (");
+            let mut separated = query.separated(", ");
+            for id in ids {
+                separated.push_bind(id);
+            }
+            separated.push_unseparated(")");
+        }
+        query.push(" ORDER BY m.created_at DESC, m.id, t.id");
+
+        // (meeting id, signature); the signature holds the summary timestamp
+        // until the meeting's rows are hashed
+        let mut signatures: Vec<(String, String)> = Vec::new();
+        let mut hasher = Sha256::new();
+        let mut rows = query
+            .build_query_as::<(
+                String,
+                String,
+                Option<String>,
+                Option<String>,
+                Option<String>,
+                Option<f64>,
+            )>()
+            .fetch(pool);
+        while let Some((meeting_id, summary_saved_at, id, speaker, text, start)) =
+            rows.try_next().await?
+        {
+            if signatures.last().map(|(last, _)| last) != Some(&meeting_id) {
+                seal_signature(&mut signatures, &mut hasher);
+                signatures.push((meeting_id, summary_saved_at));
+            }
+            let Some(id) = id else { continue };
+            let start = start.map(|t| t.to_string()).unwrap_or_default();
+            for part in [
+                id.as_str(),
+                speaker.as_deref().unwrap_or(""),
+                text.as_deref().unwrap_or(""),
+                start.as_str(),
+            ] {
+                hasher.update(part.as_bytes());
+                // Separator, so ["ab", "c"] and ["a", "bc"] differ
+                hasher.update([0u8]);
+            }
+        }
+        seal_signature(&mut signatures, &mut hasher);
+        Ok(signatures)
+    }
+
+    /// Signatures meetings were last indexed at for the given embedding model
+    pub async fn indexed_signatures(
+        pool: &SqlitePool,
+        embedding_model: &str,
+    ) -> Result<HashMap<String, String>, SqlxError> {
+        let rows: Vec<(String, String)> = sqlx::query_as(
+            "SELECT meeting_id, signature FROM qa_index_state WHERE embedding_model = ?",
+        )
+        .bind(embedding_model)
+        .fetch_all(pool)
+        .await?;
+        Ok(rows.into_iter().collect())
+    }
+
+    /// Replaces a meeting's passages for one embedding model
+    pub async fn replace_meeting(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        embedding_model: &str,
+        signature: &str,
+        passages: &[(Passage, Vec<f32>)],
+    ) -> Result<(), SqlxError> {
+        let mut transaction = pool.begin().await?;
+
+        sqlx::query("DELETE FROM qa_passages WHERE meeting_id = ? AND embedding_model = ?")
+            .bind(meeting_id)
+            .bind(embedding_model)
+            .execute(&mut *transaction)
+            .await?;
+
+        for (passage, embedding) in passages {
+            sqlx::query(
+                "INSERT INTO qa_passages (meeting_id, embedding_model, source, transcript_id, audio_start_time, text, embedding)
+                 VALUES (?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(meeting_id)
+            .bind(embedding_model)
+            .bind(passage.source.as_str())
+            .bind(&passage.transcript_id)
+            .bind(passage.audio_start_time)
+            .bind(&passage.text)
+            .bind(embedding_to_blob(embedding))
+            .execute(&mut *transaction)
+            .await?;
+        }
+
+        sqlx::query(
+            "INSERT INTO qa_index_state (meeting_id, embedding_model, signature, passage_count, indexed_at)
+             VALUES (?, ?, ?, ?, ?)
+             ON CONFLICT(meeting_id, embedding_model) DO UPDATE SET
+                signature = excluded.signature,
+                passage_count = excluded.passage_count,
+                indexed_at = excluded.indexed_at",
+        )
+        .bind(meeting_id)
+        .bind(embedding_model)
+        .bind(signature)
+        .bind(passages.len() as i64)
+        .bind(Utc::now())
+        .execute(&mut *transaction)
+        .await?;
+
+        transaction.commit().await?;
+        info!(
+            "Indexed {} passages of meeting {} for {}",
+            passages.len(),
+            meeting_id,
+            embedding_model
+        );
+        Ok(())
+    }
+
+    /// Returns the `top_k` passages most similar to `query`, streaming rows so the
+    /// whole index never has to be held in memory
+    pub async fn search(
+        pool: &SqlitePool,
+        embedding_model: &str,
+        query: &[f32],
+        meeting_ids: Option<&[String]>,
+        top_k: usize,
+    ) -> Result<Vec<RetrievedPassage>, SqlxError> {
+        let mut builder: QueryBuilder<Sqlite> = QueryBuilder::new(
+            "SELECT p.meeting_id, m.title AS meeting_title, m.created_at AS meeting_created_at,
+                    p.source, p.transcript_id, p.audio_start_time, p.text, p.embedding
+             FROM qa_passages p
+             JOIN meetings m ON m.id = p.meeting_id
+             WHERE p.embedding_model = ",
+        );
+        builder.push_bind(embedding_model);
+        if let Some(ids) = meeting_ids {
+            if ids.is_empty() {
+                return Ok(Vec::new());
+            }
+            builder.push(" AND p.meeting_id IN (");
+            let mut separated = builder.separated(", ");
+            for id in ids {
+                separated.push_bind(id);
+            }
+            separated.push_unseparated(")");
+        }
+
+        let mut top: Vec<(f32, PassageRow)> = Vec::new();
+        let mut rows = builder.build_query_as::<PassageRow>().fetch(pool);
+        while let Some(row) = rows.try_next().await? {
+            let score = cosine_similarity(query, &blob_to_embedding(&row.embedding));
+            push_top_k(&mut top, top_k, score, row);
+        }
+
+        Ok(top
+            .into_iter()
+            .map(|(score, row)| RetrievedPassage {
+                meeting_id: row.meeting_id,
+                meeting_title: row.meeting_title,
+                meeting_created_at: row.meeting_created_at,
+                passage: Passage {
+                    source: PassageSource::parse(&row.source),
+                    transcript_id: row.transcript_id,
+                    audio_start_time: row.audio_start_time,
+                    text: row.text,
+                },
+                score,
+            })
+            .collect())
+    }
+}
+
+/// Prefixes the last meeting's summary timestamp with the hash of its rows and
+/// resets the hasher for the next meeting
+fn seal_signature(signatures: &mut [(String, String)], hasher: &mut Sha256) {
+    if let Some((_, signature)) = signatures.last_mut() {
+        *signature = format!("{:x}|{}", std::mem::take(hasher).finalize(), signature);
+    }
+}
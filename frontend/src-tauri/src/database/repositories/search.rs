@@ -0,0 +1,521 @@
+use crate::api::{SearchHit, SearchMatch, SearchSource};
+use sqlx::{Error as SqlxError, FromRow, SqlitePool};
+use tracing::info;
+
+/// Upper bound on hits returned per search call
+const MAX_SEARCH_LIMIT: i64 = 200;
+
+/// Tags used by FTS5 `snippet()` to mark matched terms
+const SNIPPET_OPEN: &str = "<mark>";
+const SNIPPET_CLOSE: &str = "</mark>";
+const SNIPPET_ELLIPSIS: &str = "…";
+const SNIPPET_TOKENS: i64 = 24;
+
+/// A positive search term extracted from the user query, used to locate
+/// every match inside the returned text (FTS5 has no `offsets()` function).
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct SearchTerm {
+    /// Lowercased words; more than one word means a phrase
+    pub words: Vec<String>,
+    /// Last word is a prefix (`term*`)
+    pub prefix: bool,
+}
+
+/// User query translated into safe FTS5 MATCH syntax
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ParsedQuery {
+    pub fts_query: String,
+    pub terms: Vec<SearchTerm>,
+}
+
+#[derive(Debug)]
+enum QueryItem {
+    Term(String),
+    Op(&'static str),
+    Open,
+    Close,
+}
+
+#[derive(Debug, FromRow)]
+struct TranscriptHitRow {
+    transcript_id: String,
+    meeting_id: String,
+    title: String,
+    transcript: String,
+    timestamp: String,
+    audio_start_time: Option<f64>,
+    audio_end_time: Option<f64>,
+    speaker: Option<String>,
+    snippet: String,
+    rank: f64,
+}
+
+#[derive(Debug, FromRow)]
+struct MeetingDocumentHitRow {
+    meeting_id: String,
+    title: String,
+    content: String,
+    snippet: String,
+    rank: f64,
+}
+
+pub struct SearchRepository;
+
+impl SearchRepository {
+    /// Runs a ranked full-text search over the requested sources.
+    ///
+    /// Supports phrases (`"billing migration"`), prefixes (`migrat*`) and the
+    /// boolean operators `AND`, `OR`, `NOT` with parentheses. Anything else is
+    /// quoted so user input can never produce an FTS5 syntax error.
+    pub async fn search(
+        pool: &SqlitePool,
+        query: &str,
+        sources: &[SearchSource],
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<SearchHit>, SqlxError> {
+        let parsed = match parse_query(query) {
+            Some(parsed) => parsed,
+            None => return Ok(Vec::new()),
+        };
+
+        let limit = limit.clamp(1, MAX_SEARCH_LIMIT);
+        let offset = offset.max(0);
+        // Each source is ranked independently, so fetch enough from every source
+        // to fill the requested page after merging.
+        let per_source_limit = limit + offset;
+
+        let mut hits = Vec::new();
+        for source in sources {
+            let mut source_hits = match source {
+                SearchSource::Transcript => {
+                    Self::search_transcript_segments(pool, &parsed, per_source_limit).await?
+                }
+                SearchSource::Summary | SearchSource::Notes => {
+                    Self::search_meeting_documents(pool, &parsed, *source, per_source_limit).await?
+                }
+            };
+            hits.append(&mut source_hits);
+        }
+
+        hits.sort_by(|a, b| {
+            b.score
+                .partial_cmp(&a.score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+
+        let page: Vec<SearchHit> = hits
+            .into_iter()
+            .skip(offset as usize)
+            .take(limit as usize)
+            .collect();
+
+        info!(
+            "Full-text search '{}' returned {} hits (fts query: {})",
+            query,
+            page.len(),
+            parsed.fts_query
+        );
+
+        Ok(page)
+    }
+
+    async fn search_transcript_segments(
+        pool: &SqlitePool,
+        parsed: &ParsedQuery,
+        limit: i64,
+    ) -> Result<Vec<SearchHit>, SqlxError> {
+        let rows = sqlx::query_as::<_, TranscriptHitRow>(
+            "SELECT transcripts_fts.transcript_id AS transcript_id,
+                    transcripts_fts.meeting_id AS meeting_id,
+                    m.title AS title,
+                    t.transcript AS transcript,
+                    t.timestamp AS timestamp,
+                    t.audio_start_time AS audio_start_time,
+                    t.audio_end_time AS audio_end_time,
+                    t.speaker AS speaker,
+                    snippet(transcripts_fts, 0, ?, ?, ?, ?) AS snippet,
+                    bm25(transcripts_fts) AS rank
+             FROM transcripts_fts
+             JOIN transcripts t ON t.id = transcripts_fts.transcript_id
+             JOIN meetings m ON m.id = transcripts_fts.meeting_id
+             WHERE transcripts_fts MATCH ?
+             ORDER BY rank
+             LIMIT ?",
+        )
+        .bind(SNIPPET_OPEN)
+        .bind(SNIPPET_CLOSE)
+        .bind(SNIPPET_ELLIPSIS)
+        .bind(SNIPPET_TOKENS)
+        .bind(&parsed.fts_query)
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| SearchHit {
+                matches: find_match_offsets(&row.transcript, &parsed.terms),
+                meeting_id: row.meeting_id,
+                meeting_title: row.title,
+                source: SearchSource::Transcript,
+                source_id: row.transcript_id,
+                text: row.transcript,
+                snippet: row.snippet,
+                score: -row.rank,
+                timestamp: Some(row.timestamp),
+                audio_start_time: row.audio_start_time,
+                audio_end_time: row.audio_end_time,
+                speaker: row.speaker,
+            })
+            .collect())
+    }
+
+    async fn search_meeting_documents(
+        pool: &SqlitePool,
+        parsed: &ParsedQuery,
+        source: SearchSource,
+        limit: i64,
+    ) -> Result<Vec<SearchHit>, SqlxError> {
+        // Table and column names are fixed per source, never user input
+        let (table, column) = match source {
+            SearchSource::Summary => ("summaries_fts", "markdown"),
+            SearchSource::Notes => ("meeting_notes_fts", "notes_markdown"),
+            SearchSource::Transcript => unreachable!("transcripts use search_transcript_segments"),
+        };
+
+        let sql = format!(
+            "SELECT {table}.meeting_id AS meeting_id,
+                    m.title AS title,
+                    {table}.{column} AS content,
+                    snippet({table}, 0, ?, ?, ?, ?) AS snippet,
+                    bm25({table}) AS rank
+             FROM {table}
+             JOIN meetings m ON m.id = {table}.meeting_id
+             WHERE {table} MATCH ?
+             ORDER BY rank
+             LIMIT ?",
+            table = table,
+            column = column
+        );
+
+        let rows = sqlx::query_as::<_, MeetingDocumentHitRow>(&sql)
+            .bind(SNIPPET_OPEN)
+            .bind(SNIPPET_CLOSE)
+            .bind(SNIPPET_ELLIPSIS)
+            .bind(SNIPPET_TOKENS)
+            .bind(&parsed.fts_query)
+            .bind(limit)
+            .fetch_all(pool)
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| SearchHit {
+                matches: find_match_offsets(&row.content, &parsed.terms),
+                source_id: row.meeting_id.clone(),
+                meeting_id: row.meeting_id,
+                meeting_title: row.title,
+                source,
+                text: row.content,
+                snippet: row.snippet,
+                score: -row.rank,
+                timestamp: None,
+                audio_start_time: None,
+                audio_end_time: None,
+                speaker: None,
+            })
+            .collect())
+    }
+}
+
+/// Splits text into lowercased alphanumeric words, mirroring the `unicode61`
+/// tokenizer closely enough for highlighting. Returns (start_char, end_char, word).
+fn tokenize(text: &str) -> Vec<(usize, usize, String)> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut start = 0;
+
+    for (i, c) in text.chars().enumerate() {
+        if c.is_alphanumeric() {
+            if current.is_empty() {
+                start = i;
+            }
+            current.extend(c.to_lowercase());
+        } else if !current.is_empty() {
+            tokens.push((start, i, std::mem::take(&mut current)));
+        }
+    }
+    if !current.is_empty() {
+        tokens.push((start, text.chars().count(), current));
+    }
+
+    tokens
+}
+
+/// Builds an FTS5 term (`"quoted words"` with optional `*`) and the matching
+/// highlight term. Returns None when the input has no searchable words.
+fn make_term(raw: &str, prefix: bool) -> Option<(String, SearchTerm)> {
+    let words: Vec<String> = tokenize(raw).into_iter().map(|(_, _, w)| w).collect();
+    if words.is_empty() {
+        return None;
+    }
+
+    let mut fts = format!("\"{}\"", words.join(" "));
+    if prefix {
+        fts.push('*');
+    }
+    Some((fts, SearchTerm { words, prefix }))
+}
+
+/// Translates free-form user input into a safe FTS5 MATCH expression.
+///
+/// Returns None when the query contains nothing searchable.
+pub(crate) fn parse_query(input: &str) -> Option<ParsedQuery> {
+    let chars: Vec<char> = input.chars().collect();
+    let mut items: Vec<QueryItem> = Vec::new();
+    let mut terms = Vec::new();
+    let mut i = 0;
+
+    let mut push_term = |items: &mut Vec<QueryItem>, raw: &str, prefix: bool| {
+        if let Some((fts, term)) = make_term(raw, prefix) {
+            // Terms on the right-hand side of NOT are excluded from highlighting
+            if !matches!(items.last(), Some(QueryItem::Op("NOT"))) {
+                terms.push(term);
+            }
+            items.push(QueryItem::Term(fts));
+        }
+    };
+
+    while i < chars.len() {
+        let c = chars[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c == '"' {
+            let start = i + 1;
+            let mut end = start;
+            while end < chars.len() && chars[end] != '"' {
+                end += 1;
+            }
+            let phrase: String = chars[start..end].iter().collect();
+            i = (end + 1).min(chars.len());
+            let prefix = i < chars.len() && chars[i] == '*';
+            if prefix {
+                i += 1;
+            }
+            push_term(&mut items, &phrase, prefix);
+        } else if c == '(' {
+            items.push(QueryItem::Open);
+            i += 1;
+        } else if c == ')' {
+            items.push(QueryItem::Close);
+            i += 1;
+        } else {
+            let start = i;
+            while i < chars.len()
+                && !chars[i].is_whitespace()
+                && !matches!(chars[i], '"' | '(' | ')')
+            {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            match word.as_str() {
+                "AND" => items.push(QueryItem::Op("AND")),
+                "OR" => items.push(QueryItem::Op("OR")),
+                "NOT" => items.push(QueryItem::Op("NOT")),
+                _ => {
+                    let prefix = word.ends_with('*');
+                    push_term(&mut items, word.trim_end_matches('*'), prefix);
+                }
+            }
+        }
+    }
+
+    let fts_query = render_items(&items);
+    if fts_query.is_empty() || terms.is_empty() {
+        return None;
+    }
+
+    Some(ParsedQuery { fts_query, terms })
+}
+
+/// Renders query items, dropping dangling operators and unbalanced or empty
+/// parentheses that would otherwise be FTS5 syntax errors.
+fn render_items(items: &[QueryItem]) -> String {
+    let mut out: Vec<String> = Vec::new();
+    // For each open group: index in `out` of its "(" and whether it holds a term
+    let mut groups: Vec<(usize, bool)> = Vec::new();
+    let mut expects_operand = true;
+
+    for item in items {
+        match item {
+            QueryItem::Term(term) => {
+                out.push(term.clone());
+                if let Some(group) = groups.last_mut() {
+                    group.1 = true;
+                }
+                expects_operand = false;
+            }
+            QueryItem::Op(op) => {
+                if !expects_operand {
+                    out.push(op.to_string());
+                    expects_operand = true;
+                }
+            }
+            QueryItem::Open => {
+                groups.push((out.len(), false));
+                out.push("(".to_string());
+                expects_operand = true;
+            }
+            QueryItem::Close => {
+                let Some((open_index, has_term)) = groups.pop() else {
+                    continue;
+                };
+                if expects_operand && out.len() > open_index + 1 {
+                    // Trailing operator inside the group
+                    out.pop();
+                }
+                if has_term {
+                    out.push(")".to_string());
+                    if let Some(parent) = groups.last_mut() {
+                        parent.1 = true;
+                    }
+                    expects_operand = false;
+                } else {
+                    out.truncate(open_index);
+                    expects_operand = !matches!(out.last(), Some(t) if t != "AND" && t != "OR" && t != "NOT" && t != "(");
+                }
+            }
+        }
+    }
+
+    if expects_operand && matches!(out.last().map(String::as_str), Some("AND" | "OR" | "NOT")) {
+        out.pop();
+    }
+
+    // Close any groups left open, removing empty ones
+    while let Some((open_index, has_term)) = groups.pop() {
+        if has_term {
+            if matches!(out.last().map(String::as_str), Some("AND" | "OR" | "NOT")) {
+                out.pop();
+            }
+            out.push(")".to_string());
+        } else {
+            out.truncate(open_index);
+        }
+    }
+
+    while matches!(out.last().map(String::as_str), Some("AND" | "OR" | "NOT")) {
+        out.pop();
+    }
+
+    out.join(" ").replace("( ", "(").replace(" )", ")")
+}
+
+/// Finds every occurrence of the search terms in `text`, returned as sorted,
+/// non-overlapping character ranges.
+pub(crate) fn find_match_offsets(text: &str, terms: &[SearchTerm]) -> Vec<SearchMatch> {
+    let tokens = tokenize(text);
+    let mut matches = Vec::new();
+
+    for term in terms {
+        let n = term.words.len();
+        if n == 0 || n > tokens.len() {
+            continue;
+        }
+        for i in 0..=(tokens.len() - n) {
+            let is_match = term.words.iter().enumerate().all(|(j, word)| {
+                let token = &tokens[i + j].2;
+                if term.prefix && j == n - 1 {
+                    token.starts_with(word.as_str())
+                } else {
+                    token == word
+                }
+            });
+            if is_match {
+                matches.push(SearchMatch {
+                    start: tokens[i].0,
+                    end: tokens[i + n - 1].1,
+                });
+            }
+        }
+    }
+
+    matches.sort_by_key(|m| (m.start, m.end));
+    let mut merged: Vec<SearchMatch> = Vec::with_capacity(matches.len());
+    for m in matches {
+        match merged.last_mut() {
+            Some(last) if m.start <= last.end => last.end = last.end.max(m.end),
+            _ => merged.push(m),
+        }
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_query_quotes_bare_words() {
+        let parsed = parse_query("billing migration").unwrap();
+        assert_eq!(parsed.fts_query, "\"billing\" \"migration\"");
+        assert_eq!(parsed.terms.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_query_phrases_prefixes_and_operators() {
+        let parsed = parse_query("\"Billing Migration\" OR migrat* NOT legacy").unwrap();
+        assert_eq!(
+            parsed.fts_query,
+            "\"billing migration\" OR \"migrat\"* NOT \"legacy\""
+        );
+        // Negated terms are not highlighted
+        assert_eq!(parsed.terms.len(), 2);
+        assert!(parsed.terms[1].prefix);
+    }
+
+    #[test]
+    fn test_parse_query_never_produces_dangling_syntax() {
+        assert_eq!(parse_query("AND foo OR").unwrap().fts_query, "\"foo\"");
+        assert_eq!(
+            parse_query("(foo OR bar").unwrap().fts_query,
+            "(\"foo\" OR \"bar\")"
+        );
+        assert_eq!(
+            parse_query("foo ( ) bar)").unwrap().fts_query,
+            "\"foo\" \"bar\""
+        );
+        assert_eq!(
+            parse_query("e-mail: 10:30").unwrap().fts_query,
+            "\"e mail\" \"10 30\""
+        );
+        assert!(parse_query("  \"\" * ( ) OR ").is_none());
+    }
+
+    #[test]
+    fn test_find_match_offsets_all_occurrences() {
+        let terms = parse_query("budget").unwrap().terms;
+        let offsets = find_match_offsets("Budget first, then the budget review.", &terms);
+        assert_eq!(
+            offsets,
+            vec![
+                SearchMatch { start: 0, end: 6 },
+                SearchMatch { start: 23, end: 29 }
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_match_offsets_phrase_prefix_and_unicode() {
+        let terms = parse_query("\"über die\" planung*").unwrap().terms;
+        let offsets = find_match_offsets("Wir sprachen Über  die Planungen.", &terms);
+        assert_eq!(
+            offsets,
+            vec![
+                SearchMatch { start: 13, end: 22 },
+                SearchMatch { start: 23, end: 32 }
+            ]
+        );
+    }
+}
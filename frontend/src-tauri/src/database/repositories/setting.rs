@@ -1,5 +1,5 @@
 use crate::database::models::{Setting, TranscriptSetting};
-use crate::summary::CustomOpenAIConfig;
+use crate::summary::{CustomOpenAIConfig, FallbackModel};
 use sqlx::SqlitePool;
 
 #[derive(serde::Deserialize, Debug)]
@@ -25,7 +25,7 @@ pub struct SaveTranscriptConfigRequest {
 pub struct SettingsRepository;
 
 // Transcript providers: localWhisper, deepgram, elevenLabs, groq, openai
-// Summary providers: openai, claude, ollama, groq, added openrouter
+// Summary providers: openai, claude, ollama, groq, added openrouter, gemini
 // NOTE: Handle data exclusion in the higher layer as this is database abstraction layer(using SELECT *)
 
 impl SettingsRepository {
@@ -85,6 +85,7 @@ impl SettingsRepository {
             "ollama" => "ollamaApiKey",
             "groq" => "groqApiKey",
             "openrouter" => "openRouterApiKey",
+            "gemini" => "geminiApiKey",
             "builtin-ai" => return Ok(()), // No API key needed
             _ => {
                 return Err(sqlx::Error::Protocol(
@@ -123,6 +124,7 @@ impl SettingsRepository {
             "groq" => "groqApiKey",
             "claude" => "anthropicApiKey",
             "openrouter" => "openRouterApiKey",
+            "gemini" => "geminiApiKey",
             "builtin-ai" => return Ok(None), // No API key needed
             _ => {
                 return Err(sqlx::Error::Protocol(
@@ -249,6 +251,7 @@ impl SettingsRepository {
             "groq" => "groqApiKey",
             "claude" => "anthropicApiKey",
             "openrouter" => "openRouterApiKey",
+            "gemini" => "geminiApiKey",
             "builtin-ai" => return Ok(()), // No API key needed
             _ => {
                 return Err(sqlx::Error::Protocol(
@@ -345,4 +348,46 @@ impl SettingsRepository {
 
         Ok(())
     }
+
+    // ===== LLM FALLBACK CHAIN METHODS =====
+
+    /// Gets the providers to fall back to, in order; empty when none are configured
+    pub async fn get_llm_fallback_chain(
+        pool: &SqlitePool,
+    ) -> std::result::Result<Vec<FallbackModel>, sqlx::Error> {
+        let chain_json: Option<Option<String>> =
+            sqlx::query_scalar("SELECT llmFallbackChain FROM settings WHERE id = '1' LIMIT 1")
+                .fetch_optional(pool)
+                .await?;
+
+        match chain_json.flatten() {
+            Some(json) => serde_json::from_str(&json).map_err(|e| {
+                sqlx::Error::Protocol(format!("Invalid JSON in llmFallbackChain: {}", e).into())
+            }),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    pub async fn save_llm_fallback_chain(
+        pool: &SqlitePool,
+        chain: &[FallbackModel],
+    ) -> std::result::Result<(), sqlx::Error> {
+        let chain_json = serde_json::to_string(chain).map_err(|e| {
+            sqlx::Error::Protocol(format!("Failed to serialize fallback chain: {}", e).into())
+        })?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO settings (id, provider, model, whisperModel, llmFallbackChain)
+            VALUES ('1', 'openai', 'gpt-4o-2024-11-20', 'large-v3', $1)
+            ON CONFLICT(id) DO UPDATE SET
+                llmFallbackChain = excluded.llmFallbackChain
+            "#,
+        )
+        .bind(chain_json)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
 }
@@ -0,0 +1,404 @@
+use crate::database::models::MeetingSpeaker;
+use crate::database::repositories::speaker_profile::SpeakerProfilesRepository;
+use crate::diarization::clustering::blend_embeddings;
+use crate::diarization::{speaker_label, DiarizedSpeaker};
+use chrono::Utc;
+use sqlx::{Connection, Error as SqlxError, SqliteConnection, SqlitePool};
+use tracing::info;
+
+pub struct SpeakersRepository;
+
+impl SpeakersRepository {
+    /// Returns the diarized speakers of a meeting, ordered by label
+    pub async fn get_meeting_speakers(
+        pool: &SqlitePool,
+        meeting_id: &str,
+    ) -> Result<Vec<MeetingSpeaker>, SqlxError> {
+        sqlx::query_as::<_, MeetingSpeaker>(
+            "SELECT * FROM meeting_speakers WHERE meeting_id = ? ORDER BY label",
+        )
+        .bind(meeting_id)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Replaces the diarized speakers of a meeting.
+    /// Takes a connection so callers can run it in the same transaction as the
+    /// transcript writes it belongs to; call it after the transcript rows are written.
+    pub async fn replace_meeting_speakers(
+        conn: &mut SqliteConnection,
+        meeting_id: &str,
+        speakers: &[DiarizedSpeaker],
+    ) -> Result<(), SqlxError> {
+        sqlx::query("DELETE FROM meeting_speakers WHERE meeting_id = ?")
+            .bind(meeting_id)
+            .execute(&mut *conn)
+            .await?;
+
+        let now = Utc::now();
+        for speaker in speakers {
+            sqlx::query(
+                "INSERT INTO meeting_speakers (meeting_id, label, embedding, segment_count, total_duration, created_at, profile_id)
+                 VALUES (?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(meeting_id)
+            .bind(&speaker.label)
+            .bind(embedding_to_blob(&speaker.embedding))
+            .bind(speaker.segment_count as i64)
+            .bind(speaker.total_duration)
+            .bind(now)
+            .bind(&speaker.profile_id)
+            .execute(&mut *conn)
+            .await?;
+        }
+
+        Self::refresh_speaker_stats(&mut *conn, meeting_id).await?;
+
+        info!(
+            "Saved {} diarized speakers for meeting {}",
+            speakers.len(),
+            meeting_id
+        );
+        Ok(())
+    }
+
+    /// Writes diarized speaker labels back to transcript rows, keyed by transcript id
+    pub async fn update_transcript_speaker_labels(
+        conn: &mut SqliteConnection,
+        meeting_id: &str,
+        labels: &[(String, Option<String>)],
+    ) -> Result<(), SqlxError> {
+        for (transcript_id, label) in labels {
+            sqlx::query("UPDATE transcripts SET speaker_label = ? WHERE id = ? AND meeting_id = ?")
+                .bind(label)
+                .bind(transcript_id)
+                .bind(meeting_id)
+                .execute(&mut *conn)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Recomputes segment_count and total_duration of each speaker from the
+    /// transcript rows currently carrying their label
+    async fn refresh_speaker_stats(
+        conn: &mut SqliteConnection,
+        meeting_id: &str,
+    ) -> Result<(), SqlxError> {
+        sqlx::query(
+            "UPDATE meeting_speakers SET
+                segment_count = (SELECT COUNT(*) FROM transcripts t
+                                 WHERE t.meeting_id = meeting_speakers.meeting_id
+                                   AND t.speaker_label = meeting_speakers.label),
+                total_duration = (SELECT COALESCE(SUM(t.duration), 0.0) FROM transcripts t
+                                  WHERE t.meeting_id = meeting_speakers.meeting_id
+                                    AND t.speaker_label = meeting_speakers.label)
+             WHERE meeting_id = ?",
+        )
+        .bind(meeting_id)
+        .execute(&mut *conn)
+        .await?;
+        Ok(())
+    }
+
+    async fn label_exists(
+        conn: &mut SqliteConnection,
+        meeting_id: &str,
+        label: &str,
+    ) -> Result<bool, SqlxError> {
+        let row: Option<(i64,)> = sqlx::query_as(
+            "SELECT 1 FROM meeting_speakers WHERE meeting_id = ? AND label = ?
+             UNION SELECT 1 FROM transcripts WHERE meeting_id = ? AND speaker_label = ?
+             LIMIT 1",
+        )
+        .bind(meeting_id)
+        .bind(label)
+        .bind(meeting_id)
+        .bind(label)
+        .fetch_optional(&mut *conn)
+        .await?;
+        Ok(row.is_some())
+    }
+
+    /// Renames a speaker within a meeting, rewriting the transcript rows.
+    ///
+    /// With `save_profile`, the speaker's voice is also stored (or folded into) the
+    /// voice profile of the same name so later meetings recognize them. Fails if
+    /// another speaker in the meeting already has `new_name`; merge instead.
+    pub async fn rename_speaker(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        label: &str,
+        new_name: &str,
+        save_profile: bool,
+    ) -> Result<bool, SqlxError> {
+        let mut conn = pool.acquire().await?;
+        let mut transaction = conn.begin().await?;
+
+        if label != new_name && Self::label_exists(&mut *transaction, meeting_id, new_name).await? {
+            transaction.rollback().await?;
+            return Err(SqlxError::Protocol(format!(
+                "Speaker '{}' already exists in this meeting",
+                new_name
+            )));
+        }
+
+        let transcripts = sqlx::query(
+            "UPDATE transcripts SET speaker_label = ? WHERE meeting_id = ? AND speaker_label = ?",
+        )
+        .bind(new_name)
+        .bind(meeting_id)
+        .bind(label)
+        .execute(&mut *transaction)
+        .await?;
+        let speakers =
+            sqlx::query("UPDATE meeting_speakers SET label = ? WHERE meeting_id = ? AND label = ?")
+                .bind(new_name)
+                .bind(meeting_id)
+                .bind(label)
+                .execute(&mut *transaction)
+                .await?;
+
+        if transcripts.rows_affected() == 0 && speakers.rows_affected() == 0 {
+            transaction.rollback().await?;
+            return Ok(false);
+        }
+
+        if save_profile {
+            let speaker: Option<MeetingSpeaker> =
+                sqlx::query_as("SELECT * FROM meeting_speakers WHERE meeting_id = ? AND label = ?")
+                    .bind(meeting_id)
+                    .bind(new_name)
+                    .fetch_optional(&mut *transaction)
+                    .await?;
+
+            match speaker.filter(|s| !s.embedding.is_empty()) {
+                Some(speaker) => {
+                    let profile_id = SpeakerProfilesRepository::save_voice_sample(
+                        &mut *transaction,
+                        new_name,
+                        &blob_to_embedding(&speaker.embedding),
+                        speaker.segment_count,
+                    )
+                    .await?;
+                    sqlx::query(
+                        "UPDATE meeting_speakers SET profile_id = ? WHERE meeting_id = ? AND label = ?",
+                    )
+                    .bind(&profile_id)
+                    .bind(meeting_id)
+                    .bind(new_name)
+                    .execute(&mut *transaction)
+                    .await?;
+                }
+                None => info!(
+                    "No voice embedding for '{}' in meeting {}; voice profile not updated",
+                    new_name, meeting_id
+                ),
+            }
+        }
+
+        transaction.commit().await?;
+        info!(
+            "Renamed speaker '{}' to '{}' in meeting {}",
+            label, new_name, meeting_id
+        );
+        Ok(true)
+    }
+
+    /// Merges `source_labels` into `target_label` (e.g. when diarization split one
+    /// person into two speakers). Voice embeddings are combined weighted by the
+    /// number of transcript rows each speaker had.
+    pub async fn merge_speakers(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        source_labels: &[String],
+        target_label: &str,
+    ) -> Result<(), SqlxError> {
+        let mut conn = pool.acquire().await?;
+        let mut transaction = conn.begin().await?;
+
+        let mut target: Option<MeetingSpeaker> =
+            sqlx::query_as("SELECT * FROM meeting_speakers WHERE meeting_id = ? AND label = ?")
+                .bind(meeting_id)
+                .bind(target_label)
+                .fetch_optional(&mut *transaction)
+                .await?;
+
+        for source_label in source_labels.iter().filter(|l| l.as_str() != target_label) {
+            let source: Option<MeetingSpeaker> =
+                sqlx::query_as("SELECT * FROM meeting_speakers WHERE meeting_id = ? AND label = ?")
+                    .bind(meeting_id)
+                    .bind(source_label)
+                    .fetch_optional(&mut *transaction)
+                    .await?;
+
+            sqlx::query(
+                "UPDATE transcripts SET speaker_label = ? WHERE meeting_id = ? AND speaker_label = ?",
+            )
+            .bind(target_label)
+            .bind(meeting_id)
+            .bind(source_label)
+            .execute(&mut *transaction)
+            .await?;
+            sqlx::query("DELETE FROM meeting_speakers WHERE meeting_id = ? AND label = ?")
+                .bind(meeting_id)
+                .bind(source_label)
+                .execute(&mut *transaction)
+                .await?;
+
+            target = match (target, source) {
+                (Some(mut t), Some(s)) => {
+                    let blended = blend_embeddings(
+                        &blob_to_embedding(&t.embedding),
+                        t.segment_count as f32,
+                        &blob_to_embedding(&s.embedding),
+                        s.segment_count as f32,
+                    );
+                    t.embedding = embedding_to_blob(&blended);
+                    t.segment_count += s.segment_count;
+                    Some(t)
+                }
+                // Target only existed as transcript labels: adopt the source's voice
+                (None, Some(s)) => {
+                    sqlx::query(
+                        "INSERT INTO meeting_speakers (meeting_id, label, embedding, segment_count, total_duration, created_at)
+                         VALUES (?, ?, ?, ?, ?, ?)",
+                    )
+                    .bind(meeting_id)
+                    .bind(target_label)
+                    .bind(&s.embedding)
+                    .bind(s.segment_count)
+                    .bind(s.total_duration)
+                    .bind(Utc::now())
+                    .execute(&mut *transaction)
+                    .await?;
+                    Some(s)
+                }
+                (target, None) => target,
+            };
+        }
+
+        if let Some(target) = &target {
+            sqlx::query(
+                "UPDATE meeting_speakers SET embedding = ? WHERE meeting_id = ? AND label = ?",
+            )
+            .bind(&target.embedding)
+            .bind(meeting_id)
+            .bind(target_label)
+            .execute(&mut *transaction)
+            .await?;
+        }
+        Self::refresh_speaker_stats(&mut *transaction, meeting_id).await?;
+
+        transaction.commit().await?;
+        info!(
+            "Merged speakers {:?} into '{}' in meeting {}",
+            source_labels, target_label, meeting_id
+        );
+        Ok(())
+    }
+
+    /// Moves the given transcript rows of `label` to a new speaker (e.g. when
+    /// diarization attributed two people to one speaker). Without `new_label` the
+    /// next free 'Speaker N' label is used. The new speaker has no voice embedding
+    /// until the meeting is diarized again. Returns the new speaker's label.
+    pub async fn split_speaker(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        label: &str,
+        transcript_ids: &[String],
+        new_label: Option<&str>,
+    ) -> Result<String, SqlxError> {
+        let mut conn = pool.acquire().await?;
+        let mut transaction = conn.begin().await?;
+
+        let new_label = match new_label {
+            Some(l) => {
+                if Self::label_exists(&mut *transaction, meeting_id, l).await? {
+                    transaction.rollback().await?;
+                    return Err(SqlxError::Protocol(format!(
+                        "Speaker '{}' already exists in this meeting",
+                        l
+                    )));
+                }
+                l.to_string()
+            }
+            None => {
+                let mut index = 0;
+                loop {
+                    let candidate = speaker_label(index);
+                    if !Self::label_exists(&mut *transaction, meeting_id, &candidate).await? {
+                        break candidate;
+                    }
+                    index += 1;
+                }
+            }
+        };
+
+        let mut moved = 0;
+        for transcript_id in transcript_ids {
+            let result = sqlx::query(
+                "UPDATE transcripts SET speaker_label = ? WHERE id = ? AND meeting_id = ? AND speaker_label = ?",
+            )
+            .bind(&new_label)
+            .bind(transcript_id)
+            .bind(meeting_id)
+            .bind(label)
+            .execute(&mut *transaction)
+            .await?;
+            moved += result.rows_affected();
+        }
+
+        if moved == 0 {
+            transaction.rollback().await?;
+            return Err(SqlxError::Protocol(format!(
+                "None of the given transcripts belong to speaker '{}'",
+                label
+            )));
+        }
+
+        sqlx::query(
+            "INSERT INTO meeting_speakers (meeting_id, label, embedding, segment_count, total_duration, created_at)
+             VALUES (?, ?, ?, 0, 0.0, ?)",
+        )
+        .bind(meeting_id)
+        .bind(&new_label)
+        .bind(Vec::<u8>::new())
+        .bind(Utc::now())
+        .execute(&mut *transaction)
+        .await?;
+        Self::refresh_speaker_stats(&mut *transaction, meeting_id).await?;
+
+        transaction.commit().await?;
+        info!(
+            "Split {} transcripts from '{}' into '{}' in meeting {}",
+            moved, label, new_label, meeting_id
+        );
+        Ok(new_label)
+    }
+}
+
+/// Serializes an embedding as little-endian f32 bytes
+pub fn embedding_to_blob(embedding: &[f32]) -> Vec<u8> {
+    embedding.iter().flat_map(|v| v.to_le_bytes()).collect()
+}
+
+/// Inverse of `embedding_to_blob`; trailing partial values are ignored
+pub fn blob_to_embedding(blob: &[u8]) -> Vec<f32> {
+    blob.chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_embedding_blob_roundtrip() {
+        let embedding = vec![0.25f32, -1.5, 3.0e-7, 0.0];
+        let blob = embedding_to_blob(&embedding);
+        assert_eq!(blob.len(), 16);
+        assert_eq!(blob_to_embedding(&blob), embedding);
+    }
+}
@@ -0,0 +1,143 @@
+use crate::database::models::SpeakerProfile;
+use crate::database::repositories::speaker::{blob_to_embedding, embedding_to_blob};
+use crate::diarization::clustering::{blend_embeddings, match_embeddings};
+use crate::diarization::DiarizationResult;
+use chrono::Utc;
+use sqlx::{Connection, Error as SqlxError, SqliteConnection, SqlitePool};
+use tracing::info;
+use uuid::Uuid;
+
+/// Minimum cosine similarity between a diarized speaker and a stored profile
+/// for the speaker to be labelled with the profile's name automatically
+const PROFILE_MATCH_THRESHOLD: f32 = 0.6;
+
+pub struct SpeakerProfilesRepository;
+
+impl SpeakerProfilesRepository {
+    pub async fn get_profiles(pool: &SqlitePool) -> Result<Vec<SpeakerProfile>, SqlxError> {
+        sqlx::query_as::<_, SpeakerProfile>(
+            "SELECT * FROM speaker_profiles ORDER BY name COLLATE NOCASE",
+        )
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Creates the profile `name`, or folds `embedding` into the existing profile of
+    /// that name (case-insensitive) weighted by how many samples each side represents.
+    /// Returns the profile id.
+    pub async fn save_voice_sample(
+        conn: &mut SqliteConnection,
+        name: &str,
+        embedding: &[f32],
+        sample_count: i64,
+    ) -> Result<String, SqlxError> {
+        let now = Utc::now();
+        let sample_count = sample_count.max(1);
+        let existing: Option<SpeakerProfile> =
+            sqlx::query_as("SELECT * FROM speaker_profiles WHERE name = ? COLLATE NOCASE")
+                .bind(name)
+                .fetch_optional(&mut *conn)
+                .await?;
+
+        match existing {
+            Some(profile) => {
+                let blended = blend_embeddings(
+                    &blob_to_embedding(&profile.embedding),
+                    profile.sample_count as f32,
+                    embedding,
+                    sample_count as f32,
+                );
+                sqlx::query(
+                    "UPDATE speaker_profiles SET embedding = ?, sample_count = ?, updated_at = ? WHERE id = ?",
+                )
+                .bind(embedding_to_blob(&blended))
+                .bind(profile.sample_count + sample_count)
+                .bind(now)
+                .bind(&profile.id)
+                .execute(&mut *conn)
+                .await?;
+                info!("Updated voice profile '{}' ({})", profile.name, profile.id);
+                Ok(profile.id)
+            }
+            None => {
+                let id = format!("profile-{}", Uuid::new_v4());
+                sqlx::query(
+                    "INSERT INTO speaker_profiles (id, name, embedding, sample_count, created_at, updated_at)
+                     VALUES (?, ?, ?, ?, ?, ?)",
+                )
+                .bind(&id)
+                .bind(name)
+                .bind(embedding_to_blob(embedding))
+                .bind(sample_count)
+                .bind(now)
+                .bind(now)
+                .execute(&mut *conn)
+                .await?;
+                info!("Created voice profile '{}' ({})", name, id);
+                Ok(id)
+            }
+        }
+    }
+
+    /// Deletes a profile and unlinks it from meeting speakers. Speaker names already
+    /// written to transcripts are kept.
+    pub async fn delete_profile(pool: &SqlitePool, profile_id: &str) -> Result<bool, SqlxError> {
+        let mut conn = pool.acquire().await?;
+        let mut transaction = conn.begin().await?;
+
+        sqlx::query("UPDATE meeting_speakers SET profile_id = NULL WHERE profile_id = ?")
+            .bind(profile_id)
+            .execute(&mut *transaction)
+            .await?;
+        let result = sqlx::query("DELETE FROM speaker_profiles WHERE id = ?")
+            .bind(profile_id)
+            .execute(&mut *transaction)
+            .await?;
+
+        transaction.commit().await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    /// Renames diarized speakers whose voice matches a stored profile, so a
+    /// colleague named in an earlier meeting is recognized automatically.
+    /// Returns the number of speakers recognized.
+    pub async fn recognize_speakers(
+        pool: &SqlitePool,
+        result: &mut DiarizationResult,
+    ) -> Result<usize, SqlxError> {
+        let profiles = Self::get_profiles(pool).await?;
+        if profiles.is_empty() || result.speakers.is_empty() {
+            return Ok(0);
+        }
+
+        let profile_embeddings: Vec<Vec<f32>> = profiles
+            .iter()
+            .map(|p| blob_to_embedding(&p.embedding))
+            .collect();
+        let speaker_embeddings: Vec<Vec<f32>> = result
+            .speakers
+            .iter()
+            .map(|s| s.embedding.clone())
+            .collect();
+        let matches = match_embeddings(
+            &speaker_embeddings,
+            &profile_embeddings,
+            PROFILE_MATCH_THRESHOLD,
+        );
+
+        let renames: Vec<(String, usize)> = result
+            .speakers
+            .iter()
+            .zip(matches)
+            .filter_map(|(speaker, m)| m.map(|p| (speaker.label.clone(), p)))
+            .collect();
+
+        for (label, profile_index) in &renames {
+            let profile = &profiles[*profile_index];
+            result.rename_speaker(label, &profile.name, Some(profile.id.clone()));
+            info!("Recognized {} as voice profile '{}'", label, profile.name);
+        }
+
+        Ok(renames.len())
+    }
+}
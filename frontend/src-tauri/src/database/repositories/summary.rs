@@ -40,7 +40,25 @@ impl SummaryProcessesRepository {
             return Ok(false);
         }
 
-        let result_json = serde_json::to_string(summary);
+        // Editor saves only send the markdown and blocks; keep the citations of
+        // the generated summary so the UI can still jump to the source audio
+        let mut summary = summary.clone();
+        if summary.get("citations").is_none() {
+            let stored: Option<Option<String>> =
+                sqlx::query_scalar("SELECT result FROM summary_processes WHERE meeting_id = ?")
+                    .bind(meeting_id)
+                    .fetch_optional(&mut *transaction)
+                    .await?;
+            let citations = stored
+                .flatten()
+                .and_then(|result| serde_json::from_str::<Value>(&result).ok())
+                .and_then(|mut result| result.get_mut("citations").map(Value::take));
+            if let (Some(citations), Some(object)) = (citations, summary.as_object_mut()) {
+                object.insert("citations".to_string(), citations);
+            }
+        }
+
+        let result_json = serde_json::to_string(&summary);
         if result_json.is_err() {
             error!("Can't convert the json to string for saving to Database");
             transaction.rollback().await?;
@@ -0,0 +1,50 @@
+use crate::summary::chunking::SummaryCacheKey;
+use chrono::Utc;
+use sqlx::{Error as SqlxError, SqlitePool};
+
+pub struct SummaryCacheRepository;
+
+impl SummaryCacheRepository {
+    /// Cached chunk or combine summary of a meeting, if one was stored for `key`
+    pub async fn get(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        key: &SummaryCacheKey,
+    ) -> Result<Option<String>, SqlxError> {
+        sqlx::query_scalar(
+            "SELECT summary FROM summary_chunk_cache
+             WHERE meeting_id = ? AND level = ? AND content_hash = ? AND model = ? AND prompt_hash = ?",
+        )
+        .bind(meeting_id)
+        .bind(key.level as i64)
+        .bind(&key.content_hash)
+        .bind(&key.model)
+        .bind(&key.prompt_hash)
+        .fetch_optional(pool)
+        .await
+    }
+
+    /// Stores a chunk or combine summary, replacing an entry with the same key
+    pub async fn save(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        key: &SummaryCacheKey,
+        summary: &str,
+    ) -> Result<(), SqlxError> {
+        sqlx::query(
+            "INSERT OR REPLACE INTO summary_chunk_cache
+                (meeting_id, level, content_hash, model, prompt_hash, summary, created_at)
+             VALUES (?, ?, ?, ?, ?, ?, ?)",
+        )
+        .bind(meeting_id)
+        .bind(key.level as i64)
+        .bind(&key.content_hash)
+        .bind(&key.model)
+        .bind(&key.prompt_hash)
+        .bind(summary)
+        .bind(Utc::now().to_rfc3339())
+        .execute(pool)
+        .await?;
+        Ok(())
+    }
+}
@@ -1,9 +1,15 @@
-use crate::api::{TranscriptSearchResult, TranscriptSegment};
+use crate::api::{SearchMatch, SearchSource, TranscriptSearchResult, TranscriptSegment};
+use crate::audio::transcription::WordTiming;
+use crate::database::models::{TranscriptLineRow, TranscriptWord};
+use crate::database::repositories::search::SearchRepository;
 use chrono::Utc;
-use sqlx::{Connection, Error as SqlxError, SqlitePool};
+use sqlx::{Connection, Error as SqlxError, SqliteConnection, SqlitePool};
 use tracing::{error, info};
 use uuid::Uuid;
 
+/// Maximum number of results returned by the legacy sidebar search
+const SEARCH_RESULT_LIMIT: i64 = 100;
+
 pub struct TranscriptsRepository;
 
 impl TranscriptsRepository {
@@ -47,8 +53,8 @@ impl TranscriptsRepository {
         for segment in transcripts {
             let transcript_id = format!("transcript-{}", Uuid::new_v4());
             let result = sqlx::query(
-                "INSERT INTO transcripts (id, meeting_id, transcript, timestamp, audio_start_time, audio_end_time, duration)
-                 VALUES (?, ?, ?, ?, ?, ?, ?)"
+                "INSERT INTO transcripts (id, meeting_id, transcript, timestamp, audio_start_time, audio_end_time, duration, speaker_label)
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?)"
             )
             .bind(&transcript_id)
             .bind(&meeting_id)
@@ -57,6 +63,7 @@ impl TranscriptsRepository {
             .bind(segment.audio_start_time)
             .bind(segment.audio_end_time)
             .bind(segment.duration)
+            .bind(&segment.speaker_label)
             .execute(&mut *transaction)
             .await;
 
@@ -68,6 +75,22 @@ impl TranscriptsRepository {
                 transaction.rollback().await?;
                 return Err(e);
             }
+
+            if let Err(e) = Self::insert_transcript_words(
+                &mut *transaction,
+                &meeting_id,
+                &transcript_id,
+                &segment.words,
+            )
+            .await
+            {
+                error!(
+                    "Failed to save word timings for meeting {}: {}",
+                    meeting_id, e
+                );
+                transaction.rollback().await?;
+                return Err(e);
+            }
         }
 
         info!(
@@ -84,6 +107,9 @@ impl TranscriptsRepository {
 
     /// Searches for a query string within the transcripts.
     /// It returns a list of matching transcripts with context.
+    ///
+    /// Backed by the `transcripts_fts` index; see `SearchRepository::search`
+    /// for the ranked API with all match offsets and audio timestamps.
     pub async fn search_transcripts(
         pool: &SqlitePool,
         query: &str,
@@ -92,27 +118,24 @@ impl TranscriptsRepository {
             return Ok(Vec::new());
         }
 
-        let search_query = format!("%{}%", query.to_lowercase());
-
-        let rows = sqlx::query_as::<_, (String, String, String, String)>(
-            "SELECT m.id, m.title, t.transcript, t.timestamp
-             FROM meetings m
-             JOIN transcripts t ON m.id = t.meeting_id
-             WHERE LOWER(t.transcript) LIKE ?",
+        let hits = SearchRepository::search(
+            pool,
+            query,
+            &[SearchSource::Transcript],
+            SEARCH_RESULT_LIMIT,
+            0,
         )
-        .bind(&search_query)
-        .fetch_all(pool)
         .await?;
 
-        let results = rows
+        let results = hits
             .into_iter()
-            .map(|(id, title, transcript, timestamp)| {
-                let match_context = Self::get_match_context(&transcript, query);
+            .map(|hit| {
+                let match_context = Self::get_match_context(&hit.text, hit.matches.first());
                 TranscriptSearchResult {
-                    id,
-                    title,
+                    id: hit.meeting_id,
+                    title: hit.meeting_title,
                     match_context,
-                    timestamp,
+                    timestamp: hit.timestamp.unwrap_or_default(),
                 }
             })
             .collect();
@@ -120,22 +143,97 @@ impl TranscriptsRepository {
         Ok(results)
     }
 
-    /// Helper function to extract a snippet of text around the first match of a query.
-    fn get_match_context(transcript: &str, query: &str) -> String {
-        let transcript_lower = transcript.to_lowercase();
-        let query_lower = query.to_lowercase();
+    /// Stores the word timings of one transcript row.
+    /// Takes a connection so callers can write words in the same transaction as the row.
+    pub async fn insert_transcript_words(
+        conn: &mut SqliteConnection,
+        meeting_id: &str,
+        transcript_id: &str,
+        words: &[WordTiming],
+    ) -> Result<(), SqlxError> {
+        for (index, word) in words.iter().enumerate() {
+            sqlx::query(
+                "INSERT INTO transcript_words (transcript_id, meeting_id, word_index, word, start_time, end_time, confidence)
+                 VALUES (?, ?, ?, ?, ?, ?, ?)",
+            )
+            .bind(transcript_id)
+            .bind(meeting_id)
+            .bind(index as i64)
+            .bind(&word.text)
+            .bind(word.start)
+            .bind(word.end)
+            .bind(word.confidence)
+            .execute(&mut *conn)
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Returns the word timings of a meeting in playback order, optionally limited
+    /// to the given transcript rows
+    pub async fn get_transcript_words(
+        pool: &SqlitePool,
+        meeting_id: &str,
+        transcript_ids: Option<&[String]>,
+    ) -> Result<Vec<TranscriptWord>, SqlxError> {
+        let id_filter = match transcript_ids {
+            Some(ids) if ids.is_empty() => return Ok(Vec::new()),
+            Some(ids) => format!(
+                " AND transcript_id IN ({})",
+                vec!["?"; ids.len()].join(", ")
+            ),
+            None => String::new(),
+        };
+        let sql = format!(
+            "SELECT transcript_id, word_index, word, start_time, end_time, confidence
+             FROM transcript_words
+             WHERE meeting_id = ?{}
+             ORDER BY start_time, transcript_id, word_index",
+            id_filter
+        );
 
-        match transcript_lower.find(&query_lower) {
-            Some(match_index) => {
-                let start_index = match_index.saturating_sub(100);
-                let end_index = (match_index + query.len() + 100).min(transcript.len());
+        let mut query = sqlx::query_as::<_, TranscriptWord>(&sql).bind(meeting_id);
+        for id in transcript_ids.unwrap_or_default() {
+            query = query.bind(id);
+        }
+        query.fetch_all(pool).await
+    }
+
+    /// The transcript segments of a meeting in playback order
+    pub async fn get_transcript_lines(
+        pool: &SqlitePool,
+        meeting_id: &str,
+    ) -> Result<Vec<TranscriptLineRow>, SqlxError> {
+        sqlx::query_as(
+            "SELECT id, speaker_label, transcript, audio_start_time, audio_end_time FROM transcripts
+             WHERE meeting_id = ?
+             ORDER BY COALESCE(audio_start_time, 0), timestamp",
+        )
+        .bind(meeting_id)
+        .fetch_all(pool)
+        .await
+    }
+
+    /// Helper function to extract a snippet of text around the first match.
+    /// Works on characters so multi-byte text is never split mid-codepoint.
+    fn get_match_context(transcript: &str, first_match: Option<&SearchMatch>) -> String {
+        match first_match {
+            Some(m) => {
+                let total_chars = transcript.chars().count();
+                let start_index = m.start.saturating_sub(100);
+                let end_index = (m.end + 100).min(total_chars);
 
                 let mut context = String::new();
                 if start_index > 0 {
                     context.push_str("...");
                 }
-                context.push_str(&transcript[start_index..end_index]);
-                if end_index < transcript.len() {
+                context.extend(
+                    transcript
+                        .chars()
+                        .skip(start_index)
+                        .take(end_index - start_index),
+                );
+                if end_index < total_chars {
                     context.push_str("...");
                 }
                 context
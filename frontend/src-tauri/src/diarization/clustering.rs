@@ -0,0 +1,266 @@
+// Agglomerative clustering of speaker embeddings
+
+/// Cosine similarity between two embeddings (0.0 when either is all zeros)
+pub fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let mut dot = 0.0f32;
+    let mut norm_a = 0.0f32;
+    let mut norm_b = 0.0f32;
+    for (x, y) in a.iter().zip(b.iter()) {
+        dot += x * y;
+        norm_a += x * x;
+        norm_b += y * y;
+    }
+    if norm_a <= f32::EPSILON || norm_b <= f32::EPSILON {
+        return 0.0;
+    }
+    dot / (norm_a.sqrt() * norm_b.sqrt())
+}
+
+/// Scales an embedding to unit length in place
+pub fn l2_normalize(v: &mut [f32]) {
+    let norm = v.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm > f32::EPSILON {
+        for x in v.iter_mut() {
+            *x /= norm;
+        }
+    }
+}
+
+/// Mean of the given embeddings, L2-normalized
+pub fn centroid<'a>(embeddings: impl Iterator<Item = &'a [f32]>) -> Vec<f32> {
+    let mut sum: Vec<f32> = Vec::new();
+    for embedding in embeddings {
+        if sum.is_empty() {
+            sum = vec![0.0; embedding.len()];
+        }
+        for (s, x) in sum.iter_mut().zip(embedding.iter()) {
+            *s += x;
+        }
+    }
+    l2_normalize(&mut sum);
+    sum
+}
+
+/// Weighted mean of two embeddings, L2-normalized.
+/// Used to fold a new observation into a stored centroid.
+pub fn blend_embeddings(a: &[f32], weight_a: f32, b: &[f32], weight_b: f32) -> Vec<f32> {
+    if a.is_empty() {
+        return b.to_vec();
+    }
+    if b.is_empty() || a.len() != b.len() {
+        return a.to_vec();
+    }
+    let total = (weight_a + weight_b).max(f32::EPSILON);
+    let mut blended: Vec<f32> = a
+        .iter()
+        .zip(b.iter())
+        .map(|(x, y)| (x * weight_a + y * weight_b) / total)
+        .collect();
+    l2_normalize(&mut blended);
+    blended
+}
+
+/// Matches each query embedding to at most one candidate (and vice versa),
+/// greedily taking the most similar pairs first. Pairs below `threshold` stay
+/// unmatched. Returns the matched candidate index per query.
+pub fn match_embeddings(
+    queries: &[Vec<f32>],
+    candidates: &[Vec<f32>],
+    threshold: f32,
+) -> Vec<Option<usize>> {
+    let mut pairs = Vec::new();
+    for (q, query) in queries.iter().enumerate() {
+        for (c, candidate) in candidates.iter().enumerate() {
+            if query.len() != candidate.len() {
+                continue;
+            }
+            let sim = cosine_similarity(query, candidate);
+            if sim >= threshold {
+                pairs.push((sim, q, c));
+            }
+        }
+    }
+    pairs.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+
+    let mut matches = vec![None; queries.len()];
+    let mut taken = vec![false; candidates.len()];
+    for (_, q, c) in pairs {
+        if matches[q].is_none() && !taken[c] {
+            matches[q] = Some(c);
+            taken[c] = true;
+        }
+    }
+    matches
+}
+
+/// Average-linkage agglomerative clustering on cosine similarity.
+///
+/// Clusters keep merging while the most similar pair is at least
+/// `similarity_threshold`, and keep merging past the threshold while there are
+/// more than `max_clusters` clusters. Returns one cluster index per embedding,
+/// numbered in order of first appearance.
+pub fn agglomerative_cluster(
+    embeddings: &[Vec<f32>],
+    similarity_threshold: f32,
+    max_clusters: Option<usize>,
+) -> Vec<usize> {
+    let n = embeddings.len();
+    if n == 0 {
+        return Vec::new();
+    }
+
+    let mut sim = vec![vec![0.0f32; n]; n];
+    for i in 0..n {
+        for j in (i + 1)..n {
+            let s = cosine_similarity(&embeddings[i], &embeddings[j]);
+            sim[i][j] = s;
+            sim[j][i] = s;
+        }
+    }
+
+    let mut active = vec![true; n];
+    let mut size = vec![1usize; n];
+    let mut owner: Vec<usize> = (0..n).collect();
+    let mut active_count = n;
+
+    // Cached nearest neighbour per cluster to avoid a full O(n^2) scan per merge
+    let mut nn = vec![usize::MAX; n];
+    let mut nn_sim = vec![f32::NEG_INFINITY; n];
+    let find_nn = |i: usize, sim: &Vec<Vec<f32>>, active: &Vec<bool>| -> (usize, f32) {
+        let mut best = (usize::MAX, f32::NEG_INFINITY);
+        for k in 0..active.len() {
+            if k != i && active[k] && sim[i][k] > best.1 {
+                best = (k, sim[i][k]);
+            }
+        }
+        best
+    };
+    for i in 0..n {
+        let (k, s) = find_nn(i, &sim, &active);
+        nn[i] = k;
+        nn_sim[i] = s;
+    }
+
+    let max_allowed = max_clusters.unwrap_or(usize::MAX).max(1);
+    while active_count > 1 {
+        let mut best_i = usize::MAX;
+        let mut best_sim = f32::NEG_INFINITY;
+        for i in 0..n {
+            if active[i] && nn[i] != usize::MAX && nn_sim[i] > best_sim {
+                best_i = i;
+                best_sim = nn_sim[i];
+            }
+        }
+        if best_i == usize::MAX {
+            break;
+        }
+        if best_sim < similarity_threshold && active_count <= max_allowed {
+            break;
+        }
+
+        // Merge j into i
+        let i = best_i;
+        let j = nn[i];
+        let (size_i, size_j) = (size[i] as f32, size[j] as f32);
+        for k in 0..n {
+            if active[k] && k != i && k != j {
+                let merged = (size_i * sim[i][k] + size_j * sim[j][k]) / (size_i + size_j);
+                sim[i][k] = merged;
+                sim[k][i] = merged;
+            }
+        }
+        size[i] += size[j];
+        active[j] = false;
+        active_count -= 1;
+        for o in owner.iter_mut() {
+            if *o == j {
+                *o = i;
+            }
+        }
+
+        for k in 0..n {
+            if !active[k] {
+                continue;
+            }
+            if k == i || nn[k] == i || nn[k] == j {
+                let (m, s) = find_nn(k, &sim, &active);
+                nn[k] = m;
+                nn_sim[k] = s;
+            } else if sim[k][i] > nn_sim[k] {
+                nn[k] = i;
+                nn_sim[k] = sim[k][i];
+            }
+        }
+    }
+
+    // Renumber clusters by first appearance
+    let mut mapping = std::collections::HashMap::new();
+    owner
+        .into_iter()
+        .map(|o| {
+            let next = mapping.len();
+            *mapping.entry(o).or_insert(next)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cosine_similarity() {
+        assert!((cosine_similarity(&[1.0, 0.0], &[2.0, 0.0]) - 1.0).abs() < 1e-6);
+        assert!(cosine_similarity(&[1.0, 0.0], &[0.0, 1.0]).abs() < 1e-6);
+        assert_eq!(cosine_similarity(&[0.0, 0.0], &[1.0, 0.0]), 0.0);
+    }
+
+    #[test]
+    fn test_agglomerative_cluster_separates_speakers() {
+        let embeddings = vec![
+            vec![1.0, 0.05, 0.0],
+            vec![0.0, 1.0, 0.1],
+            vec![0.95, 0.1, 0.0],
+            vec![0.05, 0.9, 0.0],
+            vec![1.0, 0.0, 0.05],
+        ];
+        let labels = agglomerative_cluster(&embeddings, 0.7, None);
+        assert_eq!(labels, vec![0, 1, 0, 1, 0]);
+    }
+
+    #[test]
+    fn test_agglomerative_cluster_respects_max_clusters() {
+        let embeddings = vec![
+            vec![1.0, 0.0, 0.0],
+            vec![0.0, 1.0, 0.0],
+            vec![0.0, 0.0, 1.0],
+        ];
+        assert_eq!(agglomerative_cluster(&embeddings, 0.9, None), vec![0, 1, 2]);
+        let labels = agglomerative_cluster(&embeddings, 0.9, Some(1));
+        assert_eq!(labels, vec![0, 0, 0]);
+    }
+
+    #[test]
+    fn test_match_embeddings_is_one_to_one() {
+        let profiles = vec![vec![1.0, 0.0], vec![0.0, 1.0]];
+        let speakers = vec![vec![0.9, 0.1], vec![0.95, 0.05], vec![-1.0, 0.0]];
+        // Both speakers resemble profile 0; only the closer one gets it
+        assert_eq!(
+            match_embeddings(&speakers, &profiles, 0.8),
+            vec![None, Some(0), None]
+        );
+    }
+
+    #[test]
+    fn test_blend_embeddings() {
+        let blended = blend_embeddings(&[1.0, 0.0], 3.0, &[0.0, 1.0], 1.0);
+        assert!(blended[0] > blended[1]);
+        assert!((blended.iter().map(|x| x * x).sum::<f32>() - 1.0).abs() < 1e-5);
+        assert_eq!(blend_embeddings(&[], 1.0, &[0.0, 1.0], 1.0), vec![0.0, 1.0]);
+    }
+
+    #[test]
+    fn test_agglomerative_cluster_empty() {
+        assert!(agglomerative_cluster(&[], 0.5, None).is_empty());
+    }
+}
@@ -0,0 +1,382 @@
+use crate::audio::decoder::decode_audio_file;
+use crate::audio::vad::{get_speech_chunks, SpeechSegment};
+use crate::database::repositories::meeting::MeetingsRepository;
+use crate::database::repositories::speaker::SpeakersRepository;
+use crate::database::repositories::speaker_profile::SpeakerProfilesRepository;
+use crate::state::AppState;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
+use sqlx::{Connection, SqlitePool};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tauri::{command, AppHandle, Emitter, Manager, Runtime};
+use tokio::io::AsyncWriteExt;
+
+use super::diarizer::{speaker_for_range, DiarizationResult, Diarizer};
+
+/// File name of the speaker embedding model inside the diarization models directory
+pub const DIARIZATION_MODEL_FILE: &str = "wespeaker-voxceleb-resnet34-LM.onnx";
+const DIARIZATION_MODEL_URL: &str =
+    "https://huggingface.co/Wespeaker/wespeaker-voxceleb-resnet34-LM/resolve/main/voxceleb_resnet34_LM.onnx";
+/// Approximate model size, used for progress when the server sends no content length
+const DIARIZATION_MODEL_SIZE_BYTES: u64 = 26_500_000;
+
+/// Same redemption time as import/retranscription so segments line up
+const VAD_REDEMPTION_TIME_MS: u32 = 2000;
+
+// Global diarization models directory (set during app initialization)
+static MODELS_DIR: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+static DOWNLOAD_IN_PROGRESS: AtomicBool = AtomicBool::new(false);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiarizationModelStatus {
+    pub downloaded: bool,
+    pub path: Option<String>,
+    pub size_mb: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiarizationProgress {
+    pub meeting_id: String,
+    pub progress_percentage: u32,
+    pub message: String,
+}
+
+/// Initialize the diarization models directory using app_data_dir
+/// This should be called during app setup
+pub fn set_models_directory<R: Runtime>(app: &AppHandle<R>) {
+    let app_data_dir = app
+        .path()
+        .app_data_dir()
+        .expect("Failed to get app data dir");
+
+    set_models_directory_from_data_dir(&app_data_dir);
+}
+
+/// Same as `set_models_directory` for callers without an AppHandle (the CLI)
+pub fn set_models_directory_from_data_dir(app_data_dir: &Path) {
+    let models_dir = app_data_dir.join("models").join("diarization");
+
+    if !models_dir.exists() {
+        if let Err(e) = std::fs::create_dir_all(&models_dir) {
+            log::error!("Failed to create diarization models directory: {}", e);
+            return;
+        }
+    }
+
+    log::info!(
+        "Diarization models directory set to: {}",
+        models_dir.display()
+    );
+
+    let mut guard = MODELS_DIR.lock().unwrap();
+    *guard = Some(models_dir);
+}
+
+fn get_models_directory() -> Option<PathBuf> {
+    MODELS_DIR.lock().unwrap().clone()
+}
+
+/// Path to the embedding model if it has been downloaded
+pub fn downloaded_model_path() -> Option<PathBuf> {
+    get_models_directory()
+        .map(|dir| dir.join(DIARIZATION_MODEL_FILE))
+        .filter(|path| path.exists())
+}
+
+/// Runs diarization over speech segments on a blocking thread.
+///
+/// Returns `Ok(None)` when the embedding model has not been downloaded, so batch
+/// pipelines can skip the stage without failing. `progress` receives 0-100 while
+/// embedding; returning false cancels and yields `Ok(None)`.
+pub async fn diarize_speech_segments<F>(
+    segments: Arc<Vec<SpeechSegment>>,
+    max_speakers: Option<usize>,
+    mut progress: F,
+) -> Result<Option<DiarizationResult>>
+where
+    F: FnMut(u32) -> bool + Send + 'static,
+{
+    let Some(model_path) = downloaded_model_path() else {
+        log::warn!("Speaker diarization skipped: embedding model not downloaded");
+        return Ok(None);
+    };
+
+    tokio::task::spawn_blocking(move || {
+        let mut cancelled = false;
+        let mut diarizer = Diarizer::new(&model_path)
+            .map_err(|e| anyhow!("Failed to load speaker embedding model: {}", e))?;
+        let result = diarizer
+            .diarize(&segments, max_speakers, |p| {
+                cancelled = !progress(p);
+                !cancelled
+            })
+            .map_err(|e| anyhow!("Speaker diarization failed: {}", e))?;
+        Ok(if cancelled { None } else { Some(result) })
+    })
+    .await
+    .map_err(|e| anyhow!("Diarization task panicked: {}", e))?
+}
+
+/// Relabels speakers that match a stored voice profile with the profile's name.
+/// Failures are logged and leave the generic labels in place.
+pub async fn recognize_known_speakers(pool: &SqlitePool, result: &mut DiarizationResult) {
+    match SpeakerProfilesRepository::recognize_speakers(pool, result).await {
+        Ok(0) => {}
+        Ok(count) => log::info!("Recognized {} speakers from voice profiles", count),
+        Err(e) => log::warn!("Failed to match speakers against voice profiles: {}", e),
+    }
+}
+
+/// Diarizes an already saved meeting from its recording and writes the speaker
+/// labels onto the existing transcript rows. Returns the number of speakers found.
+pub async fn diarize_meeting<R: Runtime>(
+    app: &AppHandle<R>,
+    meeting_id: &str,
+    max_speakers: Option<usize>,
+) -> Result<usize> {
+    let app_state = app
+        .try_state::<AppState>()
+        .ok_or_else(|| anyhow!("App state not available"))?;
+    let pool = app_state.db_manager.pool();
+
+    let meeting = MeetingsRepository::get_meeting_metadata(pool, meeting_id)
+        .await
+        .map_err(|e| anyhow!("Failed to load meeting: {}", e))?
+        .ok_or_else(|| anyhow!("Meeting not found: {}", meeting_id))?;
+    let folder = meeting
+        .folder_path
+        .ok_or_else(|| anyhow!("Meeting {} has no recording folder", meeting_id))?;
+    let audio_path = crate::audio::retranscription::find_audio_file(Path::new(&folder))?;
+
+    emit_progress(app, meeting_id, 5, "Decoding audio file...");
+    let speech_segments = tokio::task::spawn_blocking(move || -> Result<Vec<SpeechSegment>> {
+        let decoded = decode_audio_file(&audio_path)?;
+        let samples = decoded.to_whisper_format();
+        get_speech_chunks(&samples, VAD_REDEMPTION_TIME_MS)
+    })
+    .await
+    .map_err(|e| anyhow!("Decode task panicked: {}", e))??;
+
+    emit_progress(app, meeting_id, 20, "Identifying speakers...");
+    let app_for_progress = app.clone();
+    let meeting_for_progress = meeting_id.to_string();
+    let mut result = diarize_speech_segments(Arc::new(speech_segments), max_speakers, move |p| {
+        let overall = 20 + (p as f32 * 0.7) as u32;
+        emit_progress(
+            &app_for_progress,
+            &meeting_for_progress,
+            overall,
+            "Identifying speakers...",
+        );
+        true
+    })
+    .await?
+    .ok_or_else(|| anyhow!("Speaker diarization model is not downloaded"))?;
+    recognize_known_speakers(pool, &mut result).await;
+
+    emit_progress(app, meeting_id, 90, "Saving speaker labels...");
+    let rows: Vec<(String, Option<f64>, Option<f64>)> = sqlx::query_as(
+        "SELECT id, audio_start_time, audio_end_time FROM transcripts WHERE meeting_id = ?",
+    )
+    .bind(meeting_id)
+    .fetch_all(pool)
+    .await?;
+
+    let labels: Vec<(String, Option<String>)> = rows
+        .into_iter()
+        .map(|(id, start, end)| {
+            let label = match (start, end) {
+                (Some(start), Some(end)) => {
+                    speaker_for_range(&result.turns, start * 1000.0, end * 1000.0)
+                }
+                _ => None,
+            };
+            (id, label)
+        })
+        .collect();
+
+    let mut conn = pool.acquire().await?;
+    let mut tx = conn.begin().await?;
+    SpeakersRepository::update_transcript_speaker_labels(&mut *tx, meeting_id, &labels).await?;
+    SpeakersRepository::replace_meeting_speakers(&mut *tx, meeting_id, &result.speakers).await?;
+    tx.commit().await?;
+
+    emit_progress(app, meeting_id, 100, "Speaker identification complete");
+    Ok(result.speakers.len())
+}
+
+/// Spawns `diarize_meeting` in the background and reports the outcome via events
+pub fn spawn_meeting_diarization<R: Runtime>(
+    app: AppHandle<R>,
+    meeting_id: String,
+    max_speakers: Option<usize>,
+) {
+    tauri::async_runtime::spawn(async move {
+        match diarize_meeting(&app, &meeting_id, max_speakers).await {
+            Ok(speaker_count) => {
+                log::info!(
+                    "Diarization complete for meeting {}: {} speakers",
+                    meeting_id,
+                    speaker_count
+                );
+                let _ = app.emit(
+                    "diarization-complete",
+                    serde_json::json!({
+                        "meeting_id": meeting_id,
+                        "speaker_count": speaker_count
+                    }),
+                );
+            }
+            Err(e) => {
+                log::error!("Diarization failed for meeting {}: {}", meeting_id, e);
+                let _ = app.emit(
+                    "diarization-error",
+                    serde_json::json!({
+                        "meeting_id": meeting_id,
+                        "error": e.to_string()
+                    }),
+                );
+            }
+        }
+    });
+}
+
+fn emit_progress<R: Runtime>(app: &AppHandle<R>, meeting_id: &str, progress: u32, message: &str) {
+    let _ = app.emit(
+        "diarization-progress",
+        DiarizationProgress {
+            meeting_id: meeting_id.to_string(),
+            progress_percentage: progress,
+            message: message.to_string(),
+        },
+    );
+}
+
+#[command]
+pub async fn diarization_get_model_status() -> Result<DiarizationModelStatus, String> {
+    let path = downloaded_model_path();
+    let size_mb = match &path {
+        Some(p) => std::fs::metadata(p)
+            .map(|m| m.len() as f64 / 1_048_576.0)
+            .unwrap_or(0.0),
+        None => 0.0,
+    };
+    Ok(DiarizationModelStatus {
+        downloaded: path.is_some(),
+        path: path.map(|p| p.to_string_lossy().to_string()),
+        size_mb,
+    })
+}
+
+#[command]
+pub async fn diarization_download_model<R: Runtime>(
+    app_handle: AppHandle<R>,
+) -> Result<(), String> {
+    if DOWNLOAD_IN_PROGRESS.swap(true, Ordering::SeqCst) {
+        return Err("Diarization model download already in progress".to_string());
+    }
+
+    let result = download_model(&app_handle).await;
+    DOWNLOAD_IN_PROGRESS.store(false, Ordering::SeqCst);
+
+    match &result {
+        Ok(()) => {
+            let _ = app_handle.emit("diarization-model-download-complete", serde_json::json!({}));
+        }
+        Err(e) => {
+            log::error!("Diarization model download failed: {}", e);
+            let _ = app_handle.emit(
+                "diarization-model-download-error",
+                serde_json::json!({ "error": e }),
+            );
+        }
+    }
+    result
+}
+
+async fn download_model<R: Runtime>(app_handle: &AppHandle<R>) -> Result<(), String> {
+    let models_dir = get_models_directory()
+        .ok_or_else(|| "Diarization models directory not initialized".to_string())?;
+    let final_path = models_dir.join(DIARIZATION_MODEL_FILE);
+    if final_path.exists() {
+        return Ok(());
+    }
+    let part_path = models_dir.join(format!("{}.part", DIARIZATION_MODEL_FILE));
+
+    let client = reqwest::Client::builder()
+        .connect_timeout(Duration::from_secs(30))
+        .timeout(Duration::from_secs(1800))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {}", e))?;
+
+    let mut response = client
+        .get(DIARIZATION_MODEL_URL)
+        .send()
+        .await
+        .and_then(|r| r.error_for_status())
+        .map_err(|e| format!("Failed to start diarization model download: {}", e))?;
+
+    let total = response
+        .content_length()
+        .unwrap_or(DIARIZATION_MODEL_SIZE_BYTES);
+    let mut file = tokio::fs::File::create(&part_path)
+        .await
+        .map_err(|e| format!("Failed to create model file: {}", e))?;
+
+    let mut downloaded: u64 = 0;
+    let mut last_progress = 0u8;
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .map_err(|e| format!("Download interrupted: {}", e))?
+    {
+        file.write_all(&chunk)
+            .await
+            .map_err(|e| format!("Failed to write model file: {}", e))?;
+        downloaded += chunk.len() as u64;
+
+        let progress = ((downloaded * 100) / total.max(1)).min(100) as u8;
+        if progress != last_progress {
+            last_progress = progress;
+            let _ = app_handle.emit(
+                "diarization-model-download-progress",
+                serde_json::json!({
+                    "progress": progress,
+                    "downloaded_mb": downloaded as f64 / 1_048_576.0,
+                    "total_mb": total as f64 / 1_048_576.0
+                }),
+            );
+        }
+    }
+
+    file.flush()
+        .await
+        .map_err(|e| format!("Failed to flush model file: {}", e))?;
+    drop(file);
+
+    tokio::fs::rename(&part_path, &final_path)
+        .await
+        .map_err(|e| format!("Failed to finalize model file: {}", e))?;
+
+    log::info!("Diarization model downloaded to {}", final_path.display());
+    Ok(())
+}
+
+/// Re-run speaker diarization for a saved meeting (runs in the background;
+/// progress is reported via `diarization-progress` events)
+#[command]
+pub async fn diarize_meeting_command<R: Runtime>(
+    app: AppHandle<R>,
+    meeting_id: String,
+    max_speakers: Option<usize>,
+) -> Result<(), String> {
+    if downloaded_model_path().is_none() {
+        return Err("Speaker diarization model is not downloaded".to_string());
+    }
+    spawn_meeting_diarization(app, meeting_id, max_speakers);
+    Ok(())
+}
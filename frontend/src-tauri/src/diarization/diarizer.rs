@@ -0,0 +1,338 @@
+use crate::api::TranscriptSegment;
+use crate::audio::vad::SpeechSegment;
+use std::path::Path;
+
+use super::clustering::{agglomerative_cluster, centroid};
+use super::embedding::{EmbeddingError, SpeakerEmbeddingModel, SAMPLE_RATE};
+
+/// Length of the audio window used for one speaker embedding
+const WINDOW_MS: f64 = 3000.0;
+/// Hop between embedding windows inside one speech segment
+const MIN_HOP_MS: f64 = 1500.0;
+/// Segments shorter than this carry too little voice information to embed
+const MIN_SEGMENT_MS: f64 = 400.0;
+/// Upper bound on embedding windows so clustering stays fast for long meetings
+const MAX_WINDOWS: usize = 2000;
+/// Cosine similarity above which two clusters are considered the same speaker
+pub const DEFAULT_SIMILARITY_THRESHOLD: f32 = 0.5;
+/// Transcript rows with no overlapping turn take the nearest turn within this distance
+const NEAREST_TURN_TOLERANCE_MS: f64 = 2000.0;
+
+/// A stretch of audio attributed to one speaker
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpeakerTurn {
+    pub start_ms: f64,
+    pub end_ms: f64,
+    pub speaker: String,
+}
+
+/// A speaker found in the recording, with the centroid of their voice embeddings
+#[derive(Debug, Clone)]
+pub struct DiarizedSpeaker {
+    pub label: String,
+    pub embedding: Vec<f32>,
+    pub segment_count: usize,
+    pub total_duration: f64,
+    /// Voice profile this speaker was recognized as, if any
+    pub profile_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct DiarizationResult {
+    pub turns: Vec<SpeakerTurn>,
+    pub speakers: Vec<DiarizedSpeaker>,
+}
+
+impl DiarizationResult {
+    /// Relabels a speaker (and all of their turns), e.g. after matching a voice profile
+    pub fn rename_speaker(&mut self, label: &str, new_label: &str, profile_id: Option<String>) {
+        for speaker in self.speakers.iter_mut().filter(|s| s.label == label) {
+            speaker.label = new_label.to_string();
+            speaker.profile_id = profile_id.clone();
+        }
+        for turn in self.turns.iter_mut().filter(|t| t.speaker == label) {
+            turn.speaker = new_label.to_string();
+        }
+    }
+}
+
+/// Display label for the n-th (zero-based) speaker
+pub fn speaker_label(index: usize) -> String {
+    format!("Speaker {}", index + 1)
+}
+
+/// True for automatic labels like 'Speaker 3' (as opposed to names given by the user)
+pub fn is_generic_speaker_label(label: &str) -> bool {
+    label
+        .strip_prefix("Speaker ")
+        .is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+}
+
+/// Splits speech segments into (segment_index, start_sample, end_sample) embedding windows
+pub(crate) fn embedding_windows(segments: &[SpeechSegment]) -> Vec<(usize, usize, usize)> {
+    let window = (WINDOW_MS / 1000.0 * SAMPLE_RATE as f64) as usize;
+    let min_len = (MIN_SEGMENT_MS / 1000.0 * SAMPLE_RATE as f64) as usize;
+
+    // Widen the hop for very long recordings to bound the number of windows
+    let total_samples: usize = segments.iter().map(|s| s.samples.len()).sum();
+    let min_hop = (MIN_HOP_MS / 1000.0 * SAMPLE_RATE as f64) as usize;
+    let hop = min_hop.max(total_samples / MAX_WINDOWS).min(window);
+
+    let mut windows = Vec::new();
+    for (index, segment) in segments.iter().enumerate() {
+        let len = segment.samples.len();
+        if len < min_len {
+            continue;
+        }
+        if len <= window {
+            windows.push((index, 0, len));
+            continue;
+        }
+        let mut start = 0;
+        while start + window <= len {
+            windows.push((index, start, start + window));
+            start += hop;
+        }
+        // Cover the tail if it is long enough to be worth embedding on its own
+        if len - (start - hop + window) >= min_len {
+            windows.push((index, len - window, len));
+        }
+    }
+    windows
+}
+
+/// Offline speaker diarization over VAD speech segments
+pub struct Diarizer {
+    model: SpeakerEmbeddingModel,
+    similarity_threshold: f32,
+}
+
+impl Diarizer {
+    pub fn new<P: AsRef<Path>>(model_path: P) -> Result<Self, EmbeddingError> {
+        Ok(Self {
+            model: SpeakerEmbeddingModel::new(model_path)?,
+            similarity_threshold: DEFAULT_SIMILARITY_THRESHOLD,
+        })
+    }
+
+    pub fn with_similarity_threshold(mut self, threshold: f32) -> Self {
+        self.similarity_threshold = threshold;
+        self
+    }
+
+    /// Clusters the speech segments into speakers.
+    ///
+    /// `progress` receives a 0-100 percentage and returns false to cancel, in which
+    /// case an empty result is returned. `max_speakers` forces merging down to at most
+    /// that many speakers when the number of participants is known.
+    pub fn diarize<F>(
+        &mut self,
+        segments: &[SpeechSegment],
+        max_speakers: Option<usize>,
+        mut progress: F,
+    ) -> Result<DiarizationResult, EmbeddingError>
+    where
+        F: FnMut(u32) -> bool,
+    {
+        let windows = embedding_windows(segments);
+        log::info!(
+            "Diarizing {} speech segments using {} embedding windows",
+            segments.len(),
+            windows.len()
+        );
+
+        let mut embeddings = Vec::with_capacity(windows.len());
+        let mut spans = Vec::with_capacity(windows.len());
+        for (i, &(index, start, end)) in windows.iter().enumerate() {
+            if i % 20 == 0 && !progress((i * 90 / windows.len().max(1)) as u32) {
+                return Ok(DiarizationResult::default());
+            }
+
+            let segment = &segments[index];
+            match self.model.embed(&segment.samples[start..end]) {
+                Ok(embedding) => {
+                    let ms_per_sample = 1000.0 / SAMPLE_RATE as f64;
+                    embeddings.push(embedding);
+                    spans.push((
+                        segment.start_timestamp_ms + start as f64 * ms_per_sample,
+                        segment.start_timestamp_ms + end as f64 * ms_per_sample,
+                        index,
+                    ));
+                }
+                Err(EmbeddingError::TooShort(len)) => {
+                    log::debug!("Skipping {}-sample window for diarization", len);
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        let labels = agglomerative_cluster(&embeddings, self.similarity_threshold, max_speakers);
+        progress(100);
+
+        let speaker_count = labels.iter().copied().max().map_or(0, |m| m + 1);
+        let turns = spans
+            .iter()
+            .zip(labels.iter())
+            .map(|(&(start_ms, end_ms, _), &label)| SpeakerTurn {
+                start_ms,
+                end_ms,
+                speaker: speaker_label(label),
+            })
+            .collect();
+
+        let speakers = (0..speaker_count)
+            .map(|cluster| {
+                let members: Vec<usize> = (0..labels.len())
+                    .filter(|&i| labels[i] == cluster)
+                    .collect();
+                let mut segment_indices: Vec<usize> = members.iter().map(|&i| spans[i].2).collect();
+                segment_indices.dedup();
+                DiarizedSpeaker {
+                    label: speaker_label(cluster),
+                    embedding: centroid(members.iter().map(|&i| embeddings[i].as_slice())),
+                    segment_count: segment_indices.len(),
+                    total_duration: segment_indices
+                        .iter()
+                        .map(|&s| {
+                            (segments[s].end_timestamp_ms - segments[s].start_timestamp_ms) / 1000.0
+                        })
+                        .sum(),
+                    profile_id: None,
+                }
+            })
+            .collect();
+
+        log::info!("Diarization found {} speakers", speaker_count);
+        Ok(DiarizationResult { turns, speakers })
+    }
+}
+
+/// Picks the speaker with the most overlap with `[start_ms, end_ms]`, falling back
+/// to the nearest turn when nothing overlaps (e.g. segments too short to embed).
+pub fn speaker_for_range(turns: &[SpeakerTurn], start_ms: f64, end_ms: f64) -> Option<String> {
+    let mut overlap_by_speaker: Vec<(&str, f64)> = Vec::new();
+    for turn in turns {
+        let overlap = end_ms.min(turn.end_ms) - start_ms.max(turn.start_ms);
+        if overlap <= 0.0 {
+            continue;
+        }
+        match overlap_by_speaker
+            .iter_mut()
+            .find(|(s, _)| *s == turn.speaker)
+        {
+            Some(entry) => entry.1 += overlap,
+            None => overlap_by_speaker.push((&turn.speaker, overlap)),
+        }
+    }
+
+    if let Some((speaker, _)) = overlap_by_speaker
+        .into_iter()
+        .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+    {
+        return Some(speaker.to_string());
+    }
+
+    turns
+        .iter()
+        .map(|turn| {
+            let distance = (turn.start_ms - end_ms).max(start_ms - turn.end_ms);
+            (turn, distance)
+        })
+        .filter(|(_, distance)| *distance <= NEAREST_TURN_TOLERANCE_MS)
+        .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal))
+        .map(|(turn, _)| turn.speaker.clone())
+}
+
+/// Fills `speaker_label` on transcript segments from the diarization turns
+pub fn apply_speaker_labels(segments: &mut [TranscriptSegment], turns: &[SpeakerTurn]) {
+    for segment in segments.iter_mut() {
+        if let (Some(start), Some(end)) = (segment.audio_start_time, segment.audio_end_time) {
+            segment.speaker_label = speaker_for_range(turns, start * 1000.0, end * 1000.0);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn turn(start_ms: f64, end_ms: f64, speaker: &str) -> SpeakerTurn {
+        SpeakerTurn {
+            start_ms,
+            end_ms,
+            speaker: speaker.to_string(),
+        }
+    }
+
+    fn segment(start_ms: f64, duration_ms: f64) -> SpeechSegment {
+        SpeechSegment {
+            samples: vec![0.0; (duration_ms / 1000.0 * SAMPLE_RATE as f64) as usize],
+            start_timestamp_ms: start_ms,
+            end_timestamp_ms: start_ms + duration_ms,
+            confidence: 1.0,
+        }
+    }
+
+    #[test]
+    fn test_speaker_for_range_prefers_largest_overlap() {
+        let turns = vec![
+            turn(0.0, 3000.0, "Speaker 1"),
+            turn(1500.0, 4500.0, "Speaker 1"),
+            turn(4000.0, 7000.0, "Speaker 2"),
+        ];
+        assert_eq!(
+            speaker_for_range(&turns, 0.0, 4200.0).as_deref(),
+            Some("Speaker 1")
+        );
+        assert_eq!(
+            speaker_for_range(&turns, 4600.0, 6500.0).as_deref(),
+            Some("Speaker 2")
+        );
+    }
+
+    #[test]
+    fn test_speaker_for_range_nearest_fallback() {
+        let turns = vec![
+            turn(0.0, 3000.0, "Speaker 1"),
+            turn(10000.0, 13000.0, "Speaker 2"),
+        ];
+        assert_eq!(
+            speaker_for_range(&turns, 3500.0, 3800.0).as_deref(),
+            Some("Speaker 1")
+        );
+        assert_eq!(
+            speaker_for_range(&turns, 9000.0, 9500.0).as_deref(),
+            Some("Speaker 2")
+        );
+        assert_eq!(speaker_for_range(&turns, 6000.0, 6500.0), None);
+        assert_eq!(speaker_for_range(&[], 0.0, 1000.0), None);
+    }
+
+    #[test]
+    fn test_is_generic_speaker_label() {
+        assert!(is_generic_speaker_label("Speaker 1"));
+        assert!(is_generic_speaker_label("Speaker 12"));
+        assert!(!is_generic_speaker_label("Speaker "));
+        assert!(!is_generic_speaker_label("Speaker Bob"));
+        assert!(!is_generic_speaker_label("Alice"));
+    }
+
+    #[test]
+    fn test_embedding_windows() {
+        let segments = vec![
+            segment(0.0, 200.0),
+            segment(1000.0, 2000.0),
+            segment(5000.0, 7000.0),
+        ];
+        let windows = embedding_windows(&segments);
+
+        // Too-short segment is skipped, short one is embedded whole
+        assert!(windows.iter().all(|w| w.0 != 0));
+        assert_eq!(windows.iter().filter(|w| w.0 == 1).count(), 1);
+
+        // Long segment: windows at 0s, 1.5s, 3s plus a tail window ending at 7s
+        let long: Vec<_> = windows.iter().filter(|w| w.0 == 2).collect();
+        assert_eq!(long.len(), 4);
+        assert_eq!(long.last().unwrap().2, 7 * SAMPLE_RATE);
+    }
+}
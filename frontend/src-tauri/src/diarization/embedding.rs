@@ -0,0 +1,225 @@
+use ndarray::Array3;
+use ort::execution_providers::CPUExecutionProvider;
+use ort::inputs;
+use ort::session::builder::GraphOptimizationLevel;
+use ort::session::Session;
+use ort::value::TensorRef;
+use realfft::RealFftPlanner;
+use std::path::Path;
+
+use super::clustering::l2_normalize;
+
+/// Sample rate expected by the embedding model (same as the VAD/Whisper pipeline)
+pub const SAMPLE_RATE: usize = 16000;
+
+const FRAME_LENGTH: usize = 400; // 25ms
+const FRAME_SHIFT: usize = 160; // 10ms
+const FFT_SIZE: usize = 512;
+const NUM_MEL_BINS: usize = 80;
+const LOW_FREQ: f32 = 20.0;
+const PREEMPHASIS: f32 = 0.97;
+
+#[derive(thiserror::Error, Debug)]
+pub enum EmbeddingError {
+    #[error("ORT error: {0}")]
+    Ort(#[from] ort::Error),
+    #[error("ndarray shape error: {0}")]
+    Shape(#[from] ndarray::ShapeError),
+    #[error("Model has no {0}")]
+    MissingIo(&'static str),
+    #[error("Audio too short for a speaker embedding ({0} samples)")]
+    TooShort(usize),
+}
+
+/// Kaldi-compatible log mel filterbank features, as used to train WeSpeaker models
+pub struct FbankExtractor {
+    window: Vec<f32>,
+    mel_banks: Vec<Vec<(usize, f32)>>,
+    planner: RealFftPlanner<f32>,
+}
+
+impl FbankExtractor {
+    pub fn new() -> Self {
+        // Povey window: a Hann window raised to 0.85
+        let window = (0..FRAME_LENGTH)
+            .map(|n| {
+                let hann = 0.5
+                    - 0.5
+                        * (2.0 * std::f32::consts::PI * n as f32 / (FRAME_LENGTH - 1) as f32).cos();
+                hann.powf(0.85)
+            })
+            .collect();
+
+        Self {
+            window,
+            mel_banks: Self::mel_banks(),
+            planner: RealFftPlanner::new(),
+        }
+    }
+
+    fn mel(freq: f32) -> f32 {
+        1127.0 * (1.0 + freq / 700.0).ln()
+    }
+
+    /// Triangular filters in the mel domain, stored as sparse (fft_bin, weight) pairs
+    fn mel_banks() -> Vec<Vec<(usize, f32)>> {
+        let nyquist = SAMPLE_RATE as f32 / 2.0;
+        let mel_low = Self::mel(LOW_FREQ);
+        let mel_high = Self::mel(nyquist);
+        let mel_delta = (mel_high - mel_low) / (NUM_MEL_BINS + 1) as f32;
+        let bin_width = SAMPLE_RATE as f32 / FFT_SIZE as f32;
+
+        (0..NUM_MEL_BINS)
+            .map(|m| {
+                let left = mel_low + m as f32 * mel_delta;
+                let center = left + mel_delta;
+                let right = center + mel_delta;
+                (0..FFT_SIZE / 2)
+                    .filter_map(|k| {
+                        let mel = Self::mel(bin_width * k as f32);
+                        if mel <= left || mel >= right {
+                            return None;
+                        }
+                        let weight = if mel <= center {
+                            (mel - left) / (center - left)
+                        } else {
+                            (right - mel) / (right - center)
+                        };
+                        Some((k, weight))
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Computes mean-normalized fbank features, one row of `NUM_MEL_BINS` per 10ms frame
+    pub fn compute(&mut self, samples: &[f32]) -> Vec<[f32; NUM_MEL_BINS]> {
+        if samples.len() < FRAME_LENGTH {
+            return Vec::new();
+        }
+        let num_frames = 1 + (samples.len() - FRAME_LENGTH) / FRAME_SHIFT;
+        let fft = self.planner.plan_fft_forward(FFT_SIZE);
+        let mut input = fft.make_input_vec();
+        let mut spectrum = fft.make_output_vec();
+
+        let mut frames = Vec::with_capacity(num_frames);
+        for f in 0..num_frames {
+            let start = f * FRAME_SHIFT;
+            // Kaldi operates on 16-bit sample magnitudes
+            let mut frame: Vec<f32> = samples[start..start + FRAME_LENGTH]
+                .iter()
+                .map(|s| s * 32768.0)
+                .collect();
+
+            let mean = frame.iter().sum::<f32>() / FRAME_LENGTH as f32;
+            for s in frame.iter_mut() {
+                *s -= mean;
+            }
+            for i in (1..FRAME_LENGTH).rev() {
+                frame[i] -= PREEMPHASIS * frame[i - 1];
+            }
+            frame[0] -= PREEMPHASIS * frame[0];
+
+            input.iter_mut().for_each(|x| *x = 0.0);
+            for (i, s) in frame.iter().enumerate() {
+                input[i] = s * self.window[i];
+            }
+            if fft.process(&mut input, &mut spectrum).is_err() {
+                continue;
+            }
+
+            let mut row = [0.0f32; NUM_MEL_BINS];
+            for (m, bank) in self.mel_banks.iter().enumerate() {
+                let energy: f32 = bank.iter().map(|&(k, w)| w * spectrum[k].norm_sqr()).sum();
+                row[m] = energy.max(f32::EPSILON).ln();
+            }
+            frames.push(row);
+        }
+
+        // Cepstral mean normalization over the whole window
+        if !frames.is_empty() {
+            let mut means = [0.0f32; NUM_MEL_BINS];
+            for row in &frames {
+                for (m, v) in row.iter().enumerate() {
+                    means[m] += v;
+                }
+            }
+            let count = frames.len() as f32;
+            for row in frames.iter_mut() {
+                for (m, v) in row.iter_mut().enumerate() {
+                    *v -= means[m] / count;
+                }
+            }
+        }
+
+        frames
+    }
+}
+
+impl Default for FbankExtractor {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// ONNX speaker embedding model (WeSpeaker ResNet, fbank in -> embedding out)
+pub struct SpeakerEmbeddingModel {
+    session: Session,
+    input_name: String,
+    output_name: String,
+    fbank: FbankExtractor,
+}
+
+impl SpeakerEmbeddingModel {
+    pub fn new<P: AsRef<Path>>(model_path: P) -> Result<Self, EmbeddingError> {
+        log::info!(
+            "Loading speaker embedding model from {}",
+            model_path.as_ref().display()
+        );
+        let session = Session::builder()?
+            .with_optimization_level(GraphOptimizationLevel::Level3)?
+            .with_execution_providers(vec![CPUExecutionProvider::default().build()])?
+            .commit_from_file(model_path.as_ref())?;
+
+        let input_name = session
+            .inputs
+            .first()
+            .map(|i| i.name.clone())
+            .ok_or(EmbeddingError::MissingIo("inputs"))?;
+        let output_name = session
+            .outputs
+            .first()
+            .map(|o| o.name.clone())
+            .ok_or(EmbeddingError::MissingIo("outputs"))?;
+
+        Ok(Self {
+            session,
+            input_name,
+            output_name,
+            fbank: FbankExtractor::new(),
+        })
+    }
+
+    /// Computes an L2-normalized speaker embedding for 16kHz mono audio
+    pub fn embed(&mut self, samples: &[f32]) -> Result<Vec<f32>, EmbeddingError> {
+        let frames = self.fbank.compute(samples);
+        if frames.is_empty() {
+            return Err(EmbeddingError::TooShort(samples.len()));
+        }
+
+        let flat: Vec<f32> = frames.iter().flat_map(|row| row.iter().copied()).collect();
+        let features = Array3::from_shape_vec((1, frames.len(), NUM_MEL_BINS), flat)?;
+
+        let outputs = self.session.run(inputs![
+            self.input_name.as_str() => TensorRef::from_array_view(features.view())?,
+        ])?;
+        let embedding = outputs
+            .get(self.output_name.as_str())
+            .ok_or(EmbeddingError::MissingIo("embedding output"))?
+            .try_extract_array::<f32>()?;
+
+        let mut embedding: Vec<f32> = embedding.iter().copied().collect();
+        l2_normalize(&mut embedding);
+        Ok(embedding)
+    }
+}
@@ -0,0 +1,25 @@
+//! Offline speaker diarization.
+//!
+//! Splits VAD speech segments into short windows, computes a voice embedding for
+//! each window with a local ONNX speaker model (WeSpeaker ResNet34), and clusters
+//! the embeddings into speakers. The resulting turns are mapped onto transcript
+//! rows as `speaker_label` ('Speaker 1', 'Speaker 2', ...).
+//!
+//! # Module Structure
+//!
+//! - `embedding`: Kaldi-style fbank features and the ONNX embedding model
+//! - `clustering`: Average-linkage clustering on cosine similarity
+//! - `diarizer`: Windowing, clustering and transcript label assignment
+//! - `commands`: Model download, pipeline helpers and Tauri commands
+
+pub mod clustering;
+pub mod commands;
+pub mod diarizer;
+pub mod embedding;
+
+pub use commands::*;
+pub use diarizer::{
+    apply_speaker_labels, is_generic_speaker_label, speaker_for_range, speaker_label,
+    DiarizationResult, DiarizedSpeaker, Diarizer, SpeakerTurn,
+};
+pub use embedding::{EmbeddingError, SpeakerEmbeddingModel};
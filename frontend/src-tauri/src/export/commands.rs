@@ -0,0 +1,247 @@
+use crate::audio::audio_processing::sanitize_filename;
+use crate::audio::recording_preferences::get_default_recordings_folder;
+use crate::audio::retranscription::find_audio_file;
+use crate::database::repositories::meeting::MeetingsRepository;
+use crate::database::repositories::summary::SummaryProcessesRepository;
+use crate::database::repositories::transcript::TranscriptsRepository;
+use crate::state::AppState;
+use anyhow::{anyhow, Context, Result};
+use log::{error as log_error, info as log_info, warn as log_warn};
+use sqlx::SqlitePool;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tauri::command;
+
+use super::document::{
+    ExportDocument, ExportFormat, ExportLine, ExportOptions, ExportResult, ExportWord,
+};
+use super::{docx, markdown, pdf, subtitles};
+
+type TranscriptRow = (
+    String,
+    String,
+    Option<f64>,
+    Option<f64>,
+    Option<String>,
+    Option<f64>,
+);
+
+/// Loads everything needed to export a meeting
+pub async fn load_export_document(pool: &SqlitePool, meeting_id: &str) -> Result<ExportDocument> {
+    let meeting = MeetingsRepository::get_meeting_metadata(pool, meeting_id)
+        .await?
+        .ok_or_else(|| anyhow!("Meeting not found: {}", meeting_id))?;
+
+    let summary_markdown = SummaryProcessesRepository::get_summary_data(pool, meeting_id)
+        .await?
+        .and_then(|process| process.result)
+        .and_then(|result| serde_json::from_str::<serde_json::Value>(&result).ok())
+        .and_then(|json| json.get("markdown")?.as_str().map(str::to_string));
+
+    let notes_markdown: Option<String> =
+        sqlx::query_scalar("SELECT notes_markdown FROM meeting_notes WHERE meeting_id = ?")
+            .bind(meeting_id)
+            .fetch_optional(pool)
+            .await?
+            .flatten();
+
+    let rows: Vec<TranscriptRow> = sqlx::query_as(
+        "SELECT t.id, t.transcript, t.audio_start_time, t.audio_end_time, t.speaker_label,
+                (SELECT AVG(w.confidence) FROM transcript_words w WHERE w.transcript_id = t.id)
+         FROM transcripts t
+         WHERE t.meeting_id = ?
+         ORDER BY COALESCE(t.audio_start_time, 0), t.timestamp",
+    )
+    .bind(meeting_id)
+    .fetch_all(pool)
+    .await?;
+
+    let mut words_by_transcript: HashMap<String, Vec<ExportWord>> = HashMap::new();
+    for word in TranscriptsRepository::get_transcript_words(pool, meeting_id, None).await? {
+        words_by_transcript
+            .entry(word.transcript_id)
+            .or_default()
+            .push(ExportWord {
+                text: word.word,
+                start: word.start_time,
+                end: word.end_time,
+            });
+    }
+
+    let lines = rows
+        .into_iter()
+        .map(|(id, text, start, end, speaker, confidence)| ExportLine {
+            words: words_by_transcript.remove(&id).unwrap_or_default(),
+            text,
+            start,
+            end,
+            speaker,
+            confidence,
+        })
+        .collect();
+
+    let folder = meeting
+        .folder_path
+        .map(PathBuf::from)
+        .filter(|p| p.is_dir());
+    let audio_path = folder
+        .as_deref()
+        .and_then(|folder| find_audio_file(folder).ok());
+
+    Ok(ExportDocument {
+        title: meeting.title,
+        created_at: meeting.created_at.0,
+        summary_markdown,
+        notes_markdown,
+        lines,
+        audio_path,
+        folder,
+    })
+}
+
+/// Renders the document and writes it to disk, returning the written path and
+/// warnings about content the format couldn't represent
+pub fn write_export(
+    doc: &ExportDocument,
+    format: ExportFormat,
+    options: &ExportOptions,
+    default_dir: &Path,
+) -> Result<(PathBuf, Vec<String>)> {
+    let file_name = format!(
+        "{}.{}",
+        sanitize_filename(doc.title.trim()),
+        format.extension()
+    );
+    let target = match &options.output_path {
+        Some(path) => PathBuf::from(path),
+        None if format == ExportFormat::Markdown && options.bundle_audio => {
+            default_dir.join(format!("{} export", sanitize_filename(doc.title.trim())))
+        }
+        None => default_dir.join(&file_name),
+    };
+
+    if format == ExportFormat::Markdown && options.bundle_audio {
+        return Ok((
+            write_markdown_bundle(doc, options, &target, &file_name)?,
+            Vec::new(),
+        ));
+    }
+
+    let mut warnings = Vec::new();
+    let bytes = match format {
+        ExportFormat::Srt => subtitles::render_srt(doc, options).into_bytes(),
+        ExportFormat::Vtt => subtitles::render_vtt(doc, options).into_bytes(),
+        ExportFormat::Docx => docx::render_docx(doc, options)?,
+        ExportFormat::Pdf => {
+            let pdf = pdf::render_pdf(doc, options);
+            if !pdf.missing_chars.is_empty() {
+                warnings.push(format!(
+                    "No installed font covers {}; they appear as '?' in the PDF. \
+                     Export as DOCX or Markdown to keep them.",
+                    pdf.missing_chars.iter().collect::<String>()
+                ));
+            }
+            pdf.bytes
+        }
+        ExportFormat::Markdown => {
+            let link = doc
+                .audio_path
+                .as_ref()
+                .map(|p| format!("file://{}", p.to_string_lossy().replace(' ', "%20")));
+            markdown::render_markdown(doc, options, link.as_deref()).into_bytes()
+        }
+    };
+
+    if let Some(parent) = target.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    std::fs::write(&target, bytes)
+        .with_context(|| format!("Failed to write {}", target.display()))?;
+    Ok((target, warnings))
+}
+
+/// Writes `<folder>/<title>.md` next to a copy of the recording, linked relatively
+fn write_markdown_bundle(
+    doc: &ExportDocument,
+    options: &ExportOptions,
+    folder: &Path,
+    file_name: &str,
+) -> Result<PathBuf> {
+    std::fs::create_dir_all(folder)
+        .with_context(|| format!("Failed to create {}", folder.display()))?;
+
+    let mut link = None;
+    if let Some(audio) = &doc.audio_path {
+        if let Some(name) = audio.file_name() {
+            std::fs::copy(audio, folder.join(name))
+                .with_context(|| format!("Failed to copy {}", audio.display()))?;
+            link = Some(name.to_string_lossy().replace(' ', "%20"));
+        }
+    }
+
+    let target = folder.join(file_name);
+    std::fs::write(
+        &target,
+        markdown::render_markdown(doc, options, link.as_deref()),
+    )
+    .with_context(|| format!("Failed to write {}", target.display()))?;
+    Ok(folder.to_path_buf())
+}
+
+/// Exports a meeting as subtitles (SRT/WebVTT), a document (DOCX/PDF) or Markdown.
+/// Without `options.output_path` the file lands in the meeting's folder.
+#[command]
+pub async fn export_meeting(
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    format: ExportFormat,
+    options: Option<ExportOptions>,
+) -> Result<ExportResult, String> {
+    log_info!("export_meeting called for {} as {:?}", meeting_id, format);
+    let options = options.unwrap_or_default();
+    let pool = state.db_manager.pool();
+
+    let doc = load_export_document(pool, &meeting_id).await.map_err(|e| {
+        log_error!("Failed to load meeting {} for export: {}", meeting_id, e);
+        e.to_string()
+    })?;
+
+    let default_dir = doc
+        .folder
+        .clone()
+        .unwrap_or_else(get_default_recordings_folder);
+
+    let (path, warnings) =
+        tokio::task::spawn_blocking(move || write_export(&doc, format, &options, &default_dir))
+            .await
+            .map_err(|e| format!("Export task failed: {}", e))?
+            .map_err(|e| {
+                log_error!("Failed to export meeting {}: {}", meeting_id, e);
+                e.to_string()
+            })?;
+
+    let bytes = if path.is_dir() {
+        std::fs::read_dir(&path)
+            .map(|entries| {
+                entries
+                    .filter_map(|e| e.ok()?.metadata().ok())
+                    .map(|m| m.len())
+                    .sum()
+            })
+            .unwrap_or(0)
+    } else {
+        std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0)
+    };
+
+    for warning in &warnings {
+        log_warn!("Export of meeting {}: {}", meeting_id, warning);
+    }
+    log_info!("Exported meeting {} to {}", meeting_id, path.display());
+    Ok(ExportResult {
+        path: path.to_string_lossy().to_string(),
+        format,
+        bytes,
+        warnings,
+    })
+}
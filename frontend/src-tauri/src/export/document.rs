@@ -0,0 +1,301 @@
+use crate::api::TranscriptSegment;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Output format of `export_meeting`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Srt,
+    Vtt,
+    Docx,
+    Pdf,
+    Markdown,
+}
+
+impl ExportFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Srt => "srt",
+            Self::Vtt => "vtt",
+            Self::Docx => "docx",
+            Self::Pdf => "pdf",
+            Self::Markdown => "md",
+        }
+    }
+}
+
+/// What to include in an export. Missing fields take their defaults.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExportOptions {
+    /// Prefix lines with the diarized speaker name
+    pub include_speakers: bool,
+    /// Prefix transcript lines with their recording time (documents only)
+    pub include_timestamps: bool,
+    /// Append the average word confidence to transcript lines (documents only)
+    pub include_confidence: bool,
+    pub include_summary: bool,
+    pub include_notes: bool,
+    /// Markdown only: write a folder with the document and a copy of the recording
+    pub bundle_audio: bool,
+    /// Destination file (or folder for a Markdown bundle). Defaults to the meeting
+    /// folder, or the recordings folder when the meeting has none.
+    pub output_path: Option<String>,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            include_speakers: true,
+            include_timestamps: true,
+            include_confidence: false,
+            include_summary: true,
+            include_notes: true,
+            bundle_audio: false,
+            output_path: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportResult {
+    pub path: String,
+    pub format: ExportFormat,
+    pub bytes: u64,
+    /// Content the format couldn't represent, e.g. characters without a PDF font
+    pub warnings: Vec<String>,
+}
+
+/// A timed word, in seconds from recording start
+#[derive(Debug, Clone)]
+pub struct ExportWord {
+    pub text: String,
+    pub start: f64,
+    pub end: f64,
+}
+
+/// One transcript row as exported
+#[derive(Debug, Clone)]
+pub struct ExportLine {
+    pub text: String,
+    pub start: Option<f64>,
+    pub end: Option<f64>,
+    pub speaker: Option<String>,
+    pub confidence: Option<f64>,
+    pub words: Vec<ExportWord>,
+}
+
+impl From<&TranscriptSegment> for ExportLine {
+    fn from(segment: &TranscriptSegment) -> Self {
+        Self {
+            text: segment.text.clone(),
+            start: segment.audio_start_time,
+            end: segment.audio_end_time,
+            speaker: segment.speaker_label.clone(),
+            confidence: None,
+            words: segment
+                .words
+                .iter()
+                .map(|w| ExportWord {
+                    text: w.text.clone(),
+                    start: w.start,
+                    end: w.end,
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Everything an exporter needs about a meeting, loaded once from the database
+#[derive(Debug, Clone)]
+pub struct ExportDocument {
+    pub title: String,
+    pub created_at: DateTime<Utc>,
+    pub summary_markdown: Option<String>,
+    pub notes_markdown: Option<String>,
+    pub lines: Vec<ExportLine>,
+    pub audio_path: Option<PathBuf>,
+    /// The meeting's recording folder, where exports go by default
+    pub folder: Option<PathBuf>,
+}
+
+impl ExportDocument {
+    /// Summary to render, if requested and non-empty
+    pub fn summary(&self, options: &ExportOptions) -> Option<&str> {
+        self.summary_markdown
+            .as_deref()
+            .filter(|s| options.include_summary && !s.trim().is_empty())
+    }
+
+    /// Notes to render, if requested and non-empty
+    pub fn notes(&self, options: &ExportOptions) -> Option<&str> {
+        self.notes_markdown
+            .as_deref()
+            .filter(|s| options.include_notes && !s.trim().is_empty())
+    }
+}
+
+/// Parts of a transcript line in documents, split so formats can style them
+pub struct LineParts {
+    pub timestamp: Option<String>,
+    pub speaker: Option<String>,
+    pub text: String,
+    pub confidence: Option<String>,
+}
+
+impl LineParts {
+    pub fn new(line: &ExportLine, options: &ExportOptions) -> Self {
+        Self {
+            timestamp: line
+                .start
+                .filter(|_| options.include_timestamps)
+                .map(|s| format!("[{}]", format_clock(s))),
+            speaker: line.speaker.clone().filter(|_| options.include_speakers),
+            text: line.text.trim().to_string(),
+            confidence: line
+                .confidence
+                .filter(|_| options.include_confidence)
+                .map(|c| format!("({:.0}%)", c * 100.0)),
+        }
+    }
+
+    /// Plain-text rendering: "[01:05] Alice: text (92%)"
+    pub fn to_plain(&self) -> String {
+        let mut out = String::new();
+        if let Some(ts) = &self.timestamp {
+            out.push_str(ts);
+            out.push(' ');
+        }
+        if let Some(speaker) = &self.speaker {
+            out.push_str(speaker);
+            out.push_str(": ");
+        }
+        out.push_str(&self.text);
+        if let Some(conf) = &self.confidence {
+            out.push(' ');
+            out.push_str(conf);
+        }
+        out
+    }
+}
+
+/// Formats seconds as MM:SS, or H:MM:SS from one hour on
+pub fn format_clock(seconds: f64) -> String {
+    let total = seconds.max(0.0) as u64;
+    let (h, m, s) = (total / 3600, (total % 3600) / 60, total % 60);
+    if h > 0 {
+        format!("{}:{:02}:{:02}", h, m, s)
+    } else {
+        format!("{:02}:{:02}", m, s)
+    }
+}
+
+/// Block-level structure of a Markdown summary or notes, for formats that
+/// render their own layout (DOCX, PDF)
+#[derive(Debug, Clone, PartialEq)]
+pub enum Block {
+    Heading(usize, String),
+    Bullet(String),
+    Paragraph(String),
+}
+
+/// Splits Markdown into headings, bullets and paragraphs, dropping inline
+/// emphasis markers. Tables and code blocks come through as plain paragraphs.
+pub fn markdown_blocks(markdown: &str) -> Vec<Block> {
+    let mut blocks = Vec::new();
+    let mut paragraph: Vec<String> = Vec::new();
+
+    fn flush(paragraph: &mut Vec<String>, blocks: &mut Vec<Block>) {
+        if !paragraph.is_empty() {
+            blocks.push(Block::Paragraph(paragraph.join(" ")));
+            paragraph.clear();
+        }
+    }
+
+    for line in markdown.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with("```") || trimmed == "---" {
+            flush(&mut paragraph, &mut blocks);
+            continue;
+        }
+
+        let hashes = trimmed.chars().take_while(|&c| c == '#').count();
+        if (1..=6).contains(&hashes) && trimmed[hashes..].starts_with(' ') {
+            flush(&mut paragraph, &mut blocks);
+            blocks.push(Block::Heading(hashes, strip_inline(&trimmed[hashes..])));
+        } else if let Some(item) = trimmed
+            .strip_prefix("- ")
+            .or_else(|| trimmed.strip_prefix("* "))
+            .or_else(|| trimmed.strip_prefix("+ "))
+        {
+            flush(&mut paragraph, &mut blocks);
+            blocks.push(Block::Bullet(strip_inline(item)));
+        } else {
+            paragraph.push(strip_inline(trimmed));
+        }
+    }
+    flush(&mut paragraph, &mut blocks);
+    blocks
+}
+
+fn strip_inline(text: &str) -> String {
+    text.replace("**", "")
+        .replace("__", "")
+        .replace('`', "")
+        .trim()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_clock() {
+        assert_eq!(format_clock(65.4), "01:05");
+        assert_eq!(format_clock(3725.0), "1:02:05");
+        assert_eq!(format_clock(-1.0), "00:00");
+    }
+
+    #[test]
+    fn test_markdown_blocks() {
+        let md = "# Title\n\nSome **bold**\ntext here\n\n- item one\n* [ ] todo\n\n## Next";
+        assert_eq!(
+            markdown_blocks(md),
+            vec![
+                Block::Heading(1, "Title".to_string()),
+                Block::Paragraph("Some bold text here".to_string()),
+                Block::Bullet("item one".to_string()),
+                Block::Bullet("[ ] todo".to_string()),
+                Block::Heading(2, "Next".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_line_parts_respect_options() {
+        let line = ExportLine {
+            text: " Hello ".to_string(),
+            start: Some(65.0),
+            end: Some(67.0),
+            speaker: Some("Alice".to_string()),
+            confidence: Some(0.923),
+            words: Vec::new(),
+        };
+        let mut options = ExportOptions {
+            include_confidence: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            LineParts::new(&line, &options).to_plain(),
+            "[01:05] Alice: Hello (92%)"
+        );
+
+        options.include_speakers = false;
+        options.include_timestamps = false;
+        options.include_confidence = false;
+        assert_eq!(LineParts::new(&line, &options).to_plain(), "Hello");
+    }
+}
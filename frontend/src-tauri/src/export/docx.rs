@@ -0,0 +1,213 @@
+// DOCX export
+//
+// Writes a minimal WordprocessingML package by hand: one document part plus a
+// styles part defining Title/Heading1/Heading2 so Word's navigation pane works.
+
+use std::io::{Cursor, Write};
+
+use anyhow::Result;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+use super::document::{markdown_blocks, Block, ExportDocument, ExportOptions, LineParts};
+
+const CONTENT_TYPES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Types xmlns="http://schemas.openxmlformats.org/package/2006/content-types">
+<Default Extension="rels" ContentType="application/vnd.openxmlformats-package.relationships+xml"/>
+<Default Extension="xml" ContentType="application/xml"/>
+<Override PartName="/word/document.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.document.main+xml"/>
+<Override PartName="/word/styles.xml" ContentType="application/vnd.openxmlformats-officedocument.wordprocessingml.styles+xml"/>
+</Types>"#;
+
+const ROOT_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+<Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/officeDocument" Target="word/document.xml"/>
+</Relationships>"#;
+
+const DOCUMENT_RELS: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<Relationships xmlns="http://schemas.openxmlformats.org/package/2006/relationships">
+<Relationship Id="rId1" Type="http://schemas.openxmlformats.org/officeDocument/2006/relationships/styles" Target="styles.xml"/>
+</Relationships>"#;
+
+const STYLES: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:styles xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main">
+<w:docDefaults><w:rPrDefault><w:rPr><w:rFonts w:ascii="Calibri" w:hAnsi="Calibri" w:cs="Calibri"/><w:sz w:val="22"/></w:rPr></w:rPrDefault>
+<w:pPrDefault><w:pPr><w:spacing w:after="120"/></w:pPr></w:pPrDefault></w:docDefaults>
+<w:style w:type="paragraph" w:default="1" w:styleId="Normal"><w:name w:val="Normal"/></w:style>
+<w:style w:type="paragraph" w:styleId="Title"><w:name w:val="Title"/><w:basedOn w:val="Normal"/><w:pPr><w:spacing w:after="60"/></w:pPr><w:rPr><w:b/><w:sz w:val="40"/></w:rPr></w:style>
+<w:style w:type="paragraph" w:styleId="Subtitle"><w:name w:val="Subtitle"/><w:basedOn w:val="Normal"/><w:rPr><w:i/><w:color w:val="666666"/></w:rPr></w:style>
+<w:style w:type="paragraph" w:styleId="Heading1"><w:name w:val="heading 1"/><w:basedOn w:val="Normal"/><w:pPr><w:keepNext/><w:spacing w:before="360"/><w:outlineLvl w:val="0"/></w:pPr><w:rPr><w:b/><w:sz w:val="32"/></w:rPr></w:style>
+<w:style w:type="paragraph" w:styleId="Heading2"><w:name w:val="heading 2"/><w:basedOn w:val="Normal"/><w:pPr><w:keepNext/><w:spacing w:before="240"/><w:outlineLvl w:val="1"/></w:pPr><w:rPr><w:b/><w:sz w:val="26"/></w:rPr></w:style>
+<w:style w:type="paragraph" w:styleId="ListBullet"><w:name w:val="List Bullet"/><w:basedOn w:val="Normal"/><w:pPr><w:ind w:left="360" w:hanging="360"/></w:pPr></w:style>
+</w:styles>"#;
+
+pub fn render_docx(doc: &ExportDocument, options: &ExportOptions) -> Result<Vec<u8>> {
+    let mut zip = ZipWriter::new(Cursor::new(Vec::new()));
+    let file_options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+
+    for (name, content) in [
+        ("[Content_Types].xml", CONTENT_TYPES.to_string()),
+        ("_rels/.rels", ROOT_RELS.to_string()),
+        ("word/_rels/document.xml.rels", DOCUMENT_RELS.to_string()),
+        ("word/styles.xml", STYLES.to_string()),
+        ("word/document.xml", document_xml(doc, options)),
+    ] {
+        zip.start_file(name, file_options)?;
+        zip.write_all(content.as_bytes())?;
+    }
+
+    Ok(zip.finish()?.into_inner())
+}
+
+fn document_xml(doc: &ExportDocument, options: &ExportOptions) -> String {
+    let mut body = String::new();
+    body.push_str(&paragraph(Some("Title"), &[Run::plain(doc.title.trim())]));
+    body.push_str(&paragraph(
+        Some("Subtitle"),
+        &[Run::plain(
+            &doc.created_at.format("%Y-%m-%d %H:%M UTC").to_string(),
+        )],
+    ));
+
+    for (heading, markdown) in [
+        ("Summary", doc.summary(options)),
+        ("Notes", doc.notes(options)),
+    ] {
+        let Some(markdown) = markdown else { continue };
+        body.push_str(&paragraph(Some("Heading1"), &[Run::plain(heading)]));
+        for block in markdown_blocks(markdown) {
+            body.push_str(&match block {
+                // Summaries repeat the meeting title as their own h1
+                Block::Heading(1, text) if text.trim() == doc.title.trim() => continue,
+                Block::Heading(_, text) => paragraph(Some("Heading2"), &[Run::plain(&text)]),
+                Block::Bullet(text) => paragraph(
+                    Some("ListBullet"),
+                    &[Run::plain(&format!("\u{2022}\t{}", text))],
+                ),
+                Block::Paragraph(text) => paragraph(None, &[Run::plain(&text)]),
+            });
+        }
+    }
+
+    body.push_str(&paragraph(Some("Heading1"), &[Run::plain("Transcript")]));
+    for line in &doc.lines {
+        let parts = LineParts::new(line, options);
+        if parts.text.is_empty() {
+            continue;
+        }
+        let mut runs = Vec::new();
+        if let Some(ts) = &parts.timestamp {
+            runs.push(Run::muted(&format!("{} ", ts)));
+        }
+        if let Some(speaker) = &parts.speaker {
+            runs.push(Run::bold(&format!("{}: ", speaker)));
+        }
+        runs.push(Run::plain(&parts.text));
+        if let Some(conf) = &parts.confidence {
+            runs.push(Run::muted(&format!(" {}", conf)));
+        }
+        body.push_str(&paragraph(None, &runs));
+    }
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>{}<w:sectPr><w:pgSz w:w="11906" w:h="16838"/><w:pgMar w:top="1440" w:right="1440" w:bottom="1440" w:left="1440"/></w:sectPr></w:body></w:document>"#,
+        body
+    )
+}
+
+struct Run {
+    text: String,
+    bold: bool,
+    muted: bool,
+}
+
+impl Run {
+    fn plain(text: &str) -> Self {
+        Self {
+            text: text.to_string(),
+            bold: false,
+            muted: false,
+        }
+    }
+
+    fn bold(text: &str) -> Self {
+        Self {
+            bold: true,
+            ..Self::plain(text)
+        }
+    }
+
+    fn muted(text: &str) -> Self {
+        Self {
+            muted: true,
+            ..Self::plain(text)
+        }
+    }
+}
+
+fn paragraph(style: Option<&str>, runs: &[Run]) -> String {
+    let mut xml = String::from("<w:p>");
+    if let Some(style) = style {
+        xml.push_str(&format!(r#"<w:pPr><w:pStyle w:val="{}"/></w:pPr>"#, style));
+    }
+    for run in runs {
+        xml.push_str("<w:r>");
+        if run.bold || run.muted {
+            xml.push_str("<w:rPr>");
+            if run.bold {
+                xml.push_str("<w:b/>");
+            }
+            if run.muted {
+                xml.push_str(r#"<w:color w:val="808080"/>"#);
+            }
+            xml.push_str("</w:rPr>");
+        }
+        // Tabs must be their own element; everything else goes in a text node
+        for (i, piece) in run.text.split('\t').enumerate() {
+            if i > 0 {
+                xml.push_str("<w:tab/>");
+            }
+            if !piece.is_empty() {
+                xml.push_str(&format!(
+                    r#"<w:t xml:space="preserve">{}</w:t>"#,
+                    escape_xml(piece)
+                ));
+            }
+        }
+        xml.push_str("</w:r>");
+    }
+    xml.push_str("</w:p>");
+    xml
+}
+
+fn escape_xml(text: &str) -> String {
+    text.chars()
+        // Control characters other than tab/newline are invalid in XML 1.0
+        .filter(|c| !c.is_control() || *c == '\n')
+        .collect::<String>()
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paragraph_escapes_and_styles_runs() {
+        let xml = paragraph(
+            Some("ListBullet"),
+            &[Run::bold("A&B: "), Run::plain("x < y\tz")],
+        );
+        assert_eq!(
+            xml,
+            "<w:p><w:pPr><w:pStyle w:val=\"ListBullet\"/></w:pPr>\
+             <w:r><w:rPr><w:b/></w:rPr><w:t xml:space=\"preserve\">A&amp;B: </w:t></w:r>\
+             <w:r><w:t xml:space=\"preserve\">x &lt; y</w:t><w:tab/>\
+             <w:t xml:space=\"preserve\">z</w:t></w:r></w:p>"
+        );
+    }
+}
@@ -0,0 +1,114 @@
+// Markdown export: summary, notes, transcript and a link to the recording
+
+use super::document::{ExportDocument, ExportOptions, LineParts};
+
+/// Renders the meeting as one Markdown document. `audio_link` is the link target
+/// for the recording (a relative path inside a bundle, or a file URL).
+pub fn render_markdown(
+    doc: &ExportDocument,
+    options: &ExportOptions,
+    audio_link: Option<&str>,
+) -> String {
+    let mut out = format!("# {}\n\n", doc.title.trim());
+    out.push_str(&format!(
+        "*{}*\n\n",
+        doc.created_at.format("%Y-%m-%d %H:%M UTC")
+    ));
+
+    if let Some(link) = audio_link {
+        let name = doc
+            .audio_path
+            .as_ref()
+            .and_then(|p| p.file_name())
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "Recording".to_string());
+        out.push_str(&format!("Recording: [{}]({})\n\n", name, link));
+    }
+
+    if let Some(summary) = doc.summary(options) {
+        out.push_str("## Summary\n\n");
+        out.push_str(&demote_headings(summary));
+        out.push_str("\n\n");
+    }
+
+    if let Some(notes) = doc.notes(options) {
+        out.push_str("## Notes\n\n");
+        out.push_str(&demote_headings(notes));
+        out.push_str("\n\n");
+    }
+
+    out.push_str("## Transcript\n\n");
+    for line in &doc.lines {
+        let parts = LineParts::new(line, options);
+        if parts.text.is_empty() {
+            continue;
+        }
+        let mut rendered = String::new();
+        if let Some(ts) = &parts.timestamp {
+            rendered.push_str(&format!("`{}` ", ts));
+        }
+        if let Some(speaker) = &parts.speaker {
+            rendered.push_str(&format!("**{}:** ", speaker));
+        }
+        rendered.push_str(&parts.text);
+        if let Some(conf) = &parts.confidence {
+            rendered.push_str(&format!(" _{}_", conf));
+        }
+        out.push_str(&rendered);
+        out.push_str("\n\n");
+    }
+
+    out.trim_end().to_string() + "\n"
+}
+
+/// Nests embedded Markdown under the export's own `##` sections by pushing
+/// every heading down two levels (capped at h6)
+fn demote_headings(markdown: &str) -> String {
+    markdown
+        .trim()
+        .lines()
+        .map(|line| {
+            let hashes = line.chars().take_while(|&c| c == '#').count();
+            if hashes > 0 && line[hashes..].starts_with(' ') {
+                format!("{}{}", "#".repeat((hashes + 2).min(6)), &line[hashes..])
+            } else {
+                line.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::document::ExportLine;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_render_markdown_sections() {
+        let doc = ExportDocument {
+            title: "Planning".to_string(),
+            created_at: chrono::Utc.with_ymd_and_hms(2025, 3, 4, 9, 30, 0).unwrap(),
+            summary_markdown: Some("# Planning\n## Decisions\n- Ship it".to_string()),
+            notes_markdown: Some("   ".to_string()),
+            lines: vec![ExportLine {
+                text: "Let's ship".to_string(),
+                start: Some(5.0),
+                end: Some(6.0),
+                speaker: Some("Bob".to_string()),
+                confidence: Some(0.8),
+                words: Vec::new(),
+            }],
+            audio_path: Some("/tmp/meeting/audio.mp4".into()),
+            folder: None,
+        };
+
+        let md = render_markdown(&doc, &ExportOptions::default(), Some("audio.mp4"));
+        assert!(md.starts_with("# Planning\n\n*2025-03-04 09:30 UTC*\n\n"));
+        assert!(md.contains("Recording: [audio.mp4](audio.mp4)"));
+        assert!(md.contains("## Summary\n\n### Planning\n#### Decisions\n- Ship it"));
+        assert!(!md.contains("## Notes"));
+        assert!(md.ends_with("`[00:05]` **Bob:** Let's ship\n"));
+    }
+}
@@ -0,0 +1,27 @@
+//! Meeting export.
+//!
+//! Renders a meeting's transcript, summary and notes to files people can share
+//! outside the app: SRT/WebVTT subtitles timed from `audio_start_time` /
+//! `audio_end_time`, DOCX and PDF documents, and Markdown (optionally bundled
+//! with a copy of the recording).
+//!
+//! # Module Structure
+//!
+//! - `document`: Export model, options and shared line/Markdown helpers
+//! - `subtitles`: SRT and WebVTT cues
+//! - `markdown`: Markdown document and bundle link
+//! - `docx`: Minimal WordprocessingML package
+//! - `pdf`: Minimal PDF writer; Helvetica, or embedded font subsets for other scripts
+//! - `truetype`: System font lookup and TrueType subsetting for PDF export
+//! - `commands`: Database loading, file output and the Tauri command
+
+pub mod commands;
+pub mod document;
+pub mod docx;
+pub mod markdown;
+pub mod pdf;
+pub mod subtitles;
+pub mod truetype;
+
+pub use commands::*;
+pub use document::{ExportDocument, ExportFormat, ExportOptions, ExportResult};
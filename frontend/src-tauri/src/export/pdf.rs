@@ -0,0 +1,756 @@
+// PDF export
+//
+// A small hand-written PDF 1.4 writer with A4 pages. Text that fits WinAnsi is
+// set in the built-in Helvetica fonts, so nothing needs to be embedded. Other
+// documents (Cyrillic, Greek, CJK, ...) are set in TrueType system fonts,
+// embedded as subsets with a ToUnicode map so the text can still be copied.
+// Characters no available font covers are rendered as '?' and reported back.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::PathBuf;
+
+use super::document::{markdown_blocks, Block, ExportDocument, ExportOptions, LineParts};
+use super::truetype::{system_fonts, FontSubset, TrueTypeFont};
+
+const PAGE_WIDTH: f64 = 595.0;
+const PAGE_HEIGHT: f64 = 842.0;
+const MARGIN: f64 = 56.0;
+
+const TITLE_SIZE: f64 = 20.0;
+const HEADING_SIZE: f64 = 14.0;
+const SUBHEADING_SIZE: f64 = 12.0;
+const BODY_SIZE: f64 = 10.5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Font {
+    Regular,
+    Bold,
+}
+
+impl Font {
+    fn resource(&self) -> &'static str {
+        match self {
+            Self::Regular => "F1",
+            Self::Bold => "F2",
+        }
+    }
+}
+
+/// A rendered PDF and the characters it couldn't show
+pub struct RenderedPdf {
+    pub bytes: Vec<u8>,
+    /// Characters no available font covers, rendered as '?'
+    pub missing_chars: Vec<char>,
+}
+
+/// A line of text or vertical space, in reading order
+enum Item {
+    Space(f64),
+    Text {
+        text: String,
+        font: Font,
+        size: f64,
+        indent: f64,
+    },
+}
+
+pub fn render_pdf(doc: &ExportDocument, options: &ExportOptions) -> RenderedPdf {
+    render_with_fonts(doc, options, &system_fonts())
+}
+
+/// Renders the document, taking fonts for text outside WinAnsi from
+/// `candidates`, (regular, bold) file pairs in order of preference
+fn render_with_fonts(
+    doc: &ExportDocument,
+    options: &ExportOptions,
+    candidates: &[(PathBuf, Option<PathBuf>)],
+) -> RenderedPdf {
+    let items = document_items(doc, options);
+    let mut chars: BTreeSet<char> = items
+        .iter()
+        .filter_map(|item| match item {
+            Item::Text { text, .. } => Some(text.chars().filter(|c| !c.is_whitespace())),
+            Item::Space(_) => None,
+        })
+        .flatten()
+        .collect();
+    chars.insert(' ');
+
+    let fonts = Fonts::for_chars(&chars, candidates);
+    let mut layout = Layout::new(&fonts);
+    for item in &items {
+        match item {
+            Item::Space(amount) => layout.space(*amount),
+            Item::Text {
+                text,
+                font,
+                size,
+                indent,
+            } => layout.text(text, *font, *size, *indent),
+        }
+    }
+
+    RenderedPdf {
+        bytes: write_document(&layout.finish(), &fonts),
+        missing_chars: fonts.missing_chars.clone(),
+    }
+}
+
+fn document_items(doc: &ExportDocument, options: &ExportOptions) -> Vec<Item> {
+    let text = |text: &str, font: Font, size: f64, indent: f64| Item::Text {
+        text: text.to_string(),
+        font,
+        size,
+        indent,
+    };
+
+    let mut items = vec![
+        text(doc.title.trim(), Font::Bold, TITLE_SIZE, 0.0),
+        text(
+            &doc.created_at.format("%Y-%m-%d %H:%M UTC").to_string(),
+            Font::Regular,
+            BODY_SIZE,
+            0.0,
+        ),
+    ];
+
+    for (heading, markdown) in [
+        ("Summary", doc.summary(options)),
+        ("Notes", doc.notes(options)),
+    ] {
+        let Some(markdown) = markdown else { continue };
+        items.push(Item::Space(HEADING_SIZE));
+        items.push(text(heading, Font::Bold, HEADING_SIZE, 0.0));
+        for block in markdown_blocks(markdown) {
+            match block {
+                Block::Heading(1, title) if title.trim() == doc.title.trim() => {}
+                Block::Heading(_, heading) => {
+                    items.push(Item::Space(BODY_SIZE * 0.5));
+                    items.push(text(&heading, Font::Bold, SUBHEADING_SIZE, 0.0));
+                }
+                Block::Bullet(bullet) => items.push(text(
+                    &format!("\u{2022} {}", bullet),
+                    Font::Regular,
+                    BODY_SIZE,
+                    12.0,
+                )),
+                Block::Paragraph(paragraph) => {
+                    items.push(text(&paragraph, Font::Regular, BODY_SIZE, 0.0))
+                }
+            }
+        }
+    }
+
+    items.push(Item::Space(HEADING_SIZE));
+    items.push(text("Transcript", Font::Bold, HEADING_SIZE, 0.0));
+    for line in &doc.lines {
+        let parts = LineParts::new(line, options);
+        if parts.text.is_empty() {
+            continue;
+        }
+        items.push(text(&parts.to_plain(), Font::Regular, BODY_SIZE, 0.0));
+        items.push(Item::Space(BODY_SIZE * 0.3));
+    }
+    items
+}
+
+/// The fonts a document is set in: the built-in Helvetica when nothing is
+/// embedded, otherwise the embedded fonts
+struct Fonts {
+    embedded: Vec<EmbeddedFont>,
+    /// Glyph of each character per style, when fonts are embedded
+    glyphs: HashMap<(Font, char), Glyph>,
+    missing_chars: Vec<char>,
+}
+
+struct EmbeddedFont {
+    font: TrueTypeFont,
+    subset: FontSubset,
+    /// Character of each subset glyph, for the ToUnicode map
+    chars: BTreeMap<u16, char>,
+}
+
+#[derive(Clone, Copy)]
+struct Glyph {
+    /// Index into `Fonts::embedded`
+    font: usize,
+    /// Glyph id in the subset, which is also its CID
+    id: u16,
+    /// Advance width in em
+    width: f64,
+}
+
+impl Fonts {
+    fn standard(missing_chars: Vec<char>) -> Self {
+        Self {
+            embedded: Vec::new(),
+            glyphs: HashMap::new(),
+            missing_chars,
+        }
+    }
+
+    /// Helvetica if every character is in WinAnsi, otherwise the first font
+    /// families of `candidates` that together cover the characters
+    fn for_chars(chars: &BTreeSet<char>, candidates: &[(PathBuf, Option<PathBuf>)]) -> Self {
+        let outside: Vec<char> = chars
+            .iter()
+            .copied()
+            .filter(|&c| win_ansi(c).is_none())
+            .collect();
+        if outside.is_empty() {
+            return Self::standard(Vec::new());
+        }
+
+        // (regular, bold) indexes into `faces`; a family is only loaded if it
+        // covers characters the earlier ones don't
+        let mut faces: Vec<TrueTypeFont> = Vec::new();
+        let mut families: Vec<(usize, Option<usize>)> = Vec::new();
+        let mut uncovered = chars.clone();
+        for (regular, bold) in candidates {
+            if uncovered.is_empty() {
+                break;
+            }
+            let Some(font) = TrueTypeFont::load(regular) else {
+                continue;
+            };
+            let covered: Vec<char> = uncovered
+                .iter()
+                .copied()
+                .filter(|&c| font.glyph(c).is_some())
+                .collect();
+            if covered.is_empty() {
+                continue;
+            }
+            for c in covered {
+                uncovered.remove(&c);
+            }
+            faces.push(font);
+            let regular = faces.len() - 1;
+            let bold = bold.as_deref().and_then(TrueTypeFont::load).map(|font| {
+                faces.push(font);
+                faces.len() - 1
+            });
+            families.push((regular, bold));
+        }
+        if families.is_empty() {
+            return Self::standard(outside);
+        }
+
+        // Pick a glyph for every character in both styles; uncovered ones get
+        // the first family's '?'
+        let mut missing_chars = Vec::new();
+        let mut picked: Vec<((Font, char), usize, u16)> = Vec::new();
+        let mut used: Vec<BTreeMap<u16, char>> = vec![BTreeMap::new(); faces.len()];
+        for &c in chars {
+            for style in [Font::Regular, Font::Bold] {
+                let found = families.iter().find_map(|&(regular, bold)| {
+                    let id = faces[regular].glyph(c)?;
+                    let bold = bold
+                        .filter(|_| style == Font::Bold)
+                        .and_then(|bold| Some((bold, faces[bold].glyph(c)?)));
+                    Some(bold.unwrap_or((regular, id)))
+                });
+                let (face, id, shown) = match found {
+                    Some((face, id)) => (face, id, c),
+                    None => {
+                        if style == Font::Regular {
+                            missing_chars.push(c);
+                        }
+                        let face = families[0].0;
+                        (face, faces[face].glyph('?').unwrap_or(0), '?')
+                    }
+                };
+                used[face].entry(id).or_insert(shown);
+                picked.push(((style, c), face, id));
+            }
+        }
+
+        // Subset the faces that are used, in order
+        let mut embedded = Vec::new();
+        let mut embedded_index = vec![None; faces.len()];
+        for (i, (font, used)) in faces.into_iter().zip(used).enumerate() {
+            if used.is_empty() {
+                continue;
+            }
+            let Some(subset) = font.subset(&used.keys().copied().collect()) else {
+                log::warn!("Failed to subset font {} for PDF export", font.name);
+                return Self::standard(outside);
+            };
+            let chars = used
+                .into_iter()
+                .filter_map(|(id, c)| Some((*subset.glyph_ids.get(&id)?, c)))
+                .collect();
+            embedded_index[i] = Some(embedded.len());
+            embedded.push(EmbeddedFont {
+                font,
+                subset,
+                chars,
+            });
+        }
+
+        let mut glyphs = HashMap::new();
+        for (key, face, id) in picked {
+            let Some(font) = embedded_index[face] else {
+                continue;
+            };
+            let embedded_font = &embedded[font];
+            let id = embedded_font
+                .subset
+                .glyph_ids
+                .get(&id)
+                .copied()
+                .unwrap_or(0);
+            let advance = embedded_font.subset.advances[id as usize];
+            let width = advance as f64 / embedded_font.font.units_per_em.max(1) as f64;
+            glyphs.insert(key, Glyph { font, id, width });
+        }
+        Self {
+            embedded,
+            glyphs,
+            missing_chars,
+        }
+    }
+
+    fn text_width(&self, text: &str, font: Font, size: f64) -> f64 {
+        let em: f64 = if self.embedded.is_empty() {
+            text.chars().map(char_width).sum()
+        } else {
+            text.chars()
+                .filter_map(|c| self.glyphs.get(&(font, c)))
+                .map(|glyph| glyph.width)
+                .sum()
+        };
+        em * size
+    }
+
+    /// Operators that select the font and show `text`, switching between
+    /// embedded fonts where the characters need it
+    fn show(&self, text: &str, font: Font, size: f64) -> String {
+        if self.embedded.is_empty() {
+            return format!(
+                "/{} {:.1} Tf ({}) Tj",
+                font.resource(),
+                size,
+                encode_text(text)
+            );
+        }
+
+        let mut out = String::new();
+        let mut current = None;
+        for glyph in text.chars().filter_map(|c| self.glyphs.get(&(font, c))) {
+            if current != Some(glyph.font) {
+                if current.is_some() {
+                    out.push_str("> Tj ");
+                }
+                out.push_str(&format!("/U{} {:.1} Tf <", glyph.font + 1, size));
+                current = Some(glyph.font);
+            }
+            out.push_str(&format!("{:04X}", glyph.id));
+        }
+        if current.is_some() {
+            out.push_str("> Tj");
+        }
+        out
+    }
+
+    /// Font objects, numbered from `first_id`, and the page resource entries
+    /// naming them
+    fn objects(&self, first_id: usize) -> (Vec<String>, String) {
+        if self.embedded.is_empty() {
+            return (
+                vec![
+                    "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>"
+                        .to_string(),
+                    "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>"
+                        .to_string(),
+                ],
+                format!("/F1 {} 0 R /F2 {} 0 R", first_id, first_id + 1),
+            );
+        }
+
+        // Type0 font, CIDFont, descriptor, font file and ToUnicode map per font
+        let mut objects = Vec::new();
+        let mut resources = Vec::new();
+        for (i, embedded) in self.embedded.iter().enumerate() {
+            let id = first_id + i * 5;
+            let font = &embedded.font;
+            // Subset fonts are named with a unique six-letter tag
+            let name = format!("AAAAA{}+{}", (b'A' + (i % 26) as u8) as char, font.name);
+            let scale = 1000.0 / font.units_per_em.max(1) as f64;
+            let units = |value: i16| (value as f64 * scale).round() as i64;
+            let widths: Vec<String> = embedded
+                .subset
+                .advances
+                .iter()
+                .map(|&advance| ((advance as f64 * scale).round() as i64).to_string())
+                .collect();
+            let hex: Vec<String> = embedded
+                .subset
+                .data
+                .chunks(32)
+                .map(|line| line.iter().map(|b| format!("{:02X}", b)).collect())
+                .collect();
+            let hex = format!("{}>\n", hex.join("\n"));
+            let cmap = to_unicode_cmap(&embedded.chars);
+
+            resources.push(format!("/U{} {} 0 R", i + 1, id));
+            objects.push(format!(
+                "<< /Type /Font /Subtype /Type0 /BaseFont /{} /Encoding /Identity-H \
+                 /DescendantFonts [{} 0 R] /ToUnicode {} 0 R >>",
+                name,
+                id + 1,
+                id + 4
+            ));
+            objects.push(format!(
+                "<< /Type /Font /Subtype /CIDFontType2 /BaseFont /{} \
+                 /CIDSystemInfo << /Registry (Adobe) /Ordering (Identity) /Supplement 0 >> \
+                 /FontDescriptor {} 0 R /W [0 [{}]] /CIDToGIDMap /Identity >>",
+                name,
+                id + 2,
+                widths.join(" ")
+            ));
+            objects.push(format!(
+                "<< /Type /FontDescriptor /FontName /{} /Flags 4 /FontBBox [{} {} {} {}] \
+                 /ItalicAngle 0 /Ascent {} /Descent {} /CapHeight {} /StemV 80 /FontFile2 {} 0 R >>",
+                name,
+                units(font.bbox[0]),
+                units(font.bbox[1]),
+                units(font.bbox[2]),
+                units(font.bbox[3]),
+                units(font.ascent),
+                units(font.descent),
+                units(font.cap_height),
+                id + 3
+            ));
+            objects.push(format!(
+                "<< /Length {} /Length1 {} /Filter /ASCIIHexDecode >>\nstream\n{}endstream",
+                hex.len(),
+                embedded.subset.data.len(),
+                hex
+            ));
+            objects.push(format!(
+                "<< /Length {} >>\nstream\n{}endstream",
+                cmap.len(),
+                cmap
+            ));
+        }
+        (objects, resources.join(" "))
+    }
+}
+
+/// CMap from glyph ids (as 2-byte codes) to the characters they show
+fn to_unicode_cmap(chars: &BTreeMap<u16, char>) -> String {
+    let mut out = String::from(
+        "/CIDInit /ProcSet findresource begin\n12 dict begin\nbegincmap\n\
+         /CIDSystemInfo << /Registry (Adobe) /Ordering (UCS) /Supplement 0 >> def\n\
+         /CMapName /Adobe-Identity-UCS def\n/CMapType 2 def\n\
+         1 begincodespacerange\n<0000> <FFFF>\nendcodespacerange\n",
+    );
+    let entries: Vec<(&u16, &char)> = chars.iter().collect();
+    // At most 100 mappings per block
+    for block in entries.chunks(100) {
+        out.push_str(&format!("{} beginbfchar\n", block.len()));
+        for (id, c) in block {
+            let utf16: String = c
+                .encode_utf16(&mut [0; 2])
+                .iter()
+                .map(|unit| format!("{:04X}", unit))
+                .collect();
+            out.push_str(&format!("<{:04X}> <{}>\n", id, utf16));
+        }
+        out.push_str("endbfchar\n");
+    }
+    out.push_str("endcmap\nCMapName currentdict /CMap defineresource pop\nend\nend\n");
+    out
+}
+
+/// Flows lines of text top-down, starting a new page when one fills up
+struct Layout<'a> {
+    fonts: &'a Fonts,
+    pages: Vec<String>,
+    current: String,
+    y: f64,
+}
+
+impl<'a> Layout<'a> {
+    fn new(fonts: &'a Fonts) -> Self {
+        Self {
+            fonts,
+            pages: Vec::new(),
+            current: String::new(),
+            y: PAGE_HEIGHT - MARGIN,
+        }
+    }
+
+    fn space(&mut self, amount: f64) {
+        self.y -= amount;
+    }
+
+    fn text(&mut self, text: &str, font: Font, size: f64, indent: f64) {
+        let leading = size * 1.35;
+        let max_width = PAGE_WIDTH - 2.0 * MARGIN - indent;
+        let fonts = self.fonts;
+        for line in wrap(text, max_width, |line| fonts.text_width(line, font, size)) {
+            if self.y - leading < MARGIN {
+                self.new_page();
+            }
+            self.y -= leading;
+            self.current.push_str(&format!(
+                "BT {:.2} {:.2} Td {} ET\n",
+                MARGIN + indent,
+                self.y,
+                fonts.show(&line, font, size)
+            ));
+        }
+    }
+
+    fn new_page(&mut self) {
+        self.pages.push(std::mem::take(&mut self.current));
+        self.y = PAGE_HEIGHT - MARGIN;
+    }
+
+    fn finish(mut self) -> Vec<String> {
+        self.pages.push(self.current);
+        self.pages
+    }
+}
+
+/// Approximate Helvetica advance width in em units; close enough to wrap lines
+/// without carrying the full metrics table
+fn char_width(c: char) -> f64 {
+    match c {
+        'i' | 'j' | 'l' | '.' | ',' | ';' | ':' | '\'' | '|' | '!' | ' ' => 0.28,
+        'f' | 't' | 'r' | '(' | ')' | '[' | ']' | '-' => 0.33,
+        'm' | 'w' | 'M' | 'W' | '@' => 0.83,
+        'A'..='Z' => 0.67,
+        _ => 0.556,
+    }
+}
+
+fn wrap(text: &str, max_width: f64, width: impl Fn(&str) -> f64) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    for word in text.split_whitespace() {
+        let candidate = if current.is_empty() {
+            word.to_string()
+        } else {
+            format!("{} {}", current, word)
+        };
+        if width(&candidate) <= max_width || current.is_empty() {
+            current = candidate;
+        } else {
+            lines.push(std::mem::replace(&mut current, word.to_string()));
+        }
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+/// Maps a character to its WinAnsiEncoding byte, if it has one
+fn win_ansi(c: char) -> Option<u8> {
+    Some(match c {
+        ' '..='~' => c as u8,
+        '\u{a0}'..='\u{ff}' => c as u32 as u8,
+        '\u{20ac}' => 0x80,
+        '\u{2026}' => 0x85,
+        '\u{2018}' => 0x91,
+        '\u{2019}' => 0x92,
+        '\u{201c}' => 0x93,
+        '\u{201d}' => 0x94,
+        '\u{2022}' => 0x95,
+        '\u{2013}' => 0x96,
+        '\u{2014}' => 0x97,
+        '\t' => b' ',
+        _ => return None,
+    })
+}
+
+/// Encodes text as the body of a PDF string literal, keeping the file ASCII
+fn encode_text(text: &str) -> String {
+    let mut out = String::new();
+    for byte in text.chars().map(|c| win_ansi(c).unwrap_or(b'?')) {
+        match byte {
+            b'(' | b')' | b'\\' => {
+                out.push('\\');
+                out.push(byte as char);
+            }
+            0x20..=0x7e => out.push(byte as char),
+            _ => out.push_str(&format!("\\{:03o}", byte)),
+        }
+    }
+    out
+}
+
+/// Serialises page content streams into a complete PDF with a correct xref table
+fn write_document(pages: &[String], fonts: &Fonts) -> Vec<u8> {
+    // 1: catalog, 2: page tree, then the fonts and a page + content pair per page
+    let (font_objects, font_resources) = fonts.objects(3);
+    let first_page = 3 + font_objects.len();
+    let page_ids: Vec<usize> = (0..pages.len()).map(|i| first_page + i * 2).collect();
+    let mut objects: Vec<String> = vec![
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            page_ids
+                .iter()
+                .map(|id| format!("{} 0 R", id))
+                .collect::<Vec<_>>()
+                .join(" "),
+            pages.len()
+        ),
+    ];
+    objects.extend(font_objects);
+    for (content, page_id) in pages.iter().zip(&page_ids) {
+        objects.push(format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] \
+             /Resources << /Font << {} >> >> /Contents {} 0 R >>",
+            PAGE_WIDTH,
+            PAGE_HEIGHT,
+            font_resources,
+            page_id + 1
+        ));
+        objects.push(format!(
+            "<< /Length {} >>\nstream\n{}endstream",
+            content.len(),
+            content
+        ));
+    }
+
+    let mut out = String::from("%PDF-1.4\n");
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(out.len());
+        out.push_str(&format!("{} 0 obj\n{}\nendobj\n", i + 1, object));
+    }
+
+    let xref_offset = out.len();
+    out.push_str(&format!(
+        "xref\n0 {}\n0000000000 65535 f \n",
+        objects.len() + 1
+    ));
+    for offset in offsets {
+        out.push_str(&format!("{:010} 00000 n \n", offset));
+    }
+    out.push_str(&format!(
+        "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+        objects.len() + 1,
+        xref_offset
+    ));
+    out.into_bytes()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::document::ExportLine;
+
+    fn document(text: &str) -> ExportDocument {
+        ExportDocument {
+            title: "Weekly sync".to_string(),
+            created_at: chrono::Utc::now(),
+            summary_markdown: None,
+            notes_markdown: None,
+            lines: vec![ExportLine {
+                text: text.to_string(),
+                start: Some(1.0),
+                end: Some(2.0),
+                speaker: None,
+                confidence: None,
+                words: Vec::new(),
+            }],
+            audio_path: None,
+            folder: None,
+        }
+    }
+
+    #[test]
+    fn test_encode_text() {
+        assert_eq!(encode_text("a (b) \\ c"), "a \\(b\\) \\\\ c");
+        assert_eq!(encode_text("café – 日"), "caf\\351 \\226 ?");
+    }
+
+    #[test]
+    fn test_wrap_respects_width() {
+        let fonts = Fonts::standard(Vec::new());
+        let width = |line: &str| fonts.text_width(line, Font::Regular, BODY_SIZE);
+        let text = "word ".repeat(200);
+        let lines = wrap(&text, 200.0, width);
+        assert!(lines.len() > 1);
+        assert!(lines.iter().all(|l| width(l) <= 200.0));
+        assert_eq!(lines.join(" "), text.trim());
+    }
+
+    #[test]
+    fn test_xref_offsets_point_at_objects() {
+        let pdf = String::from_utf8(write_document(
+            &vec!["BT ET\n".to_string(); 2],
+            &Fonts::standard(Vec::new()),
+        ))
+        .unwrap();
+        assert!(pdf.starts_with("%PDF-1.4\n"));
+        assert!(pdf.ends_with("%%EOF\n"));
+
+        let startxref: usize = pdf
+            .rsplit("startxref\n")
+            .next()
+            .and_then(|tail| tail.lines().next())
+            .and_then(|n| n.parse().ok())
+            .unwrap();
+        assert!(pdf[startxref..].starts_with("xref\n0 9\n"));
+
+        let entries = pdf[startxref..].lines().skip(3).take(8);
+        for (i, entry) in entries.enumerate() {
+            let offset: usize = entry[..10].parse().unwrap();
+            assert!(pdf[offset..].starts_with(&format!("{} 0 obj", i + 1)));
+        }
+    }
+
+    #[test]
+    fn test_latin_text_uses_helvetica() {
+        let pdf = render_with_fonts(
+            &document("Café – déjà vu"),
+            &ExportOptions::default(),
+            &system_fonts(),
+        );
+        assert!(pdf.missing_chars.is_empty());
+        let text = String::from_utf8(pdf.bytes).unwrap();
+        assert!(text.contains("/BaseFont /Helvetica "));
+        assert!(!text.contains("/FontFile2"));
+    }
+
+    #[test]
+    fn test_reports_characters_without_a_font() {
+        let pdf = render_with_fonts(&document("Привет, 世界"), &ExportOptions::default(), &[]);
+        // The title, date and headings are WinAnsi; the transcript line isn't
+        assert_eq!(
+            pdf.missing_chars,
+            vec!['П', 'в', 'е', 'и', 'р', 'т', '世', '界']
+        );
+        assert!(String::from_utf8(pdf.bytes)
+            .unwrap()
+            .starts_with("%PDF-1.4\n"));
+    }
+
+    #[test]
+    fn test_embeds_a_system_font_for_other_scripts() {
+        let candidates = system_fonts();
+        let pdf = render_with_fonts(
+            &document("Привет, Αθήνα"),
+            &ExportOptions::default(),
+            &candidates,
+        );
+        // Needs a TrueType system font covering Cyrillic and Greek
+        if !pdf.missing_chars.is_empty() {
+            return;
+        }
+        let text = String::from_utf8(pdf.bytes).unwrap();
+        for needle in ["/CIDFontType2", "/Identity-H", "/ToUnicode", "/FontFile2"] {
+            assert!(text.contains(needle), "missing {}", needle);
+        }
+        // The ToUnicode map lets viewers copy the text back out
+        assert!(text.contains("<041F>"));
+        assert!(text.contains("<0391>"));
+    }
+}
@@ -0,0 +1,227 @@
+// SRT and WebVTT subtitle export
+//
+// One cue per transcript row, timed from audio_start_time/audio_end_time. Rows
+// with word timings are split into shorter cues so captions stay readable.
+
+use super::document::{ExportDocument, ExportLine, ExportOptions};
+
+/// Longest cue built from word timings
+const MAX_CUE_SECS: f64 = 6.0;
+/// Two subtitle lines of 42 characters (common broadcast guideline)
+const MAX_LINE_CHARS: usize = 42;
+const MAX_CUE_CHARS: usize = MAX_LINE_CHARS * 2;
+
+#[derive(Debug, Clone, PartialEq)]
+struct Cue {
+    start: f64,
+    end: f64,
+    speaker: Option<String>,
+    text: String,
+}
+
+pub fn render_srt(doc: &ExportDocument, options: &ExportOptions) -> String {
+    let mut out = String::new();
+    for (i, cue) in cues(doc, options).iter().enumerate() {
+        let text = match &cue.speaker {
+            Some(speaker) => format!("{}: {}", speaker, cue.text),
+            None => cue.text.clone(),
+        };
+        out.push_str(&format!(
+            "{}\n{} --> {}\n{}\n\n",
+            i + 1,
+            format_timestamp(cue.start, ','),
+            format_timestamp(cue.end, ','),
+            wrap_cue_text(&text)
+        ));
+    }
+    out
+}
+
+pub fn render_vtt(doc: &ExportDocument, options: &ExportOptions) -> String {
+    let mut out = String::from("WEBVTT\n\n");
+    for cue in cues(doc, options) {
+        let text = wrap_cue_text(&cue.text);
+        let text = match &cue.speaker {
+            // Voice span so players can style or filter by speaker
+            Some(speaker) => format!("<v {}>{}", escape_vtt(speaker), escape_vtt(&text)),
+            None => escape_vtt(&text),
+        };
+        out.push_str(&format!(
+            "{} --> {}\n{}\n\n",
+            format_timestamp(cue.start, '.'),
+            format_timestamp(cue.end, '.'),
+            text
+        ));
+    }
+    out
+}
+
+fn cues(doc: &ExportDocument, options: &ExportOptions) -> Vec<Cue> {
+    doc.lines
+        .iter()
+        .filter(|line| !line.text.trim().is_empty())
+        .flat_map(|line| {
+            let speaker = line.speaker.clone().filter(|_| options.include_speakers);
+            line_cues(line, speaker)
+        })
+        .collect()
+}
+
+fn line_cues(line: &ExportLine, speaker: Option<String>) -> Vec<Cue> {
+    let (Some(start), Some(end)) = (line.start, line.end) else {
+        return Vec::new();
+    };
+
+    if line.words.is_empty() {
+        return vec![Cue {
+            start,
+            end: end.max(start),
+            speaker,
+            text: line.text.trim().to_string(),
+        }];
+    }
+
+    let mut cues: Vec<Cue> = Vec::new();
+    let mut current: Option<Cue> = None;
+    for word in &line.words {
+        if let Some(cue) = current.as_mut() {
+            let too_long = word.end - cue.start > MAX_CUE_SECS
+                || cue.text.chars().count() + 1 + word.text.chars().count() > MAX_CUE_CHARS;
+            if !too_long {
+                cue.text.push(' ');
+                cue.text.push_str(&word.text);
+                cue.end = word.end.max(cue.end);
+                continue;
+            }
+            cues.extend(current.take());
+        }
+        current = Some(Cue {
+            start: word.start,
+            end: word.end.max(word.start),
+            speaker: speaker.clone(),
+            text: word.text.clone(),
+        });
+    }
+    cues.extend(current);
+    cues
+}
+
+/// Breaks cue text into at most two lines near the middle
+fn wrap_cue_text(text: &str) -> String {
+    if text.chars().count() <= MAX_LINE_CHARS {
+        return text.to_string();
+    }
+    let middle = text.len() / 2;
+    let split = text
+        .char_indices()
+        .filter(|(_, c)| *c == ' ')
+        .map(|(i, _)| i)
+        .min_by_key(|i| i.abs_diff(middle));
+    match split {
+        Some(i) => format!("{}\n{}", &text[..i], &text[i + 1..]),
+        None => text.to_string(),
+    }
+}
+
+fn escape_vtt(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// HH:MM:SS,mmm (SRT) or HH:MM:SS.mmm (VTT)
+fn format_timestamp(seconds: f64, separator: char) -> String {
+    let total_ms = (seconds.max(0.0) * 1000.0).round() as u64;
+    let (h, m, s, ms) = (
+        total_ms / 3_600_000,
+        (total_ms % 3_600_000) / 60_000,
+        (total_ms % 60_000) / 1000,
+        total_ms % 1000,
+    );
+    format!("{:02}:{:02}:{:02}{}{:03}", h, m, s, separator, ms)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::export::document::ExportWord;
+    use chrono::Utc;
+
+    fn doc(lines: Vec<ExportLine>) -> ExportDocument {
+        ExportDocument {
+            title: "Standup".to_string(),
+            created_at: Utc::now(),
+            summary_markdown: None,
+            notes_markdown: None,
+            lines,
+            audio_path: None,
+            folder: None,
+        }
+    }
+
+    fn line(text: &str, start: f64, end: f64, speaker: Option<&str>) -> ExportLine {
+        ExportLine {
+            text: text.to_string(),
+            start: Some(start),
+            end: Some(end),
+            speaker: speaker.map(str::to_string),
+            confidence: None,
+            words: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_format_timestamp() {
+        assert_eq!(format_timestamp(3725.0456, ','), "01:02:05,046");
+        assert_eq!(format_timestamp(0.5, '.'), "00:00:00.500");
+    }
+
+    #[test]
+    fn test_render_srt_and_vtt() {
+        let d = doc(vec![
+            line("Hello team", 1.0, 2.5, Some("Alice")),
+            line("  ", 3.0, 4.0, None),
+            line("Hi <all>", 3.0, 4.0, None),
+        ]);
+        let options = ExportOptions::default();
+
+        assert_eq!(
+            render_srt(&d, &options),
+            "1\n00:00:01,000 --> 00:00:02,500\nAlice: Hello team\n\n\
+             2\n00:00:03,000 --> 00:00:04,000\nHi <all>\n\n"
+        );
+        assert_eq!(
+            render_vtt(&d, &options),
+            "WEBVTT\n\n00:00:01.000 --> 00:00:02.500\n<v Alice>Hello team\n\n\
+             00:00:03.000 --> 00:00:04.000\nHi &lt;all&gt;\n\n"
+        );
+
+        let no_speakers = ExportOptions {
+            include_speakers: false,
+            ..Default::default()
+        };
+        assert!(!render_srt(&d, &no_speakers).contains("Alice"));
+    }
+
+    #[test]
+    fn test_word_timings_split_long_rows() {
+        let mut l = line("", 0.0, 10.0, None);
+        l.text = "one two three four".to_string();
+        l.words = ["one", "two", "three", "four"]
+            .iter()
+            .enumerate()
+            .map(|(i, w)| ExportWord {
+                text: w.to_string(),
+                start: i as f64 * 2.5,
+                end: i as f64 * 2.5 + 2.0,
+            })
+            .collect();
+
+        let cues = line_cues(&l, None);
+        assert_eq!(cues.len(), 2);
+        assert_eq!(cues[0].text, "one two");
+        assert_eq!((cues[0].start, cues[0].end), (0.0, 4.5));
+        assert_eq!(cues[1].text, "three four");
+        assert_eq!((cues[1].start, cues[1].end), (5.0, 9.5));
+    }
+}
@@ -0,0 +1,397 @@
+// TrueType fonts for PDF export
+//
+// Loads system fonts and cuts them down to the glyphs a document uses, so the
+// PDF writer can embed them as CIDFontType2 fonts. Only fonts with TrueType
+// outlines (a `glyf` table) are used; CFF-based OpenType fonts are skipped.
+// There is no text shaping, so scripts that need it (Arabic joining, Indic
+// conjuncts) come out as unjoined glyphs.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use ttf_parser::{name_id, Face, Tag};
+
+/// Font files tried for text outside WinAnsi, in order, as (regular, bold)
+pub fn system_fonts() -> Vec<(PathBuf, Option<PathBuf>)> {
+    #[cfg(target_os = "windows")]
+    let (dir, names): (PathBuf, &[(&str, Option<&str>)]) = (
+        PathBuf::from(std::env::var("WINDIR").unwrap_or_else(|_| "C:\\Windows".to_string()))
+            .join("Fonts"),
+        &[
+            ("arial.ttf", Some("arialbd.ttf")),
+            ("segoeui.ttf", Some("segoeuib.ttf")),
+            ("msyh.ttc", Some("msyhbd.ttc")),
+            ("YuGothR.ttc", Some("YuGothB.ttc")),
+            ("malgun.ttf", Some("malgunbd.ttf")),
+            ("Nirmala.ttf", Some("NirmalaB.ttf")),
+            ("seguisym.ttf", None),
+        ],
+    );
+    #[cfg(target_os = "macos")]
+    let (dir, names): (PathBuf, &[(&str, Option<&str>)]) = (
+        PathBuf::from("/System/Library/Fonts"),
+        &[
+            (
+                "Supplemental/Arial.ttf",
+                Some("Supplemental/Arial Bold.ttf"),
+            ),
+            ("Supplemental/Arial Unicode.ttf", None),
+            ("/Library/Fonts/Arial Unicode.ttf", None),
+        ],
+    );
+    #[cfg(not(any(target_os = "windows", target_os = "macos")))]
+    let (dir, names): (PathBuf, &[(&str, Option<&str>)]) = (
+        PathBuf::from("/usr/share/fonts"),
+        &[
+            (
+                "truetype/dejavu/DejaVuSans.ttf",
+                Some("truetype/dejavu/DejaVuSans-Bold.ttf"),
+            ),
+            (
+                "truetype/noto/NotoSans-Regular.ttf",
+                Some("truetype/noto/NotoSans-Bold.ttf"),
+            ),
+            ("truetype/droid/DroidSansFallbackFull.ttf", None),
+            ("truetype/wqy/wqy-microhei.ttc", None),
+            ("truetype/arphic/uming.ttc", None),
+        ],
+    );
+
+    names
+        .iter()
+        .map(|(regular, bold)| (dir.join(regular), bold.map(|bold| dir.join(bold))))
+        .collect()
+}
+
+/// One face of a .ttf or .ttc file
+pub struct TrueTypeFont {
+    data: Vec<u8>,
+    index: u32,
+    /// PostScript name, used as the PDF BaseFont
+    pub name: String,
+    pub units_per_em: u16,
+    pub ascent: i16,
+    pub descent: i16,
+    pub cap_height: i16,
+    /// x_min, y_min, x_max, y_max
+    pub bbox: [i16; 4],
+}
+
+impl TrueTypeFont {
+    /// Loads the first face of a font file, if it has TrueType outlines
+    pub fn load(path: &Path) -> Option<Self> {
+        Self::from_data(std::fs::read(path).ok()?, 0)
+    }
+
+    pub fn from_data(data: Vec<u8>, index: u32) -> Option<Self> {
+        let face = Face::parse(&data, index).ok()?;
+        face.tables().glyf?;
+        let name = face
+            .names()
+            .into_iter()
+            .filter(|name| name.name_id == name_id::POST_SCRIPT_NAME)
+            .find_map(|name| name.to_string())
+            .map(|name| {
+                name.chars()
+                    .filter(|c| c.is_ascii_alphanumeric() || *c == '-')
+                    .collect::<String>()
+            })
+            .filter(|name| !name.is_empty())
+            .unwrap_or_else(|| "Font".to_string());
+        let rect = face.global_bounding_box();
+        let (units_per_em, ascent, descent) =
+            (face.units_per_em(), face.ascender(), face.descender());
+        let cap_height = face.capital_height().unwrap_or(ascent);
+        Some(Self {
+            data,
+            index,
+            name,
+            units_per_em,
+            ascent,
+            descent,
+            cap_height,
+            bbox: [rect.x_min, rect.y_min, rect.x_max, rect.y_max],
+        })
+    }
+
+    fn face(&self) -> Option<Face<'_>> {
+        Face::parse(&self.data, self.index).ok()
+    }
+
+    /// Glyph of a character, None if the font doesn't cover it
+    pub fn glyph(&self, c: char) -> Option<u16> {
+        self.face()?
+            .glyph_index(c)
+            .map(|id| id.0)
+            .filter(|&id| id != 0)
+    }
+
+    /// Builds a font with only `glyphs` (and the components of composite
+    /// glyphs), renumbered from 1 in ascending order after .notdef
+    pub fn subset(&self, glyphs: &BTreeSet<u16>) -> Option<FontSubset> {
+        let face = self.face()?;
+        let raw = face.raw_face();
+        let table = |tag: &[u8; 4]| raw.table(Tag::from_bytes(tag));
+        let head = table(b"head")?;
+        let hhea = table(b"hhea")?;
+        let maxp = table(b"maxp")?;
+        let hmtx = table(b"hmtx")?;
+        let loca = table(b"loca")?;
+        let glyf = table(b"glyf")?;
+
+        let num_glyphs = read_u16(maxp, 4)?;
+        let long_loca = read_u16(head, 50)? == 1;
+        let glyph_data = |id: u16| -> Option<&[u8]> {
+            let (start, end) = if long_loca {
+                (
+                    read_u32(loca, id as usize * 4)? as usize,
+                    read_u32(loca, id as usize * 4 + 4)? as usize,
+                )
+            } else {
+                (
+                    read_u16(loca, id as usize * 2)? as usize * 2,
+                    read_u16(loca, id as usize * 2 + 2)? as usize * 2,
+                )
+            };
+            glyf.get(start..end)
+        };
+
+        // .notdef, the requested glyphs and everything composites refer to
+        let mut keep = BTreeSet::new();
+        let mut pending: Vec<u16> = std::iter::once(0)
+            .chain(glyphs.iter().copied())
+            .filter(|&id| id < num_glyphs)
+            .collect();
+        while let Some(id) = pending.pop() {
+            if keep.insert(id) {
+                for (_, component) in components(glyph_data(id)?) {
+                    if component < num_glyphs && !keep.contains(&component) {
+                        pending.push(component);
+                    }
+                }
+            }
+        }
+        let glyph_ids: BTreeMap<u16, u16> = keep
+            .iter()
+            .enumerate()
+            .map(|(new, &old)| (old, new as u16))
+            .collect();
+
+        let h_metrics = read_u16(hhea, 34)?.max(1);
+        let mut new_glyf = Vec::new();
+        let mut new_loca = Vec::new();
+        let mut new_hmtx = Vec::new();
+        let mut advances = Vec::with_capacity(keep.len());
+        for &old in &keep {
+            new_loca.extend_from_slice(&(new_glyf.len() as u32).to_be_bytes());
+            let data = glyph_data(old)?;
+            let start = new_glyf.len();
+            new_glyf.extend_from_slice(data);
+            for (offset, component) in components(data) {
+                let new = glyph_ids.get(&component).copied().unwrap_or(0);
+                new_glyf[start + offset..start + offset + 2].copy_from_slice(&new.to_be_bytes());
+            }
+            while new_glyf.len() % 4 != 0 {
+                new_glyf.push(0);
+            }
+
+            let (advance, lsb) = if old < h_metrics {
+                (
+                    read_u16(hmtx, old as usize * 4)?,
+                    read_u16(hmtx, old as usize * 4 + 2)?,
+                )
+            } else {
+                (
+                    read_u16(hmtx, (h_metrics as usize - 1) * 4)?,
+                    read_u16(
+                        hmtx,
+                        h_metrics as usize * 4 + (old - h_metrics) as usize * 2,
+                    )?,
+                )
+            };
+            new_hmtx.extend_from_slice(&advance.to_be_bytes());
+            new_hmtx.extend_from_slice(&lsb.to_be_bytes());
+            advances.push(advance);
+        }
+        new_loca.extend_from_slice(&(new_glyf.len() as u32).to_be_bytes());
+
+        let count = (keep.len() as u16).to_be_bytes();
+        let mut new_head = head.to_vec();
+        new_head.get_mut(8..12)?.copy_from_slice(&[0; 4]);
+        new_head
+            .get_mut(50..52)?
+            .copy_from_slice(&1u16.to_be_bytes());
+        let mut new_hhea = hhea.to_vec();
+        new_hhea.get_mut(34..36)?.copy_from_slice(&count);
+        let mut new_maxp = maxp.to_vec();
+        new_maxp.get_mut(4..6)?.copy_from_slice(&count);
+
+        let mut tables: Vec<([u8; 4], Vec<u8>)> = vec![
+            (*b"glyf", new_glyf),
+            (*b"head", new_head),
+            (*b"hhea", new_hhea),
+            (*b"hmtx", new_hmtx),
+            (*b"loca", new_loca),
+            (*b"maxp", new_maxp),
+        ];
+        // Hinting programs refer to no glyph ids and are kept as they are
+        for tag in [b"cvt ", b"fpgm", b"prep"] {
+            if let Some(data) = table(tag) {
+                tables.push((*tag, data.to_vec()));
+            }
+        }
+
+        Some(FontSubset {
+            data: write_font(tables),
+            glyph_ids,
+            advances,
+        })
+    }
+}
+
+/// A font cut down to the glyphs a document uses
+pub struct FontSubset {
+    /// A standalone TrueType font
+    pub data: Vec<u8>,
+    /// Glyph id in the original font → glyph id in the subset
+    pub glyph_ids: BTreeMap<u16, u16>,
+    /// Advance width of each subset glyph, in font units
+    pub advances: Vec<u16>,
+}
+
+/// (offset of the glyph index, glyph index) of each component of a composite
+/// glyph; empty for simple glyphs
+fn components(glyph: &[u8]) -> Vec<(usize, u16)> {
+    const ARGS_ARE_WORDS: u16 = 0x0001;
+    const HAVE_SCALE: u16 = 0x0008;
+    const MORE_COMPONENTS: u16 = 0x0020;
+    const HAVE_XY_SCALE: u16 = 0x0040;
+    const HAVE_TWO_BY_TWO: u16 = 0x0080;
+
+    let mut found = Vec::new();
+    if glyph.len() < 10 || i16::from_be_bytes([glyph[0], glyph[1]]) >= 0 {
+        return found;
+    }
+    let mut offset = 10;
+    while let (Some(flags), Some(id)) = (read_u16(glyph, offset), read_u16(glyph, offset + 2)) {
+        found.push((offset + 2, id));
+        offset += 4;
+        offset += if flags & ARGS_ARE_WORDS != 0 { 4 } else { 2 };
+        if flags & HAVE_SCALE != 0 {
+            offset += 2;
+        } else if flags & HAVE_XY_SCALE != 0 {
+            offset += 4;
+        } else if flags & HAVE_TWO_BY_TWO != 0 {
+            offset += 8;
+        }
+        if flags & MORE_COMPONENTS == 0 {
+            break;
+        }
+    }
+    found
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(
+        data.get(offset..offset + 2)?.try_into().ok()?,
+    ))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(
+        data.get(offset..offset + 4)?.try_into().ok()?,
+    ))
+}
+
+fn checksum(data: &[u8]) -> u32 {
+    data.chunks(4).fold(0u32, |sum, chunk| {
+        let mut word = [0u8; 4];
+        word[..chunk.len()].copy_from_slice(chunk);
+        sum.wrapping_add(u32::from_be_bytes(word))
+    })
+}
+
+/// Serialises tables into an sfnt file, sorted by tag, with checksums
+fn write_font(mut tables: Vec<([u8; 4], Vec<u8>)>) -> Vec<u8> {
+    tables.sort_by_key(|(tag, _)| *tag);
+    let count = tables.len() as u16;
+    let entry_selector = 15 - count.leading_zeros() as u16;
+    let search_range = (1u16 << entry_selector) * 16;
+
+    let mut out = Vec::new();
+    out.extend_from_slice(&0x0001_0000u32.to_be_bytes());
+    out.extend_from_slice(&count.to_be_bytes());
+    out.extend_from_slice(&search_range.to_be_bytes());
+    out.extend_from_slice(&entry_selector.to_be_bytes());
+    out.extend_from_slice(&(count * 16 - search_range).to_be_bytes());
+
+    let mut offset = 12 + tables.len() * 16;
+    let mut head_offset = None;
+    for (tag, data) in &tables {
+        if tag == b"head" {
+            head_offset = Some(offset);
+        }
+        out.extend_from_slice(tag);
+        out.extend_from_slice(&checksum(data).to_be_bytes());
+        out.extend_from_slice(&(offset as u32).to_be_bytes());
+        out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+        offset += data.len().div_ceil(4) * 4;
+    }
+    for (_, data) in &tables {
+        out.extend_from_slice(data);
+        out.resize(out.len().div_ceil(4) * 4, 0);
+    }
+
+    if let Some(head) = head_offset {
+        let adjustment = 0xB1B0_AFBAu32.wrapping_sub(checksum(&out));
+        out[head + 8..head + 12].copy_from_slice(&adjustment.to_be_bytes());
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ttf_parser::GlyphId;
+
+    fn system_font() -> Option<TrueTypeFont> {
+        system_fonts()
+            .into_iter()
+            .find_map(|(regular, _)| TrueTypeFont::load(&regular))
+    }
+
+    #[test]
+    fn test_subset_keeps_used_glyphs() {
+        // Needs a TrueType system font; nothing to check without one
+        let Some(font) = system_font() else { return };
+        let glyphs: BTreeSet<u16> = "Hi".chars().filter_map(|c| font.glyph(c)).collect();
+        let subset = font.subset(&glyphs).unwrap();
+        assert_eq!(subset.glyph_ids.len(), glyphs.len() + 1);
+        assert_eq!(subset.advances.len(), glyphs.len() + 1);
+
+        let face = Face::parse(&subset.data, 0).unwrap();
+        assert_eq!(face.number_of_glyphs() as usize, glyphs.len() + 1);
+        let original = font.face().unwrap();
+        for (&old, &new) in &subset.glyph_ids {
+            assert_eq!(
+                face.glyph_hor_advance(GlyphId(new)),
+                original.glyph_hor_advance(GlyphId(old))
+            );
+            assert_eq!(
+                face.glyph_bounding_box(GlyphId(new)),
+                original.glyph_bounding_box(GlyphId(old))
+            );
+        }
+        assert_eq!(checksum(&subset.data), 0xB1B0_AFBA);
+    }
+
+    #[test]
+    fn test_composite_components() {
+        // A composite glyph (-1 contours) of two components, word arguments
+        // and a scale on the first
+        let mut glyph = vec![0xff, 0xff, 0, 0, 0, 0, 0, 0, 0, 0];
+        glyph.extend_from_slice(&[0x00, 0x29, 0x00, 0x07, 0, 0, 0, 0, 0x40, 0x00]);
+        glyph.extend_from_slice(&[0x00, 0x00, 0x00, 0x09, 0, 0]);
+        assert_eq!(components(&glyph), vec![(12, 7), (22, 9)]);
+        assert!(components(&[0, 1, 0, 0, 0, 0, 0, 0, 0, 0]).is_empty());
+    }
+}
@@ -0,0 +1,188 @@
+use serde::{Deserialize, Serialize};
+use std::sync::RwLock;
+use std::time::{Duration, Instant};
+use tauri::command;
+
+/// Gemini model information returned to frontend
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct GeminiModel {
+    pub id: String,
+    pub display_name: Option<String>,
+    pub input_token_limit: Option<u32>,
+}
+
+/// API response model from Gemini
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiApiModel {
+    /// Resource name, e.g. "models/gemini-2.5-flash"
+    name: String,
+    display_name: Option<String>,
+    input_token_limit: Option<u32>,
+    #[serde(default)]
+    supported_generation_methods: Vec<String>,
+}
+
+/// API response wrapper from Gemini
+#[derive(Debug, Deserialize)]
+struct GeminiApiResponse {
+    #[serde(default)]
+    models: Vec<GeminiApiModel>,
+}
+
+/// Cache entry for models
+struct CacheEntry {
+    models: Vec<GeminiModel>,
+    fetched_at: Instant,
+}
+
+/// Global cache for Gemini models (5 minute TTL)
+static MODELS_CACHE: RwLock<Option<CacheEntry>> = RwLock::new(None);
+
+/// Cache TTL in seconds
+const CACHE_TTL_SECS: u64 = 300;
+
+/// Fallback models when API fetch fails (matches frontend hardcoded values)
+const FALLBACK_MODELS: &[(&str, &str)] = &[
+    ("gemini-2.5-flash", "Gemini 2.5 Flash"),
+    ("gemini-2.5-pro", "Gemini 2.5 Pro"),
+    ("gemini-2.5-flash-lite", "Gemini 2.5 Flash-Lite"),
+];
+
+/// Get fallback models as GeminiModel vec
+fn get_fallback_models() -> Vec<GeminiModel> {
+    FALLBACK_MODELS
+        .iter()
+        .map(|(id, name)| GeminiModel {
+            id: id.to_string(),
+            display_name: Some(name.to_string()),
+            input_token_limit: None,
+        })
+        .collect()
+}
+
+/// Check if model can generate text (filter out embedding, image and TTS models)
+fn is_chat_model(model: &GeminiApiModel) -> bool {
+    let id = model.name.to_lowercase();
+    model
+        .supported_generation_methods
+        .iter()
+        .any(|m| m == "generateContent")
+        && id.contains("gemini")
+        && !id.contains("embedding")
+        && !id.contains("image")
+        && !id.contains("tts")
+}
+
+/// Fetch Gemini models from API
+///
+/// # Arguments
+/// * `api_key` - Gemini API key
+///
+/// # Returns
+/// Vector of available models, or fallback models on error
+#[command]
+pub async fn get_gemini_models(api_key: Option<String>) -> Result<Vec<GeminiModel>, String> {
+    // Return fallback if no API key provided
+    let api_key = match api_key {
+        Some(key) if !key.trim().is_empty() => key.trim().to_string(),
+        _ => {
+            log::info!("No Gemini API key provided, returning fallback models");
+            return Ok(get_fallback_models());
+        }
+    };
+
+    // Check cache first
+    {
+        let cache = MODELS_CACHE.read().map_err(|e| e.to_string())?;
+        if let Some(entry) = cache.as_ref() {
+            if entry.fetched_at.elapsed() < Duration::from_secs(CACHE_TTL_SECS) {
+                log::info!(
+                    "Returning cached Gemini models ({} models)",
+                    entry.models.len()
+                );
+                return Ok(entry.models.clone());
+            }
+        }
+    }
+
+    // Fetch from API
+    log::info!("Fetching Gemini models from API...");
+    let client = reqwest::Client::new();
+
+    let response = match client
+        .get("https://generativelanguage.googleapis.com/v1beta/models")
+        .query(&[("pageSize", "1000")])
+        .header("x-goog-api-key", &api_key)
+        .timeout(Duration::from_secs(5))
+        .send()
+        .await
+    {
+        Ok(resp) => resp,
+        Err(e) => {
+            log::warn!("Failed to fetch Gemini models: {}. Using fallback.", e);
+            return Ok(get_fallback_models());
+        }
+    };
+
+    if !response.status().is_success() {
+        let status = response.status();
+        log::warn!(
+            "Gemini API returned status {}. Using fallback models.",
+            status
+        );
+        return Ok(get_fallback_models());
+    }
+
+    let api_response: GeminiApiResponse = match response.json().await {
+        Ok(data) => data,
+        Err(e) => {
+            log::warn!("Failed to parse Gemini response: {}. Using fallback.", e);
+            return Ok(get_fallback_models());
+        }
+    };
+
+    // Filter to only text generation models and map to our struct. The id drops
+    // the "models/" prefix so it can be used directly in generateContent URLs.
+    let models: Vec<GeminiModel> = api_response
+        .models
+        .into_iter()
+        .filter(is_chat_model)
+        .map(|m| GeminiModel {
+            id: m
+                .name
+                .strip_prefix("models/")
+                .unwrap_or(&m.name)
+                .to_string(),
+            display_name: m.display_name,
+            input_token_limit: m.input_token_limit,
+        })
+        .collect();
+
+    // If no models returned, use fallback
+    if models.is_empty() {
+        log::warn!("No chat models returned from Gemini API. Using fallback.");
+        return Ok(get_fallback_models());
+    }
+
+    log::info!("Fetched {} Gemini models from API", models.len());
+
+    // Update cache
+    {
+        let mut cache = MODELS_CACHE.write().map_err(|e| e.to_string())?;
+        *cache = Some(CacheEntry {
+            models: models.clone(),
+            fetched_at: Instant::now(),
+        });
+    }
+
+    Ok(models)
+}
+
+/// Clear the models cache (useful when API key changes)
+pub fn clear_cache() {
+    if let Ok(mut cache) = MODELS_CACHE.write() {
+        *cache = None;
+        log::info!("Gemini models cache cleared");
+    }
+}
@@ -41,14 +41,19 @@ pub mod audio;
 pub mod config;
 pub mod console_utils;
 pub mod database;
+pub mod diarization;
+pub mod export;
 pub mod notifications;
 pub mod ollama;
 pub mod onboarding;
 pub mod openai;
 pub mod anthropic;
 pub mod groq;
+pub mod gemini;
 pub mod openrouter;
 pub mod parakeet_engine;
+pub mod qa;
+pub mod server;
 pub mod state;
 pub mod summary;
 pub mod tray;
@@ -456,6 +461,9 @@ pub fn run() {
                 }
             });
 
+            // Set speaker diarization models directory
+            diarization::commands::set_models_directory(&_app.handle());
+
             // Initialize ModelManager for summary engine (async, non-blocking)
             let app_handle_for_model_manager = _app.handle().clone();
             tauri::async_runtime::spawn(async move {
@@ -484,6 +492,9 @@ pub fn run() {
             })
             .expect("Failed to initialize database");
 
+            // Start the local REST/WebSocket API if the user enabled it
+            tauri::async_runtime::spawn(server::start_if_enabled(_app.handle().clone()));
+
             // Initialize bundled templates directory for dynamic template discovery
             log::info!("Initializing bundled templates directory...");
             if let Ok(resource_path) = _app.handle().path().resource_dir() {
@@ -603,8 +614,22 @@ pub fn run() {
             openai::openai::get_openai_models,
             anthropic::anthropic::get_anthropic_models,
             groq::groq::get_groq_models,
+            gemini::gemini::get_gemini_models,
             api::api_get_meetings,
             api::api_search_transcripts,
+            api::api_search_meetings,
+            api::api_get_transcript_words,
+            api::api_get_meeting_speakers,
+            api::api_rename_meeting_speaker,
+            api::api_merge_meeting_speakers,
+            api::api_split_meeting_speaker,
+            api::api_get_speaker_profiles,
+            api::api_delete_speaker_profile,
+            export::export_meeting,
+            server::api_server_get_settings,
+            server::api_server_save_settings,
+            server::api_server_regenerate_token,
+            server::api_server_status,
             api::api_get_profile,
             api::api_save_profile,
             api::api_update_profile,
@@ -629,12 +654,23 @@ pub fn run() {
             // Custom OpenAI commands
             api::api_save_custom_openai_config,
             api::api_get_custom_openai_config,
+            api::api_save_llm_fallback_chain,
+            api::api_get_llm_fallback_chain,
             api::api_test_custom_openai_connection,
             // Summary commands
             summary::api_process_transcript,
             summary::api_get_summary,
             summary::api_save_meeting_summary,
             summary::api_cancel_summary,
+            // Action item commands
+            summary::api_extract_action_items,
+            summary::api_list_action_items,
+            summary::api_update_action_item_status,
+            // Meeting Q&A commands
+            qa::api_ask_meetings,
+            qa::api_rebuild_qa_index,
+            qa::api_get_qa_settings,
+            qa::api_save_qa_settings,
             // Template commands
             summary::api_list_templates,
             summary::api_get_template_details,
@@ -682,6 +718,7 @@ pub fn run() {
             audio::system_audio_commands::start_system_audio_monitoring,
             audio::system_audio_commands::stop_system_audio_monitoring,
             audio::system_audio_commands::get_system_audio_monitoring_status,
+            audio::system_audio_commands::list_audio_producing_apps_command,
             // Screen Recording permission commands
             audio::permissions::check_screen_recording_permission_command,
             audio::permissions::request_screen_recording_permission_command,
@@ -716,6 +753,10 @@ pub fn run() {
             audio::import::start_import_audio_command,
             audio::import::cancel_import_command,
             audio::import::is_import_in_progress_command,
+            // Speaker diarization commands
+            diarization::commands::diarization_get_model_status,
+            diarization::commands::diarization_download_model,
+            diarization::commands::diarize_meeting_command,
         ])
         .build(tauri::generate_context!())
         .expect("error while building tauri application")
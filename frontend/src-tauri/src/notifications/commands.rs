@@ -444,6 +444,20 @@ pub async fn show_transcription_complete_notification(
     }
 }
 
+/// Show meeting detected notification (internal use)
+pub async fn show_meeting_detected_notification(
+    manager_state: &NotificationManagerState<Wry>,
+    apps: Vec<String>,
+) -> Result<()> {
+    let manager_lock = manager_state.read().await;
+    if let Some(manager) = manager_lock.as_ref() {
+        manager.show_meeting_detected(apps).await
+    } else {
+        log_error!("Cannot show meeting detected notification: manager not initialized");
+        Ok(())
+    }
+}
+
 /// Show system error notification (internal use)
 pub async fn show_system_error_notification(
     manager_state: &NotificationManagerState<Wry>,
@@ -172,6 +172,17 @@ impl<R: Runtime> NotificationManager<R> {
         self.show_notification(notification).await
     }
 
+    /// Offer to start recording when a call is detected
+    pub async fn show_meeting_detected(&self, apps: Vec<String>) -> Result<()> {
+        let settings = self.settings.read().await;
+        if !settings.notification_preferences.show_meeting_detected {
+            return Ok(());
+        }
+
+        let notification = Notification::meeting_detected(apps);
+        self.show_notification(notification).await
+    }
+
     /// Show a system error notification
     pub async fn show_system_error(&self, error: String) -> Result<()> {
         let settings = self.settings.read().await;
@@ -299,6 +310,7 @@ impl<R: Runtime> NotificationManager<R> {
             NotificationType::RecordingResumed => settings.notification_preferences.show_recording_resumed,
             NotificationType::TranscriptionComplete => settings.notification_preferences.show_transcription_complete,
             NotificationType::MeetingReminder(_) => settings.notification_preferences.show_meeting_reminders,
+            NotificationType::MeetingDetected(_) => settings.notification_preferences.show_meeting_detected,
             NotificationType::SystemError(_) => settings.notification_preferences.show_system_errors,
             NotificationType::Test => true, // Always show test notifications
         }
@@ -55,6 +55,10 @@ pub struct NotificationPreferences {
     /// Show meeting reminder notifications
     pub show_meeting_reminders: bool,
 
+    /// Offer to start recording when a call is detected
+    #[serde(default = "default_true")]
+    pub show_meeting_detected: bool,
+
     /// Show system error notifications
     pub show_system_errors: bool,
 
@@ -87,12 +91,17 @@ impl Default for NotificationPreferences {
             show_recording_resumed: true,
             show_transcription_complete: true,
             show_meeting_reminders: true,
+            show_meeting_detected: true,
             show_system_errors: true,
             meeting_reminder_minutes: vec![15, 5], // 15 minutes and 5 minutes before
         }
     }
 }
 
+fn default_true() -> bool {
+    true
+}
+
 /// Manages notification consent and user preferences
 pub struct ConsentManager<R: Runtime> {
     #[allow(dead_code)] // Reserved for future functionality
@@ -1,7 +1,7 @@
 use crate::notifications::types::{Notification, NotificationPriority, NotificationTimeout};
 use anyhow::{Result, anyhow};
 use log::{info as log_info, error as log_error};
-use tauri::{AppHandle, Runtime};
+use tauri::{AppHandle, Emitter, Runtime};
 use tauri_plugin_notification::NotificationExt;
 use std::time::Duration;
 
@@ -27,6 +27,14 @@ impl<R: Runtime> SystemNotificationHandler<R> {
             return Ok(());
         }
 
+        // Desktop notifications can't carry buttons, so the app window offers
+        // the actions (e.g. "Start recording") instead
+        if !notification.actions.is_empty() {
+            if let Err(e) = self.app_handle.emit("notification-actions", &notification) {
+                log_error!("Failed to emit notification actions: {}", e);
+            }
+        }
+
         // Use Tauri notification for all platforms
         log_info!("Showing Tauri notification: {}", notification.title);
 
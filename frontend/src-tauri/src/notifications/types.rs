@@ -21,6 +21,7 @@ pub enum NotificationType {
     RecordingResumed,
     TranscriptionComplete,
     MeetingReminder(u64), // Duration in minutes
+    MeetingDetected(Vec<String>), // Apps in the call
     SystemError(String),
     Test, // For testing notifications
 }
@@ -176,6 +177,23 @@ impl Notification {
             .with_timeout(NotificationTimeout::Seconds(10))
     }
 
+    pub fn meeting_detected(apps: Vec<String>) -> Self {
+        let body = match apps.as_slice() {
+            [] => "A call has started. Start recording?".to_string(),
+            [app] => format!("{} is in a call. Start recording?", app),
+            _ => format!("{} are in a call. Start recording?", apps.join(", ")),
+        };
+
+        Notification::new("Meetily", body, NotificationType::MeetingDetected(apps))
+            .with_priority(NotificationPriority::High)
+            .with_timeout(NotificationTimeout::Seconds(15))
+            .add_action(NotificationAction {
+                id: "start_recording".to_string(),
+                title: "Start recording".to_string(),
+                action_type: NotificationActionType::Button,
+            })
+    }
+
     pub fn system_error(error: impl Into<String>) -> Self {
         let error_string = error.into();
         Notification::new(
@@ -1,3 +1,4 @@
+use crate::audio::transcription::{words_from_tokens, TokenTiming, WordTiming};
 use crate::parakeet_engine::model::ParakeetModel;
 use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
@@ -10,6 +11,9 @@ use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 use tokio::time::timeout;
 
+/// Upper bound on a single token's duration when deriving word end times
+const MAX_TOKEN_DURATION_SECS: f64 = 1.0;
+
 /// Quantization type for Parakeet models
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum QuantizationType {
@@ -452,6 +456,19 @@ impl ParakeetEngine {
 
     /// Transcribe audio samples using the loaded Parakeet model
     pub async fn transcribe_audio(&self, audio_data: Vec<f32>) -> Result<String> {
+        self.transcribe_audio_with_words(audio_data)
+            .await
+            .map(|(text, _)| text)
+    }
+
+    /// Transcribe audio and return word timings relative to the start of the audio.
+    /// Parakeet reports only a start time per token, so each token ends where the
+    /// next one starts (at most `MAX_TOKEN_DURATION_SECS` later, so a word before a
+    /// pause does not stretch over the silence) and the last one ends with the audio.
+    pub async fn transcribe_audio_with_words(
+        &self,
+        audio_data: Vec<f32>,
+    ) -> Result<(String, Vec<WordTiming>)> {
         let mut model_guard = self.current_model.write().await;
         let model = model_guard
             .as_mut()
@@ -471,7 +488,26 @@ impl ParakeetEngine {
 
         log::debug!("Parakeet transcription result: '{}'", result.text);
 
-        Ok(result.text)
+        let tokens: Vec<TokenTiming> = result
+            .tokens
+            .iter()
+            .zip(result.timestamps.iter())
+            .enumerate()
+            .map(|(i, (text, &start))| TokenTiming {
+                text: text.clone(),
+                start: start as f64,
+                end: result
+                    .timestamps
+                    .get(i + 1)
+                    .map(|&next| next as f64)
+                    .unwrap_or(duration_seconds)
+                    .min(start as f64 + MAX_TOKEN_DURATION_SECS)
+                    .max(start as f64),
+                probability: None,
+            })
+            .collect();
+
+        Ok((result.text, words_from_tokens(&tokens)))
     }
 
     /// Get the models directory path
@@ -0,0 +1,168 @@
+//! Prompt construction and citation handling for answers
+
+use serde::{Deserialize, Serialize};
+
+use super::passages::PassageSource;
+use crate::database::repositories::qa_index::RetrievedPassage;
+use crate::export::document::format_clock;
+
+/// Earlier turns included with a follow-up question
+const MAX_HISTORY_MESSAGES: usize = 6;
+const MAX_HISTORY_CHARS: usize = 1000;
+
+pub const SYSTEM_PROMPT: &str = r#"You answer questions about the user's past meetings.
+
+Use ONLY the numbered sources provided with the question. Each source is an excerpt from a meeting transcript or summary, labelled with the meeting title, date and, for transcripts, the time in the recording.
+
+Rules:
+- Cite every statement with the number of the source it comes from, in square brackets, e.g. "Billing moves to Stripe in March [2]." Cite several sources as [1][3].
+- Mention the meeting (and date) when it helps the reader place an answer.
+- If the sources don't contain the answer, say so plainly instead of guessing.
+- Answer in concise Markdown."#;
+
+/// A previous turn of the conversation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QaMessage {
+    /// "user" or "assistant"
+    pub role: String,
+    pub content: String,
+}
+
+/// A retrieved source, numbered as it was shown to the model
+#[derive(Debug, Clone, Serialize)]
+pub struct QaCitation {
+    pub index: usize,
+    pub meeting_id: String,
+    pub meeting_title: String,
+    /// YYYY-MM-DD
+    pub meeting_date: String,
+    pub source: PassageSource,
+    pub transcript_id: Option<String>,
+    /// Seconds into the recording
+    pub audio_start_time: Option<f64>,
+    /// audio_start_time as MM:SS / H:MM:SS
+    pub timestamp: Option<String>,
+    pub text: String,
+    pub score: f32,
+    /// Whether the answer refers to this source
+    pub cited: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct QaAnswer {
+    pub answer: String,
+    pub citations: Vec<QaCitation>,
+}
+
+pub fn to_citations(retrieved: Vec<RetrievedPassage>) -> Vec<QaCitation> {
+    retrieved
+        .into_iter()
+        .enumerate()
+        .map(|(i, r)| QaCitation {
+            index: i + 1,
+            meeting_id: r.meeting_id,
+            meeting_title: r.meeting_title,
+            meeting_date: r.meeting_created_at.format("%Y-%m-%d").to_string(),
+            source: r.passage.source,
+            transcript_id: r.passage.transcript_id,
+            audio_start_time: r.passage.audio_start_time,
+            timestamp: r.passage.audio_start_time.map(format_clock),
+            text: r.passage.text,
+            score: r.score,
+            cited: false,
+        })
+        .collect()
+}
+
+pub fn build_user_prompt(question: &str, history: &[QaMessage], sources: &[QaCitation]) -> String {
+    let mut prompt = String::from("<sources>\n");
+    for source in sources {
+        let location = match (&source.source, &source.timestamp) {
+            (PassageSource::Summary, _) => "summary".to_string(),
+            (PassageSource::Transcript, Some(timestamp)) => format!("transcript at {}", timestamp),
+            (PassageSource::Transcript, None) => "transcript".to_string(),
+        };
+        prompt.push_str(&format!(
+            "[{}] \"{}\" ({}), {}:\n{}\n\n",
+            source.index, source.meeting_title, source.meeting_date, location, source.text
+        ));
+    }
+    prompt.push_str("</sources>\n\n");
+
+    let recent = &history[history.len().saturating_sub(MAX_HISTORY_MESSAGES)..];
+    if !recent.is_empty() {
+        prompt.push_str("<conversation>\n");
+        for message in recent {
+            let role = if message.role == "assistant" {
+                "Assistant"
+            } else {
+                "User"
+            };
+            let content: String = message.content.chars().take(MAX_HISTORY_CHARS).collect();
+            prompt.push_str(&format!("{}: {}\n", role, content.trim()));
+        }
+        prompt.push_str("</conversation>\n\n");
+    }
+
+    prompt.push_str(&format!("Question: {}", question.trim()));
+    prompt
+}
+
+/// Source numbers referenced in the answer as [n], [n, m] or [n][m]
+pub fn cited_indices(answer: &str) -> Vec<usize> {
+    let mut indices = Vec::new();
+    for part in answer.split('[').skip(1) {
+        let Some(end) = part.find(']') else {
+            continue;
+        };
+        for number in part[..end].split(',') {
+            if let Ok(n) = number.trim().parse::<usize>() {
+                if !indices.contains(&n) {
+                    indices.push(n);
+                }
+            }
+        }
+    }
+    indices
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cited_indices() {
+        assert_eq!(
+            cited_indices("Moved to Stripe [2]. Owner is Bob [1][3], see [2, 4] and [link](x)."),
+            vec![2, 1, 3, 4]
+        );
+        assert!(cited_indices("No sources mention this.").is_empty());
+    }
+
+    #[test]
+    fn test_build_user_prompt_labels_sources_and_history() {
+        let sources = vec![QaCitation {
+            index: 1,
+            meeting_id: "meeting-1".to_string(),
+            meeting_title: "Billing sync".to_string(),
+            meeting_date: "2026-01-12".to_string(),
+            source: PassageSource::Transcript,
+            transcript_id: Some("t-1".to_string()),
+            audio_start_time: Some(754.0),
+            timestamp: Some("12:34".to_string()),
+            text: "Alice: We move billing to Stripe.".to_string(),
+            score: 0.8,
+            cited: false,
+        }];
+        let history = vec![QaMessage {
+            role: "assistant".to_string(),
+            content: "Earlier answer".to_string(),
+        }];
+        let prompt = build_user_prompt("Who owns it?", &history, &sources);
+        assert!(prompt.contains(
+            "[1] \"Billing sync\" (2026-01-12), transcript at 12:34:\nAlice: We move billing to Stripe."
+        ));
+        assert!(prompt.contains("Assistant: Earlier answer"));
+        assert!(prompt.ends_with("Question: Who owns it?"));
+    }
+}
@@ -0,0 +1,169 @@
+use log::{error, info};
+use reqwest::Client;
+use tauri::{AppHandle, Manager, Runtime};
+
+use super::answer::{
+    build_user_prompt, cited_indices, to_citations, QaAnswer, QaMessage, SYSTEM_PROMPT,
+};
+use super::embeddings::embed_texts;
+use super::index::refresh_index;
+use super::settings::{load_qa_settings, save_qa_settings, EmbeddingProvider, QaSettings};
+use crate::database::repositories::qa_index::QaIndexRepository;
+use crate::database::repositories::setting::SettingsRepository;
+use crate::state::AppState;
+use crate::summary::llm_client::LlmRequest;
+use crate::summary::{clean_llm_markdown_output, SummaryService};
+
+/// Loads Q&A settings, filling in the summary Ollama endpoint when none is set
+async fn resolve_settings<R: Runtime>(
+    app: &AppHandle<R>,
+    state: &AppState,
+) -> Result<QaSettings, String> {
+    let mut settings =
+        load_qa_settings(app).map_err(|e| format!("Failed to load Q&A settings: {}", e))?;
+    if settings.embedding_provider == EmbeddingProvider::Ollama
+        && settings.ollama_endpoint.is_none()
+    {
+        settings.ollama_endpoint = SettingsRepository::get_model_config(state.db_manager.pool())
+            .await
+            .ok()
+            .flatten()
+            .and_then(|config| config.ollama_endpoint);
+    }
+    Ok(settings)
+}
+
+/// Answers a question from the meeting archive, citing the retrieved passages
+///
+/// Meetings that changed since they were last embedded are re-indexed first.
+/// `meeting_ids` restricts retrieval to those meetings; `history` carries the
+/// previous turns of a chat so follow-up questions work.
+#[tauri::command]
+pub async fn api_ask_meetings<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    question: String,
+    meeting_ids: Option<Vec<String>>,
+    history: Option<Vec<QaMessage>>,
+) -> Result<QaAnswer, String> {
+    let question = question.trim().to_string();
+    if question.is_empty() {
+        return Err("Question cannot be empty".to_string());
+    }
+    info!("api_ask_meetings called: {}", question);
+
+    let pool = state.db_manager.pool();
+    let settings = resolve_settings(&app, &state).await?;
+    let app_data_dir = app.path().app_data_dir().ok();
+    let client = Client::new();
+
+    refresh_index(
+        &app,
+        pool,
+        &client,
+        &settings,
+        app_data_dir.as_ref(),
+        meeting_ids.as_deref(),
+        false,
+    )
+    .await?;
+
+    let query_vector = embed_texts(
+        &client,
+        &settings,
+        app_data_dir.as_ref(),
+        &[question.clone()],
+    )
+    .await?
+    .pop()
+    .ok_or("No embedding returned for the question")?;
+
+    let retrieved = QaIndexRepository::search(
+        pool,
+        &settings.index_key(),
+        &query_vector,
+        meeting_ids.as_deref(),
+        settings.top_k.max(1),
+    )
+    .await
+    .map_err(|e| format!("Failed to search Q&A index: {}", e))?;
+    if retrieved.is_empty() {
+        return Err("No meeting content has been indexed yet".to_string());
+    }
+
+    let mut citations = to_citations(retrieved);
+    let user_prompt = build_user_prompt(&question, &history.unwrap_or_default(), &citations);
+
+    let model_config = SettingsRepository::get_model_config(pool)
+        .await
+        .map_err(|e| format!("Failed to load model config: {}", e))?
+        .ok_or("No summary model configured")?;
+    let provider_settings = SummaryService::resolve_provider_settings(
+        pool,
+        &model_config.provider,
+        &model_config.model,
+        app_data_dir.as_ref(),
+    )
+    .await?;
+
+    let request = LlmRequest {
+        system_prompt: SYSTEM_PROMPT,
+        user_prompt: &user_prompt,
+        json_schema: None,
+    };
+    let raw_answer = provider_settings
+        .llm
+        .complete(&request, None, None)
+        .await
+        .map_err(|e| {
+            error!("Q&A answer generation failed: {}", e);
+            e.to_string()
+        })?;
+
+    let answer = clean_llm_markdown_output(&raw_answer);
+    for index in cited_indices(&answer) {
+        if let Some(citation) = citations.iter_mut().find(|c| c.index == index) {
+            citation.cited = true;
+        }
+    }
+
+    Ok(QaAnswer { answer, citations })
+}
+
+/// Embeds meetings that changed since they were last indexed, or all of them
+/// when `force` is set. Emits `qa-index-progress` per meeting.
+#[tauri::command]
+pub async fn api_rebuild_qa_index<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    force: Option<bool>,
+) -> Result<usize, String> {
+    let settings = resolve_settings(&app, &state).await?;
+    let app_data_dir = app.path().app_data_dir().ok();
+    refresh_index(
+        &app,
+        state.db_manager.pool(),
+        &Client::new(),
+        &settings,
+        app_data_dir.as_ref(),
+        None,
+        force.unwrap_or(false),
+    )
+    .await
+}
+
+#[tauri::command]
+pub async fn api_get_qa_settings<R: Runtime>(app: AppHandle<R>) -> Result<QaSettings, String> {
+    load_qa_settings(&app).map_err(|e| format!("Failed to load Q&A settings: {}", e))
+}
+
+#[tauri::command]
+pub async fn api_save_qa_settings<R: Runtime>(
+    app: AppHandle<R>,
+    settings: QaSettings,
+) -> Result<(), String> {
+    if settings.embedding_model.trim().is_empty() {
+        return Err("Embedding model cannot be empty".to_string());
+    }
+    save_qa_settings(&app, &settings).map_err(|e| format!("Failed to save Q&A settings: {}", e))
+}
@@ -0,0 +1,114 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use log::info;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+
+use super::settings::{EmbeddingProvider, QaSettings};
+use crate::diarization::clustering::l2_normalize;
+use crate::summary::summary_engine::embed_with_builtin;
+
+/// Texts sent per embedding request
+const EMBEDDING_BATCH_SIZE: usize = 16;
+
+const OLLAMA_TIMEOUT_SECS: u64 = 120;
+
+#[derive(Debug, Serialize)]
+struct OllamaEmbedRequest<'a> {
+    model: &'a str,
+    input: &'a [String],
+}
+
+#[derive(Debug, Deserialize)]
+struct OllamaEmbedResponse {
+    embeddings: Vec<Vec<f32>>,
+}
+
+/// Embeds texts with the configured provider; vectors are L2-normalized
+pub async fn embed_texts(
+    client: &Client,
+    settings: &QaSettings,
+    app_data_dir: Option<&PathBuf>,
+    texts: &[String],
+) -> Result<Vec<Vec<f32>>, String> {
+    let mut vectors = Vec::with_capacity(texts.len());
+
+    for batch in texts.chunks(EMBEDDING_BATCH_SIZE) {
+        let mut batch_vectors = match settings.embedding_provider {
+            EmbeddingProvider::BuiltinAi => {
+                let app_data_dir =
+                    app_data_dir.ok_or("App data directory is required for built-in embeddings")?;
+                embed_with_builtin(app_data_dir, &settings.embedding_model, batch)
+                    .await
+                    .map_err(|e| format!("Built-in embedding failed: {}", e))?
+            }
+            EmbeddingProvider::Ollama => {
+                embed_with_ollama(
+                    client,
+                    settings.ollama_endpoint.as_deref(),
+                    &settings.embedding_model,
+                    batch,
+                )
+                .await?
+            }
+        };
+        for vector in batch_vectors.iter_mut() {
+            l2_normalize(vector);
+        }
+        vectors.extend(batch_vectors);
+    }
+
+    info!(
+        "Embedded {} texts with {}",
+        texts.len(),
+        settings.index_key()
+    );
+    Ok(vectors)
+}
+
+async fn embed_with_ollama(
+    client: &Client,
+    endpoint: Option<&str>,
+    model: &str,
+    texts: &[String],
+) -> Result<Vec<Vec<f32>>, String> {
+    let host = endpoint
+        .map(|s| s.trim_end_matches('/').to_string())
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| "http://localhost:11434".to_string());
+
+    let response = client
+        .post(format!("{}/api/embed", host))
+        .timeout(Duration::from_secs(OLLAMA_TIMEOUT_SECS))
+        .json(&OllamaEmbedRequest {
+            model,
+            input: texts,
+        })
+        .send()
+        .await
+        .map_err(|e| format!("Ollama embedding request failed: {}", e))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!(
+            "Ollama embedding request failed ({}): {}",
+            status, body
+        ));
+    }
+
+    let parsed: OllamaEmbedResponse = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse Ollama embeddings: {}", e))?;
+
+    if parsed.embeddings.len() != texts.len() {
+        return Err(format!(
+            "Ollama returned {} embeddings for {} texts",
+            parsed.embeddings.len(),
+            texts.len()
+        ));
+    }
+    Ok(parsed.embeddings)
+}
@@ -0,0 +1,121 @@
+use std::path::PathBuf;
+
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use reqwest::Client;
+use serde::Serialize;
+use sqlx::SqlitePool;
+use tauri::{AppHandle, Emitter, Runtime};
+use tokio::sync::Mutex;
+
+use super::embeddings::embed_texts;
+use super::passages::{summary_passages, transcript_passages, TranscriptLine};
+use super::settings::QaSettings;
+use crate::database::repositories::qa_index::QaIndexRepository;
+use crate::database::repositories::summary::SummaryProcessesRepository;
+use crate::database::repositories::transcript::TranscriptsRepository;
+
+/// Serializes index refreshes so two questions don't embed the same meeting twice
+static INDEX_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+/// Payload of the `qa-index-progress` event
+#[derive(Debug, Clone, Serialize)]
+pub struct QaIndexProgress {
+    pub meeting_id: String,
+    pub indexed: usize,
+    pub total: usize,
+}
+
+/// Embeds every meeting (or the given ones) whose content changed since it was
+/// last indexed with the configured embedding model. Returns the number of
+/// meetings (re)indexed.
+pub async fn refresh_index<R: Runtime>(
+    app: &AppHandle<R>,
+    pool: &SqlitePool,
+    client: &Client,
+    settings: &QaSettings,
+    app_data_dir: Option<&PathBuf>,
+    meeting_ids: Option<&[String]>,
+    force: bool,
+) -> Result<usize, String> {
+    let _guard = INDEX_LOCK.lock().await;
+    let index_key = settings.index_key();
+
+    let signatures = QaIndexRepository::meeting_signatures(pool, meeting_ids)
+        .await
+        .map_err(|e| format!("Failed to read meetings: {}", e))?;
+    let indexed = QaIndexRepository::indexed_signatures(pool, &index_key)
+        .await
+        .map_err(|e| format!("Failed to read Q&A index: {}", e))?;
+
+    let stale: Vec<(String, String)> = signatures
+        .into_iter()
+        .filter(|(id, signature)| force || indexed.get(id) != Some(signature))
+        .collect();
+    if stale.is_empty() {
+        return Ok(0);
+    }
+
+    info!(
+        "Indexing {} meeting(s) for Q&A with {}",
+        stale.len(),
+        index_key
+    );
+    for (i, (meeting_id, signature)) in stale.iter().enumerate() {
+        index_meeting(pool, client, settings, app_data_dir, meeting_id, signature).await?;
+
+        if let Err(e) = app.emit(
+            "qa-index-progress",
+            QaIndexProgress {
+                meeting_id: meeting_id.clone(),
+                indexed: i + 1,
+                total: stale.len(),
+            },
+        ) {
+            warn!("Failed to emit qa-index-progress: {}", e);
+        }
+    }
+
+    Ok(stale.len())
+}
+
+async fn index_meeting(
+    pool: &SqlitePool,
+    client: &Client,
+    settings: &QaSettings,
+    app_data_dir: Option<&PathBuf>,
+    meeting_id: &str,
+    signature: &str,
+) -> Result<(), String> {
+    let lines: Vec<TranscriptLine> = TranscriptsRepository::get_transcript_lines(pool, meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load transcripts for {}: {}", meeting_id, e))?
+        .into_iter()
+        .map(|row| TranscriptLine {
+            id: row.id,
+            speaker: row.speaker_label,
+            text: row.transcript,
+            audio_start_time: row.audio_start_time,
+        })
+        .collect();
+
+    let summary_markdown = SummaryProcessesRepository::get_summary_data(pool, meeting_id)
+        .await
+        .map_err(|e| format!("Failed to load summary for {}: {}", meeting_id, e))?
+        .and_then(|process| process.result)
+        .and_then(|result| serde_json::from_str::<serde_json::Value>(&result).ok())
+        .and_then(|json| json.get("markdown")?.as_str().map(str::to_string));
+
+    let mut passages = transcript_passages(&lines);
+    if let Some(markdown) = summary_markdown {
+        passages.extend(summary_passages(&markdown));
+    }
+
+    let texts: Vec<String> = passages.iter().map(|p| p.text.clone()).collect();
+    let vectors = embed_texts(client, settings, app_data_dir, &texts).await?;
+    let indexed: Vec<_> = passages.into_iter().zip(vectors).collect();
+
+    QaIndexRepository::replace_meeting(pool, meeting_id, &settings.index_key(), signature, &indexed)
+        .await
+        .map_err(|e| format!("Failed to store Q&A index for {}: {}", meeting_id, e))
+}
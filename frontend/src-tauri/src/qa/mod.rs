@@ -0,0 +1,29 @@
+//! Ask-your-meetings: retrieval-augmented Q&A over the meeting archive.
+//!
+//! Transcripts (in windows of consecutive segments) and summaries (per section)
+//! are embedded into `qa_passages`, with the bundled GGUF model through the
+//! llama-helper sidecar or with Ollama. A question is embedded the same way, the
+//! closest passages are retrieved by cosine similarity, and the configured
+//! summary `LLMProvider` answers from them, citing each source's meeting and
+//! recording time. With the built-in provider for both steps nothing leaves the
+//! machine.
+//!
+//! # Module Structure
+//!
+//! - `settings`: Embedding provider/model, persisted in the `qa.json` store
+//! - `passages`: Transcript and summary passage building, top-k ranking
+//! - `embeddings`: Built-in and Ollama embedding backends
+//! - `index`: Incremental (re)indexing of changed meetings
+//! - `answer`: Prompt construction and citations
+//! - `commands`: Tauri commands
+
+pub mod answer;
+pub mod commands;
+pub mod embeddings;
+pub mod index;
+pub mod passages;
+pub mod settings;
+
+pub use answer::{QaAnswer, QaCitation, QaMessage};
+pub use commands::*;
+pub use settings::QaSettings;
@@ -0,0 +1,219 @@
+//! Splitting meetings into passages for embedding, and ranking them
+
+use serde::{Deserialize, Serialize};
+
+/// Soft size limit of a passage; well inside the embedding context
+pub const MAX_PASSAGE_CHARS: usize = 1200;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PassageSource {
+    Transcript,
+    Summary,
+}
+
+impl PassageSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Transcript => "transcript",
+            Self::Summary => "summary",
+        }
+    }
+
+    pub fn parse(value: &str) -> Self {
+        match value {
+            "summary" => Self::Summary,
+            _ => Self::Transcript,
+        }
+    }
+}
+
+/// A transcript row, in playback order
+#[derive(Debug, Clone)]
+pub struct TranscriptLine {
+    pub id: String,
+    pub speaker: Option<String>,
+    pub text: String,
+    pub audio_start_time: Option<f64>,
+}
+
+/// A unit of retrieval. Transcript passages cite their first segment.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Passage {
+    pub source: PassageSource,
+    pub transcript_id: Option<String>,
+    pub audio_start_time: Option<f64>,
+    pub text: String,
+}
+
+/// Groups consecutive transcript lines into passages of about `MAX_PASSAGE_CHARS`
+pub fn transcript_passages(lines: &[TranscriptLine]) -> Vec<Passage> {
+    let mut passages = Vec::new();
+    let mut current: Option<Passage> = None;
+
+    for line in lines {
+        let text = line.text.trim();
+        if text.is_empty() {
+            continue;
+        }
+        let formatted = match &line.speaker {
+            Some(speaker) => format!("{}: {}", speaker, text),
+            None => text.to_string(),
+        };
+
+        if let Some(passage) = current.as_mut() {
+            if passage.text.len() + formatted.len() < MAX_PASSAGE_CHARS {
+                passage.text.push('\n');
+                passage.text.push_str(&formatted);
+                continue;
+            }
+            passages.extend(current.take());
+        }
+        current = Some(Passage {
+            source: PassageSource::Transcript,
+            transcript_id: Some(line.id.clone()),
+            audio_start_time: line.audio_start_time,
+            text: formatted,
+        });
+    }
+
+    passages.extend(current);
+    passages
+}
+
+/// Splits a Markdown summary into one passage per section. Long sections are
+/// split at paragraph boundaries, each part keeping the section heading.
+pub fn summary_passages(markdown: &str) -> Vec<Passage> {
+    let mut sections: Vec<(String, Vec<String>)> = vec![(String::new(), Vec::new())];
+    let mut paragraph = String::new();
+
+    let flush = |paragraph: &mut String, sections: &mut Vec<(String, Vec<String>)>| {
+        if !paragraph.trim().is_empty() {
+            if let Some((_, paragraphs)) = sections.last_mut() {
+                paragraphs.push(paragraph.trim().to_string());
+            }
+        }
+        paragraph.clear();
+    };
+
+    for line in markdown.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('#') {
+            flush(&mut paragraph, &mut sections);
+            sections.push((
+                trimmed.trim_start_matches('#').trim().to_string(),
+                Vec::new(),
+            ));
+        } else if trimmed.is_empty() {
+            flush(&mut paragraph, &mut sections);
+        } else {
+            paragraph.push_str(trimmed);
+            paragraph.push('\n');
+        }
+    }
+    flush(&mut paragraph, &mut sections);
+
+    let mut passages = Vec::new();
+    for (heading, paragraphs) in sections {
+        let mut text = String::new();
+        for paragraph in paragraphs {
+            if !text.is_empty() && text.len() + paragraph.len() >= MAX_PASSAGE_CHARS {
+                passages.push(summary_passage(&heading, &text));
+                text.clear();
+            }
+            if !text.is_empty() {
+                text.push_str("\n\n");
+            }
+            text.push_str(&paragraph);
+        }
+        if !text.is_empty() {
+            passages.push(summary_passage(&heading, &text));
+        }
+    }
+    passages
+}
+
+fn summary_passage(heading: &str, body: &str) -> Passage {
+    let text = if heading.is_empty() {
+        body.to_string()
+    } else {
+        format!("{}\n{}", heading, body)
+    };
+    Passage {
+        source: PassageSource::Summary,
+        transcript_id: None,
+        audio_start_time: None,
+        text,
+    }
+}
+
+/// Keeps the `k` best-scoring items, sorted by descending score
+pub fn push_top_k<T>(top: &mut Vec<(f32, T)>, k: usize, score: f32, item: T) {
+    if top.len() >= k && top.last().is_some_and(|(worst, _)| score <= *worst) {
+        return;
+    }
+    let position = top.partition_point(|(s, _)| *s >= score);
+    top.insert(position, (score, item));
+    top.truncate(k);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(id: &str, speaker: Option<&str>, text: &str, start: f64) -> TranscriptLine {
+        TranscriptLine {
+            id: id.to_string(),
+            speaker: speaker.map(str::to_string),
+            text: text.to_string(),
+            audio_start_time: Some(start),
+        }
+    }
+
+    #[test]
+    fn test_transcript_passages_group_lines_and_cite_first_segment() {
+        let long = "x".repeat(MAX_PASSAGE_CHARS - 10);
+        let lines = vec![
+            line("t-1", Some("Alice"), "Billing moves to Stripe.", 0.0),
+            line("t-2", None, "  ", 4.0),
+            line("t-3", Some("Bob"), "Agreed.", 6.0),
+            line("t-4", Some("Bob"), &long, 9.0),
+        ];
+        let passages = transcript_passages(&lines);
+        assert_eq!(passages.len(), 2);
+        assert_eq!(
+            passages[0].text,
+            "Alice: Billing moves to Stripe.\nBob: Agreed."
+        );
+        assert_eq!(passages[0].transcript_id.as_deref(), Some("t-1"));
+        assert_eq!(passages[1].transcript_id.as_deref(), Some("t-4"));
+        assert_eq!(passages[1].audio_start_time, Some(9.0));
+    }
+
+    #[test]
+    fn test_summary_passages_split_by_section() {
+        let markdown = "# Weekly sync\n\n## Decisions\n- Ship on Monday\n- Freeze billing\n\n## Action Items\n\nBob updates the docs.\n";
+        let passages = summary_passages(markdown);
+        let texts: Vec<&str> = passages.iter().map(|p| p.text.as_str()).collect();
+        assert_eq!(
+            texts,
+            vec![
+                "Decisions\n- Ship on Monday\n- Freeze billing",
+                "Action Items\nBob updates the docs."
+            ]
+        );
+        assert!(passages
+            .iter()
+            .all(|p| p.source == PassageSource::Summary && p.transcript_id.is_none()));
+    }
+
+    #[test]
+    fn test_push_top_k() {
+        let mut top = Vec::new();
+        for (score, id) in [(0.2, "a"), (0.9, "b"), (0.5, "c"), (0.1, "d"), (0.7, "e")] {
+            push_top_k(&mut top, 3, score, id);
+        }
+        let ids: Vec<&str> = top.iter().map(|(_, id)| *id).collect();
+        assert_eq!(ids, vec!["b", "e", "c"]);
+    }
+}
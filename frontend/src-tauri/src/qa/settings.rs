@@ -0,0 +1,103 @@
+use anyhow::Result;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_store::StoreExt;
+
+use crate::summary::summary_engine::get_default_model;
+
+const STORE_FILE: &str = "qa.json";
+const STORE_KEY: &str = "settings";
+
+/// Default Ollama embedding model
+pub const DEFAULT_OLLAMA_EMBEDDING_MODEL: &str = "nomic-embed-text";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum EmbeddingProvider {
+    /// Bundled GGUF model through the llama-helper sidecar (fully offline)
+    BuiltinAi,
+    /// Ollama's /api/embed endpoint
+    Ollama,
+}
+
+impl EmbeddingProvider {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::BuiltinAi => "builtin-ai",
+            Self::Ollama => "ollama",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QaSettings {
+    pub embedding_provider: EmbeddingProvider,
+    /// Built-in model name ("gemma3:1b") or Ollama model tag
+    pub embedding_model: String,
+    /// Ollama endpoint; defaults to the one configured for summaries
+    pub ollama_endpoint: Option<String>,
+    /// Passages retrieved per question
+    pub top_k: usize,
+}
+
+impl Default for QaSettings {
+    fn default() -> Self {
+        Self {
+            embedding_provider: EmbeddingProvider::BuiltinAi,
+            embedding_model: get_default_model().name,
+            ollama_endpoint: None,
+            top_k: 8,
+        }
+    }
+}
+
+impl QaSettings {
+    /// Key the index is stored under, e.g. "builtin-ai:gemma3:1b"
+    pub fn index_key(&self) -> String {
+        format!(
+            "{}:{}",
+            self.embedding_provider.as_str(),
+            self.embedding_model
+        )
+    }
+}
+
+/// Load Q&A settings from store, falling back to defaults
+pub fn load_qa_settings<R: Runtime>(app: &AppHandle<R>) -> Result<QaSettings> {
+    let store = app
+        .store(STORE_FILE)
+        .map_err(|e| anyhow::anyhow!("Failed to access store: {}", e))?;
+
+    let settings = match store.get(STORE_KEY) {
+        Some(value) => serde_json::from_value::<QaSettings>(value).unwrap_or_else(|e| {
+            warn!("Failed to deserialize Q&A settings: {}, using defaults", e);
+            QaSettings::default()
+        }),
+        None => QaSettings::default(),
+    };
+
+    Ok(settings)
+}
+
+/// Save Q&A settings to store
+pub fn save_qa_settings<R: Runtime>(app: &AppHandle<R>, settings: &QaSettings) -> Result<()> {
+    info!(
+        "Saving Q&A settings: embedding model={}, top_k={}",
+        settings.index_key(),
+        settings.top_k
+    );
+
+    let store = app
+        .store(STORE_FILE)
+        .map_err(|e| anyhow::anyhow!("Failed to access store: {}", e))?;
+
+    let value = serde_json::to_value(settings)
+        .map_err(|e| anyhow::anyhow!("Failed to serialize Q&A settings: {}", e))?;
+    store.set(STORE_KEY, value);
+    store
+        .save()
+        .map_err(|e| anyhow::anyhow!("Failed to save store to disk: {}", e))?;
+
+    Ok(())
+}
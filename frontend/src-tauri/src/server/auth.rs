@@ -0,0 +1,87 @@
+use axum::extract::{Request, State};
+use axum::http::{header, HeaderMap, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use tauri::Runtime;
+
+use super::routes::{ApiContext, ApiError};
+
+/// Rejects requests that don't carry the configured token.
+///
+/// The token is read from `Authorization: Bearer <token>`, or from a `token`
+/// query parameter for clients that can't set headers (browser WebSockets).
+pub async fn require_token<R: Runtime>(
+    State(ctx): State<ApiContext<R>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let provided = request_token(request.headers(), request.uri().query());
+    match provided {
+        Some(token) if tokens_match(&ctx.token, &token) => next.run(request).await,
+        _ => {
+            ApiError::new(StatusCode::UNAUTHORIZED, "Missing or invalid API token").into_response()
+        }
+    }
+}
+
+pub fn request_token(headers: &HeaderMap, query: Option<&str>) -> Option<String> {
+    let bearer = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(|token| token.trim().to_string());
+
+    bearer.or_else(|| {
+        url::form_urlencoded::parse(query?.as_bytes())
+            .find(|(key, _)| key == "token")
+            .map(|(_, value)| value.into_owned())
+    })
+}
+
+/// Compares without short-circuiting so response timing doesn't leak the token
+pub fn tokens_match(expected: &str, provided: &str) -> bool {
+    let expected = expected.as_bytes();
+    let provided = provided.as_bytes();
+    if expected.is_empty() || expected.len() != provided.len() {
+        return false;
+    }
+    expected
+        .iter()
+        .zip(provided)
+        .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+        == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::HeaderValue;
+
+    #[test]
+    fn test_tokens_match() {
+        assert!(tokens_match("abc123", "abc123"));
+        assert!(!tokens_match("abc123", "abc124"));
+        assert!(!tokens_match("abc123", "abc12"));
+        assert!(!tokens_match("", ""));
+    }
+
+    #[test]
+    fn test_request_token_prefers_header_then_query() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(
+            request_token(&headers, Some("q=x&token=from%20query")).as_deref(),
+            Some("from query")
+        );
+        assert_eq!(request_token(&headers, Some("q=x")), None);
+        assert_eq!(request_token(&headers, None), None);
+
+        headers.insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_static("Bearer secret"),
+        );
+        assert_eq!(
+            request_token(&headers, Some("token=other")).as_deref(),
+            Some("secret")
+        );
+    }
+}
@@ -0,0 +1,69 @@
+use log::info;
+use tauri::{AppHandle, Runtime};
+
+use super::lifecycle::{server_status, start_server, stop_server, ApiServerStatus};
+use super::settings::{
+    generate_token, load_api_server_settings, save_api_server_settings, ApiServerSettings,
+};
+
+#[tauri::command]
+pub async fn api_server_get_settings<R: Runtime>(
+    app: AppHandle<R>,
+) -> Result<ApiServerSettings, String> {
+    load_api_server_settings(&app).map_err(|e| format!("Failed to load API server settings: {}", e))
+}
+
+/// Saves the settings and starts, restarts or stops the server to match
+#[tauri::command]
+pub async fn api_server_save_settings<R: Runtime>(
+    app: AppHandle<R>,
+    mut settings: ApiServerSettings,
+) -> Result<ApiServerStatus, String> {
+    if settings.port == 0 {
+        return Err("Port must be between 1 and 65535".to_string());
+    }
+    if settings.token.trim().is_empty() {
+        settings.token = generate_token();
+    }
+
+    save_api_server_settings(&app, &settings)
+        .map_err(|e| format!("Failed to save API server settings: {}", e))?;
+    apply_settings(app, &settings).await
+}
+
+/// Replaces the token; clients using the old one are rejected from now on
+#[tauri::command]
+pub async fn api_server_regenerate_token<R: Runtime>(
+    app: AppHandle<R>,
+) -> Result<ApiServerSettings, String> {
+    let mut settings = load_api_server_settings(&app)
+        .map_err(|e| format!("Failed to load API server settings: {}", e))?;
+    settings.token = generate_token();
+    save_api_server_settings(&app, &settings)
+        .map_err(|e| format!("Failed to save API server settings: {}", e))?;
+
+    if server_status().running {
+        apply_settings(app, &settings).await?;
+    }
+    info!("API server token regenerated");
+    Ok(settings)
+}
+
+#[tauri::command]
+pub async fn api_server_status() -> Result<ApiServerStatus, String> {
+    Ok(server_status())
+}
+
+async fn apply_settings<R: Runtime>(
+    app: AppHandle<R>,
+    settings: &ApiServerSettings,
+) -> Result<ApiServerStatus, String> {
+    if settings.enabled {
+        start_server(app, settings)
+            .await
+            .map_err(|e| format!("Failed to start API server: {:#}", e))?;
+    } else {
+        stop_server();
+    }
+    Ok(server_status())
+}
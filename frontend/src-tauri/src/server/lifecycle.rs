@@ -0,0 +1,116 @@
+use std::net::{Ipv4Addr, SocketAddr};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use log::{error, info};
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use tauri::{AppHandle, Runtime};
+use tokio::net::TcpListener;
+use tokio_util::sync::CancellationToken;
+
+use super::routes::{self, ApiContext};
+use super::settings::{load_api_server_settings, ApiServerSettings};
+
+/// A stopped server releases its port asynchronously; retry binding for this long
+const BIND_ATTEMPTS: u32 = 10;
+const BIND_RETRY_DELAY: Duration = Duration::from_millis(100);
+
+struct RunningServer {
+    address: SocketAddr,
+    shutdown: CancellationToken,
+}
+
+static RUNNING_SERVER: Lazy<Mutex<Option<RunningServer>>> = Lazy::new(|| Mutex::new(None));
+
+#[derive(Debug, Serialize, Clone)]
+pub struct ApiServerStatus {
+    pub running: bool,
+    /// e.g. `http://127.0.0.1:5170`
+    pub url: Option<String>,
+}
+
+pub fn server_status() -> ApiServerStatus {
+    let address = RUNNING_SERVER
+        .lock()
+        .unwrap()
+        .as_ref()
+        .map(|server| server.address);
+    ApiServerStatus {
+        running: address.is_some(),
+        url: address.map(|address| format!("http://{}", address)),
+    }
+}
+
+/// Starts the server on 127.0.0.1 with the given settings, replacing any running instance
+pub async fn start_server<R: Runtime>(
+    app: AppHandle<R>,
+    settings: &ApiServerSettings,
+) -> Result<SocketAddr> {
+    stop_server();
+
+    let listener = bind_loopback(settings.port).await?;
+    let address = listener.local_addr()?;
+    let shutdown = CancellationToken::new();
+    let router = routes::router(ApiContext {
+        app,
+        token: Arc::from(settings.token.as_str()),
+        shutdown: shutdown.clone(),
+    });
+
+    let signal = shutdown.clone();
+    tauri::async_runtime::spawn(async move {
+        let result = axum::serve(listener, router)
+            .with_graceful_shutdown(async move { signal.cancelled().await })
+            .await;
+        match result {
+            Ok(()) => info!("API server on {} stopped", address),
+            Err(e) => error!("API server on {} failed: {}", address, e),
+        }
+    });
+
+    *RUNNING_SERVER.lock().unwrap() = Some(RunningServer { address, shutdown });
+    info!("API server listening on http://{}", address);
+    Ok(address)
+}
+
+pub fn stop_server() {
+    if let Some(server) = RUNNING_SERVER.lock().unwrap().take() {
+        info!("Stopping API server on {}", server.address);
+        server.shutdown.cancel();
+    }
+}
+
+/// Starts the server at app launch when the user has enabled it
+pub async fn start_if_enabled<R: Runtime>(app: AppHandle<R>) {
+    let settings = match load_api_server_settings(&app) {
+        Ok(settings) => settings,
+        Err(e) => {
+            error!("Failed to load API server settings: {}", e);
+            return;
+        }
+    };
+    if !settings.enabled {
+        return;
+    }
+    if let Err(e) = start_server(app, &settings).await {
+        error!("Failed to start API server: {:#}", e);
+    }
+}
+
+async fn bind_loopback(port: u16) -> Result<TcpListener> {
+    let mut attempt = 1;
+    loop {
+        match TcpListener::bind((Ipv4Addr::LOCALHOST, port)).await {
+            Ok(listener) => return Ok(listener),
+            Err(e) if e.kind() == std::io::ErrorKind::AddrInUse && attempt < BIND_ATTEMPTS => {
+                attempt += 1;
+                tokio::time::sleep(BIND_RETRY_DELAY).await;
+            }
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to bind 127.0.0.1:{}", port));
+            }
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use once_cell::sync::Lazy;
+use tokio::sync::broadcast;
+
+use crate::audio::transcription::worker::TranscriptUpdate;
+
+/// Updates buffered per WebSocket client before a slow client starts skipping
+const LIVE_CHANNEL_CAPACITY: usize = 256;
+
+static LIVE_TRANSCRIPTS: Lazy<broadcast::Sender<TranscriptUpdate>> =
+    Lazy::new(|| broadcast::channel(LIVE_CHANNEL_CAPACITY).0);
+
+/// Forwards a live transcript update to connected WebSocket clients.
+///
+/// Called next to the `transcript-update` event; a no-op while nobody is
+/// subscribed, so it costs nothing when the API server is disabled.
+pub fn publish_transcript_update(update: &TranscriptUpdate) {
+    if LIVE_TRANSCRIPTS.receiver_count() > 0 {
+        let _ = LIVE_TRANSCRIPTS.send(update.clone());
+    }
+}
+
+pub fn subscribe_transcript_updates() -> broadcast::Receiver<TranscriptUpdate> {
+    LIVE_TRANSCRIPTS.subscribe()
+}
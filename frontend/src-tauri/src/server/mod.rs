@@ -0,0 +1,28 @@
+//! Local REST + WebSocket API.
+//!
+//! An opt-in HTTP server bound to 127.0.0.1 that exposes meetings, transcripts,
+//! summaries and search to other local tools, plus a WebSocket that streams live
+//! transcript updates while recording. Every request except `/api/v1/health`
+//! must carry the token from the settings (`Authorization: Bearer <token>` or
+//! `?token=<token>`).
+//!
+//! # Module Structure
+//!
+//! - `settings`: Enabled flag, port and token persisted in the app store
+//! - `auth`: Token extraction and the auth middleware
+//! - `routes`: Axum router and request handlers
+//! - `live`: Broadcast channel fed by the transcription worker
+//! - `lifecycle`: Starting, stopping and status of the server task
+//! - `commands`: Tauri commands for the settings UI
+
+pub mod auth;
+pub mod commands;
+pub mod lifecycle;
+pub mod live;
+pub mod routes;
+pub mod settings;
+
+pub use commands::*;
+pub use lifecycle::{server_status, start_if_enabled, stop_server, ApiServerStatus};
+pub use live::publish_transcript_update;
+pub use settings::ApiServerSettings;
@@ -0,0 +1,361 @@
+use std::sync::Arc;
+
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Path, Query, State};
+use axum::http::StatusCode;
+use axum::middleware;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use log::{error, info, warn};
+use serde::{Deserialize, Serialize};
+use sqlx::SqlitePool;
+use tauri::{AppHandle, Manager, Runtime};
+use tokio::sync::broadcast::error::RecvError;
+use tokio_util::sync::CancellationToken;
+
+use super::auth::require_token;
+use super::live::subscribe_transcript_updates;
+use crate::api::{
+    MeetingDetails, MeetingMetadata, MeetingTranscript, PaginatedTranscriptsResponse, SearchHit,
+    SearchSource,
+};
+use crate::database::repositories::meeting::MeetingsRepository;
+use crate::database::repositories::search::SearchRepository;
+use crate::database::repositories::summary::SummaryProcessesRepository;
+use crate::state::AppState;
+
+const DEFAULT_PAGE_SIZE: i64 = 100;
+const MAX_PAGE_SIZE: i64 = 1000;
+
+/// Shared state for every request handled by one server instance
+pub struct ApiContext<R: Runtime> {
+    pub app: AppHandle<R>,
+    pub token: Arc<str>,
+    /// Cancelled when the server stops, closing open WebSockets
+    pub shutdown: CancellationToken,
+}
+
+impl<R: Runtime> Clone for ApiContext<R> {
+    fn clone(&self) -> Self {
+        Self {
+            app: self.app.clone(),
+            token: self.token.clone(),
+            shutdown: self.shutdown.clone(),
+        }
+    }
+}
+
+impl<R: Runtime> ApiContext<R> {
+    /// The database is managed after startup (and replaced on legacy import),
+    /// so look it up per request instead of capturing a pool
+    fn pool(&self) -> Result<SqlitePool, ApiError> {
+        self.app
+            .try_state::<AppState>()
+            .map(|state| state.db_manager.pool().clone())
+            .ok_or_else(|| {
+                ApiError::new(
+                    StatusCode::SERVICE_UNAVAILABLE,
+                    "Database is not initialized",
+                )
+            })
+    }
+}
+
+/// JSON error body: `{"error": "..."}`
+#[derive(Debug)]
+pub struct ApiError {
+    status: StatusCode,
+    message: String,
+}
+
+impl ApiError {
+    pub fn new(status: StatusCode, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+
+    fn not_found(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::NOT_FOUND, message)
+    }
+}
+
+impl From<sqlx::Error> for ApiError {
+    fn from(e: sqlx::Error) -> Self {
+        match e {
+            sqlx::Error::RowNotFound => Self::not_found("Not found"),
+            e => {
+                error!("API server database error: {}", e);
+                Self::new(StatusCode::INTERNAL_SERVER_ERROR, e.to_string())
+            }
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        (
+            self.status,
+            Json(serde_json::json!({ "error": self.message })),
+        )
+            .into_response()
+    }
+}
+
+pub fn router<R: Runtime>(ctx: ApiContext<R>) -> Router {
+    let api = Router::new()
+        .route("/meetings", get(list_meetings::<R>))
+        .route("/meetings/:id", get(get_meeting::<R>))
+        .route("/meetings/:id/transcripts", get(get_transcripts::<R>))
+        .route("/meetings/:id/summary", get(get_summary::<R>))
+        .route("/search", get(search::<R>))
+        .route("/transcripts/live", get(live_transcripts::<R>))
+        .route_layer(middleware::from_fn_with_state(
+            ctx.clone(),
+            require_token::<R>,
+        ))
+        // Unauthenticated so clients can probe for the server
+        .route("/health", get(health));
+
+    Router::new().nest("/api/v1", api).with_state(ctx)
+}
+
+async fn health() -> Json<serde_json::Value> {
+    Json(serde_json::json!({
+        "status": "ok",
+        "version": env!("CARGO_PKG_VERSION"),
+    }))
+}
+
+async fn list_meetings<R: Runtime>(
+    State(ctx): State<ApiContext<R>>,
+) -> Result<Json<Vec<MeetingMetadata>>, ApiError> {
+    let meetings = MeetingsRepository::get_meetings(&ctx.pool()?).await?;
+    Ok(Json(
+        meetings
+            .into_iter()
+            .map(|m| MeetingMetadata {
+                id: m.id,
+                title: m.title,
+                created_at: m.created_at.0.to_rfc3339(),
+                updated_at: m.updated_at.0.to_rfc3339(),
+                folder_path: m.folder_path,
+            })
+            .collect(),
+    ))
+}
+
+async fn get_meeting<R: Runtime>(
+    State(ctx): State<ApiContext<R>>,
+    Path(meeting_id): Path<String>,
+) -> Result<Json<MeetingDetails>, ApiError> {
+    MeetingsRepository::get_meeting(&ctx.pool()?, &meeting_id)
+        .await?
+        .map(Json)
+        .ok_or_else(|| ApiError::not_found(format!("Meeting not found: {}", meeting_id)))
+}
+
+#[derive(Debug, Deserialize)]
+struct PageQuery {
+    limit: Option<i64>,
+    offset: Option<i64>,
+}
+
+async fn get_transcripts<R: Runtime>(
+    State(ctx): State<ApiContext<R>>,
+    Path(meeting_id): Path<String>,
+    Query(page): Query<PageQuery>,
+) -> Result<Json<PaginatedTranscriptsResponse>, ApiError> {
+    let pool = ctx.pool()?;
+    if MeetingsRepository::get_meeting_metadata(&pool, &meeting_id)
+        .await?
+        .is_none()
+    {
+        return Err(ApiError::not_found(format!(
+            "Meeting not found: {}",
+            meeting_id
+        )));
+    }
+
+    let limit = page
+        .limit
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE);
+    let offset = page.offset.unwrap_or(0).max(0);
+    let (transcripts, total_count) =
+        MeetingsRepository::get_meeting_transcripts_paginated(&pool, &meeting_id, limit, offset)
+            .await?;
+
+    let transcripts: Vec<MeetingTranscript> = transcripts
+        .into_iter()
+        .map(|t| MeetingTranscript {
+            id: t.id,
+            text: t.transcript,
+            timestamp: t.timestamp,
+            audio_start_time: t.audio_start_time,
+            audio_end_time: t.audio_end_time,
+            duration: t.duration,
+            speaker_label: t.speaker_label,
+        })
+        .collect();
+    let has_more = offset + (transcripts.len() as i64) < total_count;
+
+    Ok(Json(PaginatedTranscriptsResponse {
+        transcripts,
+        total_count,
+        has_more,
+    }))
+}
+
+#[derive(Debug, Serialize)]
+struct SummaryResponse {
+    meeting_id: String,
+    /// pending, processing, completed, failed or cancelled
+    status: String,
+    markdown: Option<String>,
+    /// Full stored result (markdown plus any edited summary JSON)
+    result: Option<serde_json::Value>,
+    error: Option<String>,
+    updated_at: String,
+}
+
+async fn get_summary<R: Runtime>(
+    State(ctx): State<ApiContext<R>>,
+    Path(meeting_id): Path<String>,
+) -> Result<Json<SummaryResponse>, ApiError> {
+    let process = SummaryProcessesRepository::get_summary_data(&ctx.pool()?, &meeting_id)
+        .await?
+        .ok_or_else(|| ApiError::not_found(format!("No summary for meeting: {}", meeting_id)))?;
+
+    let result = process
+        .result
+        .as_deref()
+        .and_then(|result| serde_json::from_str::<serde_json::Value>(result).ok());
+    let markdown = result
+        .as_ref()
+        .and_then(|json| json.get("markdown")?.as_str().map(str::to_string));
+
+    Ok(Json(SummaryResponse {
+        meeting_id: process.meeting_id,
+        status: process.status,
+        markdown,
+        result,
+        error: process.error,
+        updated_at: process.updated_at.to_rfc3339(),
+    }))
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchQuery {
+    q: String,
+    /// Comma-separated subset of transcript,summary,notes
+    sources: Option<String>,
+    limit: Option<i64>,
+    offset: Option<i64>,
+}
+
+async fn search<R: Runtime>(
+    State(ctx): State<ApiContext<R>>,
+    Query(query): Query<SearchQuery>,
+) -> Result<Json<Vec<SearchHit>>, ApiError> {
+    let sources = match query.sources.as_deref() {
+        Some(sources) => parse_sources(sources)?,
+        None => vec![
+            SearchSource::Transcript,
+            SearchSource::Summary,
+            SearchSource::Notes,
+        ],
+    };
+
+    let hits = SearchRepository::search(
+        &ctx.pool()?,
+        &query.q,
+        &sources,
+        query.limit.unwrap_or(50),
+        query.offset.unwrap_or(0),
+    )
+    .await?;
+    Ok(Json(hits))
+}
+
+fn parse_sources(sources: &str) -> Result<Vec<SearchSource>, ApiError> {
+    sources
+        .split(',')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|source| match source {
+            "transcript" => Ok(SearchSource::Transcript),
+            "summary" => Ok(SearchSource::Summary),
+            "notes" => Ok(SearchSource::Notes),
+            other => Err(ApiError::new(
+                StatusCode::BAD_REQUEST,
+                format!("Unknown search source: {}", other),
+            )),
+        })
+        .collect()
+}
+
+async fn live_transcripts<R: Runtime>(
+    State(ctx): State<ApiContext<R>>,
+    ws: WebSocketUpgrade,
+) -> Response {
+    let shutdown = ctx.shutdown.clone();
+    ws.on_upgrade(move |socket| stream_transcripts(socket, shutdown))
+}
+
+/// Sends each live `TranscriptUpdate` as a JSON text message until the client
+/// disconnects or the server stops
+async fn stream_transcripts(mut socket: WebSocket, shutdown: CancellationToken) {
+    info!("API server: live transcript client connected");
+    let mut updates = subscribe_transcript_updates();
+
+    loop {
+        tokio::select! {
+            _ = shutdown.cancelled() => {
+                let _ = socket.send(Message::Close(None)).await;
+                break;
+            }
+            update = updates.recv() => match update {
+                Ok(update) => {
+                    let json = match serde_json::to_string(&update) {
+                        Ok(json) => json,
+                        Err(e) => {
+                            error!("Failed to serialize transcript update: {}", e);
+                            continue;
+                        }
+                    };
+                    if socket.send(Message::Text(json)).await.is_err() {
+                        break;
+                    }
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    warn!("API server: live transcript client lagged, skipped {} updates", skipped);
+                }
+                Err(RecvError::Closed) => break,
+            },
+            // Pings are answered by axum; anything else from the client is ignored
+            incoming = socket.recv() => match incoming {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => {}
+            },
+        }
+    }
+
+    info!("API server: live transcript client disconnected");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_sources() {
+        assert_eq!(
+            parse_sources("transcript, notes,").unwrap(),
+            vec![SearchSource::Transcript, SearchSource::Notes]
+        );
+        assert!(parse_sources("transcript,slides").is_err());
+    }
+}
@@ -0,0 +1,95 @@
+use anyhow::Result;
+use log::{info, warn};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Runtime};
+use tauri_plugin_store::StoreExt;
+
+const STORE_FILE: &str = "api_server.json";
+const STORE_KEY: &str = "settings";
+
+/// Default port, next to the legacy backend on 5167
+pub const DEFAULT_PORT: u16 = 5170;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ApiServerSettings {
+    /// The server only runs when explicitly enabled
+    pub enabled: bool,
+    pub port: u16,
+    /// Bearer token every request must present
+    pub token: String,
+}
+
+impl Default for ApiServerSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: DEFAULT_PORT,
+            token: generate_token(),
+        }
+    }
+}
+
+/// 256-bit random token, hex encoded
+pub fn generate_token() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Load API server settings from store, creating (and persisting) a token on first use
+pub fn load_api_server_settings<R: Runtime>(app: &AppHandle<R>) -> Result<ApiServerSettings> {
+    let store = app
+        .store(STORE_FILE)
+        .map_err(|e| anyhow::anyhow!("Failed to access store: {}", e))?;
+
+    let (mut settings, mut needs_save) = match store.get(STORE_KEY) {
+        Some(value) => match serde_json::from_value::<ApiServerSettings>(value) {
+            Ok(settings) => (settings, false),
+            Err(e) => {
+                warn!(
+                    "Failed to deserialize API server settings: {}, using defaults",
+                    e
+                );
+                (ApiServerSettings::default(), true)
+            }
+        },
+        None => (ApiServerSettings::default(), true),
+    };
+
+    if settings.token.trim().is_empty() {
+        settings.token = generate_token();
+        needs_save = true;
+    }
+
+    // Persist generated tokens so clients keep working across restarts
+    if needs_save {
+        save_api_server_settings(app, &settings)?;
+    }
+
+    Ok(settings)
+}
+
+/// Save API server settings to store
+pub fn save_api_server_settings<R: Runtime>(
+    app: &AppHandle<R>,
+    settings: &ApiServerSettings,
+) -> Result<()> {
+    info!(
+        "Saving API server settings: enabled={}, port={}",
+        settings.enabled, settings.port
+    );
+
+    let store = app
+        .store(STORE_FILE)
+        .map_err(|e| anyhow::anyhow!("Failed to access store: {}", e))?;
+
+    let value = serde_json::to_value(settings)
+        .map_err(|e| anyhow::anyhow!("Failed to serialize API server settings: {}", e))?;
+    store.set(STORE_KEY, value);
+    store
+        .save()
+        .map_err(|e| anyhow::anyhow!("Failed to save store to disk: {}", e))?;
+
+    Ok(())
+}
@@ -0,0 +1,97 @@
+use crate::database::models::ActionItem;
+use crate::database::repositories::action_item::{ActionItemFilter, ActionItemsRepository};
+use crate::database::repositories::setting::SettingsRepository;
+use crate::state::AppState;
+use crate::summary::extraction::ActionItemStatus;
+use crate::summary::service::SummaryService;
+use tauri::{AppHandle, Manager, Runtime};
+use tracing::{error, info};
+
+/// Runs action item and decision extraction for a meeting and returns its items
+///
+/// Uses the configured summary model unless `provider`/`model` are given. Items
+/// still open are replaced; completed and cancelled items are kept.
+#[tauri::command]
+pub async fn api_extract_action_items<R: Runtime>(
+    app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    meeting_id: String,
+    provider: Option<String>,
+    model: Option<String>,
+) -> Result<Vec<ActionItem>, String> {
+    info!(
+        "api_extract_action_items called for meeting_id: {}",
+        meeting_id
+    );
+    let pool = state.db_manager.pool();
+
+    let (provider, model) = match (provider, model) {
+        (Some(provider), Some(model)) => (provider, model),
+        (provider, model) => {
+            let config = SettingsRepository::get_model_config(pool)
+                .await
+                .map_err(|e| format!("Failed to load model config: {}", e))?
+                .ok_or("No summary model configured")?;
+            (
+                provider.unwrap_or(config.provider),
+                model.unwrap_or(config.model),
+            )
+        }
+    };
+
+    let app_data_dir = app.path().app_data_dir().ok();
+    SummaryService::extract_and_store_action_items(
+        app_data_dir.as_ref(),
+        pool,
+        &meeting_id,
+        &provider,
+        &model,
+        None,
+    )
+    .await
+    .map_err(|e| {
+        error!("Action item extraction failed for {}: {}", meeting_id, e);
+        e
+    })
+}
+
+/// Lists action items and decisions across all meetings
+#[tauri::command]
+pub async fn api_list_action_items<R: Runtime>(
+    _app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    filter: Option<ActionItemFilter>,
+) -> Result<Vec<ActionItem>, String> {
+    let filter = filter.unwrap_or_default();
+    info!("api_list_action_items called with filter: {:?}", filter);
+
+    ActionItemsRepository::list(state.db_manager.pool(), &filter)
+        .await
+        .map_err(|e| {
+            error!("Failed to list action items: {}", e);
+            format!("Failed to list action items: {}", e)
+        })
+}
+
+/// Marks an item open, completed or cancelled
+#[tauri::command]
+pub async fn api_update_action_item_status<R: Runtime>(
+    _app: AppHandle<R>,
+    state: tauri::State<'_, AppState>,
+    id: String,
+    status: ActionItemStatus,
+) -> Result<ActionItem, String> {
+    info!(
+        "api_update_action_item_status called: {} -> {:?}",
+        id, status
+    );
+
+    match ActionItemsRepository::set_status(state.db_manager.pool(), &id, status).await {
+        Ok(Some(item)) => Ok(item),
+        Ok(None) => Err(format!("Action item not found: {}", id)),
+        Err(e) => {
+            error!("Failed to update action item {}: {}", id, e);
+            Err(format!("Failed to update action item: {}", e))
+        }
+    }
+}
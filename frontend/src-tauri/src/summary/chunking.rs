@@ -0,0 +1,340 @@
+//! Splitting a transcript into chunks that fit the model's context.
+//!
+//! Chunks are built from the stored transcript segments, so a boundary always
+//! falls between two segments and every chunk covers a contiguous time range.
+//! Segments are grouped into small pieces that are counted with the model's
+//! tokenizer, then packed greedily into chunks.
+
+use async_trait::async_trait;
+use sha2::{Digest, Sha256};
+use std::ops::Range;
+use tracing::warn;
+
+use super::citations::format_marker;
+use super::llm_client::LlmClient;
+use super::processor::{format_speaker_transcript, rough_token_count};
+use crate::utils::format_timestamp;
+
+/// Target size, in estimated tokens, of the pieces segments are grouped into
+/// before counting. Chunks are packed from whole pieces, so smaller pieces fill
+/// the context more tightly at the cost of more tokenizer calls.
+const PIECE_TOKENS: usize = 512;
+
+/// One transcript row, the unit chunk boundaries fall between
+#[derive(Debug, Clone, PartialEq)]
+pub struct SummarySegment {
+    /// Transcript row id, the target of summary citations
+    pub id: Option<String>,
+    pub speaker: Option<String>,
+    pub text: String,
+    /// Seconds from recording start
+    pub start_time: Option<f64>,
+    pub end_time: Option<f64>,
+}
+
+impl SummarySegment {
+    /// Untimed segments, one per line, for a transcript that only exists as text
+    pub fn from_text(text: &str) -> Vec<Self> {
+        text.lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| Self {
+                id: None,
+                speaker: None,
+                text: line.to_string(),
+                start_time: None,
+                end_time: None,
+            })
+            .collect()
+    }
+}
+
+/// Text covering a contiguous range of the meeting: a group of transcript
+/// segments, or a summary of such groups
+#[derive(Debug, Clone, PartialEq)]
+pub struct TranscriptChunk {
+    pub text: String,
+    /// Size of `text` with the model's tokenizer
+    pub tokens: usize,
+    pub start_time: Option<f64>,
+    pub end_time: Option<f64>,
+}
+
+impl TranscriptChunk {
+    /// "00:12:30 - 00:25:10", or None when the segments have no timing
+    pub fn time_range(&self) -> Option<String> {
+        match (self.start_time, self.end_time) {
+            (Some(start), Some(end)) => Some(format!(
+                "{} - {}",
+                format_timestamp(start),
+                format_timestamp(end)
+            )),
+            (Some(start), None) => Some(format!("from {}", format_timestamp(start))),
+            _ => None,
+        }
+    }
+
+    /// Joins consecutive chunks into one covering their combined range
+    pub fn concat(chunks: &[TranscriptChunk]) -> TranscriptChunk {
+        TranscriptChunk {
+            text: chunks.iter().map(|chunk| chunk.text.as_str()).collect(),
+            tokens: chunks.iter().map(|chunk| chunk.tokens).sum(),
+            start_time: chunks.iter().find_map(|chunk| chunk.start_time),
+            end_time: chunks.iter().rev().find_map(|chunk| chunk.end_time),
+        }
+    }
+}
+
+/// Identifies an intermediate summary: the same input summarized by the same
+/// model with the same prompt can be reused
+#[derive(Debug, Clone, PartialEq)]
+pub struct SummaryCacheKey {
+    /// 0 for transcript chunks, 1 and up for the levels of the reduce tree
+    pub level: u32,
+    pub content_hash: String,
+    /// "provider:model"
+    pub model: String,
+    pub prompt_hash: String,
+}
+
+/// Storage for chunk and combine summaries, so regenerating a summary only
+/// reruns the stages whose input changed
+#[async_trait]
+pub trait SummaryCache: Send + Sync {
+    async fn get(&self, key: &SummaryCacheKey) -> Option<String>;
+
+    async fn put(&self, key: &SummaryCacheKey, summary: &str);
+}
+
+/// Hex SHA-256 of the parts, used as cache keys for chunk summaries
+pub fn content_hash(parts: &[&str]) -> String {
+    let mut hasher = Sha256::new();
+    for part in parts {
+        hasher.update(part.as_bytes());
+        // Separator, so ["ab", "c"] and ["a", "bc"] differ
+        hasher.update([0u8]);
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+fn piece(
+    lines: &[(Option<String>, String)],
+    start: Option<f64>,
+    end: Option<f64>,
+) -> TranscriptChunk {
+    let mut text = format_speaker_transcript(lines);
+    text.push('\n');
+    TranscriptChunk {
+        text,
+        tokens: 0,
+        start_time: start,
+        end_time: end,
+    }
+}
+
+/// Groups segments into uncounted pieces of at most `PIECE_TOKENS` estimated
+/// tokens. A segment longer than that is split between words, each part keeping
+/// the segment's speaker and timing. Timed segments are prefixed with their
+/// `[mm:ss]` citation marker.
+pub fn split_pieces(segments: &[SummarySegment]) -> Vec<TranscriptChunk> {
+    let mut pieces = Vec::new();
+    let mut lines: Vec<(Option<String>, String)> = Vec::new();
+    let mut estimate = 0;
+    let mut start = None;
+    let mut end = None;
+
+    for segment in segments {
+        let text = segment.text.trim();
+        if text.is_empty() {
+            continue;
+        }
+        let parts: Vec<String> = if rough_token_count(text) <= PIECE_TOKENS {
+            vec![text.to_string()]
+        } else {
+            let mut parts = vec![String::new()];
+            for word in text.split_inclusive(' ') {
+                let last = parts.last_mut().unwrap();
+                if !last.is_empty()
+                    && rough_token_count(last) + rough_token_count(word) > PIECE_TOKENS
+                {
+                    parts.push(String::new());
+                }
+                parts.last_mut().unwrap().push_str(word);
+            }
+            parts
+        };
+
+        for part in parts {
+            let part_estimate = rough_token_count(&part);
+            if !lines.is_empty() && estimate + part_estimate > PIECE_TOKENS {
+                pieces.push(piece(&lines, start, end));
+                lines.clear();
+                estimate = 0;
+                start = None;
+                end = None;
+            }
+            start = start.or(segment.start_time);
+            end = segment.end_time.or(end);
+            let part = match segment.start_time {
+                Some(start) => format!("{} {}", format_marker(start), part),
+                None => part,
+            };
+            lines.push((segment.speaker.clone(), part));
+            estimate += part_estimate;
+        }
+    }
+    if !lines.is_empty() {
+        pieces.push(piece(&lines, start, end));
+    }
+    pieces
+}
+
+/// Counts each text with the client's tokenizer, falling back to the estimate
+/// when the tokenizer is unavailable
+pub async fn count_tokens(client: &dyn LlmClient, texts: &[String]) -> Vec<usize> {
+    match client.count_tokens(texts).await {
+        Ok(counts) if counts.len() == texts.len() => counts,
+        Ok(counts) => {
+            warn!(
+                "{} returned {} token counts for {} texts, using estimates",
+                client.provider().name(),
+                counts.len(),
+                texts.len()
+            );
+            texts.iter().map(|text| rough_token_count(text)).collect()
+        }
+        Err(e) => {
+            warn!(
+                "Token counting with {} failed, using estimates: {}",
+                client.provider().name(),
+                e
+            );
+            texts.iter().map(|text| rough_token_count(text)).collect()
+        }
+    }
+}
+
+/// Splits `sizes` into consecutive groups whose total is at most `max_tokens`.
+/// An item larger than the budget gets a group of its own.
+pub fn group_by_budget(sizes: &[usize], max_tokens: usize) -> Vec<Range<usize>> {
+    let mut groups = Vec::new();
+    let mut start = 0;
+    let mut total = 0;
+    for (i, size) in sizes.iter().enumerate() {
+        if i > start && total + size > max_tokens {
+            groups.push(start..i);
+            start = i;
+            total = 0;
+        }
+        if *size > max_tokens {
+            warn!(
+                "Text of {} tokens exceeds the budget of {} tokens",
+                size, max_tokens
+            );
+        }
+        total += size;
+    }
+    if start < sizes.len() {
+        groups.push(start..sizes.len());
+    }
+    groups
+}
+
+/// Packs counted pieces into chunks of at most `max_tokens` tokens
+pub fn pack_pieces(pieces: &[TranscriptChunk], max_tokens: usize) -> Vec<TranscriptChunk> {
+    let sizes: Vec<usize> = pieces.iter().map(|piece| piece.tokens).collect();
+    group_by_budget(&sizes, max_tokens)
+        .into_iter()
+        .map(|range| TranscriptChunk::concat(&pieces[range]))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(speaker: &str, text: &str, start: f64) -> SummarySegment {
+        SummarySegment {
+            id: None,
+            speaker: Some(speaker.to_string()),
+            text: text.to_string(),
+            start_time: Some(start),
+            end_time: Some(start + 5.0),
+        }
+    }
+
+    #[test]
+    fn test_split_pieces_breaks_between_segments() {
+        let long = "word ".repeat(600);
+        let segments = vec![
+            segment("Alice", "Let's start.", 0.0),
+            segment("Alice", "First item.", 5.0),
+            segment("Bob", long.trim(), 10.0),
+            segment("Alice", "Bye.", 600.0),
+        ];
+        let pieces = split_pieces(&segments);
+
+        assert!(pieces.len() > 3);
+        assert_eq!(
+            pieces[0].text,
+            "Alice: [00:00] Let's start. [00:05] First item.\n"
+        );
+        assert_eq!(pieces[0].start_time, Some(0.0));
+        // Every part of the long segment keeps its speaker
+        assert!(pieces[1].text.starts_with("Bob: [00:10] word"));
+        assert!(pieces[2].text.starts_with("Bob: [00:10] word"));
+        let last = pieces.last().unwrap();
+        assert!(last.text.ends_with("Alice: [10:00] Bye.\n"));
+        assert_eq!(last.end_time, Some(605.0));
+
+        let words: usize = pieces
+            .iter()
+            .map(|piece| piece.text.matches("word").count())
+            .sum();
+        assert_eq!(words, 600);
+    }
+
+    #[test]
+    fn test_group_by_budget() {
+        assert_eq!(
+            group_by_budget(&[40, 50, 30, 100], 100),
+            vec![0..2, 2..3, 3..4]
+        );
+        assert_eq!(group_by_budget(&[40, 50, 30, 100], 1000), vec![0..4]);
+        assert_eq!(group_by_budget(&[250, 10], 100), vec![0..1, 1..2]);
+        assert!(group_by_budget(&[], 100).is_empty());
+    }
+
+    #[test]
+    fn test_pack_pieces_merges_time_ranges() {
+        let piece = |text: &str, tokens, start, end| TranscriptChunk {
+            text: text.to_string(),
+            tokens,
+            start_time: Some(start),
+            end_time: Some(end),
+        };
+        let chunks = pack_pieces(
+            &[
+                piece("a\n", 60, 0.0, 60.0),
+                piece("b\n", 30, 60.0, 750.0),
+                piece("c\n", 50, 750.0, 900.0),
+            ],
+            100,
+        );
+
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].text, "a\nb\n");
+        assert_eq!(chunks[0].tokens, 90);
+        assert_eq!(
+            chunks[0].time_range().as_deref(),
+            Some("00:00:00 - 00:12:30")
+        );
+        assert_eq!(chunks[1].text, "c\n");
+    }
+
+    #[test]
+    fn test_content_hash_separates_parts() {
+        assert_ne!(content_hash(&["ab", "c"]), content_hash(&["a", "bc"]));
+        assert_eq!(content_hash(&["x"]), content_hash(&["x"]));
+        assert_eq!(content_hash(&["x"]).len(), 64);
+    }
+}
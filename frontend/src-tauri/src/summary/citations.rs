@@ -0,0 +1,247 @@
+//! Timestamp citations from summary bullets back into the transcript.
+//!
+//! Transcript lines are fed to the model with `[mm:ss]` markers of their
+//! segment's start, and the model is asked to end each bullet with the markers
+//! it is based on. After generation every marker is checked against the
+//! segments: markers that match no segment are removed, and bullets left
+//! without a citation are flagged as unverified.
+
+use once_cell::sync::Lazy;
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tracing::warn;
+
+use super::chunking::SummarySegment;
+
+/// One or more markers in a single bracket: "[12:04]" or "[12:04, 13:30]"
+static CITATION_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r" ?\[(\d{1,3}:\d{2}(?:\s*[,;]\s*\d{1,3}:\d{2})*)\]").unwrap());
+
+/// Appended to bullets that cite no transcript segment
+pub const UNVERIFIED_FLAG: &str = "_(unverified)_";
+
+/// A summary citation resolved to the transcript segment it points at
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SummaryCitation {
+    /// Marker as it appears in the summary markdown, e.g. "[12:04]"
+    pub marker: String,
+    pub segment_id: String,
+    /// Seconds from recording start, for seeking the audio player
+    pub audio_start_time: f64,
+}
+
+/// Summary markdown after its citations were checked
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CitedSummary {
+    pub markdown: String,
+    /// Distinct citations in order of first appearance
+    pub citations: Vec<SummaryCitation>,
+    /// Markers that matched no segment and were removed
+    pub dropped_markers: usize,
+    /// Bullets flagged with `UNVERIFIED_FLAG`
+    pub unverified_bullets: usize,
+}
+
+/// "[mm:ss]" marker of a segment start; minutes keep counting past the hour
+pub fn format_marker(seconds: f64) -> String {
+    let total = seconds.max(0.0) as u64;
+    format!("[{:02}:{:02}]", total / 60, total % 60)
+}
+
+/// Normalizes "1:05" to "[01:05]" so the model's spelling of a marker matches
+fn normalize_marker(marker: &str) -> Option<String> {
+    let (minutes, seconds) = marker.trim().split_once(':')?;
+    let minutes: u64 = minutes.parse().ok()?;
+    let seconds: u64 = seconds.parse().ok()?;
+    (seconds < 60).then(|| format!("[{:02}:{:02}]", minutes, seconds))
+}
+
+fn is_bullet(line: &str) -> bool {
+    let line = line.trim_start();
+    if line.starts_with("- ") || line.starts_with("* ") || line.starts_with("+ ") {
+        return true;
+    }
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    digits > 0 && line[digits..].starts_with(". ")
+}
+
+/// Text stating there is nothing to cite, e.g. "None noted in this section."
+fn is_placeholder(line: &str) -> bool {
+    line.to_lowercase().contains("none noted")
+}
+
+/// Checks citations line by line against the segments a summary was generated
+/// from, collecting the ones that resolve
+pub struct CitationResolver<'a> {
+    /// Marker -> (segment id, start); the first segment starting in a given
+    /// second owns its marker
+    targets: HashMap<String, (&'a str, f64)>,
+    result: CitedSummary,
+}
+
+impl<'a> CitationResolver<'a> {
+    pub fn new(segments: &'a [SummarySegment]) -> Self {
+        let mut targets = HashMap::new();
+        for segment in segments {
+            if let (Some(id), Some(start)) = (&segment.id, segment.start_time) {
+                targets
+                    .entry(format_marker(start))
+                    .or_insert((id.as_str(), start));
+            }
+        }
+        Self {
+            targets,
+            result: CitedSummary::default(),
+        }
+    }
+
+    /// Whether the transcript has timed segments to cite
+    pub fn is_enabled(&self) -> bool {
+        !self.targets.is_empty()
+    }
+
+    /// Removes markers of `text` that match no segment. Text that must cite a
+    /// segment (a bullet) and cites none is flagged with `UNVERIFIED_FLAG`.
+    pub fn resolve(&mut self, text: &str, require_citation: bool) -> String {
+        if !self.is_enabled() {
+            return text.to_string();
+        }
+
+        let targets = &self.targets;
+        let result = &mut self.result;
+        let mut cited = false;
+        let text = CITATION_REGEX.replace_all(text, |caps: &Captures| {
+            let mut valid = Vec::new();
+            for marker in caps[1].split([',', ';']) {
+                let Some(marker) = normalize_marker(marker) else {
+                    result.dropped_markers += 1;
+                    continue;
+                };
+                let Some(&(segment_id, start)) = targets.get(&marker) else {
+                    result.dropped_markers += 1;
+                    continue;
+                };
+                if !result.citations.iter().any(|c| c.marker == marker) {
+                    result.citations.push(SummaryCitation {
+                        marker: marker.clone(),
+                        segment_id: segment_id.to_string(),
+                        audio_start_time: start,
+                    });
+                }
+                valid.push(marker);
+            }
+            if valid.is_empty() {
+                return String::new();
+            }
+            cited = true;
+            format!(" {}", valid.join(" "))
+        });
+
+        if require_citation && !cited && !is_placeholder(&text) {
+            result.unverified_bullets += 1;
+            format!("{} {}", text.trim_end(), UNVERIFIED_FLAG)
+        } else {
+            text.into_owned()
+        }
+    }
+
+    /// The resolved citations, with `markdown` as the checked summary
+    pub fn finish(mut self, markdown: String) -> CitedSummary {
+        if self.result.dropped_markers > 0 || self.result.unverified_bullets > 0 {
+            warn!(
+                "Summary citations: removed {} markers matching no segment, flagged {} uncited bullets",
+                self.result.dropped_markers, self.result.unverified_bullets
+            );
+        }
+        self.result.markdown = markdown;
+        self.result
+    }
+}
+
+/// Checks the citations of a generated summary against the segments it was
+/// generated from. Summaries of untimed transcripts are returned unchanged.
+pub fn resolve_citations(markdown: &str, segments: &[SummarySegment]) -> CitedSummary {
+    let mut resolver = CitationResolver::new(segments);
+    let markdown = markdown
+        .lines()
+        .map(|line| resolver.resolve(line, is_bullet(line)))
+        .collect::<Vec<_>>()
+        .join("\n");
+    resolver.finish(markdown)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segment(id: &str, start: f64) -> SummarySegment {
+        SummarySegment {
+            id: Some(id.to_string()),
+            speaker: None,
+            text: String::new(),
+            start_time: Some(start),
+            end_time: Some(start + 4.0),
+        }
+    }
+
+    #[test]
+    fn test_format_marker() {
+        assert_eq!(format_marker(0.0), "[00:00]");
+        assert_eq!(format_marker(754.6), "[12:34]");
+        assert_eq!(format_marker(4512.0), "[75:12]");
+        assert_eq!(normalize_marker("1:05").as_deref(), Some("[01:05]"));
+        assert_eq!(normalize_marker("1:75"), None);
+    }
+
+    #[test]
+    fn test_resolve_citations() {
+        let segments = vec![
+            segment("t1", 5.0),
+            segment("t2", 754.0),
+            segment("t3", 754.5),
+        ];
+        let markdown = "## Decisions\n\
+            - Ship on Friday [00:05]\n\
+            - Alice owns the rollout [12:34, 1:05] [99:00]\n\
+            - Budget was approved\n\
+            - None noted in this section.\n\
+            Plain paragraph";
+        let cited = resolve_citations(markdown, &segments);
+
+        assert_eq!(
+            cited.markdown,
+            "## Decisions\n\
+            - Ship on Friday [00:05]\n\
+            - Alice owns the rollout [12:34]\n\
+            - Budget was approved _(unverified)_\n\
+            - None noted in this section.\n\
+            Plain paragraph"
+        );
+        assert_eq!(
+            cited.citations,
+            vec![
+                SummaryCitation {
+                    marker: "[00:05]".to_string(),
+                    segment_id: "t1".to_string(),
+                    audio_start_time: 5.0,
+                },
+                SummaryCitation {
+                    marker: "[12:34]".to_string(),
+                    segment_id: "t2".to_string(),
+                    audio_start_time: 754.0,
+                },
+            ]
+        );
+        assert_eq!(cited.dropped_markers, 2);
+        assert_eq!(cited.unverified_bullets, 1);
+    }
+
+    #[test]
+    fn test_untimed_transcript_is_left_alone() {
+        let segments = SummarySegment::from_text("Alice: hello");
+        let cited = resolve_citations("- Greeting [00:01]", &segments);
+        assert_eq!(cited.markdown, "- Greeting [00:01]");
+        assert!(cited.citations.is_empty());
+    }
+}
@@ -4,9 +4,11 @@ use crate::database::repositories::{
 };
 use crate::state::AppState;
 use crate::summary::service::SummaryService;
+use crate::summary::streaming::{SummaryDelta, SummaryDeltaCallback, SummaryStage};
 use log::{error as log_error, info as log_info, warn as log_warn};
 use serde::{Deserialize, Serialize};
-use tauri::{AppHandle, Runtime};
+use std::sync::Arc;
+use tauri::{AppHandle, Emitter, Manager, Runtime};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SummaryResponse {
@@ -175,6 +177,7 @@ pub async fn api_process_transcript<R: Runtime>(
     _overlap: Option<i32>,
     custom_prompt: Option<String>,
     template_id: Option<String>,
+    structured_output: Option<bool>,
     _auth_token: Option<String>,
 ) -> Result<ProcessTranscriptResponse, String> {
     use uuid::Uuid;
@@ -217,9 +220,25 @@ pub async fn api_process_transcript<R: Runtime>(
 
     // Spawn background task for actual processing
     let meeting_id_clone = m_id.clone();
+    let app_data_dir = app.path().app_data_dir().ok();
+
+    // Forward generated text to the UI as it streams in
+    let delta_app = app.clone();
+    let delta_meeting_id = m_id.clone();
+    let on_delta: Arc<SummaryDeltaCallback> = Arc::new(move |stage: SummaryStage, delta: &str| {
+        let _ = delta_app.emit(
+            "summary-delta",
+            SummaryDelta {
+                meeting_id: delta_meeting_id.clone(),
+                stage,
+                delta: delta.to_string(),
+            },
+        );
+    });
+
     tauri::async_runtime::spawn(async move {
-        SummaryService::process_transcript_background(
-            app,
+        let _ = SummaryService::process_transcript_background(
+            app_data_dir,
             pool,
             meeting_id_clone.clone(),
             text,
@@ -227,6 +246,8 @@ pub async fn api_process_transcript<R: Runtime>(
             model_name,
             final_prompt,
             final_template_id,
+            structured_output.unwrap_or(false),
+            Some(on_delta),
         )
         .await;
     });
@@ -0,0 +1,393 @@
+//! Action item and decision extraction.
+//!
+//! Asks the LLM for a JSON object matching a fixed schema, validates it, and
+//! retries with the validation error when the reply can't be used. Transcript
+//! segments are numbered in the prompt (`[12] Alice: ...`) so items can point
+//! back at the segment they came from without spending tokens on row ids.
+
+use std::collections::HashSet;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use super::llm_client::LlmRequest;
+use super::processor::rough_token_count;
+use super::service::ProviderSettings;
+
+/// Attempts per transcript chunk before giving up on a malformed reply
+const MAX_EXTRACTION_ATTEMPTS: usize = 3;
+
+/// Values models use for "nobody" that shouldn't be stored as an owner
+const NO_OWNER: &[&str] = &["", "null", "none", "unknown", "unassigned", "n/a", "tbd"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActionItemKind {
+    ActionItem,
+    Decision,
+}
+
+impl ActionItemKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ActionItem => "action_item",
+            Self::Decision => "decision",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ActionItemStatus {
+    Open,
+    Completed,
+    Cancelled,
+}
+
+impl ActionItemStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Open => "open",
+            Self::Completed => "completed",
+            Self::Cancelled => "cancelled",
+        }
+    }
+}
+
+/// A transcript row as shown to the model
+#[derive(Debug, Clone)]
+pub struct ExtractionSegment {
+    pub id: String,
+    pub speaker: Option<String>,
+    pub text: String,
+}
+
+/// A validated item, ready to be stored
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtractedItem {
+    pub kind: ActionItemKind,
+    pub text: String,
+    pub owner: Option<String>,
+    /// YYYY-MM-DD
+    pub due_date: Option<String>,
+    pub source_segment_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawExtraction {
+    action_items: Vec<RawActionItem>,
+    decisions: Vec<RawDecision>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawActionItem {
+    task: String,
+    #[serde(default)]
+    owner: Option<String>,
+    #[serde(default)]
+    due_date: Option<String>,
+    #[serde(default)]
+    source_segment: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawDecision {
+    decision: String,
+    #[serde(default)]
+    source_segment: Option<serde_json::Value>,
+}
+
+fn system_prompt(meeting_date: NaiveDate) -> String {
+    format!(
+        r#"You extract action items and decisions from meeting transcripts.
+
+Each transcript line starts with its segment number in brackets, e.g. "[12] Alice: I'll send the deck".
+
+Reply with ONLY a JSON object, no markdown and no commentary, in exactly this shape:
+{{
+  "action_items": [
+    {{"task": "what must be done", "owner": "person responsible or null", "due_date": "YYYY-MM-DD or null", "source_segment": 12}}
+  ],
+  "decisions": [
+    {{"decision": "what was decided", "source_segment": 7}}
+  ]
+}}
+
+Rules:
+- Only include tasks someone committed to or was assigned, and decisions that were actually agreed.
+- "task" and "decision" are short, self-contained sentences.
+- "owner" is the name used in the transcript; null if nobody was named.
+- The meeting took place on {}. Convert relative deadlines ("Friday", "next week") to dates; null if no deadline was given.
+- "source_segment" is the number of the line the item comes from.
+- Use empty arrays when there is nothing to report."#,
+        meeting_date.format("%Y-%m-%d (%A)")
+    )
+}
+
+fn format_segment(index: usize, segment: &ExtractionSegment) -> String {
+    match &segment.speaker {
+        Some(speaker) => format!("[{}] {}: {}", index + 1, speaker, segment.text.trim()),
+        None => format!("[{}] {}", index + 1, segment.text.trim()),
+    }
+}
+
+/// Groups numbered transcript lines into prompts of at most `token_threshold` tokens
+fn chunk_segments(segments: &[ExtractionSegment], token_threshold: usize) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+    for (index, segment) in segments.iter().enumerate() {
+        if segment.text.trim().is_empty() {
+            continue;
+        }
+        let line = format_segment(index, segment);
+        if !current.is_empty()
+            && rough_token_count(&current) + rough_token_count(&line) > token_threshold
+        {
+            chunks.push(std::mem::take(&mut current));
+        }
+        current.push_str(&line);
+        current.push('\n');
+    }
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+    chunks
+}
+
+/// Pulls the JSON object out of a reply that may be wrapped in code fences or prose
+pub(crate) fn json_object(raw: &str) -> Option<&str> {
+    let start = raw.find('{')?;
+    let end = raw.rfind('}')?;
+    (start < end).then(|| &raw[start..=end])
+}
+
+/// Maps a `source_segment` value (12, "12", "[12]", "S12") to its transcript id
+fn resolve_segment(
+    value: Option<&serde_json::Value>,
+    segments: &[ExtractionSegment],
+) -> Option<String> {
+    let number = match value? {
+        serde_json::Value::Number(n) => n.as_u64()?,
+        serde_json::Value::String(s) => {
+            s.trim_matches(|c: char| !c.is_ascii_digit()).parse().ok()?
+        }
+        _ => return None,
+    };
+    let index = usize::try_from(number).ok()?.checked_sub(1)?;
+    segments.get(index).map(|segment| segment.id.clone())
+}
+
+fn clean_owner(owner: Option<String>) -> Option<String> {
+    let owner = owner?.trim().to_string();
+    if NO_OWNER.contains(&owner.to_lowercase().as_str()) {
+        None
+    } else {
+        Some(owner)
+    }
+}
+
+fn clean_due_date(due_date: Option<String>) -> Option<String> {
+    let due_date = due_date?;
+    let due_date = due_date.trim();
+    if due_date.is_empty() || due_date.eq_ignore_ascii_case("null") {
+        return None;
+    }
+    match NaiveDate::parse_from_str(due_date, "%Y-%m-%d") {
+        Ok(date) => Some(date.format("%Y-%m-%d").to_string()),
+        Err(_) => {
+            warn!("Ignoring due date that is not YYYY-MM-DD: '{}'", due_date);
+            None
+        }
+    }
+}
+
+/// Parses and validates one model reply. The error describes what was wrong so it
+/// can be fed back to the model on retry.
+pub fn parse_extraction(
+    raw: &str,
+    segments: &[ExtractionSegment],
+) -> Result<Vec<ExtractedItem>, String> {
+    let json = json_object(raw).ok_or("The reply did not contain a JSON object")?;
+    let parsed: RawExtraction = serde_json::from_str(json)
+        .map_err(|e| format!("The JSON did not match the schema: {}", e))?;
+
+    let mut items = Vec::new();
+    for (i, item) in parsed.action_items.into_iter().enumerate() {
+        let task = item.task.trim();
+        if task.is_empty() {
+            return Err(format!("action_items[{}].task is empty", i));
+        }
+        items.push(ExtractedItem {
+            kind: ActionItemKind::ActionItem,
+            text: task.to_string(),
+            owner: clean_owner(item.owner),
+            due_date: clean_due_date(item.due_date),
+            source_segment_id: resolve_segment(item.source_segment.as_ref(), segments),
+        });
+    }
+    for (i, decision) in parsed.decisions.into_iter().enumerate() {
+        let text = decision.decision.trim();
+        if text.is_empty() {
+            return Err(format!("decisions[{}].decision is empty", i));
+        }
+        items.push(ExtractedItem {
+            kind: ActionItemKind::Decision,
+            text: text.to_string(),
+            owner: None,
+            due_date: None,
+            source_segment_id: resolve_segment(decision.source_segment.as_ref(), segments),
+        });
+    }
+    Ok(items)
+}
+
+/// Case- and whitespace-insensitive key used to drop duplicates across chunks
+pub fn dedup_key(kind: &str, text: &str) -> String {
+    format!(
+        "{}:{}",
+        kind,
+        text.split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .to_lowercase()
+    )
+}
+
+/// Extracts action items and decisions from the transcript, one request per chunk
+pub async fn extract_action_items(
+    settings: &ProviderSettings,
+    segments: &[ExtractionSegment],
+    meeting_date: NaiveDate,
+    cancellation_token: Option<&CancellationToken>,
+) -> Result<Vec<ExtractedItem>, String> {
+    let system_prompt = system_prompt(meeting_date);
+    let token_threshold = settings.contexts[0].available(rough_token_count(&system_prompt));
+    let chunks = chunk_segments(segments, token_threshold);
+    info!(
+        "Extracting action items from {} segments in {} chunk(s)",
+        segments.len(),
+        chunks.len()
+    );
+
+    let mut items = Vec::new();
+    let mut seen = HashSet::new();
+    for (i, chunk) in chunks.iter().enumerate() {
+        let base_prompt = format!("<transcript>\n{}</transcript>", chunk);
+        let mut user_prompt = base_prompt.clone();
+        let mut attempt = 1;
+
+        let chunk_items = loop {
+            let request = LlmRequest {
+                system_prompt: &system_prompt,
+                user_prompt: &user_prompt,
+                json_schema: None,
+            };
+            let reply = settings
+                .llm
+                .complete(&request, cancellation_token, None)
+                .await?;
+
+            match parse_extraction(&reply, segments) {
+                Ok(chunk_items) => break chunk_items,
+                Err(e) if attempt < MAX_EXTRACTION_ATTEMPTS => {
+                    warn!(
+                        "Chunk {}/{}: invalid extraction reply (attempt {}): {}",
+                        i + 1,
+                        chunks.len(),
+                        attempt,
+                        e
+                    );
+                    attempt += 1;
+                    user_prompt = format!(
+                        "{}\n\nYour previous reply could not be used: {}\nReply again with only the JSON object described in the instructions.",
+                        base_prompt, e
+                    );
+                }
+                Err(e) => {
+                    return Err(format!(
+                        "Action item extraction failed after {} attempts: {}",
+                        MAX_EXTRACTION_ATTEMPTS, e
+                    ))
+                }
+            }
+        };
+
+        for item in chunk_items {
+            if seen.insert(dedup_key(item.kind.as_str(), &item.text)) {
+                items.push(item);
+            }
+        }
+    }
+
+    info!("Extracted {} action items and decisions", items.len());
+    Ok(items)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segments() -> Vec<ExtractionSegment> {
+        vec![
+            ExtractionSegment {
+                id: "t-1".to_string(),
+                speaker: Some("Alice".to_string()),
+                text: "We'll ship on Monday.".to_string(),
+            },
+            ExtractionSegment {
+                id: "t-2".to_string(),
+                speaker: Some("Bob".to_string()),
+                text: "I'll update the docs by Friday.".to_string(),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_parse_extraction_validates_and_resolves_segments() {
+        let reply = r#"Sure! ```json
+{"action_items": [{"task": " Update the docs ", "owner": "Bob", "due_date": "2026-01-23", "source_segment": "[2]"},
+                  {"task": "Book a room", "owner": "unknown", "due_date": "Friday", "source_segment": 9}],
+ "decisions": [{"decision": "Ship on Monday", "source_segment": 1}]}
+```"#;
+        let items = parse_extraction(reply, &segments()).unwrap();
+        assert_eq!(
+            items[0],
+            ExtractedItem {
+                kind: ActionItemKind::ActionItem,
+                text: "Update the docs".to_string(),
+                owner: Some("Bob".to_string()),
+                due_date: Some("2026-01-23".to_string()),
+                source_segment_id: Some("t-2".to_string()),
+            }
+        );
+        assert_eq!(items[1].owner, None);
+        assert_eq!(items[1].due_date, None);
+        assert_eq!(items[1].source_segment_id, None);
+        assert_eq!(items[2].kind, ActionItemKind::Decision);
+        assert_eq!(items[2].source_segment_id.as_deref(), Some("t-1"));
+    }
+
+    #[test]
+    fn test_parse_extraction_rejects_invalid_replies() {
+        assert!(parse_extraction("No action items.", &segments()).is_err());
+        assert!(parse_extraction(r#"{"action_items": []}"#, &segments())
+            .unwrap_err()
+            .contains("decisions"));
+        assert!(parse_extraction(
+            r#"{"action_items": [{"task": "  "}], "decisions": []}"#,
+            &segments()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_chunk_segments_numbers_lines_globally() {
+        let chunks = chunk_segments(&segments(), 10);
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0], "[1] Alice: We'll ship on Monday.\n");
+        assert_eq!(chunks[1], "[2] Bob: I'll update the docs by Friday.\n");
+        assert_eq!(chunk_segments(&segments(), 1000).len(), 1);
+    }
+}
@@ -0,0 +1,72 @@
+//! Built-in AI through the llama-helper sidecar (no HTTP API).
+
+use async_trait::async_trait;
+use std::path::PathBuf;
+use tokio_util::sync::CancellationToken;
+
+use super::{LLMProvider, LlmClient, LlmError, LlmRequest};
+use crate::summary::streaming::DeltaCallback;
+use crate::summary::summary_engine::{
+    count_tokens_with_builtin, generate_with_builtin, OutputConstraint,
+};
+
+pub struct BuiltInClient {
+    app_data_dir: PathBuf,
+    model: String,
+}
+
+impl BuiltInClient {
+    pub fn new(app_data_dir: PathBuf, model: String) -> Self {
+        Self {
+            app_data_dir,
+            model,
+        }
+    }
+}
+
+#[async_trait]
+impl LlmClient for BuiltInClient {
+    fn provider(&self) -> LLMProvider {
+        LLMProvider::BuiltInAI
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    async fn complete(
+        &self,
+        request: &LlmRequest<'_>,
+        cancellation_token: Option<&CancellationToken>,
+        on_delta: Option<&DeltaCallback<'_>>,
+    ) -> Result<String, LlmError> {
+        // The sidecar enforces the schema with a grammar
+        let constraint = request
+            .json_schema
+            .cloned()
+            .map(OutputConstraint::JsonSchema);
+        generate_with_builtin(
+            &self.app_data_dir,
+            &self.model,
+            request.system_prompt,
+            request.user_prompt,
+            constraint.as_ref(),
+            cancellation_token,
+            on_delta,
+        )
+        .await
+        .map_err(|e| {
+            if cancellation_token.is_some_and(|token| token.is_cancelled()) {
+                LlmError::Cancelled
+            } else {
+                LlmError::Provider(e.to_string())
+            }
+        })
+    }
+
+    async fn count_tokens(&self, texts: &[String]) -> Result<Vec<usize>, LlmError> {
+        count_tokens_with_builtin(&self.app_data_dir, &self.model, texts)
+            .await
+            .map_err(|e| LlmError::Provider(e.to_string()))
+    }
+}
@@ -0,0 +1,122 @@
+//! Anthropic Messages API.
+
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+use tracing::info;
+
+use super::{
+    read_json, read_stream, send_request, ChatMessage, LLMProvider, LlmClient, LlmError, LlmRequest,
+};
+use crate::summary::streaming::{parse_claude_sse_line, DeltaCallback};
+
+const MESSAGES_URL: &str = "https://api.anthropic.com/v1/messages";
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+const MAX_TOKENS: u32 = 2048;
+
+// Claude-specific request structure
+#[derive(Debug, Serialize)]
+pub struct ClaudeRequest {
+    pub model: String,
+    pub max_tokens: u32,
+    pub system: String,
+    pub messages: Vec<ChatMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream: Option<bool>,
+}
+
+// Claude-specific response structure
+#[derive(Deserialize, Debug)]
+pub struct ClaudeChatResponse {
+    pub content: Vec<ClaudeChatContent>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ClaudeChatContent {
+    pub text: String,
+}
+
+pub struct ClaudeClient {
+    http: Client,
+    api_key: String,
+    model: String,
+    timeout: Duration,
+}
+
+impl ClaudeClient {
+    pub fn new(http: Client, api_key: String, model: String, timeout: Duration) -> Self {
+        Self {
+            http,
+            api_key,
+            model,
+            timeout,
+        }
+    }
+}
+
+#[async_trait]
+impl LlmClient for ClaudeClient {
+    fn provider(&self) -> LLMProvider {
+        LLMProvider::Claude
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    async fn complete(
+        &self,
+        request: &LlmRequest<'_>,
+        cancellation_token: Option<&CancellationToken>,
+        on_delta: Option<&DeltaCallback<'_>>,
+    ) -> Result<String, LlmError> {
+        let body = ClaudeRequest {
+            model: self.model.clone(),
+            max_tokens: MAX_TOKENS,
+            system: request.system_prompt.to_string(),
+            messages: vec![ChatMessage::new("user", request.user_prompt)],
+            stream: on_delta.map(|_| true),
+        };
+
+        info!("🐞 LLM Request to Claude: model={}", self.model);
+        let http_request = self
+            .http
+            .post(MESSAGES_URL)
+            .header("x-api-key", &self.api_key)
+            .header("anthropic-version", ANTHROPIC_VERSION)
+            .json(&body);
+        let response = send_request(
+            &LLMProvider::Claude,
+            http_request,
+            self.timeout,
+            cancellation_token,
+        )
+        .await?;
+
+        if let Some(on_delta) = on_delta {
+            let content = read_stream(
+                &LLMProvider::Claude,
+                response,
+                parse_claude_sse_line,
+                on_delta,
+                self.timeout,
+                cancellation_token,
+            )
+            .await?;
+            return Ok(content.trim().to_string());
+        }
+
+        let chat_response: ClaudeChatResponse = read_json(response, self.timeout).await?;
+        info!("🐞 LLM Response received from Claude");
+
+        let content = chat_response
+            .content
+            .first()
+            .ok_or_else(|| LlmError::InvalidResponse("No content in LLM response".to_string()))?
+            .text
+            .trim();
+        Ok(content.to_string())
+    }
+}
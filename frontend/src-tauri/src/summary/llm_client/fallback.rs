@@ -0,0 +1,247 @@
+//! Ordered fallback across providers, e.g. Claude → Ollama → Built-in AI, so a
+//! summary still completes when the primary provider is down.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use super::retry::{complete_with_retry, RetryPolicy};
+use super::{LlmClient, LlmError, LlmRequest};
+use crate::summary::streaming::DeltaCallback;
+
+/// The primary client followed by its fallbacks, each retried per `policy`
+pub struct FallbackChain {
+    clients: Vec<Arc<dyn LlmClient>>,
+    policy: RetryPolicy,
+}
+
+impl FallbackChain {
+    pub fn new(primary: Box<dyn LlmClient>, policy: RetryPolicy) -> Self {
+        Self {
+            clients: vec![Arc::from(primary)],
+            policy,
+        }
+    }
+
+    pub fn with_fallback(mut self, client: Box<dyn LlmClient>) -> Self {
+        self.clients.push(Arc::from(client));
+        self
+    }
+
+    pub fn primary(&self) -> &dyn LlmClient {
+        self.clients[0].as_ref()
+    }
+
+    /// All clients, in the order they are tried
+    pub fn clients(&self) -> impl Iterator<Item = &dyn LlmClient> {
+        self.clients.iter().map(|client| client.as_ref())
+    }
+
+    /// A chain of the clients at `indices`, in that order, with the same
+    /// retry policy. The first index becomes the primary.
+    pub fn select(&self, indices: &[usize]) -> FallbackChain {
+        FallbackChain {
+            clients: indices.iter().map(|&i| self.clients[i].clone()).collect(),
+            policy: self.policy.clone(),
+        }
+    }
+
+    /// Completes the request with the first provider that succeeds.
+    ///
+    /// Any error except cancellation moves on to the next provider once the
+    /// current one has used up its retries. As with retries, a provider that
+    /// failed after streaming some text isn't followed by the next one.
+    pub async fn complete(
+        &self,
+        request: &LlmRequest<'_>,
+        cancellation_token: Option<&CancellationToken>,
+        on_delta: Option<&DeltaCallback<'_>>,
+    ) -> Result<String, LlmError> {
+        self.complete_with_index(request, cancellation_token, on_delta)
+            .await
+            .map(|(_, text)| text)
+    }
+
+    /// Like `complete`, also returning the position in the chain of the
+    /// provider that answered (0 for the primary)
+    pub async fn complete_with_index(
+        &self,
+        request: &LlmRequest<'_>,
+        cancellation_token: Option<&CancellationToken>,
+        on_delta: Option<&DeltaCallback<'_>>,
+    ) -> Result<(usize, String), LlmError> {
+        let emitted = AtomicBool::new(false);
+        let tracked_delta = on_delta.map(|on_delta| {
+            let emitted = &emitted;
+            move |delta: &str| {
+                emitted.store(true, Ordering::Relaxed);
+                on_delta(delta)
+            }
+        });
+
+        let mut last_error = None;
+        for (i, client) in self.clients.iter().enumerate() {
+            if i > 0 {
+                info!(
+                    "Falling back to {} ({})",
+                    client.provider().name(),
+                    client.model()
+                );
+            }
+            match complete_with_retry(
+                client.as_ref(),
+                &self.policy,
+                request,
+                cancellation_token,
+                tracked_delta.as_ref().map(|f| f as &DeltaCallback),
+            )
+            .await
+            {
+                Ok(text) => return Ok((i, text)),
+                Err(LlmError::Cancelled) => return Err(LlmError::Cancelled),
+                Err(e) if emitted.load(Ordering::Relaxed) => return Err(e),
+                Err(e) => {
+                    warn!(
+                        "{} ({}) failed: {}",
+                        client.provider().name(),
+                        client.model(),
+                        e
+                    );
+                    last_error = Some(e);
+                }
+            }
+        }
+        Err(last_error.unwrap_or_else(|| LlmError::Provider("No LLM provider configured".into())))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::summary::llm_client::LLMProvider;
+    use async_trait::async_trait;
+    use std::sync::atomic::AtomicUsize;
+    use std::time::Duration;
+
+    struct MockClient {
+        provider: LLMProvider,
+        result: Result<String, LlmError>,
+        calls: AtomicUsize,
+    }
+
+    impl MockClient {
+        fn boxed(provider: LLMProvider, result: Result<&str, LlmError>) -> Box<Self> {
+            Box::new(Self {
+                provider,
+                result: result.map(str::to_string),
+                calls: AtomicUsize::new(0),
+            })
+        }
+    }
+
+    #[async_trait]
+    impl LlmClient for MockClient {
+        fn provider(&self) -> LLMProvider {
+            self.provider.clone()
+        }
+
+        fn model(&self) -> &str {
+            "mock"
+        }
+
+        async fn complete(
+            &self,
+            _request: &LlmRequest<'_>,
+            _cancellation_token: Option<&CancellationToken>,
+            _on_delta: Option<&DeltaCallback<'_>>,
+        ) -> Result<String, LlmError> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            self.result.clone()
+        }
+    }
+
+    fn fast_policy() -> RetryPolicy {
+        RetryPolicy {
+            max_attempts: 2,
+            initial_backoff: Duration::ZERO,
+            ..RetryPolicy::default()
+        }
+    }
+
+    const REQUEST: LlmRequest<'static> = LlmRequest {
+        system_prompt: "system",
+        user_prompt: "user",
+        json_schema: None,
+    };
+
+    #[tokio::test]
+    async fn test_falls_back_after_retries() {
+        let chain = FallbackChain::new(
+            MockClient::boxed(
+                LLMProvider::Claude,
+                Err(LlmError::Unavailable {
+                    status: Some(529),
+                    message: "overloaded".to_string(),
+                }),
+            ),
+            fast_policy(),
+        )
+        .with_fallback(MockClient::boxed(LLMProvider::Ollama, Ok("summary")));
+
+        assert_eq!(
+            chain
+                .complete_with_index(&REQUEST, None, None)
+                .await
+                .unwrap(),
+            (1, "summary".to_string())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_select_keeps_the_chosen_clients() {
+        let chain = FallbackChain::new(
+            MockClient::boxed(LLMProvider::Claude, Ok("claude")),
+            fast_policy(),
+        )
+        .with_fallback(MockClient::boxed(LLMProvider::OpenAI, Ok("openai")))
+        .with_fallback(MockClient::boxed(LLMProvider::Ollama, Ok("ollama")));
+
+        let selected = chain.select(&[2, 1]);
+        assert_eq!(selected.primary().provider(), LLMProvider::Ollama);
+        assert_eq!(selected.clients().count(), 2);
+        assert_eq!(
+            selected.complete(&REQUEST, None, None).await.unwrap(),
+            "ollama"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cancellation_stops_the_chain() {
+        let token = CancellationToken::new();
+        token.cancel();
+        let chain = FallbackChain::new(
+            MockClient::boxed(LLMProvider::Claude, Ok("never")),
+            fast_policy(),
+        )
+        .with_fallback(MockClient::boxed(LLMProvider::BuiltInAI, Ok("never")));
+
+        assert_eq!(
+            chain.complete(&REQUEST, Some(&token), None).await,
+            Err(LlmError::Cancelled)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_returns_last_error_when_all_fail() {
+        let rejected = LlmError::Rejected {
+            status: 401,
+            message: "bad key".to_string(),
+        };
+        let chain = FallbackChain::new(
+            MockClient::boxed(LLMProvider::OpenAI, Err(rejected.clone())),
+            fast_policy(),
+        );
+        assert_eq!(chain.complete(&REQUEST, None, None).await, Err(rejected));
+    }
+}
@@ -0,0 +1,146 @@
+//! Gemini generateContent, with safety blocks reported as errors.
+
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::Serialize;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+use tracing::info;
+
+use super::{read_json, read_stream, send_request, LLMProvider, LlmClient, LlmError, LlmRequest};
+use crate::summary::streaming::{parse_gemini_response, parse_gemini_sse_line, DeltaCallback};
+
+const GEMINI_API_BASE: &str = "https://generativelanguage.googleapis.com/v1beta";
+
+// Gemini generateContent request structure
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeminiRequest {
+    pub system_instruction: GeminiContent,
+    pub contents: Vec<GeminiContent>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub generation_config: Option<GeminiGenerationConfig>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GeminiGenerationConfig {
+    pub response_mime_type: String,
+    pub response_json_schema: serde_json::Value,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GeminiContent {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub role: Option<String>,
+    pub parts: Vec<GeminiPart>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GeminiPart {
+    pub text: String,
+}
+
+impl GeminiContent {
+    fn text(role: Option<&str>, text: &str) -> Self {
+        Self {
+            role: role.map(str::to_string),
+            parts: vec![GeminiPart {
+                text: text.to_string(),
+            }],
+        }
+    }
+}
+
+pub struct GeminiClient {
+    http: Client,
+    api_key: String,
+    model: String,
+    timeout: Duration,
+}
+
+impl GeminiClient {
+    pub fn new(http: Client, api_key: String, model: String, timeout: Duration) -> Self {
+        Self {
+            http,
+            api_key,
+            model,
+            timeout,
+        }
+    }
+}
+
+#[async_trait]
+impl LlmClient for GeminiClient {
+    fn provider(&self) -> LLMProvider {
+        LLMProvider::Gemini
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    async fn complete(
+        &self,
+        request: &LlmRequest<'_>,
+        cancellation_token: Option<&CancellationToken>,
+        on_delta: Option<&DeltaCallback<'_>>,
+    ) -> Result<String, LlmError> {
+        let body = GeminiRequest {
+            system_instruction: GeminiContent::text(None, request.system_prompt),
+            contents: vec![GeminiContent::text(Some("user"), request.user_prompt)],
+            generation_config: request.json_schema.map(|schema| GeminiGenerationConfig {
+                response_mime_type: "application/json".to_string(),
+                response_json_schema: schema.clone(),
+            }),
+        };
+
+        // Model ids may be given with or without the "models/" resource prefix
+        let model = self.model.trim_start_matches("models/");
+        let url = if on_delta.is_some() {
+            format!(
+                "{}/models/{}:streamGenerateContent?alt=sse",
+                GEMINI_API_BASE, model
+            )
+        } else {
+            format!("{}/models/{}:generateContent", GEMINI_API_BASE, model)
+        };
+
+        info!("🐞 LLM Request to Gemini: model={}", self.model);
+        let http_request = self
+            .http
+            .post(url)
+            .header("x-goog-api-key", &self.api_key)
+            .json(&body);
+        let response = send_request(
+            &LLMProvider::Gemini,
+            http_request,
+            self.timeout,
+            cancellation_token,
+        )
+        .await?;
+
+        if let Some(on_delta) = on_delta {
+            let content = read_stream(
+                &LLMProvider::Gemini,
+                response,
+                parse_gemini_sse_line,
+                on_delta,
+                self.timeout,
+                cancellation_token,
+            )
+            .await?;
+            return Ok(content.trim().to_string());
+        }
+
+        let json: serde_json::Value = read_json(response, self.timeout).await?;
+        info!("🐞 LLM Response received from Gemini");
+
+        // Safety blocks surface as errors here instead of an empty summary
+        let content = parse_gemini_response(&json)
+            .map_err(LlmError::Provider)?
+            .delta
+            .ok_or_else(|| LlmError::InvalidResponse("No content in LLM response".to_string()))?;
+        Ok(content.trim().to_string())
+    }
+}
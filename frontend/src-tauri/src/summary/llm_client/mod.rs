@@ -0,0 +1,521 @@
+//! Provider-agnostic LLM client.
+//!
+//! Every provider implements `LlmClient`, which makes a single completion
+//! attempt. Retries with exponential backoff (`retry`) and fallback to other
+//! providers (`fallback`) are layered on top, so the summary pipeline, action
+//! item extraction and Q&A all get the same behaviour through `FallbackChain`.
+//!
+//! # Module Structure
+//!
+//! - `openai`: OpenAI-compatible chat completions (OpenAI, Groq, OpenRouter, custom endpoints)
+//! - `claude`: Anthropic Messages API
+//! - `gemini`: Gemini generateContent
+//! - `ollama`: Ollama (OpenAI-compatible API, native chat API when streaming)
+//! - `builtin`: Built-in AI through the llama-helper sidecar
+//! - `retry`: Retry policy, backoff and `Retry-After` handling
+//! - `fallback`: Ordered fallback across providers
+
+use async_trait::async_trait;
+use reqwest::{header::HeaderMap, Client, RequestBuilder, Response, StatusCode};
+use serde::Serialize;
+use std::fmt;
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use super::processor::rough_token_count;
+use super::streaming::{DeltaCallback, LineBuffer, StreamChunk};
+use super::CustomOpenAIConfig;
+
+pub mod builtin;
+pub mod claude;
+pub mod fallback;
+pub mod gemini;
+pub mod ollama;
+pub mod openai;
+pub mod retry;
+
+pub use fallback::FallbackChain;
+pub use retry::{parse_retry_after, RetryPolicy};
+
+/// LLM Provider enumeration for multi-provider support
+#[derive(Debug, Clone, PartialEq)]
+pub enum LLMProvider {
+    OpenAI,
+    Claude,
+    Groq,
+    Ollama,
+    OpenRouter,
+    BuiltInAI,
+    CustomOpenAI,
+    Gemini,
+}
+
+impl LLMProvider {
+    /// Parse provider from string (case-insensitive)
+    pub fn from_str(s: &str) -> Result<Self, String> {
+        match s.to_lowercase().as_str() {
+            "openai" => Ok(Self::OpenAI),
+            "claude" => Ok(Self::Claude),
+            "groq" => Ok(Self::Groq),
+            "ollama" => Ok(Self::Ollama),
+            "openrouter" => Ok(Self::OpenRouter),
+            "builtin-ai" | "local-llama" | "localllama" => Ok(Self::BuiltInAI),
+            "custom-openai" => Ok(Self::CustomOpenAI),
+            "gemini" => Ok(Self::Gemini),
+            _ => Err(format!("Unsupported LLM provider: {}", s)),
+        }
+    }
+
+    /// Display name for logs and error messages
+    pub fn name(&self) -> &'static str {
+        match self {
+            LLMProvider::OpenAI => "OpenAI",
+            LLMProvider::Claude => "Claude",
+            LLMProvider::Groq => "Groq",
+            LLMProvider::Ollama => "Ollama",
+            LLMProvider::BuiltInAI => "Built-in AI",
+            LLMProvider::OpenRouter => "OpenRouter",
+            LLMProvider::CustomOpenAI => "Custom OpenAI",
+            LLMProvider::Gemini => "Gemini",
+        }
+    }
+}
+
+/// Connection settings for one provider, resolved from the settings tables
+#[derive(Debug, Clone)]
+pub enum ProviderConfig {
+    OpenAI { api_key: String },
+    Claude { api_key: String },
+    Groq { api_key: String },
+    OpenRouter { api_key: String },
+    Gemini { api_key: String },
+    Ollama { endpoint: Option<String> },
+    BuiltInAI { app_data_dir: PathBuf },
+    CustomOpenAI(CustomOpenAIConfig),
+}
+
+impl ProviderConfig {
+    pub fn provider(&self) -> LLMProvider {
+        match self {
+            ProviderConfig::OpenAI { .. } => LLMProvider::OpenAI,
+            ProviderConfig::Claude { .. } => LLMProvider::Claude,
+            ProviderConfig::Groq { .. } => LLMProvider::Groq,
+            ProviderConfig::OpenRouter { .. } => LLMProvider::OpenRouter,
+            ProviderConfig::Gemini { .. } => LLMProvider::Gemini,
+            ProviderConfig::Ollama { .. } => LLMProvider::Ollama,
+            ProviderConfig::BuiltInAI { .. } => LLMProvider::BuiltInAI,
+            ProviderConfig::CustomOpenAI(_) => LLMProvider::CustomOpenAI,
+        }
+    }
+}
+
+/// A single-turn completion request
+#[derive(Debug, Clone, Copy)]
+pub struct LlmRequest<'a> {
+    pub system_prompt: &'a str,
+    pub user_prompt: &'a str,
+    /// JSON schema the reply must follow. Providers with structured output
+    /// enforce it; the others rely on the prompt describing it.
+    pub json_schema: Option<&'a serde_json::Value>,
+}
+
+/// Why a completion failed. `is_retryable` decides whether the same provider
+/// is tried again; any error other than cancellation moves a fallback chain on
+/// to its next provider.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LlmError {
+    Cancelled,
+    Timeout(Duration),
+    RateLimited {
+        retry_after: Option<Duration>,
+        message: String,
+    },
+    // 5xx/408 responses and connection failures (status is None)
+    Unavailable {
+        status: Option<u16>,
+        message: String,
+    },
+    // Other 4xx responses: bad key, unknown model, invalid request
+    Rejected {
+        status: u16,
+        message: String,
+    },
+    InvalidResponse(String),
+    // Configuration, sidecar and in-stream errors
+    Provider(String),
+}
+
+impl LlmError {
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            LlmError::Timeout(_) | LlmError::RateLimited { .. } | LlmError::Unavailable { .. }
+        )
+    }
+
+    pub fn retry_after(&self) -> Option<Duration> {
+        match self {
+            LlmError::RateLimited { retry_after, .. } => *retry_after,
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for LlmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LlmError::Cancelled => write!(f, "Summary generation was cancelled"),
+            LlmError::Timeout(timeout) => {
+                write!(
+                    f,
+                    "LLM request timed out after {} seconds",
+                    timeout.as_secs()
+                )
+            }
+            LlmError::RateLimited { message, .. } => {
+                write!(f, "LLM API rate limit exceeded: {}", message)
+            }
+            LlmError::Unavailable {
+                status: Some(status),
+                message,
+            }
+            | LlmError::Rejected { status, message } => {
+                write!(f, "LLM API request failed ({}): {}", status, message)
+            }
+            LlmError::Unavailable {
+                status: None,
+                message,
+            } => write!(f, "Failed to send request to LLM: {}", message),
+            LlmError::InvalidResponse(message) => {
+                write!(f, "Failed to parse LLM response: {}", message)
+            }
+            LlmError::Provider(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+impl std::error::Error for LlmError {}
+
+impl From<LlmError> for String {
+    fn from(error: LlmError) -> Self {
+        error.to_string()
+    }
+}
+
+/// One LLM provider and model. `complete` makes a single attempt; retries and
+/// fallbacks are handled by `retry::complete_with_retry` and `FallbackChain`.
+#[async_trait]
+pub trait LlmClient: Send + Sync {
+    fn provider(&self) -> LLMProvider;
+
+    fn model(&self) -> &str;
+
+    /// Generates a completion. When `on_delta` is given the response is
+    /// streamed and every piece of text is passed to it as it arrives; the
+    /// complete text is still returned at the end. Cancellation is checked
+    /// while waiting for each piece.
+    async fn complete(
+        &self,
+        request: &LlmRequest<'_>,
+        cancellation_token: Option<&CancellationToken>,
+        on_delta: Option<&DeltaCallback<'_>>,
+    ) -> Result<String, LlmError>;
+
+    /// Counts the tokens of each text with the model's tokenizer, excluding
+    /// special and chat template tokens. Providers without an offline or API
+    /// tokenizer use the character-based estimate.
+    async fn count_tokens(&self, texts: &[String]) -> Result<Vec<usize>, LlmError> {
+        Ok(texts.iter().map(|text| rough_token_count(text)).collect())
+    }
+}
+
+/// Creates the client for a provider. `timeout` bounds each HTTP request,
+/// including the time spent reading a streamed body.
+pub fn build_client(
+    config: ProviderConfig,
+    model: &str,
+    http: Client,
+    timeout: Duration,
+) -> Box<dyn LlmClient> {
+    let model = model.to_string();
+    match config {
+        ProviderConfig::OpenAI { api_key } => Box::new(openai::OpenAiCompatibleClient::new(
+            http,
+            LLMProvider::OpenAI,
+            "https://api.openai.com/v1",
+            Some(api_key),
+            model,
+            timeout,
+        )),
+        ProviderConfig::Groq { api_key } => Box::new(openai::OpenAiCompatibleClient::new(
+            http,
+            LLMProvider::Groq,
+            "https://api.groq.com/openai/v1",
+            Some(api_key),
+            model,
+            timeout,
+        )),
+        ProviderConfig::OpenRouter { api_key } => Box::new(openai::OpenAiCompatibleClient::new(
+            http,
+            LLMProvider::OpenRouter,
+            "https://openrouter.ai/api/v1",
+            Some(api_key),
+            model,
+            timeout,
+        )),
+        ProviderConfig::CustomOpenAI(config) => Box::new(openai::OpenAiCompatibleClient::custom(
+            http, config, model, timeout,
+        )),
+        ProviderConfig::Claude { api_key } => {
+            Box::new(claude::ClaudeClient::new(http, api_key, model, timeout))
+        }
+        ProviderConfig::Gemini { api_key } => {
+            Box::new(gemini::GeminiClient::new(http, api_key, model, timeout))
+        }
+        ProviderConfig::Ollama { endpoint } => {
+            Box::new(ollama::OllamaClient::new(http, endpoint, model, timeout))
+        }
+        ProviderConfig::BuiltInAI { app_data_dir } => {
+            Box::new(builtin::BuiltInClient::new(app_data_dir, model))
+        }
+    }
+}
+
+// ============================================================================
+// Shared HTTP helpers
+// ============================================================================
+
+/// Generic structure for chat messages (OpenAI-compatible, Claude and Ollama)
+#[derive(Debug, Serialize)]
+pub struct ChatMessage {
+    pub role: String,
+    pub content: String,
+}
+
+impl ChatMessage {
+    pub fn new(role: &str, content: &str) -> Self {
+        Self {
+            role: role.to_string(),
+            content: content.to_string(),
+        }
+    }
+}
+
+/// Sends a request, racing it against cancellation, and turns non-success
+/// responses into typed errors
+pub(crate) async fn send_request(
+    provider: &LLMProvider,
+    request: RequestBuilder,
+    timeout: Duration,
+    cancellation_token: Option<&CancellationToken>,
+) -> Result<Response, LlmError> {
+    let request_future = request.timeout(timeout).send();
+    let result = match cancellation_token {
+        Some(token) => tokio::select! {
+            result = request_future => result,
+            _ = token.cancelled() => return Err(LlmError::Cancelled),
+        },
+        None => request_future.await,
+    };
+    let response = result.map_err(|e| transport_error(e, timeout))?;
+
+    let status = response.status();
+    if status.is_success() {
+        return Ok(response);
+    }
+
+    let retry_after = retry_after_from_headers(response.headers());
+    let body = response
+        .text()
+        .await
+        .unwrap_or_else(|_| "Unknown error".to_string());
+    let error = classify_status(status, retry_after, api_error_message(&body));
+    warn!("{} request failed: {}", provider.name(), error);
+    Err(error)
+}
+
+fn transport_error(error: reqwest::Error, timeout: Duration) -> LlmError {
+    if error.is_timeout() {
+        LlmError::Timeout(timeout)
+    } else {
+        LlmError::Unavailable {
+            status: None,
+            message: error.to_string(),
+        }
+    }
+}
+
+fn retry_after_from_headers(headers: &HeaderMap) -> Option<Duration> {
+    // OpenAI also sends a millisecond variant, which is more precise
+    if let Some(ms) = headers
+        .get("retry-after-ms")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<f64>().ok())
+    {
+        return Some(Duration::from_secs_f64(ms.max(0.0) / 1000.0));
+    }
+    headers
+        .get(reqwest::header::RETRY_AFTER)
+        .and_then(|v| v.to_str().ok())
+        .and_then(parse_retry_after)
+}
+
+/// Maps an unsuccessful HTTP status to an error
+pub(crate) fn classify_status(
+    status: StatusCode,
+    retry_after: Option<Duration>,
+    message: String,
+) -> LlmError {
+    match status.as_u16() {
+        429 => LlmError::RateLimited {
+            retry_after,
+            message,
+        },
+        // 529 is Anthropic's "overloaded"
+        code @ (408 | 500..=599) => LlmError::Unavailable {
+            status: Some(code),
+            message,
+        },
+        code => LlmError::Rejected {
+            status: code,
+            message,
+        },
+    }
+}
+
+/// Extracts `error.message` from a JSON error body, falling back to the raw body
+pub(crate) fn api_error_message(body: &str) -> String {
+    serde_json::from_str::<serde_json::Value>(body)
+        .ok()
+        .and_then(|json| {
+            let error = json.get("error")?;
+            Some(
+                error
+                    .get("message")
+                    .and_then(|m| m.as_str())
+                    .or_else(|| error.as_str())
+                    .map(str::to_string)
+                    .unwrap_or_else(|| error.to_string()),
+            )
+        })
+        .unwrap_or_else(|| body.trim().to_string())
+}
+
+/// Reads a streamed response line by line, forwarding text deltas as they arrive
+pub(crate) async fn read_stream(
+    provider: &LLMProvider,
+    mut response: Response,
+    parse_line: fn(&str) -> Result<StreamChunk, String>,
+    on_delta: &DeltaCallback<'_>,
+    timeout: Duration,
+    cancellation_token: Option<&CancellationToken>,
+) -> Result<String, LlmError> {
+    let mut lines = LineBuffer::default();
+    let mut content = String::new();
+
+    loop {
+        let next = match cancellation_token {
+            Some(token) => tokio::select! {
+                chunk = response.chunk() => chunk,
+                _ = token.cancelled() => {
+                    info!("LLM stream cancelled after {} chars", content.len());
+                    return Err(LlmError::Cancelled);
+                }
+            },
+            None => response.chunk().await,
+        };
+
+        let bytes = match next.map_err(|e| transport_error(e, timeout))? {
+            Some(bytes) => bytes,
+            None => break,
+        };
+
+        for line in lines.push(&bytes) {
+            let chunk = parse_line(&line).map_err(LlmError::Provider)?;
+            if let Some(delta) = chunk.delta {
+                on_delta(&delta);
+                content.push_str(&delta);
+            }
+            if chunk.done {
+                info!(
+                    "🐞 LLM stream completed from {} ({} chars)",
+                    provider.name(),
+                    content.len()
+                );
+                return Ok(content);
+            }
+        }
+    }
+
+    // Some servers close the connection without a final event
+    if let Some(line) = lines.finish() {
+        if let Some(delta) = parse_line(&line).map_err(LlmError::Provider)?.delta {
+            on_delta(&delta);
+            content.push_str(&delta);
+        }
+    }
+    if content.is_empty() {
+        return Err(LlmError::InvalidResponse(
+            "LLM stream ended without any content".to_string(),
+        ));
+    }
+    warn!("LLM stream ended without a completion event");
+    Ok(content)
+}
+
+/// Parses a JSON response body
+pub(crate) async fn read_json<T: serde::de::DeserializeOwned>(
+    response: Response,
+    timeout: Duration,
+) -> Result<T, LlmError> {
+    let body = response
+        .text()
+        .await
+        .map_err(|e| transport_error(e, timeout))?;
+    serde_json::from_str(&body).map_err(|e| LlmError::InvalidResponse(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_status() {
+        let limited = classify_status(
+            StatusCode::TOO_MANY_REQUESTS,
+            Some(Duration::from_secs(7)),
+            "slow down".to_string(),
+        );
+        assert!(limited.is_retryable());
+        assert_eq!(limited.retry_after(), Some(Duration::from_secs(7)));
+
+        let overloaded = classify_status(StatusCode::from_u16(529).unwrap(), None, String::new());
+        assert!(overloaded.is_retryable());
+
+        let unauthorized = classify_status(StatusCode::UNAUTHORIZED, None, "bad key".to_string());
+        assert!(!unauthorized.is_retryable());
+        assert_eq!(
+            unauthorized.to_string(),
+            "LLM API request failed (401): bad key"
+        );
+        assert!(LlmError::Cancelled.to_string().contains("cancelled"));
+    }
+
+    #[test]
+    fn test_api_error_message() {
+        assert_eq!(
+            api_error_message(r#"{"error":{"message":"Invalid model","type":"invalid_request"}}"#),
+            "Invalid model"
+        );
+        assert_eq!(
+            api_error_message(r#"{"error":"model not found"}"#),
+            "model not found"
+        );
+        assert_eq!(
+            api_error_message(" upstream timeout \n"),
+            "upstream timeout"
+        );
+    }
+}
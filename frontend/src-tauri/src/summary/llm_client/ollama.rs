@@ -0,0 +1,202 @@
+//! Ollama. Plain requests go through its OpenAI-compatible API; streaming uses
+//! the native `/api/chat`, which streams NDJSON.
+
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+use tracing::{info, warn};
+
+use super::openai::{json_schema_response_format, ChatResponse};
+use super::{
+    read_json, read_stream, send_request, ChatMessage, LLMProvider, LlmClient, LlmError, LlmRequest,
+};
+use crate::summary::streaming::{parse_ollama_line, DeltaCallback};
+
+const DEFAULT_ENDPOINT: &str = "http://localhost:11434";
+
+// Ollama request for both the OpenAI-compatible and the native chat API
+#[derive(Debug, Serialize)]
+pub struct OllamaChatRequest {
+    pub model: String,
+    pub messages: Vec<ChatMessage>,
+    pub stream: bool,
+    /// JSON schema of the reply (native API)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<serde_json::Value>,
+    /// JSON schema of the reply (OpenAI-compatible API)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_format: Option<serde_json::Value>,
+}
+
+// Tokenizes text with the model's vocabulary without evaluating it; older
+// Ollama versions answer 404
+#[derive(Debug, Serialize)]
+struct OllamaTokenizeRequest<'a> {
+    model: &'a str,
+    text: &'a str,
+}
+
+#[derive(Debug, Deserialize)]
+struct OllamaTokenizeResponse {
+    tokens: Vec<i64>,
+}
+
+pub struct OllamaClient {
+    http: Client,
+    endpoint: String,
+    model: String,
+    timeout: Duration,
+    /// Set once the server turned out not to have `/api/tokenize`
+    no_tokenizer: AtomicBool,
+}
+
+impl OllamaClient {
+    pub fn new(http: Client, endpoint: Option<String>, model: String, timeout: Duration) -> Self {
+        let endpoint = endpoint
+            .filter(|e| !e.trim().is_empty())
+            .unwrap_or_else(|| DEFAULT_ENDPOINT.to_string());
+        Self {
+            http,
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            model,
+            timeout,
+            no_tokenizer: AtomicBool::new(false),
+        }
+    }
+}
+
+#[async_trait]
+impl LlmClient for OllamaClient {
+    fn provider(&self) -> LLMProvider {
+        LLMProvider::Ollama
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    async fn complete(
+        &self,
+        request: &LlmRequest<'_>,
+        cancellation_token: Option<&CancellationToken>,
+        on_delta: Option<&DeltaCallback<'_>>,
+    ) -> Result<String, LlmError> {
+        let body = OllamaChatRequest {
+            model: self.model.clone(),
+            messages: vec![
+                ChatMessage::new("system", request.system_prompt),
+                ChatMessage::new("user", request.user_prompt),
+            ],
+            stream: on_delta.is_some(),
+            format: request.json_schema.filter(|_| on_delta.is_some()).cloned(),
+            response_format: request
+                .json_schema
+                .filter(|_| on_delta.is_none())
+                .map(json_schema_response_format),
+        };
+        let path = if on_delta.is_some() {
+            "api/chat"
+        } else {
+            "v1/chat/completions"
+        };
+
+        info!("🐞 LLM Request to Ollama: model={}", self.model);
+        let http_request = self
+            .http
+            .post(format!("{}/{}", self.endpoint, path))
+            .json(&body);
+        let response = send_request(
+            &LLMProvider::Ollama,
+            http_request,
+            self.timeout,
+            cancellation_token,
+        )
+        .await?;
+
+        if let Some(on_delta) = on_delta {
+            let content = read_stream(
+                &LLMProvider::Ollama,
+                response,
+                parse_ollama_line,
+                on_delta,
+                self.timeout,
+                cancellation_token,
+            )
+            .await?;
+            return Ok(content.trim().to_string());
+        }
+
+        let chat_response: ChatResponse = read_json(response, self.timeout).await?;
+        info!("🐞 LLM Response received from Ollama");
+
+        let content = chat_response
+            .choices
+            .first()
+            .ok_or_else(|| LlmError::InvalidResponse("No content in LLM response".to_string()))?
+            .message
+            .content
+            .trim();
+        Ok(content.to_string())
+    }
+
+    async fn count_tokens(&self, texts: &[String]) -> Result<Vec<usize>, LlmError> {
+        const NO_TOKENIZER: &str =
+            "this Ollama version has no /api/tokenize; update Ollama for exact token counts";
+        if self.no_tokenizer.load(Ordering::Relaxed) {
+            return Err(LlmError::Provider(NO_TOKENIZER.to_string()));
+        }
+
+        let mut counts = Vec::with_capacity(texts.len());
+        for text in texts {
+            if text.is_empty() {
+                counts.push(0);
+                continue;
+            }
+            let http_request = self
+                .http
+                .post(format!("{}/api/tokenize", self.endpoint))
+                .json(&OllamaTokenizeRequest {
+                    model: &self.model,
+                    text,
+                });
+            let response =
+                match send_request(&LLMProvider::Ollama, http_request, self.timeout, None).await {
+                    Ok(response) => response,
+                    Err(LlmError::Rejected {
+                        status: 404 | 405, ..
+                    }) => {
+                        self.no_tokenizer.store(true, Ordering::Relaxed);
+                        return Err(LlmError::Provider(NO_TOKENIZER.to_string()));
+                    }
+                    Err(e) => return Err(e),
+                };
+            let tokenized: OllamaTokenizeResponse = read_json(response, self.timeout).await?;
+            counts.push(tokenized.tokens.len());
+        }
+        Ok(counts)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize_messages() {
+        let request = OllamaTokenizeRequest {
+            model: "llama3.2:3b",
+            text: "Hello there",
+        };
+        assert_eq!(
+            serde_json::to_value(&request).unwrap(),
+            serde_json::json!({ "model": "llama3.2:3b", "text": "Hello there" })
+        );
+
+        let response: OllamaTokenizeResponse =
+            serde_json::from_str(r#"{"tokens": [9906, 1070]}"#).unwrap();
+        assert_eq!(response.tokens.len(), 2);
+    }
+}
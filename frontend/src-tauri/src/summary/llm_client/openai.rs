@@ -0,0 +1,206 @@
+//! OpenAI-compatible chat completions, used for OpenAI, Groq, OpenRouter and
+//! custom OpenAI-compatible servers.
+
+use async_trait::async_trait;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+use tracing::info;
+
+use super::{
+    read_json, read_stream, send_request, ChatMessage, LLMProvider, LlmClient, LlmError, LlmRequest,
+};
+use crate::summary::processor::rough_token_count;
+use crate::summary::streaming::{parse_openai_sse_line, DeltaCallback};
+use crate::summary::tokenizer::count_tiktoken_tokens;
+use crate::summary::CustomOpenAIConfig;
+
+// Generic structure for OpenAI-compatible API chat requests
+#[derive(Debug, Serialize)]
+pub struct ChatRequest {
+    pub model: String,
+    pub messages: Vec<ChatMessage>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub response_format: Option<serde_json::Value>,
+}
+
+/// `response_format` asking an OpenAI-compatible server for JSON that follows
+/// `schema`
+pub fn json_schema_response_format(schema: &serde_json::Value) -> serde_json::Value {
+    serde_json::json!({
+        "type": "json_schema",
+        "json_schema": {
+            "name": "response",
+            "strict": true,
+            "schema": schema,
+        },
+    })
+}
+
+// Generic structure for OpenAI-compatible API chat responses
+#[derive(Deserialize, Debug)]
+pub struct ChatResponse {
+    pub choices: Vec<Choice>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct Choice {
+    pub message: MessageContent,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct MessageContent {
+    pub content: String,
+}
+
+pub struct OpenAiCompatibleClient {
+    http: Client,
+    provider: LLMProvider,
+    base_url: String,
+    api_key: Option<String>,
+    model: String,
+    // Generation parameters (custom endpoints only)
+    max_tokens: Option<u32>,
+    temperature: Option<f32>,
+    top_p: Option<f32>,
+    timeout: Duration,
+}
+
+impl OpenAiCompatibleClient {
+    pub fn new(
+        http: Client,
+        provider: LLMProvider,
+        base_url: &str,
+        api_key: Option<String>,
+        model: String,
+        timeout: Duration,
+    ) -> Self {
+        Self {
+            http,
+            provider,
+            base_url: base_url.to_string(),
+            api_key,
+            model,
+            max_tokens: None,
+            temperature: None,
+            top_p: None,
+            timeout,
+        }
+    }
+
+    /// A custom OpenAI-compatible server, with its optional generation parameters
+    pub fn custom(
+        http: Client,
+        config: CustomOpenAIConfig,
+        model: String,
+        timeout: Duration,
+    ) -> Self {
+        Self {
+            http,
+            provider: LLMProvider::CustomOpenAI,
+            base_url: config.endpoint.trim_end_matches('/').to_string(),
+            api_key: config.api_key.filter(|key| !key.is_empty()),
+            model,
+            max_tokens: config.max_tokens.map(|t| t as u32),
+            temperature: config.temperature,
+            top_p: config.top_p,
+            timeout,
+        }
+    }
+}
+
+#[async_trait]
+impl LlmClient for OpenAiCompatibleClient {
+    fn provider(&self) -> LLMProvider {
+        self.provider.clone()
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    async fn complete(
+        &self,
+        request: &LlmRequest<'_>,
+        cancellation_token: Option<&CancellationToken>,
+        on_delta: Option<&DeltaCallback<'_>>,
+    ) -> Result<String, LlmError> {
+        let body = ChatRequest {
+            model: self.model.clone(),
+            messages: vec![
+                ChatMessage::new("system", request.system_prompt),
+                ChatMessage::new("user", request.user_prompt),
+            ],
+            max_tokens: self.max_tokens,
+            temperature: self.temperature,
+            top_p: self.top_p,
+            stream: on_delta.map(|_| true),
+            response_format: request.json_schema.map(json_schema_response_format),
+        };
+
+        info!(
+            "🐞 LLM Request to {}: model={}",
+            self.provider.name(),
+            self.model
+        );
+        let mut http_request = self
+            .http
+            .post(format!("{}/chat/completions", self.base_url))
+            .json(&body);
+        if let Some(api_key) = &self.api_key {
+            http_request = http_request.bearer_auth(api_key);
+        }
+        let response = send_request(
+            &self.provider,
+            http_request,
+            self.timeout,
+            cancellation_token,
+        )
+        .await?;
+
+        if let Some(on_delta) = on_delta {
+            let content = read_stream(
+                &self.provider,
+                response,
+                parse_openai_sse_line,
+                on_delta,
+                self.timeout,
+                cancellation_token,
+            )
+            .await?;
+            return Ok(content.trim().to_string());
+        }
+
+        let chat_response: ChatResponse = read_json(response, self.timeout).await?;
+        info!("🐞 LLM Response received from {}", self.provider.name());
+
+        let content = chat_response
+            .choices
+            .first()
+            .ok_or_else(|| LlmError::InvalidResponse("No content in LLM response".to_string()))?
+            .message
+            .content
+            .trim();
+        Ok(content.to_string())
+    }
+
+    async fn count_tokens(&self, texts: &[String]) -> Result<Vec<usize>, LlmError> {
+        // A custom server may run any model, so there's no tokenizer to match
+        if self.provider == LLMProvider::CustomOpenAI {
+            return Ok(texts.iter().map(|text| rough_token_count(text)).collect());
+        }
+        Ok(texts
+            .iter()
+            .map(|text| count_tiktoken_tokens(&self.model, text))
+            .collect())
+    }
+}
@@ -0,0 +1,206 @@
+//! Retries with exponential backoff for rate limits, timeouts and transient
+//! server errors.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
+use tracing::warn;
+
+use super::{LlmClient, LlmError, LlmRequest};
+use crate::summary::streaming::DeltaCallback;
+
+/// How often and how patiently a provider is retried
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Attempts per provider, including the first
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubled for each further retry
+    pub initial_backoff: Duration,
+    /// Upper bound for the exponential delay
+    pub max_backoff: Duration,
+    /// Upper bound for a server-provided `Retry-After`; longer waits fail over
+    /// to the next provider instead
+    pub max_retry_after: Duration,
+    /// Per-request timeout, including reading a streamed body
+    pub request_timeout: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 4,
+            initial_backoff: Duration::from_secs(1),
+            max_backoff: Duration::from_secs(20),
+            max_retry_after: Duration::from_secs(60),
+            request_timeout: Duration::from_secs(300),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay before retrying after `attempt` (1-based) failed with `error`, or
+    /// None when the error shouldn't be retried
+    pub fn delay_after(&self, attempt: u32, error: &LlmError) -> Option<Duration> {
+        if attempt >= self.max_attempts || !error.is_retryable() {
+            return None;
+        }
+        match error.retry_after() {
+            Some(retry_after) if retry_after > self.max_retry_after => None,
+            Some(retry_after) => Some(retry_after),
+            None => {
+                let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+                Some(
+                    self.initial_backoff
+                        .saturating_mul(factor)
+                        .min(self.max_backoff),
+                )
+            }
+        }
+    }
+}
+
+/// Parses a `Retry-After` header value: delay in seconds or an HTTP date
+pub fn parse_retry_after(value: &str) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<f64>() {
+        return (seconds >= 0.0).then(|| Duration::from_secs_f64(seconds));
+    }
+    let date = chrono::DateTime::parse_from_rfc2822(value).ok()?;
+    let delay = date.signed_duration_since(chrono::Utc::now());
+    Some(delay.to_std().unwrap_or(Duration::ZERO))
+}
+
+/// Runs `client.complete`, retrying retryable errors per `policy`.
+///
+/// A streamed attempt that has already produced text is not retried, since
+/// the listener has shown that text and a retry would repeat it.
+pub async fn complete_with_retry(
+    client: &dyn LlmClient,
+    policy: &RetryPolicy,
+    request: &LlmRequest<'_>,
+    cancellation_token: Option<&CancellationToken>,
+    on_delta: Option<&DeltaCallback<'_>>,
+) -> Result<String, LlmError> {
+    let emitted = AtomicBool::new(false);
+    let tracked_delta = on_delta.map(|on_delta| {
+        let emitted = &emitted;
+        move |delta: &str| {
+            emitted.store(true, Ordering::Relaxed);
+            on_delta(delta)
+        }
+    });
+
+    let mut attempt = 1;
+    loop {
+        if cancellation_token.is_some_and(|token| token.is_cancelled()) {
+            return Err(LlmError::Cancelled);
+        }
+
+        let result = client
+            .complete(
+                request,
+                cancellation_token,
+                tracked_delta.as_ref().map(|f| f as &DeltaCallback),
+            )
+            .await;
+        let error = match result {
+            Ok(text) => return Ok(text),
+            Err(error) => error,
+        };
+
+        let delay = policy
+            .delay_after(attempt, &error)
+            .filter(|_| !emitted.load(Ordering::Relaxed));
+        let Some(delay) = delay else {
+            return Err(error);
+        };
+
+        warn!(
+            "{} attempt {}/{} failed: {}. Retrying in {:.1}s",
+            client.provider().name(),
+            attempt,
+            policy.max_attempts,
+            error,
+            delay.as_secs_f32()
+        );
+        match cancellation_token {
+            Some(token) => tokio::select! {
+                _ = tokio::time::sleep(delay) => {}
+                _ = token.cancelled() => return Err(LlmError::Cancelled),
+            },
+            None => tokio::time::sleep(delay).await,
+        }
+        attempt += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unavailable() -> LlmError {
+        LlmError::Unavailable {
+            status: Some(503),
+            message: "overloaded".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_delay_after_backs_off_exponentially() {
+        let policy = RetryPolicy::default();
+        assert_eq!(
+            policy.delay_after(1, &unavailable()),
+            Some(Duration::from_secs(1))
+        );
+        assert_eq!(
+            policy.delay_after(3, &unavailable()),
+            Some(Duration::from_secs(4))
+        );
+        assert_eq!(policy.delay_after(4, &unavailable()), None);
+
+        let capped = RetryPolicy {
+            max_attempts: 10,
+            ..RetryPolicy::default()
+        };
+        assert_eq!(
+            capped.delay_after(9, &unavailable()),
+            Some(Duration::from_secs(20))
+        );
+
+        let rejected = LlmError::Rejected {
+            status: 401,
+            message: "bad key".to_string(),
+        };
+        assert_eq!(policy.delay_after(1, &rejected), None);
+    }
+
+    #[test]
+    fn test_delay_after_honours_retry_after() {
+        let policy = RetryPolicy::default();
+        let limited = |secs| LlmError::RateLimited {
+            retry_after: Some(Duration::from_secs(secs)),
+            message: String::new(),
+        };
+        assert_eq!(
+            policy.delay_after(1, &limited(12)),
+            Some(Duration::from_secs(12))
+        );
+        // Too long to wait; let the fallback chain move on
+        assert_eq!(policy.delay_after(1, &limited(600)), None);
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        assert_eq!(parse_retry_after("30"), Some(Duration::from_secs(30)));
+        assert_eq!(
+            parse_retry_after(" 1.5 "),
+            Some(Duration::from_millis(1500))
+        );
+        assert_eq!(
+            parse_retry_after("Wed, 21 Oct 2015 07:28:00 GMT"),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("soon"), None);
+        assert_eq!(parse_retry_after("-5"), None);
+    }
+}
@@ -1,10 +1,17 @@
 /// Summary module - handles all meeting summary generation functionality
 ///
 /// This module contains:
-/// - LLM client for communicating with various AI providers (OpenAI, Claude, Groq, Ollama, OpenRouter, CustomOpenAI)
+/// - LLM client for communicating with various AI providers (OpenAI, Claude, Groq, Gemini, Ollama, OpenRouter, CustomOpenAI),
+///   with retries, backoff and a configurable fallback chain
 /// - Processor for chunking transcripts and generating summaries
+/// - Segment-aligned chunking and the cache of intermediate chunk summaries
+/// - Timestamp citations from summary bullets back to transcript segments
+/// - Token counting with each provider's tokenizer, used to size requests to the model's context
 /// - Service layer for orchestrating summary generation
-/// - Templates for structured meeting summary generation
+/// - Streaming response parsing for incremental `summary-delta` events
+/// - Templates for structured meeting summary generation, optionally generated as JSON
+///   validated against the template and rendered to Markdown
+/// - Action item and decision extraction into the action_items table
 /// - Tauri commands for frontend integration
 
 use serde::{Deserialize, Serialize};
@@ -30,13 +37,30 @@ pub struct CustomOpenAIConfig {
     pub top_p: Option<f32>,
 }
 
+/// One entry of the LLM fallback chain, tried when the summary provider fails
+/// Stored as a JSON array in the database, in the order the entries are tried
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FallbackModel {
+    /// Provider name as used in the model config (e.g., "ollama", "builtin-ai")
+    pub provider: String,
+    /// Model identifier for that provider
+    pub model: String,
+}
+
+pub mod action_item_commands;
+pub mod chunking;
+pub mod citations;
 pub mod commands;
+pub mod extraction;
 pub mod llm_client;
 pub mod processor;
 pub mod service;
+pub mod streaming;
+pub mod structured;
 pub mod summary_engine;
 pub mod template_commands;
 pub mod templates;
+pub mod tokenizer;
 
 // Re-export Tauri commands (with their generated __cmd__ variants)
 pub use commands::{
@@ -45,6 +69,13 @@ pub use commands::{
     api_process_transcript, api_save_meeting_summary,
 };
 
+// Re-export action item commands
+pub use action_item_commands::{
+    __cmd__api_extract_action_items, __cmd__api_list_action_items,
+    __cmd__api_update_action_item_status, api_extract_action_items, api_list_action_items,
+    api_update_action_item_status,
+};
+
 // Re-export template commands
 pub use template_commands::{
     __cmd__api_get_template_details, __cmd__api_list_templates, __cmd__api_validate_template,
@@ -52,9 +83,9 @@ pub use template_commands::{
 };
 
 // Re-export commonly used items
-pub use llm_client::LLMProvider;
+pub use llm_client::{FallbackChain, LLMProvider, LlmClient, LlmError, LlmRequest, ProviderConfig};
 pub use processor::{
     chunk_text, clean_llm_markdown_output, extract_meeting_name_from_markdown,
-    generate_meeting_summary, rough_token_count,
+    generate_meeting_summary, rough_token_count, ContextBudget, GeneratedSummary,
 };
 pub use service::SummaryService;
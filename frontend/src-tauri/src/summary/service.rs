@@ -1,14 +1,25 @@
+use crate::database::models::ActionItem;
 use crate::database::repositories::{
+    action_item::{ActionItemFilter, ActionItemsRepository},
     meeting::MeetingsRepository, setting::SettingsRepository, summary::SummaryProcessesRepository,
+    summary_cache::SummaryCacheRepository, transcript::TranscriptsRepository,
+};
+use crate::summary::chunking::{SummaryCache, SummaryCacheKey, SummarySegment};
+use crate::summary::extraction::{extract_action_items, ExtractionSegment};
+use crate::summary::llm_client::{
+    build_client, FallbackChain, LLMProvider, ProviderConfig, RetryPolicy,
+};
+use crate::summary::streaming::SummaryDeltaCallback;
+use crate::summary::processor::{
+    extract_meeting_name_from_markdown, generate_meeting_summary, ContextBudget, GeneratedSummary,
 };
-use crate::summary::llm_client::LLMProvider;
-use crate::summary::processor::{extract_meeting_name_from_markdown, generate_meeting_summary};
 use crate::ollama::metadata::ModelMetadataCache;
+use async_trait::async_trait;
 use sqlx::SqlitePool;
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
-use tauri::{AppHandle, Manager};
 use tokio_util::sync::CancellationToken;
 use tracing::{error, info, warn};
 use once_cell::sync::Lazy;
@@ -22,6 +33,45 @@ static METADATA_CACHE: Lazy<ModelMetadataCache> = Lazy::new(|| {
 static CANCELLATION_REGISTRY: Lazy<Arc<Mutex<HashMap<String, CancellationToken>>>> =
     Lazy::new(|| Arc::new(Mutex::new(HashMap::new())));
 
+/// LLM clients resolved from the settings tables
+pub struct ProviderSettings {
+    /// The configured provider, followed by the fallback chain
+    pub llm: FallbackChain,
+    /// Context window of each client in `llm`, in chain order; the first is
+    /// the configured provider's, used to size requests
+    pub contexts: Vec<ContextBudget>,
+}
+
+/// Intermediate summaries of one meeting, stored in summary_chunk_cache.
+/// Cache failures only cost a regeneration, so they are logged, not returned.
+struct DbSummaryCache<'a> {
+    pool: &'a SqlitePool,
+    meeting_id: &'a str,
+}
+
+#[async_trait]
+impl SummaryCache for DbSummaryCache<'_> {
+    async fn get(&self, key: &SummaryCacheKey) -> Option<String> {
+        match SummaryCacheRepository::get(self.pool, self.meeting_id, key).await {
+            Ok(summary) => summary,
+            Err(e) => {
+                warn!(
+                    "Failed to read cached summary for {}: {}",
+                    self.meeting_id, e
+                );
+                None
+            }
+        }
+    }
+
+    async fn put(&self, key: &SummaryCacheKey, summary: &str) {
+        let result = SummaryCacheRepository::save(self.pool, self.meeting_id, key, summary).await;
+        if let Err(e) = result {
+            warn!("Failed to cache summary for {}: {}", self.meeting_id, e);
+        }
+    }
+}
+
 /// Summary service - handles all summary generation logic
 pub struct SummaryService;
 
@@ -61,10 +111,11 @@ impl SummaryService {
     /// Processes transcript in the background and generates summary
     ///
     /// This function is designed to be spawned as an async task and does not block
-    /// the main thread. It updates the database with progress and results.
+    /// the main thread. It updates the database with progress and results, and
+    /// returns the final markdown for callers that wait on it (e.g. the CLI).
     ///
     /// # Arguments
-    /// * `_app` - Tauri app handle (for future use)
+    /// * `app_data_dir` - App data directory (models for the BuiltInAI provider)
     /// * `pool` - SQLx connection pool
     /// * `meeting_id` - Unique identifier for the meeting
     /// * `text` - Full transcript text
@@ -72,8 +123,10 @@ impl SummaryService {
     /// * `model_name` - Specific model (e.g., "gpt-4", "llama3.2:latest")
     /// * `custom_prompt` - Optional user-provided context
     /// * `template_id` - Template identifier (e.g., "daily_standup", "standard_meeting")
-    pub async fn process_transcript_background<R: tauri::Runtime>(
-        _app: AppHandle<R>,
+    /// * `structured` - Whether to generate the summary as the template's structured data
+    /// * `on_delta` - Optional callback receiving generated text while the LLM streams
+    pub async fn process_transcript_background(
+        app_data_dir: Option<PathBuf>,
         pool: SqlitePool,
         meeting_id: String,
         text: String,
@@ -81,7 +134,9 @@ impl SummaryService {
         model_name: String,
         custom_prompt: String,
         template_id: String,
-    ) {
+        structured: bool,
+        on_delta: Option<Arc<SummaryDeltaCallback>>,
+    ) -> Result<String, String> {
         let start_time = Instant::now();
         info!(
             "Starting background processing for meeting_id: {}",
@@ -91,152 +146,18 @@ impl SummaryService {
         // Register cancellation token for this meeting
         let cancellation_token = Self::register_cancellation_token(&meeting_id);
 
-        // Parse provider
-        let provider = match LLMProvider::from_str(&model_provider) {
-            Ok(p) => p,
-            Err(e) => {
-                Self::update_process_failed(&pool, &meeting_id, &e).await;
-                return;
-            }
-        };
-
-        // Validate and setup api_key, Flexible for Ollama, BuiltInAI, and CustomOpenAI
-        let api_key = if provider == LLMProvider::Ollama || provider == LLMProvider::BuiltInAI || provider == LLMProvider::CustomOpenAI {
-            // These providers don't require API keys from the standard database column
-            String::new()
-        } else {
-            match SettingsRepository::get_api_key(&pool, &model_provider).await {
-                Ok(Some(key)) if !key.is_empty() => key,
-                Ok(None) | Ok(Some(_)) => {
-                    let err_msg = format!("API key not found for {}", &model_provider);
-                    Self::update_process_failed(&pool, &meeting_id, &err_msg).await;
-                    return;
-                }
-                Err(e) => {
-                    let err_msg = format!("Failed to retrieve API key for {}: {}", &model_provider, e);
-                    Self::update_process_failed(&pool, &meeting_id, &err_msg).await;
-                    return;
-                }
-            }
-        };
-
-        // Get Ollama endpoint if provider is Ollama
-        let ollama_endpoint = if provider == LLMProvider::Ollama {
-            match SettingsRepository::get_model_config(&pool).await {
-                Ok(Some(config)) => config.ollama_endpoint,
-                Ok(None) => None,
-                Err(e) => {
-                    info!("Failed to retrieve Ollama endpoint: {}, using default", e);
-                    None
-                }
-            }
-        } else {
-            None
-        };
-
-        // Get CustomOpenAI config if provider is CustomOpenAI
-        let (custom_openai_endpoint, custom_openai_api_key, custom_openai_max_tokens, custom_openai_temperature, custom_openai_top_p) =
-            if provider == LLMProvider::CustomOpenAI {
-                match SettingsRepository::get_custom_openai_config(&pool).await {
-                    Ok(Some(config)) => {
-                        info!("✓ Using custom OpenAI endpoint: {}", config.endpoint);
-                        (
-                            Some(config.endpoint),
-                            config.api_key,
-                            config.max_tokens.map(|t| t as u32),
-                            config.temperature,
-                            config.top_p,
-                        )
-                    }
-                    Ok(None) => {
-                        let err_msg = "Custom OpenAI provider selected but no configuration found";
-                        Self::update_process_failed(&pool, &meeting_id, err_msg).await;
-                        return;
-                    }
-                    Err(e) => {
-                        let err_msg = format!("Failed to retrieve custom OpenAI config: {}", e);
-                        Self::update_process_failed(&pool, &meeting_id, &err_msg).await;
-                        return;
-                    }
-                }
-            } else {
-                (None, None, None, None, None)
-            };
-
-        // For CustomOpenAI, use its API key (if any) instead of the empty string
-        let final_api_key = if provider == LLMProvider::CustomOpenAI {
-            custom_openai_api_key.unwrap_or_default()
-        } else {
-            api_key
-        };
-
-        // Dynamically fetch context size based on provider and model
-        let token_threshold = if provider == LLMProvider::Ollama {
-            match METADATA_CACHE.get_or_fetch(&model_name, ollama_endpoint.as_deref()).await {
-                Ok(metadata) => {
-                    // Reserve 300 tokens for prompt overhead
-                    let optimal = metadata.context_size.saturating_sub(300);
-                    info!(
-                        "✓ Using dynamic context for {}: {} tokens (chunk size: {})",
-                        model_name, metadata.context_size, optimal
-                    );
-                    optimal
-                }
-                Err(e) => {
-                    warn!(
-                        "Failed to fetch context for {}: {}. Using default 4000",
-                        model_name, e
-                    );
-                    4000  // Fallback to safe default
-                }
-            }
-        } else if provider == LLMProvider::BuiltInAI {
-            // Get model's context size from registry
-            use crate::summary::summary_engine::models;
-            let model = models::get_model_by_name(&model_name)
-                .ok_or_else(|| format!("Unknown model: {}", model_name));
-
-            match model {
-                Ok(model_def) => {
-                    // Reserve 300 tokens for prompt overhead
-                    let optimal = model_def.context_size.saturating_sub(300) as usize;
-                    info!(
-                        "✓ Using BuiltInAI context size: {} tokens (chunk size: {})",
-                        model_def.context_size, optimal
-                    );
-                    optimal
-                }
-                Err(e) => {
-                    warn!("{}, using default 2048", e);
-                    1748  // 2048 - 300 for overhead
-                }
-            }
-        } else {
-            // Cloud providers (OpenAI, Claude, Groq, CustomOpenAI) handle large contexts automatically
-            100000  // Effectively unlimited for single-pass processing
-        };
-
-        // Get app data directory for BuiltInAI provider
-        let app_data_dir = _app.path().app_data_dir().ok();
-
-        // Generate summary
-        let client = reqwest::Client::new();
-        let result = generate_meeting_summary(
-            &client,
-            &provider,
+        let result = Self::generate_summary(
+            app_data_dir.as_ref(),
+            &pool,
+            &meeting_id,
+            text,
+            &model_provider,
             &model_name,
-            &final_api_key,
-            &text,
             &custom_prompt,
             &template_id,
-            token_threshold,
-            ollama_endpoint.as_deref(),
-            custom_openai_endpoint.as_deref(),
-            custom_openai_max_tokens,
-            custom_openai_temperature,
-            custom_openai_top_p,
-            app_data_dir.as_ref(),
-            Some(&cancellation_token),
+            structured,
+            &cancellation_token,
+            on_delta.as_deref(),
         )
         .await;
 
@@ -246,7 +167,9 @@ impl SummaryService {
         Self::cleanup_cancellation_token(&meeting_id);
 
         match result {
-            Ok((mut final_markdown, num_chunks)) => {
+            Ok(summary) => {
+                let mut final_markdown = summary.markdown;
+                let num_chunks = summary.chunk_count;
                 if num_chunks == 0 && final_markdown.is_empty() {
                     Self::update_process_failed(
                         &pool,
@@ -254,7 +177,7 @@ impl SummaryService {
                         "Summary generation failed: No content was processed.",
                     )
                     .await;
-                    return;
+                    return Err("Summary generation failed: No content was processed.".to_string());
                 }
 
                 info!(
@@ -295,10 +218,16 @@ impl SummaryService {
                     }
                 }
 
-                // Create result JSON with markdown only (summary_json will be added on first edit)
-                let result_json = serde_json::json!({
+                // Create result JSON with markdown, its resolved citations and the
+                // structured data it was rendered from (summary_json will be added
+                // on first edit)
+                let mut result_json = serde_json::json!({
                     "markdown": final_markdown,
+                    "citations": summary.citations,
                 });
+                if let Some(structured) = summary.structured {
+                    result_json["structured"] = serde_json::json!(structured);
+                }
 
                 // Update database with completed status
                 if let Err(e) = SummaryProcessesRepository::update_process_completed(
@@ -320,6 +249,21 @@ impl SummaryService {
                         meeting_id
                     );
                 }
+
+                // Action items are a best-effort follow-up; the summary is already saved
+                if let Err(e) = Self::extract_and_store_action_items(
+                    app_data_dir.as_ref(),
+                    &pool,
+                    &meeting_id,
+                    &model_provider,
+                    &model_name,
+                    None,
+                )
+                .await
+                {
+                    warn!("Action item extraction failed for {}: {}", meeting_id, e);
+                }
+                Ok(final_markdown)
             }
             Err(e) => {
                 // Check if error is due to cancellation
@@ -331,10 +275,300 @@ impl SummaryService {
                 } else {
                     Self::update_process_failed(&pool, &meeting_id, &e).await;
                 }
+                Err(e)
+            }
+        }
+    }
+
+    /// Resolves the provider's connection settings from the settings tables
+    async fn resolve_provider_config(
+        pool: &SqlitePool,
+        model_provider: &str,
+        app_data_dir: Option<&PathBuf>,
+    ) -> Result<ProviderConfig, String> {
+        let provider = LLMProvider::from_str(model_provider)?;
+
+        let config = match provider {
+            LLMProvider::Ollama => {
+                let endpoint = match SettingsRepository::get_model_config(pool).await {
+                    Ok(Some(config)) => config.ollama_endpoint,
+                    Ok(None) => None,
+                    Err(e) => {
+                        info!("Failed to retrieve Ollama endpoint: {}, using default", e);
+                        None
+                    }
+                };
+                ProviderConfig::Ollama { endpoint }
+            }
+            LLMProvider::BuiltInAI => ProviderConfig::BuiltInAI {
+                app_data_dir: app_data_dir
+                    .cloned()
+                    .ok_or("app_data_dir is required for BuiltInAI provider")?,
+            },
+            LLMProvider::CustomOpenAI => {
+                match SettingsRepository::get_custom_openai_config(pool).await {
+                    Ok(Some(config)) => {
+                        info!("✓ Using custom OpenAI endpoint: {}", config.endpoint);
+                        ProviderConfig::CustomOpenAI(config)
+                    }
+                    Ok(None) => {
+                        let err_msg = "Custom OpenAI provider selected but no configuration found";
+                        return Err(err_msg.to_string());
+                    }
+                    Err(e) => {
+                        let err_msg = format!("Failed to retrieve custom OpenAI config: {}", e);
+                        return Err(err_msg);
+                    }
+                }
             }
+            LLMProvider::OpenAI
+            | LLMProvider::Claude
+            | LLMProvider::Groq
+            | LLMProvider::OpenRouter
+            | LLMProvider::Gemini => {
+                let api_key = match SettingsRepository::get_api_key(pool, model_provider).await {
+                    Ok(Some(key)) if !key.is_empty() => key,
+                    Ok(None) | Ok(Some(_)) => {
+                        let err_msg = format!("API key not found for {}", model_provider);
+                        return Err(err_msg);
+                    }
+                    Err(e) => {
+                        let err_msg = format!("Failed to retrieve API key for {}: {}", model_provider, e);
+                        return Err(err_msg);
+                    }
+                };
+                match provider {
+                    LLMProvider::OpenAI => ProviderConfig::OpenAI { api_key },
+                    LLMProvider::Claude => ProviderConfig::Claude { api_key },
+                    LLMProvider::Groq => ProviderConfig::Groq { api_key },
+                    LLMProvider::OpenRouter => ProviderConfig::OpenRouter { api_key },
+                    _ => ProviderConfig::Gemini { api_key },
+                }
+            }
+        };
+        Ok(config)
+    }
+
+    /// Context window and completion reserve of the model, fetched dynamically for local models
+    async fn context_budget(config: &ProviderConfig, model_name: &str) -> ContextBudget {
+        match config {
+            ProviderConfig::Ollama { endpoint } => {
+                let context_size =
+                    match METADATA_CACHE.get_or_fetch(model_name, endpoint.as_deref()).await {
+                        Ok(metadata) => {
+                            info!(
+                                "✓ Using dynamic context for {}: {} tokens",
+                                model_name, metadata.context_size
+                            );
+                            metadata.context_size
+                        }
+                        Err(e) => {
+                            warn!(
+                                "Failed to fetch context for {}: {}. Using default 4000",
+                                model_name, e
+                            );
+                            4000 // Fallback to safe default
+                        }
+                    };
+                // Ollama doesn't cap the reply, so keep a quarter of the context for it
+                ContextBudget {
+                    context_size,
+                    completion_tokens: (context_size / 4).min(2048),
+                }
+            }
+            ProviderConfig::BuiltInAI { app_data_dir } => {
+                // Get model's context size from registry (or the GGUF metadata)
+                use crate::summary::summary_engine::models;
+                let models_dir = models::get_models_directory(app_data_dir);
+                match models::get_model_by_name(&models_dir, model_name) {
+                    Some(model_def) => {
+                        info!(
+                            "✓ Using BuiltInAI context size: {} tokens",
+                            model_def.context_size
+                        );
+                        // The sidecar stops generating after DEFAULT_MAX_TOKENS
+                        ContextBudget {
+                            context_size: model_def.context_size as usize,
+                            completion_tokens: models::DEFAULT_MAX_TOKENS as usize,
+                        }
+                    }
+                    None => {
+                        warn!("Unknown model: {}, using default 2048", model_name);
+                        ContextBudget {
+                            context_size: 2048,
+                            completion_tokens: 512,
+                        }
+                    }
+                }
+            }
+            // Cloud providers (OpenAI, Claude, Groq, Gemini, CustomOpenAI) handle large contexts automatically
+            _ => ContextBudget {
+                context_size: 100000, // Effectively unlimited for single-pass processing
+                completion_tokens: 4096,
+            },
         }
     }
 
+    /// Resolves the configured provider and the fallback chain into LLM clients.
+    /// Fallback entries that can't be resolved (e.g. a missing API key) are
+    /// skipped with a warning rather than failing the request.
+    pub async fn resolve_provider_settings(
+        pool: &SqlitePool,
+        model_provider: &str,
+        model_name: &str,
+        app_data_dir: Option<&PathBuf>,
+    ) -> Result<ProviderSettings, String> {
+        let config = Self::resolve_provider_config(pool, model_provider, app_data_dir).await?;
+        let mut contexts = vec![Self::context_budget(&config, model_name).await];
+
+        let policy = RetryPolicy::default();
+        let http = reqwest::Client::new();
+        let mut llm = FallbackChain::new(
+            build_client(config, model_name, http.clone(), policy.request_timeout),
+            policy.clone(),
+        );
+
+        let fallbacks = SettingsRepository::get_llm_fallback_chain(pool)
+            .await
+            .unwrap_or_else(|e| {
+                warn!("Failed to load LLM fallback chain: {}", e);
+                Vec::new()
+            });
+        for entry in fallbacks {
+            if entry.provider == model_provider && entry.model == model_name {
+                continue;
+            }
+            match Self::resolve_provider_config(pool, &entry.provider, app_data_dir).await {
+                Ok(config) => {
+                    info!("Fallback provider: {} ({})", entry.provider, entry.model);
+                    contexts.push(Self::context_budget(&config, &entry.model).await);
+                    llm = llm.with_fallback(build_client(
+                        config,
+                        &entry.model,
+                        http.clone(),
+                        policy.request_timeout,
+                    ));
+                }
+                Err(e) => warn!(
+                    "Skipping fallback provider {} ({}): {}",
+                    entry.provider, entry.model, e
+                ),
+            }
+        }
+
+        Ok(ProviderSettings { llm, contexts })
+    }
+
+    /// Resolves provider settings, then runs the summary pipeline
+    async fn generate_summary(
+        app_data_dir: Option<&PathBuf>,
+        pool: &SqlitePool,
+        meeting_id: &str,
+        text: String,
+        model_provider: &str,
+        model_name: &str,
+        custom_prompt: &str,
+        template_id: &str,
+        structured: bool,
+        cancellation_token: &CancellationToken,
+        on_delta: Option<&SummaryDeltaCallback>,
+    ) -> Result<GeneratedSummary, String> {
+        let settings =
+            Self::resolve_provider_settings(pool, model_provider, model_name, app_data_dir).await?;
+
+        // Prefer the stored segments: they carry speaker names ("Alice: ...") once
+        // the meeting has been diarized, and timing for the chunk boundaries
+        let segments = match TranscriptsRepository::get_transcript_lines(pool, meeting_id).await {
+            Ok(rows) if !rows.is_empty() => rows
+                .into_iter()
+                .map(|row| SummarySegment {
+                    id: Some(row.id),
+                    speaker: row.speaker_label,
+                    text: row.transcript,
+                    start_time: row.audio_start_time,
+                    end_time: row.audio_end_time,
+                })
+                .collect(),
+            Ok(_) => SummarySegment::from_text(&text),
+            Err(e) => {
+                warn!("Failed to load transcript segments for {}: {}, using provided text", meeting_id, e);
+                SummarySegment::from_text(&text)
+            }
+        };
+        if segments.is_empty() {
+            return Err(format!("Meeting {} has no transcript", meeting_id));
+        }
+
+        let cache = DbSummaryCache { pool, meeting_id };
+
+        // Generate summary
+        generate_meeting_summary(
+            &settings.llm,
+            &settings.contexts,
+            &segments,
+            custom_prompt,
+            template_id,
+            structured,
+            Some(&cache),
+            Some(cancellation_token),
+            on_delta,
+        )
+        .await
+    }
+
+    /// Extracts action items and decisions from a meeting's transcript and stores
+    /// them, replacing previously extracted items that are still open
+    pub async fn extract_and_store_action_items(
+        app_data_dir: Option<&PathBuf>,
+        pool: &SqlitePool,
+        meeting_id: &str,
+        model_provider: &str,
+        model_name: &str,
+        cancellation_token: Option<&CancellationToken>,
+    ) -> Result<Vec<ActionItem>, String> {
+        let meeting = MeetingsRepository::get_meeting_metadata(pool, meeting_id)
+            .await
+            .map_err(|e| format!("Failed to load meeting: {}", e))?
+            .ok_or_else(|| format!("Meeting not found: {}", meeting_id))?;
+        let segments: Vec<ExtractionSegment> =
+            TranscriptsRepository::get_transcript_lines(pool, meeting_id)
+                .await
+                .map_err(|e| format!("Failed to load transcript: {}", e))?
+                .into_iter()
+                .map(|row| ExtractionSegment {
+                    id: row.id,
+                    speaker: row.speaker_label,
+                    text: row.transcript,
+                })
+                .collect();
+        if segments.is_empty() {
+            return Err(format!("Meeting {} has no transcript", meeting_id));
+        }
+
+        let settings =
+            Self::resolve_provider_settings(pool, model_provider, model_name, app_data_dir).await?;
+        let items = extract_action_items(
+            &settings,
+            &segments,
+            meeting.created_at.0.date_naive(),
+            cancellation_token,
+        )
+        .await?;
+
+        ActionItemsRepository::replace_extracted(pool, meeting_id, &items)
+            .await
+            .map_err(|e| format!("Failed to save action items: {}", e))?;
+        ActionItemsRepository::list(
+            pool,
+            &ActionItemFilter {
+                meeting_id: Some(meeting_id.to_string()),
+                ..Default::default()
+            },
+        )
+        .await
+        .map_err(|e| format!("Failed to load action items: {}", e))
+    }
+
     /// Updates the summary process status to failed with error message
     ///
     /// # Arguments
@@ -0,0 +1,407 @@
+//! Incremental parsing of streamed LLM responses.
+//!
+//! OpenAI-compatible APIs (OpenAI, Groq, OpenRouter, custom endpoints), Claude
+//! and Gemini stream Server-Sent Events; Ollama's native `/api/chat` streams one
+//! JSON object per line. Both are line based, so the HTTP body is split into lines first and
+//! each line is parsed into a `StreamChunk`.
+
+use serde::Serialize;
+
+/// Which stage of the summary pipeline a delta belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(tag = "stage", rename_all = "snake_case")]
+pub enum SummaryStage {
+    /// Summarizing transcript chunk `index` of `total` (1-based)
+    Chunk { index: usize, total: usize },
+    /// Merging the chunk summaries
+    Combine,
+    /// Writing the final report; these deltas are the summary the user sees
+    Final,
+}
+
+/// Payload of the `summary-delta` event
+#[derive(Debug, Clone, Serialize)]
+pub struct SummaryDelta {
+    pub meeting_id: String,
+    #[serde(flatten)]
+    pub stage: SummaryStage,
+    pub delta: String,
+}
+
+/// Receives generated text as it arrives. The lifetime lets callers pass
+/// closures that borrow from the current stack frame.
+pub type DeltaCallback<'a> = dyn Fn(&str) + Send + Sync + 'a;
+
+/// Receives generated text tagged with its pipeline stage
+pub type SummaryDeltaCallback = dyn Fn(SummaryStage, &str) + Send + Sync;
+
+/// What one streamed line contributed
+#[derive(Debug, Default, PartialEq)]
+pub struct StreamChunk {
+    pub delta: Option<String>,
+    pub done: bool,
+}
+
+/// Splits a byte stream into lines. Bytes are buffered until a newline arrives,
+/// so multi-byte characters split across network chunks stay intact.
+#[derive(Debug, Default)]
+pub struct LineBuffer {
+    buffer: Vec<u8>,
+}
+
+impl LineBuffer {
+    /// Appends bytes and returns the lines they completed, without line endings
+    pub fn push(&mut self, bytes: &[u8]) -> Vec<String> {
+        self.buffer.extend_from_slice(bytes);
+        let mut lines = Vec::new();
+        while let Some(pos) = self.buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=pos).collect();
+            lines.push(
+                String::from_utf8_lossy(&line)
+                    .trim_end_matches(['\r', '\n'])
+                    .to_string(),
+            );
+        }
+        lines
+    }
+
+    /// Returns whatever is left once the stream has ended
+    pub fn finish(&mut self) -> Option<String> {
+        let rest = String::from_utf8_lossy(&std::mem::take(&mut self.buffer))
+            .trim()
+            .to_string();
+        (!rest.is_empty()).then_some(rest)
+    }
+}
+
+/// Payload of an SSE `data:` line; other fields (event, id, comments) are ignored
+fn sse_data(line: &str) -> Option<&str> {
+    line.strip_prefix("data:").map(str::trim)
+}
+
+fn error_message(json: &serde_json::Value) -> Option<String> {
+    let error = json.get("error")?;
+    Some(
+        error
+            .get("message")
+            .unwrap_or(error)
+            .as_str()
+            .map(str::to_string)
+            .unwrap_or_else(|| error.to_string()),
+    )
+}
+
+/// Parses an OpenAI-compatible chat completion SSE line
+pub fn parse_openai_sse_line(line: &str) -> Result<StreamChunk, String> {
+    let Some(data) = sse_data(line) else {
+        return Ok(StreamChunk::default());
+    };
+    if data == "[DONE]" {
+        return Ok(StreamChunk {
+            delta: None,
+            done: true,
+        });
+    }
+    if data.is_empty() {
+        return Ok(StreamChunk::default());
+    }
+
+    let json: serde_json::Value =
+        serde_json::from_str(data).map_err(|e| format!("Failed to parse stream event: {}", e))?;
+    if let Some(message) = error_message(&json) {
+        return Err(format!("LLM stream error: {}", message));
+    }
+
+    let choice = json.get("choices").and_then(|c| c.get(0));
+    Ok(StreamChunk {
+        delta: choice
+            .and_then(|c| c.pointer("/delta/content"))
+            .and_then(|c| c.as_str())
+            .filter(|c| !c.is_empty())
+            .map(str::to_string),
+        done: false,
+    })
+}
+
+/// Parses a Claude Messages API SSE line
+pub fn parse_claude_sse_line(line: &str) -> Result<StreamChunk, String> {
+    let Some(data) = sse_data(line).filter(|d| !d.is_empty()) else {
+        return Ok(StreamChunk::default());
+    };
+
+    let json: serde_json::Value =
+        serde_json::from_str(data).map_err(|e| format!("Failed to parse stream event: {}", e))?;
+    match json.get("type").and_then(|t| t.as_str()) {
+        Some("content_block_delta") => Ok(StreamChunk {
+            delta: json
+                .pointer("/delta/text")
+                .and_then(|t| t.as_str())
+                .map(str::to_string),
+            done: false,
+        }),
+        Some("message_stop") => Ok(StreamChunk {
+            delta: None,
+            done: true,
+        }),
+        Some("error") => Err(format!(
+            "LLM stream error: {}",
+            error_message(&json).unwrap_or_else(|| data.to_string())
+        )),
+        _ => Ok(StreamChunk::default()),
+    }
+}
+
+/// Parses an Ollama `/api/chat` NDJSON line
+pub fn parse_ollama_line(line: &str) -> Result<StreamChunk, String> {
+    let line = line.trim();
+    if line.is_empty() {
+        return Ok(StreamChunk::default());
+    }
+
+    let json: serde_json::Value =
+        serde_json::from_str(line).map_err(|e| format!("Failed to parse stream event: {}", e))?;
+    if let Some(message) = error_message(&json) {
+        return Err(format!("Ollama stream error: {}", message));
+    }
+
+    Ok(StreamChunk {
+        delta: json
+            .pointer("/message/content")
+            .and_then(|c| c.as_str())
+            .filter(|c| !c.is_empty())
+            .map(str::to_string),
+        done: json.get("done").and_then(|d| d.as_bool()).unwrap_or(false),
+    })
+}
+
+/// Gemini finish reasons that mean the output was withheld rather than completed
+const GEMINI_BLOCKED_FINISH_REASONS: &[&str] = &[
+    "SAFETY",
+    "RECITATION",
+    "BLOCKLIST",
+    "PROHIBITED_CONTENT",
+    "SPII",
+];
+
+/// Parses one Gemini `GenerateContentResponse`, either a whole non-streamed
+/// response or a single streamed event. Safety blocks, of the prompt or of the
+/// generated text, are returned as errors naming the reason and categories.
+pub fn parse_gemini_response(json: &serde_json::Value) -> Result<StreamChunk, String> {
+    if let Some(message) = error_message(json) {
+        return Err(format!("Gemini API error: {}", message));
+    }
+
+    if let Some(reason) = json
+        .pointer("/promptFeedback/blockReason")
+        .and_then(|r| r.as_str())
+    {
+        let categories = json
+            .pointer("/promptFeedback/safetyRatings")
+            .map(gemini_blocked_categories)
+            .unwrap_or_default();
+        return Err(format!(
+            "Gemini blocked the prompt ({}){}",
+            reason, categories
+        ));
+    }
+
+    let Some(candidate) = json.get("candidates").and_then(|c| c.get(0)) else {
+        // Streams may end with an event that only carries usage metadata
+        return Ok(StreamChunk::default());
+    };
+
+    let finish_reason = candidate.get("finishReason").and_then(|r| r.as_str());
+    if let Some(reason) = finish_reason.filter(|r| GEMINI_BLOCKED_FINISH_REASONS.contains(r)) {
+        let categories = candidate
+            .get("safetyRatings")
+            .map(gemini_blocked_categories)
+            .unwrap_or_default();
+        return Err(format!(
+            "Gemini stopped generating ({}){}",
+            reason, categories
+        ));
+    }
+
+    // Thinking models also return their reasoning as parts flagged `thought`
+    let text: String = candidate
+        .pointer("/content/parts")
+        .and_then(|p| p.as_array())
+        .map(|parts| {
+            parts
+                .iter()
+                .filter(|part| {
+                    !part
+                        .get("thought")
+                        .and_then(|t| t.as_bool())
+                        .unwrap_or(false)
+                })
+                .filter_map(|part| part.get("text").and_then(|t| t.as_str()))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(StreamChunk {
+        delta: (!text.is_empty()).then_some(text),
+        done: finish_reason.is_some(),
+    })
+}
+
+/// Formats the categories flagged in `safetyRatings` as ": A, B", or nothing
+fn gemini_blocked_categories(ratings: &serde_json::Value) -> String {
+    let categories: Vec<&str> = ratings
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|rating| {
+            rating
+                .get("blocked")
+                .and_then(|b| b.as_bool())
+                .unwrap_or(false)
+                || matches!(
+                    rating.get("probability").and_then(|p| p.as_str()),
+                    Some("MEDIUM" | "HIGH")
+                )
+        })
+        .filter_map(|rating| rating.get("category").and_then(|c| c.as_str()))
+        .collect();
+    if categories.is_empty() {
+        String::new()
+    } else {
+        format!(": {}", categories.join(", "))
+    }
+}
+
+/// Parses a Gemini `streamGenerateContent?alt=sse` line
+pub fn parse_gemini_sse_line(line: &str) -> Result<StreamChunk, String> {
+    let Some(data) = sse_data(line).filter(|d| !d.is_empty()) else {
+        return Ok(StreamChunk::default());
+    };
+
+    let json: serde_json::Value =
+        serde_json::from_str(data).map_err(|e| format!("Failed to parse stream event: {}", e))?;
+    parse_gemini_response(&json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_buffer_keeps_split_characters() {
+        let mut buffer = LineBuffer::default();
+        let bytes = "data: caf\u{e9}\r\n\ndata: [DONE]".as_bytes();
+        let (first, second) = bytes.split_at(10); // splits the two bytes of 'é'
+        assert!(buffer.push(first).is_empty());
+        assert_eq!(buffer.push(second), vec!["data: caf\u{e9}", ""]);
+        assert_eq!(buffer.finish().as_deref(), Some("data: [DONE]"));
+        assert_eq!(buffer.finish(), None);
+    }
+
+    #[test]
+    fn test_parse_openai_sse_line() {
+        let chunk =
+            parse_openai_sse_line(r#"data: {"choices":[{"index":0,"delta":{"content":"Hello"}}]}"#)
+                .unwrap();
+        assert_eq!(chunk.delta.as_deref(), Some("Hello"));
+        assert!(parse_openai_sse_line("data: [DONE]").unwrap().done);
+        assert_eq!(
+            parse_openai_sse_line(": keep-alive").unwrap(),
+            StreamChunk::default()
+        );
+        assert!(
+            parse_openai_sse_line(r#"data: {"error":{"message":"rate limited"}}"#)
+                .unwrap_err()
+                .contains("rate limited")
+        );
+    }
+
+    #[test]
+    fn test_parse_claude_sse_line() {
+        assert_eq!(
+            parse_claude_sse_line("event: content_block_delta").unwrap(),
+            StreamChunk::default()
+        );
+        let chunk = parse_claude_sse_line(
+            r#"data: {"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Hi"}}"#,
+        )
+        .unwrap();
+        assert_eq!(chunk.delta.as_deref(), Some("Hi"));
+        assert!(
+            parse_claude_sse_line(r#"data: {"type":"message_stop"}"#)
+                .unwrap()
+                .done
+        );
+        assert!(parse_claude_sse_line(
+            r#"data: {"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_parse_ollama_line() {
+        let chunk =
+            parse_ollama_line(r#"{"message":{"role":"assistant","content":"Hi"},"done":false}"#)
+                .unwrap();
+        assert_eq!(chunk.delta.as_deref(), Some("Hi"));
+        assert!(!chunk.done);
+        let last =
+            parse_ollama_line(r#"{"message":{"role":"assistant","content":""},"done":true}"#)
+                .unwrap();
+        assert_eq!(last.delta, None);
+        assert!(last.done);
+        assert!(parse_ollama_line(r#"{"error":"model not found"}"#).is_err());
+    }
+
+    #[test]
+    fn test_parse_gemini_sse_line() {
+        let chunk = parse_gemini_sse_line(
+            r#"data: {"candidates":[{"content":{"role":"model","parts":[{"text":"Plan","thought":true},{"text":"Hello"}]},"index":0}]}"#,
+        )
+        .unwrap();
+        assert_eq!(chunk.delta.as_deref(), Some("Hello"));
+        assert!(!chunk.done);
+        let last = parse_gemini_sse_line(
+            r#"data: {"candidates":[{"content":{"role":"model","parts":[{"text":"!"}]},"finishReason":"STOP"}],"usageMetadata":{"totalTokenCount":12}}"#,
+        )
+        .unwrap();
+        assert_eq!(last.delta.as_deref(), Some("!"));
+        assert!(last.done);
+    }
+
+    #[test]
+    fn test_parse_gemini_safety_blocks() {
+        let prompt_blocked = parse_gemini_response(&serde_json::json!({
+            "promptFeedback": {
+                "blockReason": "SAFETY",
+                "safetyRatings": [
+                    {"category": "HARM_CATEGORY_HARASSMENT", "probability": "HIGH", "blocked": true},
+                    {"category": "HARM_CATEGORY_HATE_SPEECH", "probability": "NEGLIGIBLE"}
+                ]
+            }
+        }))
+        .unwrap_err();
+        assert_eq!(
+            prompt_blocked,
+            "Gemini blocked the prompt (SAFETY): HARM_CATEGORY_HARASSMENT"
+        );
+
+        let output_blocked = parse_gemini_response(&serde_json::json!({
+            "candidates": [{"finishReason": "RECITATION", "index": 0}]
+        }))
+        .unwrap_err();
+        assert_eq!(output_blocked, "Gemini stopped generating (RECITATION)");
+    }
+
+    #[test]
+    fn test_summary_delta_serialization() {
+        let delta = SummaryDelta {
+            meeting_id: "m-1".to_string(),
+            stage: SummaryStage::Chunk { index: 2, total: 5 },
+            delta: "text".to_string(),
+        };
+        assert_eq!(
+            serde_json::to_value(&delta).unwrap(),
+            serde_json::json!({"meeting_id": "m-1", "stage": "chunk", "index": 2, "total": 5, "delta": "text"})
+        );
+    }
+}
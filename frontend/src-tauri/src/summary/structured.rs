@@ -0,0 +1,265 @@
+//! Structured summaries: the model fills the template's sections as JSON
+//! (see `Template::to_json_schema`), the reply is validated against the
+//! template, and the Markdown summary is rendered from the validated data. The
+//! sections are always the template's, in the template's order.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::warn;
+
+use super::citations::CitationResolver;
+use super::extraction::json_object;
+use super::templates::Template;
+
+/// Written for sections the model had nothing for
+const EMPTY_SECTION: &str = "None noted in this section.";
+
+/// Content of one section: text for "paragraph" and "string" sections, items
+/// for "list" sections
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SectionContent {
+    Text(String),
+    Items(Vec<String>),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StructuredSection {
+    pub title: String,
+    /// The template section's format: "paragraph", "list" or "string"
+    pub format: String,
+    pub content: SectionContent,
+}
+
+/// A summary with exactly the sections of the template it was generated from
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct StructuredSummary {
+    pub title: String,
+    pub sections: Vec<StructuredSection>,
+}
+
+/// Strips a list marker ("- ", "* ", "1. ") the model may have put in an item
+fn strip_list_marker(item: &str) -> &str {
+    let item = item.trim();
+    let digits = item.chars().take_while(|c| c.is_ascii_digit()).count();
+    let item = if digits > 0 && item[digits..].starts_with(". ") {
+        &item[digits + 2..]
+    } else {
+        item
+    };
+    ["- ", "* ", "• "]
+        .iter()
+        .find_map(|marker| item.strip_prefix(marker))
+        .unwrap_or(item)
+        .trim()
+}
+
+fn item_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(strip_list_marker(s).to_string()),
+        Value::Number(n) => Some(n.to_string()),
+        _ => None,
+    }
+}
+
+/// Reads one section's value in the template section's format. Lists given as
+/// a string of lines and paragraphs given as an array are converted.
+fn section_content(title: &str, format: &str, value: &Value) -> Result<SectionContent, String> {
+    match (format, value) {
+        ("list", Value::Array(items)) => items
+            .iter()
+            .map(|item| {
+                item_text(item)
+                    .ok_or_else(|| format!("section '{}' must be a list of strings", title))
+            })
+            .filter(|item| !matches!(item, Ok(text) if text.is_empty()))
+            .collect::<Result<Vec<_>, _>>()
+            .map(SectionContent::Items),
+        ("list", Value::String(text)) => Ok(SectionContent::Items(
+            text.lines()
+                .map(strip_list_marker)
+                .filter(|line| !line.is_empty())
+                .map(str::to_string)
+                .collect(),
+        )),
+        ("list", Value::Null) => Ok(SectionContent::Items(Vec::new())),
+        ("list", _) => Err(format!("section '{}' must be a list of strings", title)),
+        (_, Value::String(text)) => Ok(SectionContent::Text(text.trim().to_string())),
+        (_, Value::Array(items)) => items
+            .iter()
+            .map(|item| {
+                item_text(item).ok_or_else(|| format!("section '{}' must be a string", title))
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(|lines| SectionContent::Text(lines.join("\n"))),
+        (_, Value::Null) => Ok(SectionContent::Text(String::new())),
+        _ => Err(format!("section '{}' must be a string", title)),
+    }
+}
+
+/// Parses and validates a structured reply against the template. The error
+/// describes what was wrong so it can be fed back to the model on retry.
+pub fn parse_structured_summary(
+    raw: &str,
+    template: &Template,
+) -> Result<StructuredSummary, String> {
+    let json = json_object(raw).ok_or("The reply did not contain a JSON object")?;
+    let parsed: Value =
+        serde_json::from_str(json).map_err(|e| format!("The reply is not valid JSON: {}", e))?;
+
+    let title = parsed
+        .get("title")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|title| !title.is_empty())
+        .ok_or("\"title\" is missing or empty")?;
+    let values = parsed
+        .get("sections")
+        .and_then(Value::as_object)
+        .ok_or("\"sections\" is missing or not an object")?;
+
+    let mut sections = Vec::with_capacity(template.sections.len());
+    for section in &template.sections {
+        // Tolerate a change of case, never a different name
+        let value = values.get(&section.title).or_else(|| {
+            values
+                .iter()
+                .find(|(key, _)| key.eq_ignore_ascii_case(&section.title))
+                .map(|(_, value)| value)
+        });
+        let value = value.ok_or_else(|| format!("section '{}' is missing", section.title))?;
+        sections.push(StructuredSection {
+            title: section.title.clone(),
+            format: section.format.clone(),
+            content: section_content(&section.title, &section.format, value)?,
+        });
+    }
+
+    for key in values.keys() {
+        if !template
+            .sections
+            .iter()
+            .any(|section| section.title.eq_ignore_ascii_case(key))
+        {
+            warn!("Ignoring section '{}' that is not in the template", key);
+        }
+    }
+
+    Ok(StructuredSummary {
+        title: title.to_string(),
+        sections,
+    })
+}
+
+impl StructuredSummary {
+    /// Checks the `[mm:ss]` citations of every section; list items must cite
+    /// a segment
+    pub fn check_citations(&mut self, resolver: &mut CitationResolver) {
+        for section in &mut self.sections {
+            match &mut section.content {
+                SectionContent::Text(text) => *text = resolver.resolve(text, false),
+                SectionContent::Items(items) => {
+                    for item in items.iter_mut() {
+                        *item = resolver.resolve(item, true);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Renders the summary in the layout of `Template::to_markdown_structure`
+    pub fn to_markdown(&self) -> String {
+        let mut markdown = format!("# {}\n\n", self.title);
+        for section in &self.sections {
+            markdown.push_str(&format!("**{}**\n\n", section.title));
+            match &section.content {
+                SectionContent::Text(text) if !text.is_empty() => {
+                    markdown.push_str(text);
+                    markdown.push_str("\n\n");
+                }
+                SectionContent::Items(items) if !items.is_empty() => {
+                    for item in items {
+                        markdown.push_str(&format!("- {}\n", item));
+                    }
+                    markdown.push('\n');
+                }
+                _ => markdown.push_str(&format!("{}\n\n", EMPTY_SECTION)),
+            }
+        }
+        markdown.trim_end().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::summary::templates::TemplateSection;
+
+    fn template() -> Template {
+        let section = |title: &str, format: &str| TemplateSection {
+            title: title.to_string(),
+            instruction: "Fill it".to_string(),
+            format: format.to_string(),
+            item_format: None,
+            example_item_format: None,
+        };
+        Template {
+            name: "Test".to_string(),
+            description: "Test".to_string(),
+            sections: vec![
+                section("Summary", "paragraph"),
+                section("Action Items", "list"),
+                section("Risks", "list"),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_parse_and_render_structured_summary() {
+        let reply = r#"```json
+{"title": "Release sync", "sections": {
+  "Summary": "We agreed to ship.",
+  "action items": ["- Alice: update docs", "Bob: tag release"],
+  "Risks": []
+}}
+```"#;
+        let summary = parse_structured_summary(reply, &template()).unwrap();
+
+        assert_eq!(summary.sections[1].title, "Action Items");
+        assert_eq!(
+            summary.sections[1].content,
+            SectionContent::Items(vec![
+                "Alice: update docs".to_string(),
+                "Bob: tag release".to_string()
+            ])
+        );
+        assert_eq!(
+            summary.to_markdown(),
+            "# Release sync\n\n\
+             **Summary**\n\nWe agreed to ship.\n\n\
+             **Action Items**\n\n- Alice: update docs\n- Bob: tag release\n\n\
+             **Risks**\n\nNone noted in this section."
+        );
+    }
+
+    #[test]
+    fn test_parse_structured_summary_rejects_invalid_replies() {
+        let template = template();
+        assert!(parse_structured_summary("no json here", &template).is_err());
+        assert_eq!(
+            parse_structured_summary(
+                r#"{"title": "T", "sections": {"Summary": "x", "Next Steps": []}}"#,
+                &template
+            ),
+            Err("section 'Action Items' is missing".to_string())
+        );
+        assert_eq!(
+            parse_structured_summary(
+                r#"{"title": "T", "sections": {"Summary": "x", "Action Items": [{"a": 1}], "Risks": []}}"#,
+                &template
+            ),
+            Err("section 'Action Items' must be a list of strings".to_string())
+        );
+        assert!(parse_structured_summary(r#"{"sections": {}}"#, &template).is_err());
+    }
+}
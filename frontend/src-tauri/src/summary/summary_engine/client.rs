@@ -15,6 +15,7 @@ use tokio_util::sync::CancellationToken;
 
 use super::models;
 use super::sidecar::SidecarManager;
+use crate::summary::streaming::DeltaCallback;
 
 // ============================================================================
 // Request/Response Types
@@ -24,22 +25,64 @@ use super::sidecar::SidecarManager;
 #[serde(tag = "type", rename_all = "snake_case")]
 enum Request {
     Generate {
-        prompt: String,
+        // Formatted by the sidecar with the model's embedded chat template
+        messages: Vec<ChatMessage>,
         max_tokens: Option<i32>,
         context_size: Option<u32>,
         model_path: Option<String>,
+        // From the GGUF metadata, for GPU offloading
+        layer_count: Option<u32>,
         // Sampling parameters
         temperature: Option<f32>,
         top_k: Option<i32>,
         top_p: Option<f32>,
         stop_tokens: Option<Vec<String>>,
+        // Emit a Token message per decoded piece before the final Response
+        stream: Option<bool>,
+        // Output constraint; the sidecar rejects a request with both
+        grammar: Option<String>,
+        json_schema: Option<serde_json::Value>,
     },
+    Embed {
+        texts: Vec<String>,
+        model_path: Option<String>,
+    },
+    Tokenize {
+        texts: Vec<String>,
+        model_path: Option<String>,
+    },
+}
+
+#[derive(Debug, Serialize)]
+struct ChatMessage {
+    role: &'static str,
+    content: String,
+}
+
+/// Prompt cache use and speed the sidecar reports for a generation
+#[derive(Debug, Clone, Deserialize)]
+pub struct GenerationStats {
+    pub prompt_tokens: usize,
+    /// Prompt tokens whose KV cache the sidecar kept from the previous request
+    pub cached_tokens: usize,
+    pub output_tokens: usize,
+    pub prompt_ms: u64,
+    pub generation_ms: u64,
+    pub prompt_tokens_per_sec: f64,
+    pub tokens_per_sec: f64,
 }
 
 #[derive(Debug, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 enum Response {
-    Response { text: String, error: Option<String> },
+    Response {
+        text: String,
+        error: Option<String>,
+        stats: Option<GenerationStats>,
+    },
+    Token { text: String },
+    Embeddings { vectors: Vec<Vec<f32>>, error: Option<String> },
+    TokenCounts { counts: Vec<usize>, error: Option<String> },
     Error { message: String },
 }
 
@@ -115,6 +158,16 @@ fn get_cached_model_path(app_data_dir: &PathBuf, model_name: &str) -> Result<Pat
 // Public API
 // ============================================================================
 
+/// Restricts what the model can generate, enforced by llama.cpp's grammar
+/// sampler
+#[derive(Debug, Clone)]
+pub enum OutputConstraint {
+    /// GBNF grammar starting at its `root` rule
+    Grammar(String),
+    /// JSON schema the sidecar converts to a grammar
+    JsonSchema(serde_json::Value),
+}
+
 /// Generate text using built-in AI
 ///
 /// # Arguments
@@ -122,7 +175,9 @@ fn get_cached_model_path(app_data_dir: &PathBuf, model_name: &str) -> Result<Pat
 /// * `model_name` - Model name (e.g., "gemma3:1b")
 /// * `system_prompt` - System instructions for the model
 /// * `user_prompt` - User message/task
+/// * `constraint` - Optional grammar or JSON schema the output must match
 /// * `cancellation_token` - Optional token for cancellation
+/// * `on_token` - Optional callback receiving text as it is generated
 ///
 /// # Returns
 /// Generated text
@@ -131,7 +186,9 @@ pub async fn generate_with_builtin(
     model_name: &str,
     system_prompt: &str,
     user_prompt: &str,
+    constraint: Option<&OutputConstraint>,
     cancellation_token: Option<&CancellationToken>,
+    on_token: Option<&DeltaCallback<'_>>,
 ) -> Result<String> {
     // Check cancellation at start
     if let Some(token) = cancellation_token {
@@ -144,15 +201,13 @@ pub async fn generate_with_builtin(
     log::info!("Model: {}", model_name);
 
     // Get model definition
-    let model_def = models::get_model_by_name(model_name)
+    let models_dir = models::get_models_directory(app_data_dir);
+    let model_def = models::get_model_by_name(&models_dir, model_name)
         .ok_or_else(|| anyhow!("Unknown model: {}", model_name))?;
 
     // Resolve model path with caching (avoids repeated filesystem I/O)
     let model_path = get_cached_model_path(app_data_dir, model_name)?;
 
-    // Apply model-specific chat template
-    let formatted_prompt =
-        models::format_prompt(&model_def.template, system_prompt, user_prompt)?;
     // Get or initialize sidecar manager
     let manager = {
         let mut global_manager = SIDECAR_MANAGER.lock().await;
@@ -176,18 +231,48 @@ pub async fn generate_with_builtin(
 
     // Prepare generation request with model-specific sampling parameters
     let request = Request::Generate {
-        prompt: formatted_prompt,
+        messages: vec![
+            ChatMessage {
+                role: "system",
+                content: system_prompt.to_string(),
+            },
+            ChatMessage {
+                role: "user",
+                content: user_prompt.to_string(),
+            },
+        ],
         max_tokens: Some(models::DEFAULT_MAX_TOKENS),
         context_size: Some(model_def.context_size),
         model_path: Some(model_path.to_string_lossy().to_string()),
+        layer_count: Some(model_def.layer_count).filter(|&layers| layers > 0),
         temperature: Some(model_def.sampling.temperature),
         top_k: Some(model_def.sampling.top_k),
         top_p: Some(model_def.sampling.top_p),
         stop_tokens: Some(model_def.sampling.stop_tokens.clone()),
+        stream: on_token.map(|_| true),
+        grammar: match constraint {
+            Some(OutputConstraint::Grammar(grammar)) => Some(grammar.clone()),
+            _ => None,
+        },
+        json_schema: match constraint {
+            Some(OutputConstraint::JsonSchema(schema)) => Some(schema.clone()),
+            _ => None,
+        },
     };
 
     let request_json = serde_json::to_string(&request)?;
 
+    // Token lines are forwarded to the callback; anything else is the final reply
+    let mut on_line = |line: &str| match serde_json::from_str::<Response>(line) {
+        Ok(Response::Token { text }) => {
+            if let Some(on_token) = on_token {
+                on_token(&text);
+            }
+            true
+        }
+        _ => false,
+    };
+
     // Send request with timeout
     let timeout = Duration::from_secs(models::GENERATION_TIMEOUT_SECS);
 
@@ -196,7 +281,7 @@ pub async fn generate_with_builtin(
     // Race between send_request and cancellation token
     let response_json = if let Some(token) = cancellation_token {
         tokio::select! {
-            result = manager.send_request(request_json, timeout) => {
+            result = manager.send_request_with_progress(request_json, timeout, &mut on_line) => {
                 result?
             }
             _ = token.cancelled() => {
@@ -209,7 +294,9 @@ pub async fn generate_with_builtin(
             }
         }
     } else {
-        manager.send_request(request_json, timeout).await?
+        manager
+            .send_request_with_progress(request_json, timeout, &mut on_line)
+            .await?
     };
 
     // Check cancellation before parsing response
@@ -224,14 +311,158 @@ pub async fn generate_with_builtin(
         .with_context(|| format!("Failed to parse response: {}", response_json))?;
 
     match response {
-        Response::Response { text, error } => {
+        Response::Response { text, error, stats } => {
             if let Some(err_msg) = error {
                 Err(anyhow!("Generation failed: {}", err_msg))
             } else {
                 log::info!("Generation completed: {} chars", text.len());
+                if let Some(stats) = stats {
+                    log::info!(
+                        "Prompt: {} tokens ({} from cache) at {:.1} tokens/sec; output: {} tokens at {:.1} tokens/sec",
+                        stats.prompt_tokens,
+                        stats.cached_tokens,
+                        stats.prompt_tokens_per_sec,
+                        stats.output_tokens,
+                        stats.tokens_per_sec
+                    );
+                }
                 Ok(text)
             }
         }
+        Response::Token { .. } | Response::Embeddings { .. } | Response::TokenCounts { .. } => {
+            Err(anyhow!("Unexpected response: {}", response_json))
+        }
+        Response::Error { message } => Err(anyhow!("Sidecar error: {}", message)),
+    }
+}
+
+/// Embed texts using built-in AI
+///
+/// Vectors are mean-pooled over the model's hidden states and L2-normalized.
+/// Shares the sidecar with generation, so embedding with a different model than
+/// the one used for summaries restarts the sidecar.
+///
+/// # Arguments
+/// * `app_data_dir` - Application data directory (for model resolution)
+/// * `model_name` - Model name (e.g., "gemma3:1b")
+/// * `texts` - Texts to embed, one vector is returned per text
+pub async fn embed_with_builtin(
+    app_data_dir: &PathBuf,
+    model_name: &str,
+    texts: &[String],
+) -> Result<Vec<Vec<f32>>> {
+    if texts.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let model_path = get_cached_model_path(app_data_dir, model_name)?;
+
+    let manager = {
+        let mut global_manager = SIDECAR_MANAGER.lock().await;
+        if global_manager.is_none() {
+            log::info!("Initializing sidecar manager");
+            let new_manager = SidecarManager::new(app_data_dir.clone())?;
+            *global_manager = Some(Arc::new(new_manager));
+        }
+        global_manager.clone().unwrap()
+    };
+
+    manager.ensure_running(model_path.clone()).await?;
+
+    let request = Request::Embed {
+        texts: texts.to_vec(),
+        model_path: Some(model_path.to_string_lossy().to_string()),
+    };
+    let request_json = serde_json::to_string(&request)?;
+
+    let timeout = Duration::from_secs(models::GENERATION_TIMEOUT_SECS);
+    let response_json = manager.send_request(request_json, timeout).await?;
+
+    let response: Response = serde_json::from_str(&response_json)
+        .with_context(|| format!("Failed to parse response: {}", response_json))?;
+
+    match response {
+        Response::Embeddings { vectors, error } => {
+            if let Some(err_msg) = error {
+                return Err(anyhow!("Embedding failed: {}", err_msg));
+            }
+            if vectors.len() != texts.len() {
+                return Err(anyhow!(
+                    "Sidecar returned {} embeddings for {} texts",
+                    vectors.len(),
+                    texts.len()
+                ));
+            }
+            Ok(vectors)
+        }
+        Response::Response { .. } | Response::Token { .. } | Response::TokenCounts { .. } => {
+            Err(anyhow!("Unexpected generation response"))
+        }
+        Response::Error { message } => Err(anyhow!("Sidecar error: {}", message)),
+    }
+}
+
+/// Count tokens using the built-in model's own tokenizer
+///
+/// Counts exclude BOS/EOS and chat template tokens, so summing the counts of
+/// consecutive pieces gives the size of their concatenation.
+///
+/// # Arguments
+/// * `app_data_dir` - Application data directory (for model resolution)
+/// * `model_name` - Model name (e.g., "gemma3:1b")
+/// * `texts` - Texts to count, one count is returned per text
+pub async fn count_tokens_with_builtin(
+    app_data_dir: &PathBuf,
+    model_name: &str,
+    texts: &[String],
+) -> Result<Vec<usize>> {
+    if texts.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let model_path = get_cached_model_path(app_data_dir, model_name)?;
+
+    let manager = {
+        let mut global_manager = SIDECAR_MANAGER.lock().await;
+        if global_manager.is_none() {
+            log::info!("Initializing sidecar manager");
+            let new_manager = SidecarManager::new(app_data_dir.clone())?;
+            *global_manager = Some(Arc::new(new_manager));
+        }
+        global_manager.clone().unwrap()
+    };
+
+    manager.ensure_running(model_path.clone()).await?;
+
+    let request = Request::Tokenize {
+        texts: texts.to_vec(),
+        model_path: Some(model_path.to_string_lossy().to_string()),
+    };
+    let request_json = serde_json::to_string(&request)?;
+
+    let timeout = Duration::from_secs(models::GENERATION_TIMEOUT_SECS);
+    let response_json = manager.send_request(request_json, timeout).await?;
+
+    let response: Response = serde_json::from_str(&response_json)
+        .with_context(|| format!("Failed to parse response: {}", response_json))?;
+
+    match response {
+        Response::TokenCounts { counts, error } => {
+            if let Some(err_msg) = error {
+                return Err(anyhow!("Tokenization failed: {}", err_msg));
+            }
+            if counts.len() != texts.len() {
+                return Err(anyhow!(
+                    "Sidecar returned {} token counts for {} texts",
+                    counts.len(),
+                    texts.len()
+                ));
+            }
+            Ok(counts)
+        }
+        Response::Response { .. } | Response::Token { .. } | Response::Embeddings { .. } => {
+            Err(anyhow!("Unexpected tokenize response"))
+        }
         Response::Error { message } => Err(anyhow!("Sidecar error: {}", message)),
     }
 }
@@ -296,21 +527,30 @@ mod tests {
     #[test]
     fn test_request_serialization() {
         let request = Request::Generate {
-            prompt: "test prompt".to_string(),
+            messages: vec![ChatMessage {
+                role: "user",
+                content: "test prompt".to_string(),
+            }],
             max_tokens: Some(512),
             context_size: Some(2048),
             model_path: Some("/path/to/model.gguf".to_string()),
+            layer_count: Some(26),
             temperature: Some(1.0),
             top_k: Some(64),
             top_p: Some(0.95),
             stop_tokens: Some(vec!["<end_of_turn>".to_string()]),
+            stream: Some(true),
+            grammar: None,
+            json_schema: Some(serde_json::json!({"type": "object"})),
         };
 
         let json = serde_json::to_string(&request).unwrap();
         assert!(json.contains("\"type\":\"generate\""));
-        assert!(json.contains("\"prompt\":\"test prompt\""));
+        assert!(json.contains("\"messages\":[{\"role\":\"user\",\"content\":\"test prompt\"}]"));
         assert!(json.contains("\"max_tokens\":512"));
         assert!(json.contains("\"temperature\":1.0"));
+        assert!(json.contains("\"stream\":true"));
+        assert!(json.contains("\"json_schema\":{\"type\":\"object\"}"));
     }
 
     #[test]
@@ -319,9 +559,59 @@ mod tests {
         let response: Response = serde_json::from_str(json).unwrap();
 
         match response {
-            Response::Response { text, error } => {
+            Response::Response { text, error, stats } => {
                 assert_eq!(text, "generated text");
                 assert!(error.is_none());
+                assert!(stats.is_none());
+            }
+            _ => panic!("Wrong response type"),
+        }
+
+        let json = r#"{"type":"response","text":"ok","error":null,"stats":{"prompt_tokens":900,"cached_tokens":850,"output_tokens":40,"prompt_ms":120,"generation_ms":2000,"prompt_tokens_per_sec":416.7,"tokens_per_sec":20.0}}"#;
+        match serde_json::from_str::<Response>(json).unwrap() {
+            Response::Response { stats, .. } => {
+                let stats = stats.unwrap();
+                assert_eq!(stats.cached_tokens, 850);
+                assert_eq!(stats.tokens_per_sec, 20.0);
+            }
+            _ => panic!("Wrong response type"),
+        }
+    }
+
+    #[test]
+    fn test_token_response_deserialization() {
+        let json = r#"{"type":"token","text":"Hel"}"#;
+        let response: Response = serde_json::from_str(json).unwrap();
+
+        match response {
+            Response::Token { text } => assert_eq!(text, "Hel"),
+            _ => panic!("Wrong response type"),
+        }
+    }
+
+    #[test]
+    fn test_embeddings_response_deserialization() {
+        let json = r#"{"type":"embeddings","vectors":[[0.6,0.8],[1.0,0.0]],"error":null}"#;
+        let response: Response = serde_json::from_str(json).unwrap();
+
+        match response {
+            Response::Embeddings { vectors, error } => {
+                assert_eq!(vectors, vec![vec![0.6, 0.8], vec![1.0, 0.0]]);
+                assert!(error.is_none());
+            }
+            _ => panic!("Wrong response type"),
+        }
+    }
+
+    #[test]
+    fn test_token_counts_response_deserialization() {
+        let json = r#"{"type":"token_counts","counts":[12,0,345],"error":null}"#;
+        let response: Response = serde_json::from_str(json).unwrap();
+
+        match response {
+            Response::TokenCounts { counts, error } => {
+                assert_eq!(counts, vec![12, 0, 345]);
+                assert!(error.is_none());
             }
             _ => panic!("Wrong response type"),
         }
@@ -0,0 +1,278 @@
+// GGUF metadata reader for registering user-supplied models
+// Reads only the key/value header; tensor data is never touched
+
+use anyhow::{anyhow, Result};
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::Path;
+
+/// "GGUF" in little-endian
+const GGUF_MAGIC: u32 = 0x4655_4747;
+
+/// Strings longer than this are rejected as a corrupt header (chat templates
+/// are a few KB)
+const MAX_STRING_LEN: u64 = 16 * 1024 * 1024;
+
+// Metadata value types
+const TYPE_UINT8: u32 = 0;
+const TYPE_INT8: u32 = 1;
+const TYPE_UINT16: u32 = 2;
+const TYPE_INT16: u32 = 3;
+const TYPE_UINT32: u32 = 4;
+const TYPE_INT32: u32 = 5;
+const TYPE_FLOAT32: u32 = 6;
+const TYPE_BOOL: u32 = 7;
+const TYPE_STRING: u32 = 8;
+const TYPE_ARRAY: u32 = 9;
+const TYPE_UINT64: u32 = 10;
+const TYPE_INT64: u32 = 11;
+const TYPE_FLOAT64: u32 = 12;
+
+/// The metadata needed to run a model with llama-helper
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GgufMetadata {
+    /// `general.architecture`, e.g. "llama", "qwen2", "gemma3"
+    pub architecture: String,
+    /// `general.name`, if set
+    pub name: Option<String>,
+    /// `<arch>.context_length`: the context the model was trained with
+    pub context_length: Option<u32>,
+    /// `<arch>.block_count`: transformer layers, for GPU offloading
+    pub block_count: Option<u32>,
+    /// `tokenizer.chat_template`: Jinja template llama.cpp formats prompts with
+    pub chat_template: Option<String>,
+}
+
+/// A metadata value, with arrays skipped
+enum Value {
+    Int(u64),
+    String(String),
+    Other,
+}
+
+struct HeaderReader<R> {
+    reader: R,
+}
+
+impl<R: Read> HeaderReader<R> {
+    fn read_bytes<const N: usize>(&mut self) -> Result<[u8; N]> {
+        let mut buf = [0u8; N];
+        self.reader.read_exact(&mut buf)?;
+        Ok(buf)
+    }
+
+    fn read_u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.read_bytes()?))
+    }
+
+    fn read_u64(&mut self) -> Result<u64> {
+        Ok(u64::from_le_bytes(self.read_bytes()?))
+    }
+
+    fn skip(&mut self, len: u64) -> Result<()> {
+        let skipped = std::io::copy(&mut (&mut self.reader).take(len), &mut std::io::sink())?;
+        if skipped < len {
+            return Err(anyhow!("Unexpected end of GGUF header"));
+        }
+        Ok(())
+    }
+
+    fn read_string(&mut self) -> Result<String> {
+        let len = self.read_u64()?;
+        if len > MAX_STRING_LEN {
+            return Err(anyhow!("GGUF string of {} bytes is too long", len));
+        }
+        let mut buf = vec![0u8; len as usize];
+        self.reader.read_exact(&mut buf)?;
+        Ok(String::from_utf8_lossy(&buf).into_owned())
+    }
+
+    fn skip_string(&mut self) -> Result<()> {
+        let len = self.read_u64()?;
+        self.skip(len)
+    }
+
+    /// Size of a fixed-size value type
+    fn scalar_size(value_type: u32) -> Option<u64> {
+        match value_type {
+            TYPE_UINT8 | TYPE_INT8 | TYPE_BOOL => Some(1),
+            TYPE_UINT16 | TYPE_INT16 => Some(2),
+            TYPE_UINT32 | TYPE_INT32 | TYPE_FLOAT32 => Some(4),
+            TYPE_UINT64 | TYPE_INT64 | TYPE_FLOAT64 => Some(8),
+            _ => None,
+        }
+    }
+
+    fn read_value(&mut self, value_type: u32) -> Result<Value> {
+        match value_type {
+            TYPE_UINT8 => Ok(Value::Int(self.read_bytes::<1>()?[0] as u64)),
+            TYPE_UINT16 => Ok(Value::Int(u16::from_le_bytes(self.read_bytes()?) as u64)),
+            TYPE_UINT32 => Ok(Value::Int(self.read_u32()? as u64)),
+            TYPE_INT32 => Ok(match i32::from_le_bytes(self.read_bytes()?) {
+                v if v >= 0 => Value::Int(v as u64),
+                _ => Value::Other,
+            }),
+            TYPE_UINT64 => Ok(Value::Int(self.read_u64()?)),
+            TYPE_INT64 => Ok(match i64::from_le_bytes(self.read_bytes()?) {
+                v if v >= 0 => Value::Int(v as u64),
+                _ => Value::Other,
+            }),
+            TYPE_STRING => Ok(Value::String(self.read_string()?)),
+            TYPE_ARRAY => {
+                // Arrays (vocabulary, merges) are large and never needed
+                let item_type = self.read_u32()?;
+                let count = self.read_u64()?;
+                if let Some(size) = Self::scalar_size(item_type) {
+                    self.skip(count.saturating_mul(size))?;
+                } else if item_type == TYPE_STRING {
+                    for _ in 0..count {
+                        self.skip_string()?;
+                    }
+                } else {
+                    for _ in 0..count {
+                        self.read_value(item_type)?;
+                    }
+                }
+                Ok(Value::Other)
+            }
+            other => match Self::scalar_size(other) {
+                Some(size) => {
+                    self.skip(size)?;
+                    Ok(Value::Other)
+                }
+                None => Err(anyhow!("Unknown GGUF value type {}", other)),
+            },
+        }
+    }
+}
+
+/// Reads the model metadata from a GGUF header
+pub fn read_metadata<R: Read>(reader: R) -> Result<GgufMetadata> {
+    let mut header = HeaderReader { reader };
+    if header.read_u32()? != GGUF_MAGIC {
+        return Err(anyhow!("Not a GGUF file"));
+    }
+    let version = header.read_u32()?;
+    if version < 2 {
+        return Err(anyhow!("GGUF version {} is not supported", version));
+    }
+    let _tensor_count = header.read_u64()?;
+    let kv_count = header.read_u64()?;
+
+    let mut metadata = GgufMetadata::default();
+    // Architecture-specific keys, resolved once the architecture is known
+    let mut arch_values: Vec<(String, u64)> = Vec::new();
+
+    for _ in 0..kv_count {
+        let key = header.read_string()?;
+        let value_type = header.read_u32()?;
+        match (key.as_str(), header.read_value(value_type)?) {
+            ("general.architecture", Value::String(arch)) => metadata.architecture = arch,
+            ("general.name", Value::String(name)) => metadata.name = Some(name),
+            ("tokenizer.chat_template", Value::String(template)) => {
+                metadata.chat_template = Some(template)
+            }
+            (key, Value::Int(value))
+                if key.ends_with(".context_length") || key.ends_with(".block_count") =>
+            {
+                arch_values.push((key.to_string(), value));
+            }
+            _ => {}
+        }
+    }
+
+    if metadata.architecture.is_empty() {
+        return Err(anyhow!("GGUF file has no general.architecture"));
+    }
+    let arch_value = |suffix: &str| {
+        let key = format!("{}.{}", metadata.architecture, suffix);
+        arch_values
+            .iter()
+            .find(|(k, _)| *k == key)
+            .map(|(_, v)| (*v).min(u32::MAX as u64) as u32)
+    };
+    metadata.context_length = arch_value("context_length");
+    metadata.block_count = arch_value("block_count");
+
+    Ok(metadata)
+}
+
+/// Reads the model metadata of a GGUF file
+pub fn read_metadata_from_file(path: &Path) -> Result<GgufMetadata> {
+    let file = File::open(path).map_err(|e| anyhow!("Failed to open {}: {}", path.display(), e))?;
+    read_metadata(BufReader::new(file))
+        .map_err(|e| anyhow!("Failed to read GGUF metadata of {}: {}", path.display(), e))
+}
+
+// ============================================================================
+// Tests
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn string(out: &mut Vec<u8>, s: &str) {
+        out.extend((s.len() as u64).to_le_bytes());
+        out.extend(s.as_bytes());
+    }
+
+    fn kv_string(out: &mut Vec<u8>, key: &str, value: &str) {
+        string(out, key);
+        out.extend(TYPE_STRING.to_le_bytes());
+        string(out, value);
+    }
+
+    fn kv_u32(out: &mut Vec<u8>, key: &str, value: u32) {
+        string(out, key);
+        out.extend(TYPE_UINT32.to_le_bytes());
+        out.extend(value.to_le_bytes());
+    }
+
+    #[test]
+    fn test_read_metadata() {
+        let mut gguf = Vec::new();
+        gguf.extend(GGUF_MAGIC.to_le_bytes());
+        gguf.extend(3u32.to_le_bytes());
+        gguf.extend(0u64.to_le_bytes());
+        gguf.extend(7u64.to_le_bytes());
+        kv_string(&mut gguf, "general.architecture", "qwen2");
+        kv_string(&mut gguf, "general.name", "Qwen2.5 7B Instruct");
+        kv_u32(&mut gguf, "qwen2.context_length", 32768);
+        kv_u32(&mut gguf, "qwen2.block_count", 28);
+        // Another architecture's key is ignored
+        kv_u32(&mut gguf, "llama.block_count", 99);
+        // Vocabulary arrays are skipped
+        string(&mut gguf, "tokenizer.ggml.tokens");
+        gguf.extend(TYPE_ARRAY.to_le_bytes());
+        gguf.extend(TYPE_STRING.to_le_bytes());
+        gguf.extend(2u64.to_le_bytes());
+        string(&mut gguf, "<|im_start|>");
+        string(&mut gguf, "hello");
+        kv_string(&mut gguf, "tokenizer.chat_template", "{{ messages }}");
+
+        let metadata = read_metadata(gguf.as_slice()).unwrap();
+        assert_eq!(
+            metadata,
+            GgufMetadata {
+                architecture: "qwen2".to_string(),
+                name: Some("Qwen2.5 7B Instruct".to_string()),
+                context_length: Some(32768),
+                block_count: Some(28),
+                chat_template: Some("{{ messages }}".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn test_read_metadata_rejects_other_files() {
+        assert!(read_metadata(b"PK\x03\x04 not a model".as_slice()).is_err());
+        // Truncated header
+        let mut gguf = Vec::new();
+        gguf.extend(GGUF_MAGIC.to_le_bytes());
+        gguf.extend(3u32.to_le_bytes());
+        gguf.extend(0u64.to_le_bytes());
+        gguf.extend(1u64.to_le_bytes());
+        assert!(read_metadata(gguf.as_slice()).is_err());
+    }
+}
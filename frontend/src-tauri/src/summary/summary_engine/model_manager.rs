@@ -187,7 +187,7 @@ impl ModelManager {
             self.models_dir.display()
         );
 
-        let model_defs = get_available_models();
+        let model_defs = get_available_models(&self.models_dir);
         let mut models_map = HashMap::new();
 
         for model_def in model_defs {
@@ -365,8 +365,14 @@ impl ModelManager {
         }
 
         // Get model definition
-        let model_def = get_model_by_name(model_name)
+        let model_def = get_model_by_name(&self.models_dir, model_name)
             .ok_or_else(|| anyhow!("Unknown model: {}", model_name))?;
+        if model_def.is_user_supplied() {
+            return Err(anyhow!(
+                "Model '{}' is a local file and can't be downloaded",
+                model_name
+            ));
+        }
 
         // Add to active downloads
         {
@@ -823,7 +829,7 @@ impl ModelManager {
     pub async fn delete_model(&self, model_name: &str) -> Result<()> {
         log::info!("Deleting model: {}", model_name);
 
-        let model_def = get_model_by_name(model_name)
+        let model_def = get_model_by_name(&self.models_dir, model_name)
             .ok_or_else(|| anyhow!("Unknown model: {}", model_name))?;
 
         let file_path = self.models_dir.join(&model_def.gguf_file);
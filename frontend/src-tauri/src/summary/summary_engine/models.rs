@@ -1,9 +1,17 @@
-// Model definitions and prompt templates for built-in AI summary generation
-// Designed for easy extension - just add new entries to get_available_models()
+// Model definitions for built-in AI summary generation
+// Downloadable models are listed in catalog_models(); any other GGUF file in the
+// models directory is registered from its metadata. Prompts are formatted by
+// llama-helper with the chat template embedded in the GGUF file.
 
 use anyhow::{anyhow, Result};
+use once_cell::sync::Lazy;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use std::time::SystemTime;
+
+use super::gguf;
 
 // ============================================================================
 // Model Definitions
@@ -25,6 +33,19 @@ pub struct SamplingParams {
     pub stop_tokens: Vec<String>,
 }
 
+impl Default for SamplingParams {
+    /// Conservative settings for models without tuned parameters. Generation
+    /// ends at the model's end-of-generation token, so no stop tokens are needed.
+    fn default() -> Self {
+        Self {
+            temperature: 0.7,
+            top_k: 40,
+            top_p: 0.95,
+            stop_tokens: Vec::new(),
+        }
+    }
+}
+
 /// Definition of a built-in AI model with all metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModelDef {
@@ -38,10 +59,7 @@ pub struct ModelDef {
     /// GGUF filename on disk (e.g., "gemma-3-1b-it-q4_0.gguf")
     pub gguf_file: String,
 
-    /// Template name for prompt formatting (e.g., "gemma3")
-    pub template: String,
-
-    /// Download URL (HuggingFace or other source)
+    /// Download URL (HuggingFace or other source), empty for user-supplied files
     pub download_url: String,
 
     /// File size in MB
@@ -61,16 +79,22 @@ pub struct ModelDef {
     pub description: String,
 }
 
-/// Get all available built-in AI models
+impl ModelDef {
+    /// Whether the model is a GGUF file the user placed in the models directory
+    pub fn is_user_supplied(&self) -> bool {
+        self.download_url.is_empty()
+    }
+}
+
+/// Models offered for download
 /// Add new models here - the system will automatically detect and manage them
-pub fn get_available_models() -> Vec<ModelDef> {
+pub fn catalog_models() -> Vec<ModelDef> {
     vec![
         // Gemma 3 1B - Fast tier
         ModelDef {
             name: "gemma3:1b".to_string(),
             display_name: "Gemma 3 1B (Fast)".to_string(),
             gguf_file: "gemma-3-1b-it-Q8_0.gguf".to_string(),
-            template: "gemma3".to_string(),
             download_url: "https://meetily.towardsgeneralintelligence.com/models/gemma-3-1b-it-Q8_0.gguf".to_string(),
             size_mb: 1019,
             context_size: 32768, 
@@ -87,7 +111,6 @@ pub fn get_available_models() -> Vec<ModelDef> {
             name: "gemma3:4b".to_string(),
             display_name: "Gemma 3 4B (Balanced)".to_string(),
             gguf_file: "gemma-3-4b-it-Q4_K_M.gguf".to_string(),
-            template: "gemma3".to_string(),
             download_url: "https://meetily.towardsgeneralintelligence.com/models/gemma-3-4b-it-Q4_K_M.gguf".to_string(),
             size_mb: 2374,
             context_size: 32768, // Supports 128k, but 32k is good for local·
@@ -103,14 +126,148 @@ pub fn get_available_models() -> Vec<ModelDef> {
     ]
 }
 
+// ============================================================================
+// User-Supplied Models
+// ============================================================================
+
+/// Name prefix of models registered from GGUF files in the models directory
+pub const USER_MODEL_PREFIX: &str = "local:";
+
+/// Largest context used for user-supplied models; longer trained contexts
+/// need more memory than most machines have for the KV cache
+pub const MAX_USER_CONTEXT_SIZE: u32 = 32768;
+
+/// Context size assumed when the GGUF file doesn't declare one
+const DEFAULT_USER_CONTEXT_SIZE: u32 = 4096;
+
+/// A file's modification time and size, with the model registered from it
+/// (None if the file couldn't be registered)
+type UserModelEntry = (SystemTime, u64, Option<ModelDef>);
+
+/// Registered user models by path; a replaced file is read again
+static USER_MODEL_CACHE: Lazy<RwLock<HashMap<PathBuf, UserModelEntry>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// Builds the definition of a user-supplied GGUF file from its metadata.
+/// Files without a chat template are rejected, since llama-helper couldn't
+/// format prompts for them.
+pub fn model_from_gguf(path: &Path) -> Result<ModelDef> {
+    let metadata = gguf::read_metadata_from_file(path)?;
+    if metadata.chat_template.is_none() {
+        return Err(anyhow!(
+            "{} has no embedded chat template (tokenizer.chat_template)",
+            path.display()
+        ));
+    }
+
+    let gguf_file = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| anyhow!("Invalid model file name: {}", path.display()))?
+        .to_string();
+    let stem = gguf_file.trim_end_matches(".gguf");
+    let size_mb = std::fs::metadata(path)?.len() / (1024 * 1024);
+    let context_size = metadata
+        .context_length
+        .unwrap_or(DEFAULT_USER_CONTEXT_SIZE)
+        .min(MAX_USER_CONTEXT_SIZE);
+
+    Ok(ModelDef {
+        name: format!("{}{}", USER_MODEL_PREFIX, stem),
+        display_name: metadata.name.clone().unwrap_or_else(|| stem.to_string()),
+        gguf_file: gguf_file.clone(),
+        download_url: String::new(),
+        size_mb,
+        context_size,
+        layer_count: metadata.block_count.unwrap_or(0),
+        sampling: SamplingParams::default(),
+        description: format!("Local {} model ({}).", metadata.architecture, gguf_file),
+    })
+}
+
+/// Registers a user-supplied GGUF file, reading its metadata only when the
+/// file is new or changed
+fn cached_user_model(path: &Path) -> Option<ModelDef> {
+    let file_metadata = std::fs::metadata(path).ok()?;
+    let modified = file_metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+    let len = file_metadata.len();
+
+    if let Some((cached_modified, cached_len, model)) = USER_MODEL_CACHE.read().unwrap().get(path) {
+        if *cached_modified == modified && *cached_len == len {
+            return model.clone();
+        }
+    }
+
+    let model = match model_from_gguf(path) {
+        Ok(model) => Some(model),
+        Err(e) => {
+            log::warn!("Skipping model file: {}", e);
+            None
+        }
+    };
+    USER_MODEL_CACHE
+        .write()
+        .unwrap()
+        .insert(path.to_path_buf(), (modified, len, model.clone()));
+    model
+}
+
+/// GGUF files in the models directory that aren't catalog models
+pub fn get_user_models(models_dir: &Path) -> Vec<ModelDef> {
+    let catalog_files: Vec<String> = catalog_models()
+        .into_iter()
+        .map(|model| model.gguf_file)
+        .collect();
+
+    let Ok(entries) = std::fs::read_dir(models_dir) else {
+        return Vec::new();
+    };
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.is_file()
+                && path
+                    .extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("gguf"))
+                && path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(|name| !catalog_files.iter().any(|file| file == name))
+        })
+        .collect();
+    paths.sort();
+
+    paths
+        .iter()
+        .filter_map(|path| cached_user_model(path))
+        .collect()
+}
+
+// ============================================================================
+// Model Lookup
+// ============================================================================
+
+/// Catalog models followed by the user-supplied models in `models_dir`
+pub fn get_available_models(models_dir: &Path) -> Vec<ModelDef> {
+    let mut models = catalog_models();
+    models.extend(get_user_models(models_dir));
+    models
+}
+
 /// Get a specific model by name
-pub fn get_model_by_name(name: &str) -> Option<ModelDef> {
-    get_available_models().into_iter().find(|m| m.name == name)
+pub fn get_model_by_name(models_dir: &Path, name: &str) -> Option<ModelDef> {
+    if name.starts_with(USER_MODEL_PREFIX) {
+        get_user_models(models_dir)
+            .into_iter()
+            .find(|m| m.name == name)
+    } else {
+        catalog_models().into_iter().find(|m| m.name == name)
+    }
 }
 
-/// Get the default model (first in list)
+/// Get the default model (first in catalog)
 pub fn get_default_model() -> ModelDef {
-    get_available_models()
+    catalog_models()
         .into_iter()
         .next()
         .expect("At least one model must be defined")
@@ -118,10 +275,10 @@ pub fn get_default_model() -> ModelDef {
 
 /// Resolve model name to full file path in the models directory
 pub fn get_model_path(app_data_dir: &PathBuf, model_name: &str) -> Result<PathBuf> {
-    let model = get_model_by_name(model_name)
+    let models_dir = get_models_directory(app_data_dir);
+    let model = get_model_by_name(&models_dir, model_name)
         .ok_or_else(|| anyhow!("Unknown model: {}", model_name))?;
 
-    let models_dir = get_models_directory(app_data_dir);
     let model_path = models_dir.join(&model.gguf_file);
 
     Ok(model_path)
@@ -132,45 +289,6 @@ pub fn get_models_directory(app_data_dir: &PathBuf) -> PathBuf {
     app_data_dir.join("models").join("summary")
 }
 
-// ============================================================================
-// Prompt Templates (Model-Specific Formatting)
-// ============================================================================
-
-/// Gemma 3 chat template format
-pub const GEMMA3_TEMPLATE: &str = "\
-<start_of_turn>user
-{system_prompt}<end_of_turn>
-<start_of_turn>user
-{user_prompt}<end_of_turn>
-<start_of_turn>model
-";
-
-/// Format a prompt using the specified template
-///
-/// # Arguments
-/// * `template_name` - Template identifier (e.g., "gemma3", "chatml", "llama3")
-/// * `system_prompt` - System message (instructions for the model)
-/// * `user_prompt` - User message (actual task/question)
-///
-/// # Returns
-/// Formatted prompt string ready to send to llama-helper
-pub fn format_prompt(
-    template_name: &str,
-    system_prompt: &str,
-    user_prompt: &str,
-) -> Result<String> {
-    let template = match template_name {
-        "gemma3" => GEMMA3_TEMPLATE,
-        _ => return Err(anyhow!("Unknown template: {}", template_name)),
-    };
-
-    let formatted = template
-        .replace("{system_prompt}", system_prompt)
-        .replace("{user_prompt}", user_prompt);
-
-    Ok(formatted)
-}
-
 // ============================================================================
 // Configuration Constants
 // ============================================================================
@@ -350,6 +350,21 @@ impl SidecarManager {
 
     /// Send a request to the sidecar and wait for response
     pub async fn send_request(&self, request_json: String, timeout: Duration) -> Result<String> {
+        self.send_request_with_progress(request_json, timeout, &mut |_: &str| false)
+            .await
+    }
+
+    /// Send a request whose final response may be preceded by progress lines
+    /// (e.g. streamed tokens). Every line read is passed to `on_line`; while it
+    /// returns true the line is treated as progress and reading continues. The
+    /// first line it doesn't consume is returned as the response. `timeout`
+    /// covers the whole exchange.
+    pub async fn send_request_with_progress(
+        &self,
+        request_json: String,
+        timeout: Duration,
+        on_line: &mut (dyn FnMut(&str) -> bool + Send),
+    ) -> Result<String> {
         // Track active request
         let _guard = RequestGuard::new(self.active_request_count.clone());
 
@@ -371,8 +386,18 @@ impl SidecarManager {
             stdin.flush().await.context("Failed to flush stdin")?;
         }
 
+        let read_until_response = async {
+            loop {
+                let line = self.read_response().await?;
+                if !on_line(&line) {
+                    return Ok::<String, anyhow::Error>(line);
+                }
+                self.update_activity().await;
+            }
+        };
+
         // Read response from stdout with timeout
-        match tokio::time::timeout(timeout, self.read_response()).await {
+        match tokio::time::timeout(timeout, read_until_response).await {
             Ok(Ok(response)) => {
                 self.update_activity().await;
                 Ok(response)
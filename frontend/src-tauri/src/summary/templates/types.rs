@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
 
 /// Represents a single section in a meeting template
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -107,6 +108,55 @@ impl Template {
 
         instructions
     }
+
+    /// JSON schema of a structured summary of this template: a title and one
+    /// required property per section, keyed by the section title. Paragraph
+    /// and string sections are strings, list sections arrays of strings.
+    pub fn to_json_schema(&self) -> Value {
+        let mut properties = Map::new();
+        for section in &self.sections {
+            let item_format = section
+                .item_format
+                .as_ref()
+                .or(section.example_item_format.as_ref());
+            let description = match item_format {
+                Some(format) => format!(
+                    "{}. Items follow the format: {}",
+                    section.instruction.trim_end_matches('.'),
+                    format
+                ),
+                None => section.instruction.clone(),
+            };
+            let property = match section.format.as_str() {
+                "list" => json!({
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": description,
+                }),
+                _ => json!({ "type": "string", "description": description }),
+            };
+            properties.insert(section.title.clone(), property);
+        }
+        let section_titles: Vec<&str> = self.sections.iter().map(|s| s.title.as_str()).collect();
+
+        json!({
+            "type": "object",
+            "properties": {
+                "title": {
+                    "type": "string",
+                    "description": "Concise, descriptive title for the meeting",
+                },
+                "sections": {
+                    "type": "object",
+                    "properties": properties,
+                    "required": section_titles,
+                    "additionalProperties": false,
+                },
+            },
+            "required": ["title", "sections"],
+            "additionalProperties": false,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -161,4 +211,38 @@ mod tests {
 
         assert!(template.validate().is_err());
     }
+
+    #[test]
+    fn test_to_json_schema() {
+        let template = Template {
+            name: "Test".to_string(),
+            description: "Test".to_string(),
+            sections: vec![
+                TemplateSection {
+                    title: "Summary".to_string(),
+                    instruction: "Provide a summary".to_string(),
+                    format: "paragraph".to_string(),
+                    item_format: None,
+                    example_item_format: None,
+                },
+                TemplateSection {
+                    title: "Action Items".to_string(),
+                    instruction: "List action items".to_string(),
+                    format: "list".to_string(),
+                    item_format: Some("Owner - Task".to_string()),
+                    example_item_format: None,
+                },
+            ],
+        };
+
+        let schema = template.to_json_schema();
+        let sections = &schema["properties"]["sections"];
+        assert_eq!(sections["required"], json!(["Summary", "Action Items"]));
+        assert_eq!(sections["properties"]["Summary"]["type"], "string");
+        assert_eq!(sections["properties"]["Action Items"]["type"], "array");
+        assert_eq!(
+            sections["properties"]["Action Items"]["description"],
+            "List action items. Items follow the format: Owner - Task"
+        );
+    }
 }
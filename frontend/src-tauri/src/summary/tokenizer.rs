@@ -0,0 +1,75 @@
+//! Token counting for OpenAI-compatible models using tiktoken encodings.
+//!
+//! OpenAI models are counted with their own encoding. Groq and OpenRouter serve
+//! mostly open-weight models whose tokenizers aren't available offline; their
+//! text is counted with `cl100k_base`, which is close to the Llama 3 and Qwen
+//! vocabularies and much closer than a character estimate.
+
+use once_cell::sync::Lazy;
+use tiktoken_rs::CoreBPE;
+
+static O200K_BASE: Lazy<CoreBPE> =
+    Lazy::new(|| tiktoken_rs::o200k_base().expect("o200k_base encoding is bundled"));
+
+static CL100K_BASE: Lazy<CoreBPE> =
+    Lazy::new(|| tiktoken_rs::cl100k_base().expect("cl100k_base encoding is bundled"));
+
+/// Model name prefixes of the OpenAI families that use `o200k_base`
+const O200K_MODEL_PREFIXES: &[&str] = &[
+    "gpt-4o",
+    "chatgpt-4o",
+    "gpt-4.1",
+    "gpt-4.5",
+    "gpt-5",
+    "gpt-oss",
+    "o1",
+    "o3",
+    "o4",
+];
+
+/// Whether `model` is tokenized with `o200k_base` rather than `cl100k_base`.
+/// OpenRouter ids carry a vendor prefix ("openai/gpt-4o").
+fn uses_o200k(model: &str) -> bool {
+    let model = model.to_lowercase();
+    let model = model.strip_prefix("openai/").unwrap_or(&model);
+    O200K_MODEL_PREFIXES
+        .iter()
+        .any(|prefix| model.starts_with(prefix))
+}
+
+/// Counts the tokens of `text` as `model` would see them
+pub fn count_tiktoken_tokens(model: &str, text: &str) -> usize {
+    let bpe = if uses_o200k(model) {
+        &*O200K_BASE
+    } else {
+        &*CL100K_BASE
+    };
+    bpe.encode_ordinary(text).len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encoding_selection() {
+        assert!(uses_o200k("gpt-4o-mini"));
+        assert!(uses_o200k("openai/gpt-4.1"));
+        assert!(uses_o200k("o3-mini"));
+        assert!(!uses_o200k("gpt-4-turbo"));
+        assert!(!uses_o200k("llama-3.3-70b-versatile"));
+        assert!(!uses_o200k("meta-llama/llama-3.1-8b-instruct"));
+    }
+
+    #[test]
+    fn test_count_tiktoken_tokens() {
+        assert_eq!(count_tiktoken_tokens("gpt-4o", "hello world"), 2);
+        assert_eq!(
+            count_tiktoken_tokens("llama-3.1-8b-instant", "hello world"),
+            2
+        );
+        assert_eq!(count_tiktoken_tokens("gpt-4o", ""), 0);
+        // Special tokens in a transcript are plain text, not control tokens
+        assert!(count_tiktoken_tokens("gpt-4o", "<|endoftext|>") > 1);
+    }
+}
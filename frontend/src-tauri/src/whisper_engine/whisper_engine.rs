@@ -4,13 +4,14 @@ use std::path::{PathBuf};
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use tokio::sync::RwLock;
-use whisper_rs::{WhisperContext, WhisperContextParameters, FullParams, SamplingStrategy};
+use whisper_rs::{WhisperContext, WhisperContextParameters, WhisperState, FullParams, SamplingStrategy};
 use serde::{Serialize, Deserialize};
 use anyhow::{Result, anyhow};
 use reqwest::Client;
 use tokio::fs;
 use tokio::io::AsyncWriteExt;
 use crate::config::WHISPER_MODEL_CATALOG;
+use crate::audio::transcription::{words_from_tokens, TokenTiming, WordTiming};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum ModelStatus {
@@ -512,8 +513,40 @@ impl WhisperEngine {
         repeated_words as f32 / total_words
     }
     
-    /// Transcribe audio with streaming support for partial results and adaptive quality
-    pub async fn transcribe_audio_with_confidence(&self, audio_data: Vec<f32>, language: Option<String>) -> Result<(String, f32, bool)> {
+    /// Collect token timings from a finished run and group them into words.
+    /// Whisper reports token times in centiseconds from the start of the audio.
+    fn collect_word_timings(ctx: &WhisperContext, state: &WhisperState, num_segments: i32) -> Vec<WordTiming> {
+        let eot = ctx.token_eot();
+        let mut tokens = Vec::new();
+
+        for segment in 0..num_segments {
+            let num_tokens = state.full_n_tokens(segment).unwrap_or(0);
+            for token in 0..num_tokens {
+                let Ok(data) = state.full_get_token_data(segment, token) else {
+                    continue;
+                };
+                // Skip special tokens ([_BEG_], timestamps, end of text, ...)
+                if data.id >= eot {
+                    continue;
+                }
+                let Ok(text) = state.full_get_token_text_lossy(segment, token) else {
+                    continue;
+                };
+                tokens.push(TokenTiming {
+                    text,
+                    start: data.t0 as f64 / 100.0,
+                    end: data.t1 as f64 / 100.0,
+                    probability: Some(data.p),
+                });
+            }
+        }
+
+        words_from_tokens(&tokens)
+    }
+
+    /// Transcribe audio with streaming support for partial results and adaptive quality.
+    /// Returns (text, confidence, is_partial, word timings relative to the audio start).
+    pub async fn transcribe_audio_with_confidence(&self, audio_data: Vec<f32>, language: Option<String>) -> Result<(String, f32, bool, Vec<WordTiming>)> {
         let ctx_lock = self.current_context.read().await;
         let ctx = ctx_lock.as_ref()
             .ok_or_else(|| anyhow!("No model loaded. Please load a model first."))?;
@@ -621,13 +654,21 @@ impl WhisperEngine {
         let final_result = result.trim().to_string();
         let cleaned_result = Self::clean_repetitive_text(&final_result);
 
+        // Word timings follow the raw decoder output, so they no longer line up
+        // with text that had repetitions removed
+        let words = if cleaned_result == final_result {
+            Self::collect_word_timings(ctx, &state, num_segments)
+        } else {
+            Vec::new()
+        };
+
         let avg_confidence = if segment_count > 0 {
             total_confidence / segment_count as f32
         } else {
             0.0
         };
 
-        Ok((cleaned_result, avg_confidence, is_partial))
+        Ok((cleaned_result, avg_confidence, is_partial, words))
     }
 
     pub async fn transcribe_audio(&self, audio_data: Vec<f32>, language: Option<String>) -> Result<String> {
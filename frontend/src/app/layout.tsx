@@ -10,6 +10,7 @@ import { Toaster, toast } from 'sonner'
 import "sonner/dist/styles.css"
 import { useState, useEffect, useCallback } from 'react'
 import { listen, UnlistenFn } from '@tauri-apps/api/event'
+import { usePathname, useRouter } from 'next/navigation'
 import { invoke } from '@tauri-apps/api/core'
 import { TooltipProvider } from '@/components/ui/tooltip'
 import { RecordingStateProvider } from '@/contexts/RecordingStateContext'
@@ -33,6 +34,13 @@ const sourceSans3 = Source_Sans_3({
   variable: '--font-source-sans-3',
 })
 
+// Payload of the backend's 'notification-actions' event
+interface NotificationWithActions {
+  title: string;
+  body: string;
+  actions: { id: string; title: string }[];
+}
+
 // Module-level component — stable reference across RootLayout re-renders.
 // Defined here (not inside RootLayout) so React never sees a new function type
 // on re-render, which would cause unmount/remount and break initialization logic.
@@ -68,6 +76,8 @@ export default function RootLayout({
 }: {
   children: React.ReactNode
 }) {
+  const router = useRouter()
+  const pathname = usePathname()
   const [showOnboarding, setShowOnboarding] = useState(false)
   const [onboardingCompleted, setOnboardingCompleted] = useState(false)
 
@@ -127,6 +137,37 @@ export default function RootLayout({
     };
   }, [showOnboarding]);
 
+  // Desktop notifications can't show buttons, so their actions are offered here
+  useEffect(() => {
+    const unlisten = listen<NotificationWithActions>('notification-actions', (event) => {
+      const { title, body, actions } = event.payload;
+      const startRecording = actions.find(action => action.id === 'start_recording');
+      if (!startRecording || showOnboarding) return;
+
+      toast(title, {
+        description: body,
+        duration: 15000,
+        action: {
+          label: startRecording.title,
+          onClick: () => {
+            console.log('[Layout] Starting recording from meeting detected notification');
+            if (pathname === '/') {
+              window.dispatchEvent(new CustomEvent('start-recording-from-sidebar'));
+            } else {
+              // Home page picks this up when it mounts
+              sessionStorage.setItem('autoStartRecording', 'true');
+              router.push('/');
+            }
+          }
+        }
+      });
+    });
+
+    return () => {
+      unlisten.then(fn => fn());
+    };
+  }, [showOnboarding, pathname, router]);
+
   // Handle file drop for audio import
   const handleFileDrop = useCallback((paths: string[]) => {
     // Check if beta features are enabled (read from localStorage directly since we're outside ConfigProvider)
@@ -0,0 +1,148 @@
+"use client"
+
+import { useEffect, useState } from "react"
+import { invoke } from "@tauri-apps/api/core"
+import { toast } from "sonner"
+import { Copy, RefreshCw } from "lucide-react"
+import { Switch } from "./ui/switch"
+import { ApiServerSettings as ApiServerSettingsType, ApiServerStatus } from "@/types"
+
+export function ApiServerSettings() {
+  const [settings, setSettings] = useState<ApiServerSettingsType | null>(null);
+  const [status, setStatus] = useState<ApiServerStatus | null>(null);
+  const [port, setPort] = useState("");
+  const [saving, setSaving] = useState(false);
+
+  useEffect(() => {
+    const load = async () => {
+      try {
+        const [loaded, currentStatus] = await Promise.all([
+          invoke<ApiServerSettingsType>("api_server_get_settings"),
+          invoke<ApiServerStatus>("api_server_status"),
+        ]);
+        setSettings(loaded);
+        setPort(String(loaded.port));
+        setStatus(currentStatus);
+      } catch (error) {
+        console.error("Failed to load API server settings:", error);
+      }
+    };
+    load();
+  }, []);
+
+  const save = async (updated: ApiServerSettingsType) => {
+    setSaving(true);
+    try {
+      const newStatus = await invoke<ApiServerStatus>("api_server_save_settings", { settings: updated });
+      setSettings(updated);
+      setStatus(newStatus);
+      toast.success(updated.enabled ? "Local API enabled" : "Local API disabled");
+    } catch (error) {
+      console.error("Failed to save API server settings:", error);
+      toast.error(String(error));
+      setStatus(await invoke<ApiServerStatus>("api_server_status").catch(() => null));
+    } finally {
+      setSaving(false);
+    }
+  };
+
+  const handlePortBlur = () => {
+    if (!settings) return;
+    const parsed = Number(port);
+    if (!Number.isInteger(parsed) || parsed < 1 || parsed > 65535) {
+      setPort(String(settings.port));
+      toast.error("Port must be between 1 and 65535");
+      return;
+    }
+    if (parsed !== settings.port) {
+      save({ ...settings, port: parsed });
+    }
+  };
+
+  const regenerateToken = async () => {
+    try {
+      const updated = await invoke<ApiServerSettingsType>("api_server_regenerate_token");
+      setSettings(updated);
+      toast.success("New API token generated");
+    } catch (error) {
+      console.error("Failed to regenerate API token:", error);
+      toast.error(String(error));
+    }
+  };
+
+  const copyToken = async () => {
+    if (!settings) return;
+    await navigator.clipboard.writeText(settings.token);
+    toast.success("API token copied");
+  };
+
+  if (!settings) {
+    return null;
+  }
+
+  return (
+    <div className="bg-white rounded-lg border border-gray-200 p-6 shadow-sm">
+      <div className="flex items-center justify-between">
+        <div>
+          <h3 className="text-lg font-semibold text-gray-900 mb-2">Local API</h3>
+          <p className="text-sm text-gray-600">
+            Serve meetings, transcripts, summaries and live transcripts to other apps on this computer
+          </p>
+        </div>
+        <Switch
+          checked={settings.enabled}
+          disabled={saving}
+          onCheckedChange={(enabled) => save({ ...settings, enabled })}
+        />
+      </div>
+
+      <div className="mt-4 space-y-4">
+        <div className="flex items-center gap-3">
+          <label htmlFor="api-server-port" className="text-sm font-medium text-gray-700 w-16">Port</label>
+          <input
+            id="api-server-port"
+            type="number"
+            min={1}
+            max={65535}
+            value={port}
+            disabled={saving}
+            onChange={(e) => setPort(e.target.value)}
+            onBlur={handlePortBlur}
+            className="w-28 px-3 py-1.5 text-sm border border-gray-300 rounded-md"
+          />
+          <span className="text-xs text-gray-500">
+            {status?.running && status.url ? `Listening on ${status.url}` : "Not running"}
+          </span>
+        </div>
+
+        <div className="flex items-center gap-3">
+          <span className="text-sm font-medium text-gray-700 w-16">Token</span>
+          <code className="flex-1 px-3 py-1.5 text-xs bg-gray-50 border rounded-md truncate font-mono">
+            {settings.token}
+          </code>
+          <button
+            onClick={copyToken}
+            className="flex items-center gap-1 px-3 py-1.5 text-sm border border-gray-300 rounded-md hover:bg-gray-100 transition-colors"
+          >
+            <Copy className="w-4 h-4" />
+            Copy
+          </button>
+          <button
+            onClick={regenerateToken}
+            className="flex items-center gap-1 px-3 py-1.5 text-sm border border-gray-300 rounded-md hover:bg-gray-100 transition-colors"
+          >
+            <RefreshCw className="w-4 h-4" />
+            Regenerate
+          </button>
+        </div>
+      </div>
+
+      <div className="mt-4 p-3 bg-blue-50 rounded-md">
+        <p className="text-xs text-blue-800">
+          <strong>Note:</strong> The server only accepts connections from this computer. Send the token as{" "}
+          <code>Authorization: Bearer &lt;token&gt;</code> or a <code>?token=</code> query parameter.
+        </p>
+      </div>
+    </div>
+  )
+}
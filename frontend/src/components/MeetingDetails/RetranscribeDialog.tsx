@@ -349,7 +349,7 @@ export function RetranscribeDialog({
                 <SelectContent>
                   {availableModels.map((model) => (
                     <SelectItem key={`${model.provider}:${model.name}`} value={`${model.provider}:${model.name}`}>
-                      {model.displayName} ({Math.round(model.size_mb)} MB)
+                      {model.displayName}{model.size_mb > 0 && ` (${Math.round(model.size_mb)} MB)`}
                     </SelectItem>
                   ))}
                 </SelectContent>
@@ -6,12 +6,39 @@ import { DeviceSelection, SelectedDevices } from '@/components/DeviceSelection';
 import Analytics from '@/lib/analytics';
 import { toast } from 'sonner';
 
+export interface DenoiseSettings {
+  enabled: boolean;
+  strength: number;
+}
+
+export interface NoiseSuppressionSettings {
+  microphone: DenoiseSettings;
+  system: DenoiseSettings;
+  imports: DenoiseSettings;
+}
+
+type NoiseSource = keyof NoiseSuppressionSettings;
+
+const DEFAULT_NOISE_SUPPRESSION: NoiseSuppressionSettings = {
+  microphone: { enabled: false, strength: 1.0 },
+  system: { enabled: false, strength: 1.0 },
+  imports: { enabled: false, strength: 1.0 },
+};
+
+const NOISE_SOURCES: { key: NoiseSource; label: string; description: string }[] = [
+  { key: 'microphone', label: 'Microphone', description: 'Keyboard, fan and room noise around you' },
+  { key: 'system', label: 'System Audio', description: 'Call apps usually denoise this already' },
+  { key: 'imports', label: 'Imported Files', description: 'Audio files imported as new meetings' },
+];
+
 export interface RecordingPreferences {
   save_folder: string;
   auto_save: boolean;
   file_format: string;
   preferred_mic_device: string | null;
   preferred_system_device: string | null;
+  echo_cancellation?: boolean;
+  noise_suppression?: NoiseSuppressionSettings;
 }
 
 interface RecordingSettingsProps {
@@ -79,6 +106,33 @@ export function RecordingSettings({ onSave }: RecordingSettingsProps) {
     });
   };
 
+  const handleEchoCancellationToggle = async (enabled: boolean) => {
+    const newPreferences = { ...preferences, echo_cancellation: enabled };
+    setPreferences(newPreferences);
+    await savePreferences(newPreferences);
+
+    await Analytics.track('echo_cancellation_toggled', {
+      enabled: enabled.toString()
+    });
+  };
+
+  const noiseSuppression = preferences.noise_suppression ?? DEFAULT_NOISE_SUPPRESSION;
+
+  const handleNoiseSuppressionChange = async (source: NoiseSource, settings: DenoiseSettings) => {
+    const newPreferences = {
+      ...preferences,
+      noise_suppression: { ...noiseSuppression, [source]: settings }
+    };
+    setPreferences(newPreferences);
+    await savePreferences(newPreferences);
+
+    await Analytics.track('noise_suppression_changed', {
+      source,
+      enabled: settings.enabled.toString(),
+      strength: settings.strength.toString()
+    });
+  };
+
   const handleDeviceChange = async (devices: SelectedDevices) => {
     const newPreferences = {
       ...preferences,
@@ -227,6 +281,66 @@ export function RecordingSettings({ onSave }: RecordingSettingsProps) {
         />
       </div>
 
+      {/* Echo Cancellation Toggle */}
+      <div className="flex items-center justify-between p-4 border rounded-lg">
+        <div className="flex-1">
+          <div className="font-medium">Echo Cancellation</div>
+          <div className="text-sm text-gray-600">
+            Remove other participants' voices picked up by your microphone from your speakers, so they aren't transcribed twice
+          </div>
+        </div>
+        <Switch
+          checked={preferences.echo_cancellation ?? false}
+          onCheckedChange={handleEchoCancellationToggle}
+          disabled={saving}
+        />
+      </div>
+
+      {/* Noise Suppression */}
+      <div className="p-4 border rounded-lg space-y-4">
+        <div>
+          <div className="font-medium">Noise Suppression</div>
+          <div className="text-sm text-gray-600">
+            Reduce background noise before transcription, so it isn't mistaken for speech
+          </div>
+        </div>
+        {NOISE_SOURCES.map(({ key, label, description }) => {
+          const settings = noiseSuppression[key];
+          return (
+            <div key={key} className="space-y-2">
+              <div className="flex items-center justify-between">
+                <div className="flex-1">
+                  <div className="text-sm font-medium">{label}</div>
+                  <div className="text-xs text-gray-500">{description}</div>
+                </div>
+                <Switch
+                  checked={settings.enabled}
+                  onCheckedChange={(enabled) => handleNoiseSuppressionChange(key, { ...settings, enabled })}
+                  disabled={saving}
+                />
+              </div>
+              {settings.enabled && (
+                <div className="flex items-center gap-3">
+                  <span className="text-xs text-gray-500 w-16">Strength</span>
+                  <input
+                    type="range"
+                    min={0}
+                    max={100}
+                    step={5}
+                    defaultValue={Math.round(settings.strength * 100)}
+                    onPointerUp={(e) => handleNoiseSuppressionChange(key, { ...settings, strength: Number(e.currentTarget.value) / 100 })}
+                    onKeyUp={(e) => handleNoiseSuppressionChange(key, { ...settings, strength: Number(e.currentTarget.value) / 100 })}
+                    disabled={saving}
+                    className="flex-1"
+                  />
+                  <span className="text-xs text-gray-500 w-10 text-right">{Math.round(settings.strength * 100)}%</span>
+                </div>
+              )}
+            </div>
+          );
+        })}
+      </div>
+
       {/* Device Preferences */}
       <div className="space-y-4">
         <div className="border-t pt-6">
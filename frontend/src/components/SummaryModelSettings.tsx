@@ -20,7 +20,7 @@ export function SummaryModelSettings({ refetchTrigger }: SummaryModelSettingsPro
     ollamaEndpoint: null
   });
 
-  const { isAutoSummary, toggleIsAutoSummary } = useConfig();
+  const { isAutoSummary, toggleIsAutoSummary, isStructuredSummary, toggleIsStructuredSummary } = useConfig();
 
   // Reusable fetch function
   const fetchModelConfig = useCallback(async () => {
@@ -133,6 +133,16 @@ export function SummaryModelSettings({ refetchTrigger }: SummaryModelSettingsPro
         </div>
       </div>
 
+      <div className="bg-white rounded-lg border border-gray-200 p-6 shadow-sm">
+        <div className="flex items-center justify-between">
+          <div>
+            <h3 className="text-lg font-semibold text-gray-900 mb-2">Structured Summary</h3>
+            <p className="text-sm text-gray-600">Generate each template section as structured data, so every section is filled in the template's format</p>
+          </div>
+          <Switch checked={isStructuredSummary} onCheckedChange={toggleIsStructuredSummary} />
+        </div>
+      </div>
+
       <div className="bg-white rounded-lg border border-gray-200 p-6 shadow-sm">
         <h3 className="text-lg font-semibold mb-4">Summary Model Configuration</h3>
         <p className="text-sm text-gray-600 mb-6">
@@ -53,12 +53,34 @@ export function TranscriptSettings({ transcriptModelConfig, setTranscriptModelCo
     const modelOptions = {
         localWhisper: [], // Model selection handled by ModelManager component
         parakeet: [], // Model selection handled by ParakeetModelManager component
-        deepgram: ['nova-2-phonecall'],
-        elevenLabs: ['eleven_multilingual_v2'],
-        groq: ['llama-3.3-70b-versatile'],
-        openai: ['gpt-4o'],
+        deepgram: ['nova-3', 'nova-2', 'nova-2-phonecall'],
+        elevenLabs: ['scribe_v1'],
+        groq: ['whisper-large-v3-turbo', 'whisper-large-v3'],
+        openai: ['whisper-1', 'gpt-4o-transcribe', 'gpt-4o-mini-transcribe'],
+    };
+    const requiresApiKey = uiProvider === 'deepgram' || uiProvider === 'elevenLabs' || uiProvider === 'openai' || uiProvider === 'groq';
+
+    // Cloud providers have no model manager to save the selection, so save it here
+    const saveCloudConfig = async (config: TranscriptModelProps) => {
+        try {
+            await invoke('api_save_transcript_config', {
+                provider: config.provider,
+                model: config.model,
+                apiKey: config.apiKey ?? null,
+            });
+            setTranscriptModelConfig(config);
+        } catch (err) {
+            console.error('Failed to save transcript config:', err);
+        }
+    };
+
+    const handleApiKeyBlur = () => {
+        if (!requiresApiKey || !apiKey) return;
+        const model = transcriptModelConfig.provider === uiProvider
+            ? transcriptModelConfig.model
+            : modelOptions[uiProvider][0];
+        saveCloudConfig({ provider: uiProvider, model, apiKey });
     };
-    const requiresApiKey = transcriptModelConfig.provider === 'deepgram' || transcriptModelConfig.provider === 'elevenLabs' || transcriptModelConfig.provider === 'openai' || transcriptModelConfig.provider === 'groq';
 
     const handleInputClick = () => {
         if (isApiKeyLocked) {
@@ -123,10 +145,10 @@ export function TranscriptSettings({ transcriptModelConfig, setTranscriptModelCo
                                 <SelectContent>
                                     <SelectItem value="parakeet">⚡ Parakeet (Recommended - Real-time / Accurate)</SelectItem>
                                     <SelectItem value="localWhisper">🏠 Local Whisper (High Accuracy)</SelectItem>
-                                    {/* <SelectItem value="deepgram">☁️ Deepgram (Backup)</SelectItem>
+                                    <SelectItem value="deepgram">☁️ Deepgram</SelectItem>
                                     <SelectItem value="elevenLabs">☁️ ElevenLabs</SelectItem>
-                                    <SelectItem value="groq">☁️ Groq</SelectItem>
-                                    <SelectItem value="openai">☁️ OpenAI</SelectItem> */}
+                                    <SelectItem value="groq">☁️ Groq Whisper</SelectItem>
+                                    <SelectItem value="openai">☁️ OpenAI</SelectItem>
                                 </SelectContent>
                             </Select>
 
@@ -135,7 +157,7 @@ export function TranscriptSettings({ transcriptModelConfig, setTranscriptModelCo
                                     value={transcriptModelConfig.model}
                                     onValueChange={(value) => {
                                         const model = value as TranscriptModelProps['model'];
-                                        setTranscriptModelConfig({ ...transcriptModelConfig, provider: uiProvider, model });
+                                        saveCloudConfig({ ...transcriptModelConfig, provider: uiProvider, model, apiKey });
                                     }}
                                 >
                                     <SelectTrigger className='focus:ring-1 focus:ring-blue-500 focus:border-blue-500'>
@@ -185,6 +207,7 @@ export function TranscriptSettings({ transcriptModelConfig, setTranscriptModelCo
                                         }`}
                                     value={apiKey || ''}
                                     onChange={(e) => setApiKey(e.target.value)}
+                                    onBlur={handleApiKeyBlur}
                                     disabled={isApiKeyLocked}
                                     onClick={handleInputClick}
                                     placeholder="Enter your API key"
@@ -37,6 +37,7 @@ export interface NotificationSettings {
     show_recording_resumed: boolean;
     show_transcription_complete: boolean;
     show_meeting_reminders: boolean;
+    show_meeting_detected: boolean;
     show_system_errors: boolean;
     meeting_reminder_minutes: number[];
   };
@@ -75,6 +76,8 @@ interface ConfigContextType {
   // Summary configuration
   isAutoSummary: boolean;
   toggleIsAutoSummary: (checked: boolean) => void;
+  isStructuredSummary: boolean;
+  toggleIsStructuredSummary: (checked: boolean) => void;
 
   // Provider-specific API keys
   providerApiKeys: {
@@ -82,6 +85,7 @@ interface ConfigContextType {
     groq: string | null;
     openai: string | null;
     openrouter: string | null;
+    gemini: string | null;
   };
   updateProviderApiKey: (provider: string, apiKey: string | null) => void;
 
@@ -113,17 +117,18 @@ export function ConfigProvider({ children }: { children: ReactNode }) {
   });
 
   // Provider-specific API keys (loaded once at startup)
-  // Note: Gemini omitted for now - add when UI support is added
   const [providerApiKeys, setProviderApiKeys] = useState<{
     claude: string | null;
     groq: string | null;
     openai: string | null;
     openrouter: string | null;
+    gemini: string | null;
   }>({
     claude: null,
     groq: null,
     openai: null,
     openrouter: null,
+    gemini: null,
   });
 
   // Ollama models list and error state
@@ -163,6 +168,15 @@ export function ConfigProvider({ children }: { children: ReactNode }) {
     return false;
   });
 
+  // Generate summaries as template-shaped JSON and render the markdown from it
+  const [isStructuredSummary, setIsStructuredSummary] = useState<boolean>(() => {
+    if (typeof window !== 'undefined') {
+      const saved = localStorage.getItem('isStructuredSummary');
+      return saved !== null ? saved === 'true' : false
+    }
+    return false;
+  });
+
   // Beta features state (localStorage)
   const [betaFeatures, setBetaFeatures] = useState<BetaFeatures>(() => {
     return loadBetaFeatures();
@@ -295,7 +309,7 @@ export function ConfigProvider({ children }: { children: ReactNode }) {
   useEffect(() => {
     const loadAllApiKeys = async () => {
       try {
-        const providers = ['claude', 'groq', 'openai', 'openrouter'];
+        const providers = ['claude', 'groq', 'openai', 'openrouter', 'gemini'];
         const keys = await Promise.all(
           providers.map(p =>
             invoke<string>('api_get_api_key', { provider: p })
@@ -308,6 +322,7 @@ export function ConfigProvider({ children }: { children: ReactNode }) {
           groq: keys[1],
           openai: keys[2],
           openrouter: keys[3],
+          gemini: keys[4],
         });
         console.log('[ConfigContext] Loaded provider API keys');
       } catch (error) {
@@ -370,6 +385,7 @@ export function ConfigProvider({ children }: { children: ReactNode }) {
     openai: ['gpt-4', 'gpt-4-turbo', 'gpt-3.5-turbo'],
     'builtin-ai': [],
     'custom-openai': [],
+    gemini: ['gemini-2.5-flash', 'gemini-2.5-pro'],
   };
 
   // Toggle confidence indicator with localStorage persistence
@@ -389,6 +405,13 @@ export function ConfigProvider({ children }: { children: ReactNode }) {
     }
   }, [])
 
+  const toggleIsStructuredSummary = useCallback((checked: boolean) => {
+    setIsStructuredSummary(checked);
+    if (typeof window !== 'undefined') {
+      localStorage.setItem('isStructuredSummary', checked.toString());
+    }
+  }, [])
+
   // Toggle beta feature with localStorage persistence and analytics
   const toggleBetaFeature = useCallback((featureKey: BetaFeatureKey, enabled: boolean) => {
     setBetaFeatures(prev => {
@@ -487,6 +510,8 @@ export function ConfigProvider({ children }: { children: ReactNode }) {
     setModelConfig,
     isAutoSummary,
     toggleIsAutoSummary,
+    isStructuredSummary,
+    toggleIsStructuredSummary,
     providerApiKeys,
     updateProviderApiKey,
     transcriptModelConfig,
@@ -511,6 +536,8 @@ export function ConfigProvider({ children }: { children: ReactNode }) {
     modelConfig,
     isAutoSummary,
     toggleIsAutoSummary,
+    isStructuredSummary,
+    toggleIsStructuredSummary,
     providerApiKeys,
     updateProviderApiKey,
     transcriptModelConfig,
@@ -7,6 +7,7 @@ import { toast } from 'sonner';
 import Analytics from '@/lib/analytics';
 import { isOllamaNotInstalledError } from '@/lib/utils';
 import { BuiltInModelInfo } from '@/lib/builtin-ai';
+import { useConfig } from '@/contexts/ConfigContext';
 
 type SummaryStatus = 'idle' | 'processing' | 'summarizing' | 'regenerating' | 'completed' | 'error';
 
@@ -38,6 +39,7 @@ export function useSummaryGeneration({
   const [originalTranscript, setOriginalTranscript] = useState<string>('');
 
   const { startSummaryPolling, stopSummaryPolling } = useSidebar();
+  const { isStructuredSummary } = useConfig();
 
   // Helper to get status message
   const getSummaryStatusMessage = useCallback((status: SummaryStatus) => {
@@ -113,6 +115,7 @@ export function useSummaryGeneration({
         overlap: 1000,
         customPrompt: customPrompt,
         templateId: selectedTemplate,
+        structuredOutput: isStructuredSummary,
       }) as any;
 
       const process_id = result.process_id;
@@ -348,6 +351,7 @@ export function useSummaryGeneration({
     meeting.created_at,
     modelConfig,
     selectedTemplate,
+    isStructuredSummary,
     startSummaryPolling,
     setAiSummary,
     updateMeetingTitle,
@@ -171,6 +171,7 @@ export function useTranscriptRecovery(): UseTranscriptRecoveryReturn {
         audio_start_time: (t as any).audio_start_time,
         audio_end_time: (t as any).audio_end_time,
         duration: (t as any).duration,
+        words: (t as any).words,
       }));
 
       // 6. Save to backend database using existing save utilities
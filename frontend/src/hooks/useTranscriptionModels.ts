@@ -7,8 +7,17 @@ export interface RawModelInfo {
   status: 'Available' | 'Missing' | { Downloading: { progress: number } } | { Error: string };
 }
 
+export type CloudTranscriptProvider = 'deepgram' | 'elevenLabs' | 'groq' | 'openai';
+
+const CLOUD_PROVIDER_NAMES: Record<CloudTranscriptProvider, string> = {
+  deepgram: 'Deepgram',
+  elevenLabs: 'ElevenLabs',
+  groq: 'Groq',
+  openai: 'OpenAI',
+};
+
 export interface ModelOption {
-  provider: 'whisper' | 'parakeet';
+  provider: 'whisper' | 'parakeet' | CloudTranscriptProvider;
   name: string;
   displayName: string;
   size_mb: number;
@@ -20,7 +29,8 @@ interface TranscriptModelConfig {
 }
 
 /**
- * Custom hook for fetching and managing transcription models (Whisper and Parakeet).
+ * Custom hook for fetching and managing transcription models (Whisper, Parakeet and
+ * the configured cloud provider, if any).
  *
  * This hook centralizes the model fetching logic that was previously duplicated
  * in ImportAudioDialog and RetranscribeDialog components.
@@ -77,18 +87,29 @@ export function useTranscriptionModels(transcriptModelConfig: TranscriptModelCon
       console.error('Failed to fetch Parakeet models:', err);
     }
 
-    setAvailableModels(allModels);
-
     // Set default model based on user's saved configuration
     const configuredProvider = transcriptModelConfig?.provider || '';
     const configuredModel = transcriptModelConfig?.model || '';
 
+    // Offer the configured cloud provider; its API key is read from settings by the backend
+    if (configuredProvider in CLOUD_PROVIDER_NAMES) {
+      const provider = configuredProvider as CloudTranscriptProvider;
+      allModels.push({
+        provider,
+        name: configuredModel,
+        displayName: `☁️ ${CLOUD_PROVIDER_NAMES[provider]}: ${configuredModel}`,
+        size_mb: 0,
+      });
+    }
+
+    setAvailableModels(allModels);
+
     // Try to match the configured model
     // Note: 'localWhisper' in config maps to 'whisper' provider in model list
     const configuredMatch = allModels.find(
       (m) =>
         (configuredProvider === 'localWhisper' && m.provider === 'whisper' && m.name === configuredModel) ||
-        (configuredProvider === 'parakeet' && m.provider === 'parakeet' && m.name === configuredModel)
+        (m.provider === configuredProvider && m.name === configuredModel)
     );
 
     // Only set default model if user hasn't manually selected one
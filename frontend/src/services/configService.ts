@@ -9,7 +9,7 @@ import { invoke } from '@tauri-apps/api/core';
 import { TranscriptModelProps } from '@/components/TranscriptSettings';
 
 export interface ModelConfig {
-  provider: 'ollama' | 'groq' | 'claude' | 'openrouter' | 'openai' | 'builtin-ai' | 'custom-openai';
+  provider: 'ollama' | 'groq' | 'claude' | 'openrouter' | 'openai' | 'builtin-ai' | 'custom-openai' | 'gemini';
   model: string;
   whisperModel: string;
   /**
@@ -36,6 +36,11 @@ export interface CustomOpenAIConfig {
   topP: number | null;
 }
 
+export interface FallbackModel {
+  provider: string;
+  model: string;
+}
+
 export interface RecordingPreferences {
   preferred_mic_device: string | null;
   preferred_system_device: string | null;
@@ -94,6 +99,23 @@ export class ConfigService {
     });
   }
 
+  /**
+   * Get the providers tried, in order, when the summary provider fails
+   * @returns Promise with the fallback chain (empty if none configured)
+   */
+  async getLlmFallbackChain(): Promise<FallbackModel[]> {
+    return invoke<FallbackModel[]>('api_get_llm_fallback_chain');
+  }
+
+  /**
+   * Save the LLM fallback chain
+   * @param chain - Fallback providers in the order they should be tried
+   * @returns Promise with result status
+   */
+  async saveLlmFallbackChain(chain: FallbackModel[]): Promise<{ status: string; message: string }> {
+    return invoke<{ status: string; message: string }>('api_save_llm_fallback_chain', { chain });
+  }
+
   /**
    * Test custom OpenAI connection
    * @param endpoint - API endpoint URL
@@ -4,6 +4,13 @@ export interface Message {
   timestamp: string;
 }
 
+export interface WordTiming {
+  text: string;
+  start: number; // Seconds from recording start
+  end: number;   // Seconds from recording start
+  confidence?: number;
+}
+
 export interface Transcript {
   id: string;
   text: string;
@@ -16,6 +23,7 @@ export interface Transcript {
   audio_start_time?: number; // Seconds from recording start (e.g., 125.3)
   audio_end_time?: number;   // Seconds from recording start (e.g., 128.6)
   duration?: number;          // Segment duration in seconds (e.g., 3.3)
+  words?: WordTiming[];       // Word-level timings, when the engine provides them
 }
 
 export interface TranscriptUpdate {
@@ -30,6 +38,7 @@ export interface TranscriptUpdate {
   audio_start_time: number; // Seconds from recording start
   audio_end_time: number;   // Seconds from recording start
   duration: number;          // Segment duration in seconds
+  words?: WordTiming[];      // Word-level timings, when the engine provides them
 }
 
 export interface Block {
@@ -76,9 +85,28 @@ export interface BlockNoteBlock {
   children?: BlockNoteBlock[];
 }
 
+// A "[mm:ss]" citation in the summary markdown, resolved to its transcript segment
+export interface SummaryCitation {
+  marker: string;
+  segment_id: string;
+  audio_start_time: number; // seconds from recording start
+}
+
+// Summary generated as the template's sections (structured mode)
+export interface StructuredSummary {
+  title: string;
+  sections: {
+    title: string;
+    format: 'paragraph' | 'list' | 'string';
+    content: string | string[];
+  }[];
+}
+
 export interface SummaryDataResponse {
   markdown?: string;
   summary_json?: BlockNoteBlock[];
+  citations?: SummaryCitation[];
+  structured?: StructuredSummary;
   // Legacy format fields
   MeetingName?: string;
   _section_order?: string[];
@@ -108,3 +136,111 @@ export interface TranscriptSegmentData {
   text: string;
   confidence?: number;
 }
+
+// Meeting export (export_meeting command)
+export type ExportFormat = 'srt' | 'vtt' | 'docx' | 'pdf' | 'markdown';
+
+export interface ExportOptions {
+  include_speakers?: boolean;
+  include_timestamps?: boolean;
+  include_confidence?: boolean;
+  include_summary?: boolean;
+  include_notes?: boolean;
+  bundle_audio?: boolean;   // Markdown only: folder with the document and a copy of the recording
+  output_path?: string;     // Defaults to the meeting folder
+}
+
+export interface ExportResult {
+  path: string;
+  format: ExportFormat;
+  bytes: number;
+  warnings: string[];
+}
+
+// Local REST/WebSocket API server (api_server_* commands)
+export interface ApiServerSettings {
+  enabled: boolean;
+  port: number;
+  token: string;
+}
+
+export interface ApiServerStatus {
+  running: boolean;
+  url?: string | null;  // e.g. http://127.0.0.1:5170
+}
+
+// Extracted action items and decisions (api_*_action_item(s) commands)
+export type ActionItemKind = 'action_item' | 'decision';
+export type ActionItemStatus = 'open' | 'completed' | 'cancelled';
+
+export interface ActionItem {
+  id: string;
+  meeting_id: string;
+  meeting_title: string;
+  kind: ActionItemKind;
+  text: string;
+  owner?: string | null;
+  due_date?: string | null;           // YYYY-MM-DD
+  source_segment_id?: string | null;  // Transcript segment the item came from
+  status: ActionItemStatus;
+  created_at: string;
+  updated_at: string;
+  completed_at?: string | null;
+}
+
+export interface ActionItemFilter {
+  meeting_id?: string;
+  status?: ActionItemStatus;
+  kind?: ActionItemKind;
+  owner?: string;
+  due_after?: string;   // Inclusive YYYY-MM-DD bounds
+  due_before?: string;
+  limit?: number;
+  offset?: number;
+}
+
+// Meeting Q&A (api_ask_meetings and api_*_qa_* commands)
+export interface QaSettings {
+  embedding_provider: 'builtin-ai' | 'ollama';
+  embedding_model: string;        // e.g. gemma3:1b or nomic-embed-text
+  ollama_endpoint?: string | null; // Defaults to the summary Ollama endpoint
+  top_k: number;
+}
+
+export interface QaMessage {
+  role: 'user' | 'assistant';
+  content: string;
+}
+
+export interface QaCitation {
+  index: number;                  // [n] as referenced in the answer
+  meeting_id: string;
+  meeting_title: string;
+  meeting_date: string;           // YYYY-MM-DD
+  source: 'transcript' | 'summary';
+  transcript_id?: string | null;
+  audio_start_time?: number | null;
+  timestamp?: string | null;      // MM:SS or H:MM:SS
+  text: string;
+  score: number;
+  cited: boolean;
+}
+
+export interface QaAnswer {
+  answer: string;
+  citations: QaCitation[];
+}
+
+export interface QaIndexProgress {
+  meeting_id: string;
+  indexed: number;
+  total: number;
+}
+
+// Payload of the `summary-delta` event, emitted while the LLM streams a summary.
+// Only `final` deltas are the summary text; the stored summary replaces them when done.
+export type SummaryDelta = { meeting_id: string; delta: string } & (
+  | { stage: 'chunk'; index: number; total: number }
+  | { stage: 'combine' }
+  | { stage: 'final' }
+);
@@ -0,0 +1,437 @@
+// ============================================================================
+// JSON Schema to GBNF conversion
+// ============================================================================
+//
+// Covers the subset of JSON Schema used for structured output: objects with
+// `properties`/`required`, arrays with `items`, strings, numbers, integers,
+// booleans, null, `enum`, `const` and `anyOf`/`oneOf`. Object properties are
+// generated in the order of `required`; properties that aren't required are
+// left out. A schema without a `type` accepts any JSON value.
+
+use std::collections::HashSet;
+
+use anyhow::{anyhow, bail, Result};
+use serde_json::{Map, Value};
+
+/// Rules for JSON values, shared by every generated grammar. Whitespace after
+/// a newline is bounded so a model can't pad the output forever.
+const JSON_RULES: &str = r#"ws ::= | " " | "\n" [ \t]{0,20}
+string ::= "\"" ( [^"\\\x7F\x00-\x1F] | "\\" (["\\/bfnrt] | "u" [0-9a-fA-F]{4}) )* "\"" ws
+number ::= "-"? ([0-9] | [1-9] [0-9]{0,15}) ("." [0-9]+)? ([eE] [-+]? [0-9]{1,3})? ws
+integer ::= "-"? ([0-9] | [1-9] [0-9]{0,15}) ws
+boolean ::= ("true" | "false") ws
+null ::= "null" ws
+value ::= object | array | string | number | boolean | null
+object ::= "{" ws ( string ":" ws value ("," ws string ":" ws value)* )? "}" ws
+array ::= "[" ws ( value ("," ws value)* )? "]" ws
+"#;
+
+const JSON_RULE_NAMES: &[&str] = &[
+    "ws", "string", "number", "integer", "boolean", "null", "value", "object", "array",
+];
+
+/// Converts a JSON schema to a GBNF grammar whose `root` rule matches it
+pub fn json_schema_to_grammar(schema: &Value) -> Result<String> {
+    let mut converter = Converter::default();
+    let root = converter.visit(schema, "root")?;
+    if root != "root" {
+        converter.add_rule("root", root);
+    }
+
+    // Root first, for readability in logs
+    converter.rules.sort_by_key(|(name, _)| name != "root");
+    let mut grammar = String::new();
+    for (name, body) in &converter.rules {
+        grammar.push_str(&format!("{} ::= {}\n", name, body));
+    }
+    grammar.push_str(JSON_RULES);
+    Ok(grammar)
+}
+
+/// GBNF string literal of `text`
+fn literal(text: &str) -> String {
+    let mut out = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => out.push_str(&format!("\\x{:02X}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+/// GBNF literal of a JSON value as it must appear in the output
+fn json_literal(value: &Value) -> String {
+    literal(&value.to_string())
+}
+
+#[derive(Default)]
+struct Converter {
+    rules: Vec<(String, String)>,
+    names: HashSet<String>,
+}
+
+impl Converter {
+    /// Adds a rule under a unique name derived from `name` and returns that name
+    fn add_rule(&mut self, name: &str, body: String) -> String {
+        let base: String = name
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+            .collect();
+        let mut unique = base.clone();
+        let mut suffix = 1;
+        while self.names.contains(&unique) || JSON_RULE_NAMES.contains(&unique.as_str()) {
+            suffix += 1;
+            unique = format!("{}-{}", base, suffix);
+        }
+        self.names.insert(unique.clone());
+        self.rules.push((unique.clone(), body));
+        unique
+    }
+
+    /// Returns a grammar expression (usually a rule name) matching `schema`
+    fn visit(&mut self, schema: &Value, name: &str) -> Result<String> {
+        let schema = match schema {
+            Value::Bool(true) => return Ok("value".to_string()),
+            Value::Object(schema) => schema,
+            _ => bail!("Schema of '{}' must be an object", name),
+        };
+        if schema.contains_key("$ref") {
+            bail!("$ref is not supported (in '{}')", name);
+        }
+
+        if let Some(value) = schema.get("const") {
+            return Ok(self.add_rule(name, format!("{} ws", json_literal(value))));
+        }
+        if let Some(values) = schema.get("enum") {
+            let values = values
+                .as_array()
+                .filter(|values| !values.is_empty())
+                .ok_or_else(|| anyhow!("enum of '{}' must be a non-empty array", name))?;
+            let alternatives: Vec<String> = values.iter().map(json_literal).collect();
+            return Ok(self.add_rule(name, format!("({}) ws", alternatives.join(" | "))));
+        }
+        for key in ["anyOf", "oneOf"] {
+            if let Some(options) = schema.get(key).and_then(Value::as_array) {
+                let alternatives = options
+                    .iter()
+                    .enumerate()
+                    .map(|(i, option)| self.visit(option, &format!("{}-{}", name, i)))
+                    .collect::<Result<Vec<_>>>()?;
+                return Ok(self.add_rule(name, alternatives.join(" | ")));
+            }
+        }
+
+        match schema.get("type") {
+            Some(Value::String(kind)) => self.visit_type(kind, schema, name),
+            Some(Value::Array(kinds)) => {
+                let alternatives = kinds
+                    .iter()
+                    .map(|kind| {
+                        let kind = kind
+                            .as_str()
+                            .ok_or_else(|| anyhow!("type of '{}' must be a string", name))?;
+                        self.visit_type(kind, schema, &format!("{}-{}", name, kind))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(self.add_rule(name, alternatives.join(" | ")))
+            }
+            Some(_) => bail!("type of '{}' must be a string or an array", name),
+            None if schema.contains_key("properties") => self.visit_type("object", schema, name),
+            None => Ok("value".to_string()),
+        }
+    }
+
+    fn visit_type(
+        &mut self,
+        kind: &str,
+        schema: &Map<String, Value>,
+        name: &str,
+    ) -> Result<String> {
+        match kind {
+            "object" => self.visit_object(schema, name),
+            "array" => self.visit_array(schema, name),
+            "string" | "number" | "integer" | "boolean" | "null" => Ok(kind.to_string()),
+            other => bail!("Unsupported type '{}' (in '{}')", other, name),
+        }
+    }
+
+    fn visit_object(&mut self, schema: &Map<String, Value>, name: &str) -> Result<String> {
+        let properties = schema.get("properties").and_then(Value::as_object);
+        let required: Vec<&str> = schema
+            .get("required")
+            .and_then(Value::as_array)
+            .map(|keys| keys.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default();
+        let Some(properties) = properties.filter(|_| !required.is_empty()) else {
+            return Ok("object".to_string());
+        };
+
+        let mut members = Vec::with_capacity(required.len());
+        for key in required {
+            let property = properties.get(key).ok_or_else(|| {
+                anyhow!("Required property '{}' of '{}' is not defined", key, name)
+            })?;
+            let value = self.visit(property, &format!("{}-{}", name, key))?;
+            members.push(format!(
+                "{} ws \":\" ws {}",
+                literal(&Value::String(key.to_string()).to_string()),
+                value
+            ));
+        }
+        Ok(self.add_rule(
+            name,
+            format!("\"{{\" ws {} \"}}\" ws", members.join(" \",\" ws ")),
+        ))
+    }
+
+    fn visit_array(&mut self, schema: &Map<String, Value>, name: &str) -> Result<String> {
+        let item = match schema.get("items") {
+            Some(items) => self.visit(items, &format!("{}-item", name))?,
+            None => "value".to_string(),
+        };
+        let min_items = schema.get("minItems").and_then(Value::as_u64).unwrap_or(0);
+        let body = if min_items > 0 {
+            format!("\"[\" ws {0} (\",\" ws {0})* \"]\" ws", item)
+        } else {
+            format!("\"[\" ws ({0} (\",\" ws {0})*)? \"]\" ws", item)
+        };
+        Ok(self.add_rule(name, body))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// Checks what llama.cpp's grammar parser checks: one `name ::= body` rule
+    /// per line, balanced literals, classes and groups, a `root` rule, and no
+    /// reference to an undefined rule
+    fn assert_well_formed(grammar: &str) {
+        let mut defined = HashSet::new();
+        let mut referenced = Vec::new();
+        for line in grammar.lines() {
+            let (name, body) = line
+                .split_once(" ::= ")
+                .unwrap_or_else(|| panic!("not a rule: {}", line));
+            assert!(
+                name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-'),
+                "bad rule name: {}",
+                name
+            );
+            assert!(defined.insert(name), "rule defined twice: {}", name);
+
+            let mut chars = body.chars().peekable();
+            let mut depth = 0i32;
+            while let Some(c) = chars.next() {
+                match c {
+                    '"' | '[' => {
+                        let close = if c == '"' { '"' } else { ']' };
+                        loop {
+                            match chars.next() {
+                                Some('\\') => {
+                                    chars.next();
+                                }
+                                Some(c) if c == close => break,
+                                Some(_) => {}
+                                None => panic!("unterminated {} in: {}", c, line),
+                            }
+                        }
+                    }
+                    '{' => while chars.next().is_some_and(|c| c != '}') {},
+                    '(' => depth += 1,
+                    ')' => depth -= 1,
+                    c if c.is_ascii_alphanumeric() || c == '-' => {
+                        let mut name = c.to_string();
+                        while let Some(&c) = chars
+                            .peek()
+                            .filter(|c| c.is_ascii_alphanumeric() || **c == '-')
+                        {
+                            name.push(c);
+                            chars.next();
+                        }
+                        referenced.push(name);
+                    }
+                    ' ' | '|' | '?' | '*' | '+' => {}
+                    other => panic!("unexpected '{}' in: {}", other, line),
+                }
+                assert!(depth >= 0, "unbalanced ')' in: {}", line);
+            }
+            assert_eq!(depth, 0, "unbalanced '(' in: {}", line);
+        }
+        assert!(defined.contains("root"), "no root rule");
+        for name in referenced {
+            assert!(defined.contains(name.as_str()), "undefined rule: {}", name);
+        }
+    }
+
+    /// The body of rule `name`
+    fn rule<'a>(grammar: &'a str, name: &str) -> &'a str {
+        let prefix = format!("{} ::= ", name);
+        grammar
+            .lines()
+            .find_map(|line| line.strip_prefix(prefix.as_str()))
+            .unwrap_or_else(|| panic!("no rule '{}' in:\n{}", name, grammar))
+    }
+
+    #[test]
+    fn test_template_schema() {
+        // Shape of Template::to_json_schema in the app
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "title": { "type": "string", "description": "Concise, descriptive title" },
+                "sections": {
+                    "type": "object",
+                    "properties": {
+                        "Summary": { "type": "string", "description": "Provide a summary" },
+                        "Action Items": {
+                            "type": "array",
+                            "items": { "type": "string" },
+                            "description": "Items follow the format: owner - task",
+                        },
+                    },
+                    "required": ["Summary", "Action Items"],
+                    "additionalProperties": false,
+                },
+            },
+            "required": ["title", "sections"],
+            "additionalProperties": false,
+        });
+        let grammar = json_schema_to_grammar(&schema).unwrap();
+        assert_well_formed(&grammar);
+
+        assert!(grammar.starts_with("root ::= "));
+        assert_eq!(
+            rule(&grammar, "root"),
+            r#""{" ws "\"title\"" ws ":" ws string "," ws "\"sections\"" ws ":" ws root-sections "}" ws"#
+        );
+        assert_eq!(
+            rule(&grammar, "root-sections"),
+            r#""{" ws "\"Summary\"" ws ":" ws string "," ws "\"Action Items\"" ws ":" ws root-sections-Action-Items "}" ws"#
+        );
+        assert_eq!(
+            rule(&grammar, "root-sections-Action-Items"),
+            r#""[" ws (string ("," ws string)*)? "]" ws"#
+        );
+    }
+
+    #[test]
+    fn test_enum_const_and_any_of() {
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "priority": { "enum": ["high", "low", 3, null] },
+                "kind": { "const": "decision" },
+                "due": { "anyOf": [{ "type": "string" }, { "type": "null" }] },
+                "count": { "type": ["integer", "null"] },
+            },
+            "required": ["priority", "kind", "due", "count"],
+        });
+        let grammar = json_schema_to_grammar(&schema).unwrap();
+        assert_well_formed(&grammar);
+
+        assert_eq!(
+            rule(&grammar, "root-priority"),
+            r#"("\"high\"" | "\"low\"" | "3" | "null") ws"#
+        );
+        assert_eq!(rule(&grammar, "root-kind"), r#""\"decision\"" ws"#);
+        assert_eq!(rule(&grammar, "root-due"), "string | null");
+        assert_eq!(rule(&grammar, "root-count"), "integer | null");
+    }
+
+    #[test]
+    fn test_nested_objects_and_arrays() {
+        let schema = json!({
+            "type": "array",
+            "minItems": 1,
+            "items": {
+                "properties": {
+                    "owner": { "type": "string" },
+                    "tags": { "type": "array" },
+                    "note": { "type": "string" },
+                },
+                "required": ["owner", "tags"],
+            },
+        });
+        let grammar = json_schema_to_grammar(&schema).unwrap();
+        assert_well_formed(&grammar);
+
+        // At least one item; properties that aren't required are left out
+        assert_eq!(
+            rule(&grammar, "root"),
+            r#""[" ws root-item ("," ws root-item)* "]" ws"#
+        );
+        let item = rule(&grammar, "root-item");
+        assert!(item.contains("\"\\\"owner\\\"\" ws \":\" ws string"));
+        assert!(item.contains("root-item-tags"));
+        assert!(!item.contains("note"));
+        assert_eq!(
+            rule(&grammar, "root-item-tags"),
+            r#""[" ws (value ("," ws value)*)? "]" ws"#
+        );
+    }
+
+    #[test]
+    fn test_rule_names_stay_unique() {
+        // Property names that collide with the shared JSON rules or with each
+        // other after sanitizing
+        let schema = json!({
+            "type": "object",
+            "properties": {
+                "a b": { "enum": [1] },
+                "a-b": { "enum": [2] },
+            },
+            "required": ["a b", "a-b"],
+        });
+        let grammar = json_schema_to_grammar(&schema).unwrap();
+        assert_well_formed(&grammar);
+        assert_eq!(rule(&grammar, "root-a-b"), r#"("1") ws"#);
+        assert_eq!(rule(&grammar, "root-a-b-2"), r#"("2") ws"#);
+
+        let grammar = json_schema_to_grammar(&json!({ "const": "x" })).unwrap();
+        assert_well_formed(&grammar);
+    }
+
+    #[test]
+    fn test_unsupported_schemas() {
+        for schema in [
+            json!({ "$ref": "#/definitions/item" }),
+            json!({ "type": "date" }),
+            json!({ "type": 1 }),
+            json!({ "enum": [] }),
+            json!("string"),
+            json!({ "type": "object", "properties": {}, "required": ["missing"] }),
+        ] {
+            assert!(
+                json_schema_to_grammar(&schema).is_err(),
+                "accepted {}",
+                schema
+            );
+        }
+
+        // Keywords without a grammar equivalent are ignored, and anything
+        // untyped falls back to any JSON value
+        let grammar = json_schema_to_grammar(&json!({
+            "type": "string",
+            "pattern": "^[a-z]+$",
+            "maxLength": 10,
+        }))
+        .unwrap();
+        assert_well_formed(&grammar);
+        assert_eq!(rule(&grammar, "root"), "string");
+
+        for schema in [json!({}), json!(true), json!({ "type": "object" })] {
+            let grammar = json_schema_to_grammar(&schema).unwrap();
+            assert_well_formed(&grammar);
+            assert!(matches!(rule(&grammar, "root"), "value" | "object"));
+        }
+    }
+}
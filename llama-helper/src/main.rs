@@ -8,13 +8,17 @@ use std::time::{Instant, SystemTime, UNIX_EPOCH};
 
 use anyhow::{Context, Result};
 use encoding_rs;
-use llama_cpp_2::context::params::LlamaContextParams;
+use llama_cpp_2::context::params::{LlamaContextParams, LlamaPoolingType};
+use llama_cpp_2::context::LlamaContext;
 use llama_cpp_2::llama_backend::LlamaBackend;
 use llama_cpp_2::llama_batch::LlamaBatch;
 use llama_cpp_2::model::params::LlamaModelParams;
-use llama_cpp_2::model::{AddBos, LlamaModel, Special};
+use llama_cpp_2::model::{AddBos, LlamaChatMessage, LlamaModel, Special};
+use llama_cpp_2::token::LlamaToken;
 use serde::{Deserialize, Serialize};
 
+mod grammar;
+
 // ============================================================================
 // Protocol Messages (JSON over stdin/stdout)
 // ============================================================================
@@ -23,29 +27,84 @@ use serde::{Deserialize, Serialize};
 #[serde(tag = "type", rename_all = "snake_case")]
 enum Request {
     Generate {
-        prompt: String,
+        /// Raw prompt, already in the model's chat format
+        prompt: Option<String>,
+        /// Conversation formatted with the model's embedded chat template;
+        /// used instead of `prompt`
+        messages: Option<Vec<ChatMessage>>,
         max_tokens: Option<i32>,
         context_size: Option<u32>,
         model_path: Option<String>,
+        /// Transformer layers of the model, for GPU offloading
+        layer_count: Option<u32>,
         // Sampling parameters
         temperature: Option<f32>,
         top_k: Option<i32>,
         top_p: Option<f32>,
         stop_tokens: Option<Vec<String>>,
+        /// Send a Token message as text is generated, before the final Response
+        stream: Option<bool>,
+        /// GBNF grammar the output must match, starting at its `root` rule
+        grammar: Option<String>,
+        /// JSON schema the output must match, converted to a grammar
+        json_schema: Option<serde_json::Value>,
+    },
+    /// Mean-pooled, L2-normalized embeddings of each text
+    Embed {
+        texts: Vec<String>,
+        model_path: Option<String>,
+    },
+    /// Number of tokens in each text, without BOS/EOS
+    Tokenize {
+        texts: Vec<String>,
+        model_path: Option<String>,
     },
     Ping,
     Shutdown,
 }
 
+#[derive(Debug, Deserialize)]
+struct ChatMessage {
+    /// "system", "user" or "assistant"
+    role: String,
+    content: String,
+}
+
 #[derive(Debug, Serialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 enum Response {
-    Response { text: String, error: Option<String> },
+    Response {
+        text: String,
+        /// Set for successful Generate requests
+        stats: Option<GenerationStats>,
+        error: Option<String>,
+    },
+    /// Incremental text of a streaming Generate request
+    Token { text: String },
+    Embeddings { vectors: Vec<Vec<f32>>, error: Option<String> },
+    TokenCounts { counts: Vec<usize>, error: Option<String> },
     Pong,
     Goodbye,
     Error { message: String },
 }
 
+/// Prompt cache use and speed of a Generate request
+#[derive(Debug, Serialize)]
+struct GenerationStats {
+    prompt_tokens: usize,
+    /// Prompt tokens whose KV cache was kept from the previous request
+    cached_tokens: usize,
+    output_tokens: usize,
+    prompt_ms: u64,
+    generation_ms: u64,
+    /// Prompt tokens evaluated per second, not counting cached ones
+    prompt_tokens_per_sec: f64,
+    tokens_per_sec: f64,
+}
+
+/// Context size for embedding requests; longer texts are truncated
+const EMBEDDING_CONTEXT_SIZE: u32 = 2048;
+
 // ============================================================================
 // VRAM Detection and GPU Layer Calculation
 // ============================================================================
@@ -182,28 +241,64 @@ fn calculate_gpu_layers(
 }
 
 /// Get default GPU layer count with smart detection
-fn get_default_gpu_layers(model_path: &PathBuf, context_size: u32) -> u32 {
+/// `model_layers` comes from the GGUF metadata when the caller knows it
+fn get_default_gpu_layers(
+    model_path: &PathBuf,
+    context_size: u32,
+    model_layers: Option<u32>,
+) -> u32 {
     let vram = detect_vram_gb();
-    // TODO: Use actual model metadata instead of heuristics
-    // Heuristic: Estimate total layers based on file size
-    // 7B models (Q4) are ~4.1GB and have ~32-35 layers
-    // 1B models (Q4) are ~1.1GB and have ~20-28 layers
-    let file_size_gb = std::fs::metadata(model_path)
-        .map(|m| m.len() as f32 / 1024.0 / 1024.0 / 1024.0)
-        .unwrap_or(0.0);
+    let layers = match model_layers.filter(|&layers| layers > 0) {
+        Some(layers) => layers,
+        None => {
+            // Heuristic: Estimate total layers based on file size
+            // 7B models (Q4) are ~4.1GB and have ~32-35 layers
+            // 1B models (Q4) are ~1.1GB and have ~20-28 layers
+            let file_size_gb = std::fs::metadata(model_path)
+                .map(|m| m.len() as f32 / 1024.0 / 1024.0 / 1024.0)
+                .unwrap_or(0.0);
+
+            if file_size_gb > 2.5 {
+                33
+            } else {
+                28
+            }
+        }
+    };
 
-    let estimated_layers = if file_size_gb > 2.5 { 33 } else { 28 };
+    calculate_gpu_layers(model_path, layers, vram, context_size)
+}
 
-    calculate_gpu_layers(model_path, estimated_layers, vram, context_size)
+/// Thread count for inference (conservative default: max(1, (Cores / 2) + 2))
+/// This ensures the UI thread is never starved
+fn thread_count() -> i32 {
+    std::thread::available_parallelism()
+        .map(|n| {
+            let cores = n.get() as i32;
+            ((cores / 2) + 2).max(1)
+        })
+        .unwrap_or(2)
 }
 
 // ============================================================================
 // Model State Management
 // ============================================================================
 
+/// KV cache of the previous Generate request. Chunks of a long meeting are
+/// sent with the same system prompt, so the next prompt usually starts with
+/// tokens that are already evaluated here.
+struct PromptCache {
+    /// Borrows the boxed model of `ModelState`, which outlives it
+    ctx: LlamaContext<'static>,
+    /// Tokens whose keys and values are in `ctx`, at positions 0..len
+    tokens: Vec<LlamaToken>,
+}
+
 struct ModelState {
     backend: LlamaBackend,
-    model: Option<LlamaModel>,
+    // Declared before `model` so it is dropped first
+    prompt_cache: Option<PromptCache>,
+    model: Option<Box<LlamaModel>>,
     model_path: Option<PathBuf>,
     context_size: u32,
     last_activity: Arc<AtomicU64>,
@@ -214,6 +309,7 @@ impl ModelState {
         let backend = LlamaBackend::init().context("Failed to init LlamaBackend")?;
         Ok(Self {
             backend,
+            prompt_cache: None,
             model: None,
             model_path: None,
             context_size: 2048,
@@ -237,7 +333,12 @@ impl ModelState {
         Self::current_timestamp() - self.last_activity.load(Ordering::SeqCst)
     }
 
-    fn load_model_if_needed(&mut self, model_path: PathBuf, context_size: u32) -> Result<()> {
+    fn load_model_if_needed(
+        &mut self,
+        model_path: PathBuf,
+        context_size: u32,
+        layer_count: Option<u32>,
+    ) -> Result<()> {
         // Check if model is already loaded
         if let Some(ref loaded_path) = self.model_path {
             if loaded_path == &model_path && self.context_size == context_size {
@@ -250,7 +351,7 @@ impl ModelState {
         eprintln!("📥 Loading model: {}", model_path.display());
 
         // Detect GPU layers
-        let gpu_layers = get_default_gpu_layers(&model_path, context_size);
+        let gpu_layers = get_default_gpu_layers(&model_path, context_size, layer_count);
 
         // Configure model parameters with GPU offload
         let model_params = LlamaModelParams::default().with_n_gpu_layers(gpu_layers);
@@ -259,7 +360,9 @@ impl ModelState {
         let model = LlamaModel::load_from_file(&self.backend, model_path.clone(), &model_params)
             .with_context(|| format!("unable to load model at {:?}", model_path))?;
 
-        self.model = Some(model);
+        // The cache borrows the previous model
+        self.prompt_cache = None;
+        self.model = Some(Box::new(model));
         self.model_path = Some(model_path);
         self.context_size = context_size;
         self.update_activity();
@@ -268,6 +371,37 @@ impl ModelState {
         Ok(())
     }
 
+    /// Formats a conversation with the chat template embedded in the model
+    /// file. llama.cpp recognizes the common template families (ChatML, Llama
+    /// 3, Gemma, Phi, Mistral, ...); models without a template fall back to
+    /// ChatML.
+    fn apply_chat_template(&self, messages: Vec<ChatMessage>) -> Result<String> {
+        use llama_cpp_2::model::LlamaChatTemplate;
+
+        let model = self.model.as_ref().context("Model not loaded")?;
+        let template = match model.chat_template(None) {
+            Ok(template) => template,
+            Err(e) => {
+                eprintln!(
+                    "⚠️ No usable chat template in model ({:?}), using ChatML",
+                    e
+                );
+                LlamaChatTemplate::new("chatml")
+                    .map_err(|e| anyhow::anyhow!("Invalid chat template: {:?}", e))?
+            }
+        };
+
+        let chat = messages
+            .into_iter()
+            .map(|message| LlamaChatMessage::new(message.role, message.content))
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| anyhow::anyhow!("Invalid chat message: {:?}", e))?;
+
+        model
+            .apply_chat_template(&template, &chat, true)
+            .map_err(|e| anyhow::anyhow!("Failed to apply chat template: {:?}", e))
+    }
+
     fn generate(
         &mut self,
         prompt: String,
@@ -276,30 +410,38 @@ impl ModelState {
         top_k: i32,
         top_p: f32,
         stop_tokens: Vec<String>,
-    ) -> Result<String> {
+        stream: bool,
+        grammar: Option<String>,
+    ) -> Result<(String, GenerationStats)> {
         let start_time = Instant::now();
         let model = self.model.as_ref().context("Model not loaded")?;
 
-        // Calculate thread count (conservative default: max(1, (Cores / 2) + 2))
-        // This ensures the UI thread is never starved
-        let threads: i32 = std::thread::available_parallelism()
-            .map(|n| {
-                let cores = n.get() as i32;
-                ((cores / 2) + 2).max(1)
-            })
-            .unwrap_or(2);
-
-        let ctx_params = LlamaContextParams::default()
-            .with_n_ctx(Some(
-                NonZeroU32::new(self.context_size).context("Invalid ctx size")?,
-            ))
-            .with_n_batch(self.context_size)
-            .with_n_threads(threads)
-            .with_n_threads_batch(threads);
-
-        let mut ctx = model
-            .new_context(&self.backend, ctx_params)
-            .context("unable to create the llama_context")?;
+        // Taken out while generating: a request that fails midway leaves no cache
+        let mut cache = match self.prompt_cache.take() {
+            Some(cache) => cache,
+            None => {
+                let threads = thread_count();
+
+                let ctx_params = LlamaContextParams::default()
+                    .with_n_ctx(Some(
+                        NonZeroU32::new(self.context_size).context("Invalid ctx size")?,
+                    ))
+                    .with_n_batch(self.context_size)
+                    .with_n_threads(threads)
+                    .with_n_threads_batch(threads);
+
+                // SAFETY: the model is boxed, so its address is stable, and the
+                // cache is always dropped before the model is replaced or dropped
+                let model: &'static LlamaModel = unsafe { &*(&**model as *const LlamaModel) };
+                let ctx = model
+                    .new_context(&self.backend, ctx_params)
+                    .context("unable to create the llama_context")?;
+                PromptCache {
+                    ctx,
+                    tokens: Vec::new(),
+                }
+            }
+        };
 
         let tokens_list = model
             .str_to_token(&prompt, AddBos::Always)
@@ -307,25 +449,91 @@ impl ModelState {
 
         eprintln!("📝 Tokenized prompt: {} tokens", tokens_list.len());
 
+        // Keep the KV cache of the longest prefix shared with the previous
+        // request; the last prompt token is always evaluated for its logits
+        let mut cached_tokens = cache
+            .tokens
+            .iter()
+            .zip(&tokens_list)
+            .take_while(|(cached, token)| cached == token)
+            .count()
+            .min(tokens_list.len() - 1);
+        if cached_tokens < cache.tokens.len() {
+            let trimmed = cache
+                .ctx
+                .clear_kv_cache_seq(Some(0), Some(cached_tokens as u32), None)
+                .unwrap_or(false);
+            if !trimmed {
+                // Some caches (e.g. recurrent models) can't drop a suffix
+                cache.ctx.clear_kv_cache();
+                cached_tokens = 0;
+            }
+        }
+        cache.tokens.truncate(cached_tokens);
+        if cached_tokens > 0 {
+            eprintln!(
+                "♻️ Reusing KV cache for {} of {} prompt tokens",
+                cached_tokens,
+                tokens_list.len()
+            );
+        }
+
         // Use context size for batch capacity to handle long prompts
         let batch_size = self.context_size as usize;
         let mut batch = LlamaBatch::new(batch_size, 1);
 
-        let last_index: i32 = (tokens_list.len() - 1) as i32;
-        for (i, token) in (0_i32..).zip(tokens_list.into_iter()) {
+        let last_index = tokens_list.len() - 1;
+        for (i, token) in tokens_list.iter().enumerate().skip(cached_tokens) {
             let is_last = i == last_index;
             batch
-                .add(token, i, &[0], is_last)
+                .add(*token, i as i32, &[0], is_last)
                 .context("Failed to add token to batch")?;
         }
 
+        let ctx = &mut cache.ctx;
         ctx.decode(&mut batch).context("llama_decode() failed")?;
+        cache
+            .tokens
+            .extend_from_slice(&tokens_list[cached_tokens..]);
         let prompt_time = start_time.elapsed();
 
-        let n_prompt_tokens = batch.n_tokens();
+        let n_prompt_tokens = tokens_list.len() as i32;
         let mut n_cur = n_prompt_tokens;
         let mut decoder = encoding_rs::UTF_8.new_decoder();
         let mut output = String::new();
+        // Bytes of output already sent as Token messages
+        let mut emitted = 0;
+
+        use llama_cpp_2::sampling::LlamaSampler;
+
+        // The sampler lives for the whole generation: a grammar sampler tracks
+        // how far into the grammar the output is
+        let mut samplers = Vec::new();
+        if let Some(grammar) = &grammar {
+            // Masks tokens the grammar doesn't allow, before any other sampler
+            samplers.push(
+                LlamaSampler::grammar(model, grammar, "root")
+                    .map_err(|e| anyhow::anyhow!("Invalid grammar: {:?}", e))?,
+            );
+        }
+        if temperature <= 0.0 {
+            // Greedy sampling for temp <= 0
+            samplers.push(LlamaSampler::greedy());
+        } else {
+            // Random sampling with temperature/top_k/top_p
+            let seed = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_millis() as u32;
+
+            samplers.extend([
+                LlamaSampler::top_k(top_k),
+                LlamaSampler::top_p(top_p, 1),
+                LlamaSampler::temp(temperature),
+                LlamaSampler::dist(seed),
+            ]);
+        }
+        let mut sampler = pin!(LlamaSampler::chain_simple(samplers));
 
         eprintln!("🔄 Starting generation (max_tokens: {})", max_tokens);
 
@@ -336,29 +544,8 @@ impl ModelState {
                 break;
             }
 
-            use llama_cpp_2::sampling::LlamaSampler;
-
-            let sampler = if temperature <= 0.0 {
-                // Greedy sampling for temp <= 0
-                LlamaSampler::chain_simple([LlamaSampler::greedy()])
-            } else {
-                // Random sampling with temperature/top_k/top_p
-                let seed = SystemTime::now()
-                    .duration_since(UNIX_EPOCH)
-                    .unwrap_or_default()
-                    .as_millis() as u32;
-
-                LlamaSampler::chain_simple([
-                    LlamaSampler::top_k(top_k),
-                    LlamaSampler::top_p(top_p, 1),
-                    LlamaSampler::temp(temperature),
-                    LlamaSampler::dist(seed),
-                ])
-            };
-
-            let mut sampler = pin!(sampler);
+            // Sampling also accepts the token, advancing the grammar
             let token = sampler.as_mut().sample(&ctx, batch.n_tokens() - 1);
-            sampler.as_mut().accept(token);
 
             if model.is_eog_token(token) {
                 eprintln!(
@@ -376,31 +563,47 @@ impl ModelState {
             let _ = decoder.decode_to_string(&output_bytes, &mut token_text, false);
             output.push_str(&token_text);
 
-            // Check for model-specific stop tokens
-            let mut should_stop = false;
-            for stop_token in &stop_tokens {
-                if output.contains(stop_token) {
-                    eprintln!(
-                        "✓ Stop token '{}' detected (generated {} chars)",
-                        stop_token,
-                        output.len()
-                    );
-                    // Remove the stop token from output
-                    output = output.replace(stop_token, "").trim_end().to_string();
-                    should_stop = true;
-                    break;
-                }
-            }
-            if should_stop {
+            // Check for model-specific stop tokens; the output ends where the
+            // first one starts
+            let first_stop = stop_tokens
+                .iter()
+                .filter(|stop| !stop.is_empty())
+                .filter_map(|stop| Some((output.find(stop.as_str())?, stop)))
+                .min_by_key(|(pos, _)| *pos);
+            if let Some((pos, stop_token)) = first_stop {
+                eprintln!(
+                    "✓ Stop token '{}' detected (generated {} chars)",
+                    stop_token, pos
+                );
+                output.truncate(pos);
+                output.truncate(output.trim_end().len());
                 break;
             }
 
+            if stream {
+                let safe = streamable_len(&output, &stop_tokens);
+                if safe > emitted {
+                    send_response(&Response::Token {
+                        text: output[emitted..safe].to_string(),
+                    })?;
+                    emitted = safe;
+                }
+            }
+
             batch.clear();
             batch
                 .add(token, n_cur, &[0], true)
                 .context("Failed to add generated token to batch")?;
             n_cur += 1;
             ctx.decode(&mut batch).context("failed to eval")?;
+            cache.tokens.push(token);
+        }
+
+        // Flush text held back as a possible stop token prefix
+        if stream && output.len() > emitted && output.is_char_boundary(emitted) {
+            send_response(&Response::Token {
+                text: output[emitted..].to_string(),
+            })?;
         }
 
         // Generation statistics
@@ -408,23 +611,122 @@ impl ModelState {
         let gen_time = total_time.saturating_sub(prompt_time);
         let output_tokens = (n_cur - n_prompt_tokens) as u64;
         let prompt_tokens = n_prompt_tokens as u64;
+        let evaluated_tokens = prompt_tokens - cached_tokens as u64;
 
         let tokens_per_sec = if gen_time.as_secs_f64() > 0.0 {
             output_tokens as f64 / gen_time.as_secs_f64()
         } else {
             0.0
         };
+        let prompt_tokens_per_sec = if prompt_time.as_secs_f64() > 0.0 {
+            evaluated_tokens as f64 / prompt_time.as_secs_f64()
+        } else {
+            0.0
+        };
 
         eprintln!("📊 Generation Statistics:");
         eprintln!("   • Prompt tokens: {}", prompt_tokens);
+        eprintln!("   • Cached prompt tokens: {}", cached_tokens);
         eprintln!("   • Output tokens: {}", output_tokens);
         eprintln!("   • Prompt processing: {:.2}s", prompt_time.as_secs_f64());
         eprintln!("   • Generation time: {:.2}s", gen_time.as_secs_f64());
         eprintln!("   • Total time: {:.2}s", total_time.as_secs_f64());
         eprintln!("   • Speed: {:.2} tokens/sec", tokens_per_sec);
 
+        self.prompt_cache = Some(cache);
         self.update_activity();
-        Ok(output)
+        Ok((
+            output,
+            GenerationStats {
+                prompt_tokens: prompt_tokens as usize,
+                cached_tokens,
+                output_tokens: output_tokens as usize,
+                prompt_ms: prompt_time.as_millis() as u64,
+                generation_ms: gen_time.as_millis() as u64,
+                prompt_tokens_per_sec,
+                tokens_per_sec,
+            },
+        ))
+    }
+
+    fn embed(&mut self, texts: Vec<String>) -> Result<Vec<Vec<f32>>> {
+        let start_time = Instant::now();
+        let model = self.model.as_ref().context("Model not loaded")?;
+        let threads = thread_count();
+
+        // Embeddings use their own small context: passages are short and the
+        // whole text must fit in one ubatch for pooling
+        let ctx_params = LlamaContextParams::default()
+            .with_n_ctx(NonZeroU32::new(EMBEDDING_CONTEXT_SIZE))
+            .with_n_batch(EMBEDDING_CONTEXT_SIZE)
+            .with_n_ubatch(EMBEDDING_CONTEXT_SIZE)
+            .with_n_threads(threads)
+            .with_n_threads_batch(threads)
+            .with_embeddings(true)
+            .with_pooling_type(LlamaPoolingType::Mean);
+
+        let mut ctx = model
+            .new_context(&self.backend, ctx_params)
+            .context("unable to create the embedding context")?;
+
+        let mut batch = LlamaBatch::new(EMBEDDING_CONTEXT_SIZE as usize, 1);
+        let mut vectors = Vec::with_capacity(texts.len());
+
+        for text in &texts {
+            let mut tokens = model
+                .str_to_token(text, AddBos::Always)
+                .with_context(|| "failed to tokenize text")?;
+            if tokens.len() > EMBEDDING_CONTEXT_SIZE as usize {
+                eprintln!(
+                    "⚠️ Truncating embedding input from {} to {} tokens",
+                    tokens.len(),
+                    EMBEDDING_CONTEXT_SIZE
+                );
+                tokens.truncate(EMBEDDING_CONTEXT_SIZE as usize);
+            }
+
+            batch.clear();
+            batch
+                .add_sequence(&tokens, 0, false)
+                .context("Failed to add tokens to batch")?;
+            ctx.clear_kv_cache();
+            ctx.decode(&mut batch).context("llama_decode() failed")?;
+
+            let embedding = ctx
+                .embeddings_seq_ith(0)
+                .context("Failed to read embeddings")?;
+            let norm = embedding.iter().map(|x| x * x).sum::<f32>().sqrt();
+            vectors.push(if norm > f32::EPSILON {
+                embedding.iter().map(|x| x / norm).collect()
+            } else {
+                embedding.to_vec()
+            });
+        }
+
+        eprintln!(
+            "📊 Embedded {} texts in {:.2}s",
+            texts.len(),
+            start_time.elapsed().as_secs_f64()
+        );
+
+        self.update_activity();
+        Ok(vectors)
+    }
+
+    fn count_tokens(&mut self, texts: &[String]) -> Result<Vec<usize>> {
+        let model = self.model.as_ref().context("Model not loaded")?;
+        let counts = texts
+            .iter()
+            .map(|text| {
+                model
+                    .str_to_token(text, AddBos::Never)
+                    .map(|tokens| tokens.len())
+                    .with_context(|| "failed to tokenize text")
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        self.update_activity();
+        Ok(counts)
     }
 }
 
@@ -432,6 +734,24 @@ impl ModelState {
 // Main Loop with Keep-Alive Protocol
 // ============================================================================
 
+/// Length of `output` that can be streamed without leaking the start of a stop
+/// token: a trailing partial match is held back until it resolves, and so is
+/// trailing whitespace, which is trimmed if a stop token follows it.
+fn streamable_len(output: &str, stop_tokens: &[String]) -> usize {
+    let held_back = stop_tokens
+        .iter()
+        .flat_map(|stop| {
+            (1..stop.len())
+                .filter(|&n| stop.is_char_boundary(n))
+                .map(move |n| &stop[..n])
+        })
+        .filter(|prefix| output.ends_with(prefix))
+        .map(str::len)
+        .max()
+        .unwrap_or(0);
+    output[..output.len() - held_back].trim_end().len()
+}
+
 fn send_response(response: &Response) -> Result<()> {
     let json = serde_json::to_string(response)?;
     println!("{}", json);
@@ -483,13 +803,18 @@ fn main() -> Result<()> {
                 match serde_json::from_str::<Request>(line) {
                     Ok(Request::Generate {
                         prompt,
+                        messages,
                         max_tokens,
                         context_size,
                         model_path,
+                        layer_count,
                         temperature,
                         top_k,
                         top_p,
                         stop_tokens,
+                        stream,
+                        grammar,
+                        json_schema,
                     }) => {
                         let max_tokens = max_tokens.unwrap_or(512);
                         let context_size = context_size.unwrap_or(2048);
@@ -500,18 +825,77 @@ fn main() -> Result<()> {
                         let top_p = top_p.unwrap_or(0.95);
                         let stop_tokens = stop_tokens.unwrap_or_else(Vec::new);
 
+                        // Output constraint: a grammar, or a schema converted to one
+                        let grammar = match (grammar, json_schema) {
+                            (Some(_), Some(_)) => {
+                                send_response(&Response::Response {
+                                    text: String::new(),
+                                    stats: None,
+                                    error: Some(
+                                        "Specify either grammar or json_schema, not both"
+                                            .to_string(),
+                                    ),
+                                })?;
+                                continue;
+                            }
+                            (Some(grammar), None) => Some(grammar),
+                            (None, Some(schema)) => {
+                                match grammar::json_schema_to_grammar(&schema) {
+                                    Ok(grammar) => Some(grammar),
+                                    Err(e) => {
+                                        send_response(&Response::Response {
+                                            text: String::new(),
+                                            stats: None,
+                                            error: Some(format!("Invalid JSON schema: {}", e)),
+                                        })?;
+                                        continue;
+                                    }
+                                }
+                            }
+                            (None, None) => None,
+                        };
+
                         // Load model if path provided
                         if let Some(path_str) = model_path {
                             let path = PathBuf::from(path_str);
-                            if let Err(e) = state.load_model_if_needed(path, context_size) {
+                            if let Err(e) =
+                                state.load_model_if_needed(path, context_size, layer_count)
+                            {
                                 send_response(&Response::Response {
                                     text: String::new(),
+                                    stats: None,
                                     error: Some(format!("Failed to load model: {}", e)),
                                 })?;
                                 continue;
                             }
                         }
 
+                        // Chat messages are formatted with the model's own template
+                        let prompt = match (messages, prompt) {
+                            (Some(messages), _) => match state.apply_chat_template(messages) {
+                                Ok(prompt) => prompt,
+                                Err(e) => {
+                                    send_response(&Response::Response {
+                                        text: String::new(),
+                                        stats: None,
+                                        error: Some(e.to_string()),
+                                    })?;
+                                    continue;
+                                }
+                            },
+                            (None, Some(prompt)) => prompt,
+                            (None, None) => {
+                                send_response(&Response::Response {
+                                    text: String::new(),
+                                    stats: None,
+                                    error: Some(
+                                        "Either prompt or messages is required".to_string(),
+                                    ),
+                                })?;
+                                continue;
+                            }
+                        };
+
                         // Generate response with sampling parameters
                         match state.generate(
                             prompt,
@@ -520,18 +904,81 @@ fn main() -> Result<()> {
                             top_k,
                             top_p,
                             stop_tokens,
+                            stream.unwrap_or(false),
+                            grammar,
                         ) {
-                            Ok(text) => {
-                                send_response(&Response::Response { text, error: None })?;
+                            Ok((text, stats)) => {
+                                send_response(&Response::Response {
+                                    text,
+                                    stats: Some(stats),
+                                    error: None,
+                                })?;
                             }
                             Err(e) => {
                                 send_response(&Response::Response {
                                     text: String::new(),
+                                    stats: None,
                                     error: Some(format!("Generation failed: {}", e)),
                                 })?;
                             }
                         }
                     }
+                    Ok(Request::Embed { texts, model_path }) => {
+                        if let Some(path_str) = model_path {
+                            let path = PathBuf::from(path_str);
+                            let context_size = state.context_size;
+                            if let Err(e) = state.load_model_if_needed(path, context_size, None) {
+                                send_response(&Response::Embeddings {
+                                    vectors: Vec::new(),
+                                    error: Some(format!("Failed to load model: {}", e)),
+                                })?;
+                                continue;
+                            }
+                        }
+
+                        match state.embed(texts) {
+                            Ok(vectors) => {
+                                send_response(&Response::Embeddings {
+                                    vectors,
+                                    error: None,
+                                })?;
+                            }
+                            Err(e) => {
+                                send_response(&Response::Embeddings {
+                                    vectors: Vec::new(),
+                                    error: Some(format!("Embedding failed: {}", e)),
+                                })?;
+                            }
+                        }
+                    }
+                    Ok(Request::Tokenize { texts, model_path }) => {
+                        if let Some(path_str) = model_path {
+                            let path = PathBuf::from(path_str);
+                            let context_size = state.context_size;
+                            if let Err(e) = state.load_model_if_needed(path, context_size, None) {
+                                send_response(&Response::TokenCounts {
+                                    counts: Vec::new(),
+                                    error: Some(format!("Failed to load model: {}", e)),
+                                })?;
+                                continue;
+                            }
+                        }
+
+                        match state.count_tokens(&texts) {
+                            Ok(counts) => {
+                                send_response(&Response::TokenCounts {
+                                    counts,
+                                    error: None,
+                                })?;
+                            }
+                            Err(e) => {
+                                send_response(&Response::TokenCounts {
+                                    counts: Vec::new(),
+                                    error: Some(format!("Tokenization failed: {}", e)),
+                                })?;
+                            }
+                        }
+                    }
                     Ok(Request::Ping) => {
                         state.update_activity();
                         send_response(&Response::Pong)?;